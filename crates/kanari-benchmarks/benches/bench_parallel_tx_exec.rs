@@ -0,0 +1,15 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{criterion_group, criterion_main};
+
+use kanari_benchmarks::config::configure_criterion;
+use kanari_benchmarks::parallel_tx_exec::parallel_tx_exec_benchmark;
+
+criterion_group! {
+    name = parallel_tx_exec_bench;
+    config = configure_criterion(None).measurement_time(std::time::Duration::from_secs(10));
+    targets = parallel_tx_exec_benchmark
+}
+
+criterion_main!(parallel_tx_exec_bench);
@@ -0,0 +1,36 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use moveos_wasm::gas_config::WASMGasConfig;
+use moveos_wasm::module_cache::{CompiledWasmModule, WasmModuleCache};
+use moveos_wasm::wasm::create_wasm_instance_from_module;
+
+const GENERATOR_WASM: &[u8] = include_bytes!("../../testsuite/data/generator.wasm");
+
+fn wasm_module_cache_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("wasm_module_cache");
+    let config = WASMGasConfig::default();
+
+    group.bench_function("compile", |b| {
+        b.iter(|| CompiledWasmModule::compile(GENERATOR_WASM, config).unwrap())
+    });
+
+    let cache = WasmModuleCache::new(1);
+    cache.get_or_compile(GENERATOR_WASM, config).unwrap();
+    group.bench_function("cached", |b| {
+        b.iter(|| cache.get_or_compile(GENERATOR_WASM, config).unwrap())
+    });
+
+    group.bench_function("cached_instantiate", |b| {
+        b.iter(|| {
+            let module = cache.get_or_compile(GENERATOR_WASM, config).unwrap();
+            create_wasm_instance_from_module(&module).unwrap()
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, wasm_module_cache_benchmark);
+criterion_main!(benches);
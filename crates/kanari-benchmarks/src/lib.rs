@@ -3,6 +3,7 @@
 
 pub mod config;
 pub mod indexer;
+pub mod parallel_tx_exec;
 pub mod smt;
 pub mod tx;
 pub mod tx_exec;
@@ -0,0 +1,73 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use criterion::{BatchSize, Criterion};
+
+use kanari_framework_tests::binding_test;
+use kanari_test_transaction_builder::TestTransactionBuilder;
+use kanari_types::crypto::KanariKeyPair;
+
+use crate::config::BenchTxConfig;
+use crate::config::TxType::{Empty, Transfer};
+use crate::tx::create_l2_tx;
+
+const BATCH_SIZE: usize = 500;
+
+// serial execution vs parallel execution of the same batch, every tx is sent by a different account
+pub fn parallel_tx_exec_benchmark(c: &mut Criterion) {
+    let config = BenchTxConfig::load();
+
+    let mut binding_test = binding_test::RustBindingTest::new_in_tokio().unwrap();
+    let tx_type = config.tx_type.clone().unwrap();
+    let bench_id = match tx_type {
+        Empty => "l2_tx_empty",
+        Transfer => "l2_tx_transfer",
+        _ => panic!("Unsupported tx type for parallel execution: {:?}", tx_type),
+    };
+
+    let txs = (0..BATCH_SIZE)
+        .map(|_| {
+            let kp = KanariKeyPair::generate_secp256k1();
+            let mut test_transaction_builder = TestTransactionBuilder::new(kp);
+            let tx = create_l2_tx(&mut test_transaction_builder, 0, tx_type.clone()).unwrap();
+            binding_test.executor.validate_l2_tx(tx).unwrap()
+        })
+        .collect::<Vec<_>>();
+    // Every iteration executes the batch from the same root
+    let base_root = binding_test.root().clone();
+
+    let mut group = c.benchmark_group("bench_parallel_tx_exec");
+    group.sample_size(10);
+    group.bench_function(format!("{}_serial", bench_id), |b| {
+        b.iter_batched(
+            || txs.clone(),
+            |txs| {
+                let mut root = base_root.clone();
+                for mut tx in txs {
+                    tx.root = root;
+                    let result = binding_test.executor.execute(tx).unwrap();
+                    root = result.transaction_info.root_metadata();
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function(format!("{}_parallel", bench_id), |b| {
+        b.iter_batched(
+            || txs.clone(),
+            |txs| {
+                binding_test
+                    .executor
+                    .refresh_state(base_root.clone(), false)
+                    .unwrap();
+                binding_test
+                    .executor
+                    .execute_batch(txs)
+                    .into_result()
+                    .unwrap();
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
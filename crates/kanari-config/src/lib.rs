@@ -12,6 +12,7 @@ use kanari_types::kanari_network::{BuiltinChainID, KanariChainID, KanariNetwork}
 use kanari_types::service_status::ServiceStatus;
 use kanari_types::service_type::ServiceType;
 use moveos_config::{DataDirPath, temp_dir};
+use moveos_types::h256::H256;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -93,6 +94,12 @@ pub struct KanariOpt {
     /// If the file path equals to builtin chain network name(local/dev/test/main), will use builtin genesis config.
     pub genesis_config: Option<String>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long)]
+    /// The genesis binary file built by `kanari genesis init --from` for custom chain network.
+    /// If set, the genesis is loaded from the file instead of being built from the genesis config.
+    pub genesis_file: Option<PathBuf>,
+
     #[clap(flatten)]
     pub store: StoreConfig,
 
@@ -152,8 +159,11 @@ pub struct KanariOpt {
     #[clap(flatten)]
     pub proposer: ProposerConfig,
 
-    #[clap(long, default_value_t, value_enum)]
-    pub service_status: ServiceStatus,
+    /// The service status of the node.
+    /// If not set, use the service status saved by `kanari genesis init --from`, or `active`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, value_enum)]
+    pub service_status: Option<ServiceStatus>,
 
     /// Set quota size that defines how many requests can occur
     /// before the governor middleware starts blocking requests from an IP address and
@@ -172,6 +182,31 @@ pub struct KanariOpt {
     #[clap(long, default_value_t, value_enum)]
     pub service_type: ServiceType,
 
+    /// The RPC URL of the remote node to fork the state from.
+    /// If set, the server starts on top of the remote state, the states missing in the local store are
+    /// fetched from the remote node lazily and cached locally.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long)]
+    pub fork_url: Option<String>,
+
+    /// The state root of the remote node to fork from, default is the latest state root of the remote node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long, requires = "fork_url")]
+    pub fork_state_root: Option<H256>,
+
+    /// The max number of the L2 transactions executed in parallel as a batch.
+    /// If set, the L2 transactions submitted concurrently are collected into batches and executed by the parallel executor,
+    /// otherwise they are executed one by one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[clap(long)]
+    pub parallel_batch_size: Option<usize>,
+
+    /// Accept the package source digests submitted via the `kanari_submitPackageSource` RPC method.
+    /// The submitted source digests are recorded as they are, without rebuilding the packages,
+    /// so only enable it when the RPC of the node is only reachable by its operator.
+    #[clap(long)]
+    pub accept_package_sources: bool,
+
     #[serde(skip)]
     #[clap(skip)]
     base: Option<Arc<BaseConfig>>,
@@ -193,6 +228,7 @@ impl KanariOpt {
             base_data_dir: Some("TMP".into()),
             chain_id: Some(BuiltinChainID::Local.into()),
             genesis_config: None,
+            genesis_file: None,
             store: StoreConfig::default(),
             port: None,
             eth_rpc_url: None,
@@ -207,11 +243,15 @@ impl KanariOpt {
             proposer_account: None,
             da: DAConfig::default(),
             proposer: ProposerConfig::default(),
-            service_status: ServiceStatus::default(),
+            service_status: None,
             traffic_per_second: None,
             traffic_burst_size: None,
             base: None,
             service_type: ServiceType::default(),
+            fork_url: None,
+            fork_state_root: None,
+            parallel_batch_size: None,
+            accept_package_sources: false,
         };
         opt.init()?;
         Ok(opt)
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 use accumulator::accumulator_info::AccumulatorInfo;
 use anyhow::{Error, Result, anyhow};
@@ -26,6 +27,7 @@ use kanari_types::indexer::state::{
 use kanari_types::sequencer::SequencerInfo;
 use moveos_common::utils::to_bytes;
 use moveos_store::config_store::STARTUP_INFO_KEY;
+use moveos_store::state_store::RemoteNodeReader;
 use moveos_store::transaction_store::TransactionStore as TxExecutionInfoStore;
 use moveos_store::{
     CONFIG_STARTUP_INFO_COLUMN_FAMILY_NAME, MoveOSStore,
@@ -41,7 +43,7 @@ use prometheus::Registry;
 use raw_store::metrics::DBMetrics;
 use raw_store::rocks::batch::WriteBatch;
 use raw_store::traits::DBStore;
-use raw_store::{StoreInstance, rocks::RocksDB};
+use raw_store::{ColumnFamilyName, StoreInstance, rocks::RocksDB};
 use tracing::{error, info, warn};
 
 #[derive(Clone)]
@@ -63,8 +65,29 @@ impl KanariDB {
         instance: StoreInstance,
         registry: &Registry,
     ) -> Result<Self> {
-        let indexer_dir = config.get_indexer_dir();
         let moveos_store = MoveOSStore::new_with_instance(instance.clone(), registry)?;
+        Self::init_with_moveos_store(config, moveos_store, instance, registry)
+    }
+
+    /// Init the db in fork mode, the state nodes missing in the local store are read from the `remote`
+    /// and cached to the local store.
+    pub fn init_fork(
+        config: &StoreConfig,
+        registry: &Registry,
+        remote: RemoteNodeReader,
+    ) -> Result<Self> {
+        let instance = Self::generate_store_instance(config, registry)?;
+        let moveos_store = MoveOSStore::new_fork_with_instance(instance.clone(), registry, remote)?;
+        Self::init_with_moveos_store(config, moveos_store, instance, registry)
+    }
+
+    fn init_with_moveos_store(
+        config: &StoreConfig,
+        moveos_store: MoveOSStore,
+        instance: StoreInstance,
+        registry: &Registry,
+    ) -> Result<Self> {
+        let indexer_dir = config.get_indexer_dir();
         let kanari_store = KanariStore::new_with_instance(instance.clone(), registry)?;
         let indexer_store = IndexerStore::new(indexer_dir.clone(), registry)?;
         let indexer_reader = IndexerReader::new(indexer_dir, registry)?;
@@ -82,6 +105,43 @@ impl KanariDB {
         registry: &Registry,
     ) -> Result<StoreInstance> {
         let store_dir = config.get_store_dir();
+        let db_metrics = DBMetrics::get_or_init(registry).clone();
+        let instance = StoreInstance::new_db_instance(
+            RocksDB::new(store_dir, Self::column_families(), config.rocksdb_config())?,
+            db_metrics,
+        );
+
+        Ok(instance)
+    }
+
+    /// Open a sandbox of the store in the `sandbox_dir` via the RocksDB checkpoint,
+    /// the writes to the sandbox do not change the original store.
+    /// The `sandbox_dir` should be on the same filesystem as the store to avoid copying the sst files.
+    pub fn open_sandbox_store(
+        config: &StoreConfig,
+        sandbox_dir: &Path,
+        registry: &Registry,
+    ) -> Result<MoveOSStore> {
+        {
+            let instance = Self::generate_store_instance(config, registry)?;
+            instance
+                .db()
+                .ok_or_else(|| anyhow!("The store instance is not a db instance"))?
+                .create_checkpoint(sandbox_dir)?;
+        }
+        let db_metrics = DBMetrics::get_or_init(registry).clone();
+        let instance = StoreInstance::new_db_instance(
+            RocksDB::new(
+                sandbox_dir,
+                Self::column_families(),
+                config.rocksdb_config(),
+            )?,
+            db_metrics,
+        );
+        MoveOSStore::new_with_instance(instance, registry)
+    }
+
+    fn column_families() -> Vec<ColumnFamilyName> {
         let mut column_families = moveos_store::StoreMeta::get_column_family_names().to_vec();
         column_families.append(&mut kanari_store::StoreMeta::get_column_family_names().to_vec());
         //ensure no duplicate column families
@@ -93,14 +153,7 @@ impl KanariDB {
                 }
             });
         }
-
-        let db_metrics = DBMetrics::get_or_init(registry).clone();
-        let instance = StoreInstance::new_db_instance(
-            RocksDB::new(store_dir, column_families, config.rocksdb_config())?,
-            db_metrics,
-        );
-
-        Ok(instance)
+        column_families
     }
 
     pub fn init_with_mock_metrics_for_test(config: &StoreConfig) -> Result<Self> {
@@ -120,7 +173,7 @@ impl KanariDB {
     /// 3. revert indexer
     pub fn revert_tx(&self, tx_hash: H256) -> Result<()> {
         let (tx_order, previous_accumulator_info, previous_execution_info) =
-            self.check_revert_tx(tx_hash)?;
+            self.check_revert_tx(tx_hash, 1)?;
         let previous_tx_hash = previous_execution_info.tx_hash;
         self.inner_revert(
             tx_order,
@@ -138,6 +191,56 @@ impl KanariDB {
         Ok(())
     }
 
+    /// revert the last txs with these operations:
+    /// 1. check preconditions of the first tx, the txs after it may be sequenced but not executed
+    /// 2. remove the txs from the last one, save the tx before the first one as startup (atomic per tx)
+    /// 3. revert indexer
+    ///
+    /// `tx_hashes` are in the tx order and end with the last tx, e.g. the rest of a failed parallel batch
+    pub fn revert_last_txs(&self, tx_hashes: &[H256]) -> Result<()> {
+        let Some((first_tx_hash, later_tx_hashes)) = tx_hashes.split_first() else {
+            return Ok(());
+        };
+        let (tx_order, previous_accumulator_info, previous_execution_info) =
+            self.check_revert_tx(*first_tx_hash, tx_hashes.len() as u64)?;
+        let later_tx_orders = (tx_order + 1..tx_order + tx_hashes.len() as u64).collect::<Vec<_>>();
+        let sequenced_tx_hashes = self
+            .kanari_store
+            .transaction_store
+            .get_tx_hashes(later_tx_orders.clone())?;
+        for ((later_tx_order, later_tx_hash), sequenced_tx_hash) in later_tx_orders
+            .iter()
+            .zip(later_tx_hashes)
+            .zip(sequenced_tx_hashes)
+        {
+            if sequenced_tx_hash != Some(*later_tx_hash) {
+                return Err(Error::msg(format!(
+                    "revert txs failed: tx_hash {:?} is not the tx of tx_order {}",
+                    later_tx_hash, later_tx_order
+                )));
+            }
+        }
+
+        for (later_tx_order, later_tx_hash) in
+            later_tx_orders.into_iter().zip(later_tx_hashes).rev()
+        {
+            self.inner_revert(later_tx_order, *later_tx_hash, None, None, false)?;
+        }
+        self.inner_revert(
+            tx_order,
+            *first_tx_hash,
+            Some(previous_accumulator_info),
+            Some(previous_execution_info),
+            true,
+        )?;
+        tracing::info!(
+            "revert last txs succeed: tx_orders [{}, {}]",
+            tx_order,
+            tx_order + later_tx_hashes.len() as u64,
+        );
+        Ok(())
+    }
+
     /// revert tx unsafe with these operations:
     /// 1. remove the tx (atomic)
     /// 2. revert indexer
@@ -155,11 +258,12 @@ impl KanariDB {
 
     // check revert tx preconditions(no side-effect):
     // 1. tx existed
-    // 2. tx is last tx
+    // 2. tx is the first one of the last `tx_count` txs
     // 3. previous tx existed and has execution info
     fn check_revert_tx(
         &self,
         tx_hash: H256,
+        tx_count: u64,
     ) -> Result<(u64, AccumulatorInfo, TransactionExecutionInfo)> {
         // ensure tx existed
         let ledger_tx_opt = self
@@ -177,7 +281,7 @@ impl KanariDB {
             tx_order
         );
 
-        // ensure tx is last tx
+        // ensure tx is the first one of the last txs
         let last_sequencer_info = self
             .kanari_store
             .get_meta_store()
@@ -187,9 +291,13 @@ impl KanariDB {
             })?;
         let last_tx_order = last_sequencer_info.last_order;
         assert_eq!(
-            sequencer_info.tx_order, last_sequencer_info.last_order,
-            "revert tx failed: tx_order {} is not last tx_order {}. tx_hash: {:?}",
-            tx_order, last_tx_order, tx_hash
+            sequencer_info.tx_order + tx_count - 1,
+            last_sequencer_info.last_order,
+            "revert tx failed: tx_order {} is not the first of the last {} tx_orders, last tx_order {}. tx_hash: {:?}",
+            tx_order,
+            tx_count,
+            last_tx_order,
+            tx_hash
         );
 
         // ensure previous tx existed
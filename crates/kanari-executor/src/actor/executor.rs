@@ -3,11 +3,13 @@
 
 use super::messages::{
     ConvertL2TransactionData, DryRunTransactionMessage, DryRunTransactionResult,
-    ExecuteTransactionMessage, ExecuteTransactionResult, GetRootMessage, SaveStateChangeSetMessage,
+    ExecuteBatchTransactionMessage, ExecuteBatchTransactionResult, ExecuteTransactionMessage,
+    ExecuteTransactionResult, GetRootMessage, SaveStateChangeSetMessage, TraceL2TxMessage,
     ValidateL1BlockMessage, ValidateL1TxMessage, ValidateL2TxMessage,
 };
+use crate::commutative_objects::FrameworkCommutativeObjects;
 use crate::metrics::ExecutorMetrics;
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use async_trait::async_trait;
 use coerce::actor::{Actor, LocalActorRef, context::ActorContext, message::Handler};
 use function_name::named;
@@ -31,11 +33,12 @@ use kanari_types::multichain_id::KanariMultiChainID;
 use kanari_types::transaction::authenticator::AUTH_PAYLOAD_SIZE;
 use kanari_types::transaction::{
     AuthenticatorInfo, KanariTransaction, KanariTransactionData, L1Block, L1BlockWithBody,
-    L1Transaction,
+    L1Transaction, TransactionSequenceInfo,
 };
 use move_core_types::account_address::AccountAddress;
 use move_core_types::vm_status::VMStatus;
 use moveos::moveos::{MoveOS, MoveOSConfig};
+use moveos::parallel_executor::ParallelExecutor;
 use moveos::vm::vm_status_explainer::explain_vm_status;
 use moveos_eventbus::bus::EventData;
 use moveos_store::MoveOSStore;
@@ -47,9 +50,13 @@ use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::moveos_std::tx_meta::TxMeta;
 use moveos_types::state::{ObjectState, StateChangeSetExt};
 use moveos_types::state_resolver::RootObjectResolver;
-use moveos_types::transaction::{FunctionCall, MoveOSTransaction, VerifiedMoveAction};
+use moveos_types::transaction::{
+    FunctionCall, MoveOSTransaction, TransactionExecutionInfo, VerifiedMoveAction,
+};
 use moveos_types::transaction::{MoveAction, VerifiedMoveOSTransaction};
+use moveos_wasm::metrics::WasmMetrics;
 use prometheus::Registry;
+use std::collections::BTreeSet;
 use std::str::FromStr;
 use std::sync::Arc;
 
@@ -83,6 +90,8 @@ impl ExecutorActor {
             system_post_execute_functions(),
         )?;
 
+        WasmMetrics::get_or_init(registry);
+
         Ok(Self {
             root,
             moveos,
@@ -119,6 +128,10 @@ impl ExecutorActor {
         &self.moveos
     }
 
+    pub fn metrics(&self) -> &ExecutorMetrics {
+        &self.metrics
+    }
+
     #[named]
     pub fn execute(&mut self, tx: VerifiedMoveOSTransaction) -> Result<ExecuteTransactionResult> {
         let fn_name = function_name!();
@@ -152,21 +165,157 @@ impl ExecutorActor {
         })
     }
 
+    /// Execute a batch of sequenced transactions in parallel, the results are the same as executing them one by one.
+    /// The transactions are executed from the current root, the root of every transaction is ignored.
+    /// Every L2 transaction writes the timestamp, the gas fee pool and the gas revenue stores in the pre_execute
+    /// and post_execute functions, these changes are rebased via the `FrameworkCommutativeObjects`,
+    /// so the L2 transactions only conflict on the objects written by their functions.
+    /// The transactions are committed until the first failed one, the conflicts are exported via the `executor_parallel_*` metrics.
     #[named]
-    pub fn dry_run(&mut self, tx: VerifiedMoveOSTransaction) -> Result<DryRunTransactionResult> {
+    pub fn execute_batch(
+        &mut self,
+        txs: Vec<VerifiedMoveOSTransaction>,
+    ) -> ExecuteBatchTransactionResult {
         let fn_name = function_name!();
         let _timer = self
             .metrics
             .executor_execute_tx_latency_seconds
             .with_label_values(&[fn_name])
             .start_timer();
+        let tx_hashes = txs.iter().map(|tx| tx.ctx.tx_hash()).collect::<Vec<_>>();
+        let size = txs.iter().map(|tx| tx.ctx.tx_size).sum::<u64>();
+        let mut results = Vec::with_capacity(txs.len());
+        let mut is_gas_upgrade = false;
+
+        let contract_addresses = txs
+            .iter()
+            .flat_map(|tx| match &tx.action {
+                VerifiedMoveAction::Function { call, .. } => {
+                    vec![*call.function_id.module_id.address()]
+                }
+                VerifiedMoveAction::Batch { calls } => calls
+                    .iter()
+                    .map(|call| *call.function_id.module_id.address())
+                    .collect(),
+                _ => vec![],
+            })
+            .collect::<BTreeSet<_>>();
+        let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+        let commutative_objects =
+            match FrameworkCommutativeObjects::load(&resolver, contract_addresses) {
+                Ok(commutative_objects) => commutative_objects,
+                Err(error) => {
+                    return ExecuteBatchTransactionResult {
+                        results,
+                        error: Some(error),
+                    };
+                }
+            };
+
+        let executor =
+            ParallelExecutor::new(&self.moveos).with_commutative_objects(&commutative_objects);
+        let moveos_store = &self.moveos_store;
+        let root = &mut self.root;
+        let ret = executor.execute_batch(root.clone(), txs, |index, raw_output, _vm_error_info| {
+            is_gas_upgrade |= raw_output.is_gas_upgrade;
+            let (output, execution_info) =
+                moveos_store.handle_tx_output(tx_hashes[index], raw_output)?;
+            *root = execution_info.root_metadata();
+            results.push(ExecuteTransactionResult {
+                output,
+                transaction_info: execution_info,
+            });
+            Ok(root.clone())
+        });
+        self.metrics
+            .executor_execute_tx_bytes
+            .with_label_values(&[fn_name])
+            .observe(size as f64);
+        let error = match ret {
+            Ok(stats) => {
+                self.metrics
+                    .executor_parallel_batch_txs
+                    .inc_by(stats.tx_count as u64);
+                self.metrics
+                    .executor_parallel_reexecuted_txs
+                    .inc_by(stats.reexecuted_count as u64);
+                if stats.serial_fallback {
+                    self.metrics.executor_parallel_serial_fallback_batches.inc();
+                }
+                None
+            }
+            Err(error) => Some(error),
+        };
+
+        if is_gas_upgrade {
+            if let Some(notify_actor) = self.notify_actor.clone() {
+                let _ = notify_actor.notify(GasUpgradeMessage {});
+            }
+        }
+        ExecuteBatchTransactionResult { results, error }
+    }
+
+    #[named]
+    pub fn dry_run(
+        &mut self,
+        tx: VerifiedMoveOSTransaction,
+        trace: bool,
+    ) -> Result<DryRunTransactionResult> {
+        let fn_name = function_name!();
+        let _timer = self
+            .metrics
+            .executor_execute_tx_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        if trace {
+            let (raw_output, vm_error_info, trace) = self.moveos.execute_with_trace(tx)?;
+            return Ok(DryRunTransactionResult {
+                raw_output,
+                vm_error_info,
+                trace: Some(trace),
+            });
+        }
         let (raw_output, vm_error_info) = self.moveos.execute_only(tx)?;
         Ok(DryRunTransactionResult {
             raw_output,
             vm_error_info,
+            trace: None,
         })
     }
 
+    /// Replay the executed L2 transaction at the `root` before it with the tracer.
+    /// The transaction is validated again and executed with its sequence info, as it was executed,
+    /// and the replayed status and state root are checked against its `execution_info`.
+    pub fn trace_l2_tx(
+        &mut self,
+        tx: KanariTransaction,
+        sequence_info: TransactionSequenceInfo,
+        root: ObjectMeta,
+        execution_info: TransactionExecutionInfo,
+    ) -> Result<DryRunTransactionResult> {
+        let tx_hash = tx.tx_hash();
+        let mut verified_tx = self.validate_l2_tx_at(tx, root)?;
+        verified_tx.ctx.add(sequence_info)?;
+        let result = self.dry_run(verified_tx, true)?;
+
+        let mut changeset = result.raw_output.changeset.clone();
+        self.moveos_store
+            .get_state_store()
+            .change_set_to_nodes(&mut changeset)?;
+        ensure!(
+            result.raw_output.status == execution_info.status
+                && changeset.state_root == execution_info.state_root,
+            "The replay of transaction {:?} diverges from its execution, status: {:?}, state root: {:?}, \
+            but the executed status: {:?}, state root: {:?}",
+            tx_hash,
+            result.raw_output.status,
+            changeset.state_root,
+            execution_info.status,
+            execution_info.state_root
+        );
+        Ok(result)
+    }
+
     #[named]
     pub fn validate_l1_block(
         &self,
@@ -279,22 +428,37 @@ impl ExecutorActor {
     }
 
     #[named]
-    pub fn validate_l2_tx(&self, mut tx: KanariTransaction) -> Result<VerifiedMoveOSTransaction> {
+    pub fn validate_l2_tx(&self, tx: KanariTransaction) -> Result<VerifiedMoveOSTransaction> {
         let fn_name = function_name!();
         let _timer = self
             .metrics
             .executor_validate_tx_latency_seconds
             .with_label_values(&[fn_name])
             .start_timer();
+        let tx_size = tx.tx_size();
+        let result = self.validate_l2_tx_at(tx, self.root.clone());
+
+        self.metrics
+            .executor_validate_tx_bytes
+            .with_label_values(&[fn_name])
+            .observe(tx_size as f64);
+        result
+    }
+
+    /// Validate the L2 transaction at the given root, e.g. to replay it at the root before it.
+    pub fn validate_l2_tx_at(
+        &self,
+        mut tx: KanariTransaction,
+        root: ObjectMeta,
+    ) -> Result<VerifiedMoveOSTransaction> {
         let sender = tx.sender();
         let tx_hash = tx.tx_hash();
         tracing::debug!("executor validate_l2_tx: {:?}, sender: {}", tx_hash, sender);
 
         let authenticator = tx.authenticator_info();
-        let mut moveos_tx: MoveOSTransaction = tx.into_moveos_transaction(self.root.clone());
-        let tx_size = moveos_tx.ctx.tx_size;
-        let tx_result = self.validate_authenticator(&moveos_tx.ctx, authenticator);
-        let result = match tx_result {
+        let mut moveos_tx: MoveOSTransaction = tx.into_moveos_transaction(root.clone());
+        let tx_result = self.validate_authenticator(&moveos_tx.ctx, authenticator, root.clone());
+        match tx_result {
             Ok(vm_result) => match vm_result {
                 Ok(tx_validate_result) => {
                     // Add the tx_validate_result to the context
@@ -317,7 +481,7 @@ impl ExecutorActor {
                     }
                 }
                 Err(e) => {
-                    let resolver = RootObjectResolver::new(self.root.clone(), &self.moveos_store);
+                    let resolver = RootObjectResolver::new(root, &self.moveos_store);
                     let status_view = explain_vm_status(&resolver, e.clone())?;
                     tracing::warn!(
                         "transaction validate vm error, tx_hash: {:?}, error:{:?}",
@@ -336,20 +500,16 @@ impl ExecutorActor {
                 );
                 Err(e)
             }
-        };
-
-        self.metrics
-            .executor_validate_tx_bytes
-            .with_label_values(&[fn_name])
-            .observe(tx_size as f64);
-        result
+        }
     }
 
+    /// Validate the authenticator of the transaction via the validator functions at the given root.
     #[named]
     pub fn validate_authenticator(
         &self,
         ctx: &TxContext,
         authenticator: AuthenticatorInfo,
+        root: ObjectMeta,
     ) -> Result<ValidateAuthenticatorResult> {
         let fn_name = function_name!();
         let _timer = self
@@ -357,7 +517,11 @@ impl ExecutorActor {
             .executor_validate_tx_latency_seconds
             .with_label_values(&[fn_name])
             .start_timer();
-        let tx_validator = self.as_module_binding::<TransactionValidator>();
+        let caller = RootFunctionCaller {
+            moveos: &self.moveos,
+            root,
+        };
+        let tx_validator = caller.as_module_binding::<TransactionValidator>();
         let tx_validate_function_result = tx_validator
             .validate(ctx, authenticator.clone())?
             .into_result();
@@ -367,7 +531,8 @@ impl ExecutorActor {
                 let auth_validator_option = tx_validate_result.auth_validator();
                 match auth_validator_option {
                     Some(auth_validator) => {
-                        let auth_validator_caller = AuthValidatorCaller::new(self, auth_validator);
+                        let auth_validator_caller =
+                            AuthValidatorCaller::new(&caller, auth_validator);
                         let auth_validator_function_result = auth_validator_caller
                             .validate(ctx, authenticator.authenticator.payload)?
                             .into_result();
@@ -388,8 +553,9 @@ impl ExecutorActor {
     pub fn convert_to_verified_tx_for_dry_run(
         &self,
         tx_data: KanariTransactionData,
+        root: Option<ObjectMeta>,
     ) -> Result<VerifiedMoveOSTransaction> {
-        let root = self.root.clone();
+        let root = root.unwrap_or_else(|| self.root.clone());
 
         // The dry run supports unsigned transactions, but when calculating the transaction size,
         // the length of the signature part needs to be included.
@@ -432,6 +598,7 @@ impl ExecutorActor {
                 module_bundle,
                 init_function_modules: vec![],
             },
+            MoveAction::Batch(calls) => VerifiedMoveAction::Batch { calls },
         };
 
         Ok(VerifiedMoveOSTransaction::new(
@@ -510,6 +677,17 @@ impl Handler<ExecuteTransactionMessage> for ExecutorActor {
     }
 }
 
+#[async_trait]
+impl Handler<ExecuteBatchTransactionMessage> for ExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: ExecuteBatchTransactionMessage,
+        _ctx: &mut ActorContext,
+    ) -> ExecuteBatchTransactionResult {
+        self.execute_batch(msg.txs)
+    }
+}
+
 #[async_trait]
 impl Handler<GetRootMessage> for ExecutorActor {
     async fn handle(
@@ -532,6 +710,20 @@ impl Handler<SaveStateChangeSetMessage> for ExecutorActor {
     }
 }
 
+/// Call the readonly functions at the given root
+struct RootFunctionCaller<'a> {
+    moveos: &'a MoveOS,
+    root: ObjectMeta,
+}
+
+impl MoveFunctionCaller for RootFunctionCaller<'_> {
+    fn call_function(&self, ctx: &TxContext, call: FunctionCall) -> Result<FunctionResult> {
+        Ok(self
+            .moveos
+            .execute_readonly_function(self.root.clone(), ctx, call))
+    }
+}
+
 impl MoveFunctionCaller for ExecutorActor {
     fn call_function(&self, ctx: &TxContext, call: FunctionCall) -> Result<FunctionResult> {
         Ok(self
@@ -568,7 +760,18 @@ impl Handler<ConvertL2TransactionData> for ExecutorActor {
         msg: ConvertL2TransactionData,
         _ctx: &mut ActorContext,
     ) -> Result<VerifiedMoveOSTransaction> {
-        self.convert_to_verified_tx_for_dry_run(msg.tx_data)
+        self.convert_to_verified_tx_for_dry_run(msg.tx_data, msg.root)
+    }
+}
+
+#[async_trait]
+impl Handler<TraceL2TxMessage> for ExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: TraceL2TxMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<DryRunTransactionResult> {
+        self.trace_l2_tx(msg.tx, msg.sequence_info, msg.root, msg.execution_info)
     }
 }
 
@@ -579,6 +782,6 @@ impl Handler<DryRunTransactionMessage> for ExecutorActor {
         msg: DryRunTransactionMessage,
         _ctx: &mut ActorContext,
     ) -> Result<DryRunTransactionResult> {
-        self.dry_run(msg.tx)
+        self.dry_run(msg.tx, msg.trace)
     }
 }
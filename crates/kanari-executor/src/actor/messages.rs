@@ -6,6 +6,7 @@ use coerce::actor::message::Message;
 use kanari_types::address::MultiChainAddress;
 use kanari_types::transaction::{
     KanariTransaction, KanariTransactionData, L1BlockWithBody, L1Transaction,
+    TransactionSequenceInfo,
 };
 use move_core_types::account_address::AccountAddress;
 use moveos_types::access_path::AccessPath;
@@ -15,6 +16,7 @@ use moveos_types::moveos_std::event::{AnnotatedEvent, Event, EventID};
 use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::state::{AnnotatedState, FieldKey, ObjectState, StateChangeSetExt};
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
+use moveos_types::trace::TxTrace;
 use moveos_types::transaction::TransactionExecutionInfo;
 use moveos_types::transaction::TransactionOutput;
 use moveos_types::transaction::VerifiedMoveOSTransaction;
@@ -64,6 +66,33 @@ impl Message for ExecuteTransactionMessage {
     type Result = Result<ExecuteTransactionResult>;
 }
 
+/// Execute a batch of sequenced transactions with the parallel executor
+#[derive(Debug)]
+pub struct ExecuteBatchTransactionMessage {
+    pub txs: Vec<VerifiedMoveOSTransaction>,
+}
+
+/// The transactions of a batch are committed in order until the first failed one,
+/// the `results` are the committed transactions and the `error` is the failure.
+#[derive(Debug)]
+pub struct ExecuteBatchTransactionResult {
+    pub results: Vec<ExecuteTransactionResult>,
+    pub error: Option<anyhow::Error>,
+}
+
+impl ExecuteBatchTransactionResult {
+    pub fn into_result(self) -> Result<Vec<ExecuteTransactionResult>> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.results),
+        }
+    }
+}
+
+impl Message for ExecuteBatchTransactionMessage {
+    type Result = ExecuteBatchTransactionResult;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExecuteViewFunctionMessage {
     pub call: FunctionCall,
@@ -213,6 +242,15 @@ impl Message for GetStateChangeSetsMessage {
     type Result = Result<Vec<Option<StateChangeSetExt>>>;
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GetStateNodesMessage {
+    pub node_hashes: Vec<H256>,
+}
+
+impl Message for GetStateNodesMessage {
+    type Result = Result<Vec<Option<Vec<u8>>>>;
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CheckStateChangeSetsMessage {
     pub tx_orders: Vec<u64>,
@@ -225,6 +263,8 @@ impl Message for CheckStateChangeSetsMessage {
 #[derive(Debug)]
 pub struct ConvertL2TransactionData {
     pub tx_data: KanariTransactionData,
+    /// The root to execute the transaction at, the current root if None.
+    pub root: Option<ObjectMeta>,
 }
 impl Message for ConvertL2TransactionData {
     type Result = Result<VerifiedMoveOSTransaction>;
@@ -233,13 +273,27 @@ impl Message for ConvertL2TransactionData {
 #[derive(Debug)]
 pub struct DryRunTransactionMessage {
     pub tx: VerifiedMoveOSTransaction,
+    pub trace: bool,
 }
 impl Message for DryRunTransactionMessage {
     type Result = Result<DryRunTransactionResult>;
 }
 
+#[derive(Debug)]
+pub struct TraceL2TxMessage {
+    pub tx: KanariTransaction,
+    pub sequence_info: TransactionSequenceInfo,
+    /// The root before the transaction
+    pub root: ObjectMeta,
+    pub execution_info: TransactionExecutionInfo,
+}
+impl Message for TraceL2TxMessage {
+    type Result = Result<DryRunTransactionResult>;
+}
+
 #[derive(Debug)]
 pub struct DryRunTransactionResult {
     pub raw_output: RawTransactionOutput,
     pub vm_error_info: Option<VMErrorInfo>,
+    pub trace: Option<TxTrace>,
 }
@@ -4,7 +4,8 @@
 use super::messages::{
     AnnotatedStatesMessage, CheckStateChangeSetsMessage, ExecuteViewFunctionMessage,
     GetAnnotatedEventsByEventHandleMessage, GetAnnotatedEventsByEventIDsMessage,
-    GetEventsByEventHandleMessage, GetStateChangeSetsMessage, RefreshStateMessage, StatesMessage,
+    GetEventsByEventHandleMessage, GetStateChangeSetsMessage, GetStateNodesMessage,
+    RefreshStateMessage, StatesMessage,
 };
 use crate::actor::messages::{
     GetEventsByEventIDsMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
@@ -367,3 +368,17 @@ impl Handler<CheckStateChangeSetsMessage> for ReaderExecutorActor {
             .check_state_change_set(tx_orders)
     }
 }
+
+#[async_trait]
+impl Handler<GetStateNodesMessage> for ReaderExecutorActor {
+    async fn handle(
+        &mut self,
+        msg: GetStateNodesMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<Option<Vec<u8>>>> {
+        let GetStateNodesMessage { node_hashes } = msg;
+        self.moveos_store
+            .get_state_node_store()
+            .multi_get_local(&node_hashes)
+    }
+}
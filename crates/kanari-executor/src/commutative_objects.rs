@@ -0,0 +1,126 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use kanari_types::addresses::KANARI_FRAMEWORK_ADDRESS;
+use kanari_types::framework::address_mapping::KanariToBitcoinAddressMapping;
+use kanari_types::framework::coin_store::CoinStore;
+use kanari_types::framework::gas_coin::KARI;
+use kanari_types::framework::onchain_config::OnchainConfig;
+use kanari_types::framework::transaction_fee::TransactionFeePool;
+use move_core_types::account_address::AccountAddress;
+use moveos::parallel_executor::CommutativeObjects;
+use moveos_types::moveos_std::object::{DynamicField, ObjectID};
+use moveos_types::moveos_std::timestamp::Timestamp;
+use moveos_types::state::{FieldKey, MoveState};
+use moveos_types::state_resolver::StateResolver;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy)]
+enum ObjectKind {
+    /// The global time only moves forward, the latest time wins
+    Timestamp,
+    /// The object is borrowed mutably but the value is not changed
+    Unchanged,
+    /// The gas fee stores, the transactions only deposit to them
+    CoinStore,
+}
+
+/// The framework objects written by every L2 transaction in the `pre_execute` and `post_execute` functions:
+/// the global timestamp, the address mapping, the transaction fee pool and the gas revenue stores.
+/// The transactions do not conflict on them in the parallel execution.
+pub struct FrameworkCommutativeObjects {
+    objects: BTreeMap<ObjectID, ObjectKind>,
+}
+
+impl FrameworkCommutativeObjects {
+    /// Load the objects via the `resolver`, the gas revenue stores of the sequencer, the framework,
+    /// and the `contract_addresses` are included if they exist.
+    pub fn load<R: StateResolver>(
+        resolver: &R,
+        contract_addresses: impl IntoIterator<Item = AccountAddress>,
+    ) -> Result<Self> {
+        let mut objects = BTreeMap::new();
+        objects.insert(Timestamp::object_id(), ObjectKind::Timestamp);
+        objects.insert(
+            KanariToBitcoinAddressMapping::object_id(),
+            ObjectKind::Unchanged,
+        );
+
+        let fee_pool_id = TransactionFeePool::object_id();
+        if let Some(fee_pool) = resolver.get_object(&fee_pool_id)? {
+            let mut revenue_addresses = vec![KANARI_FRAMEWORK_ADDRESS];
+            if let Some(config) =
+                resolver.get_object(&OnchainConfig::get_onchain_config_object_id())?
+            {
+                revenue_addresses.push(config.value_as::<OnchainConfig>()?.sequencer);
+            }
+            revenue_addresses.extend(contract_addresses);
+            for address in revenue_addresses {
+                let field = resolver.get_field_at(
+                    fee_pool.state_root(),
+                    &FieldKey::derive_from_address(&address),
+                )?;
+                if let Some(field) = field {
+                    let revenue_store = field
+                        .value_as_uncheck::<DynamicField<AccountAddress, ObjectID>>()?
+                        .value;
+                    objects.insert(revenue_store, ObjectKind::CoinStore);
+                }
+            }
+            objects.insert(
+                fee_pool.value_as::<TransactionFeePool>()?.fee.id,
+                ObjectKind::CoinStore,
+            );
+            objects.insert(fee_pool_id, ObjectKind::Unchanged);
+        }
+        Ok(Self { objects })
+    }
+}
+
+impl CommutativeObjects for FrameworkCommutativeObjects {
+    fn contains(&self, id: &ObjectID) -> bool {
+        self.objects.contains_key(id)
+    }
+
+    fn rebase(
+        &self,
+        id: &ObjectID,
+        base: &[u8],
+        current: &[u8],
+        written: &[u8],
+    ) -> Result<Option<Vec<u8>>> {
+        let Some(kind) = self.objects.get(id) else {
+            return Ok(None);
+        };
+        match kind {
+            ObjectKind::Timestamp => {
+                let current_time = Timestamp::from_bytes(current.to_vec())?;
+                let written_time = Timestamp::from_bytes(written.to_vec())?;
+                // The transaction read the time after updating it, so its view is the same as the serial execution
+                // only if its time is not earlier than the current time.
+                Ok((written_time.milliseconds >= current_time.milliseconds)
+                    .then(|| written.to_vec()))
+            }
+            ObjectKind::Unchanged => Ok((written == base).then(|| current.to_vec())),
+            ObjectKind::CoinStore => {
+                let base_store = CoinStore::<KARI>::from_bytes(base.to_vec())?;
+                let current_store = CoinStore::<KARI>::from_bytes(current.to_vec())?;
+                let written_store = CoinStore::<KARI>::from_bytes(written.to_vec())?;
+                if base_store.frozen()
+                    || current_store.frozen()
+                    || written_store.frozen()
+                    || current_store.balance() < base_store.balance()
+                    || written_store.balance() < base_store.balance()
+                {
+                    return Ok(None);
+                }
+                let deposited = written_store.balance() - base_store.balance();
+                Ok(current_store
+                    .balance()
+                    .checked_add(deposited)
+                    .map(|balance| CoinStore::<KARI>::new(balance, false).to_bytes()))
+            }
+        }
+    }
+}
@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod actor;
+pub mod commutative_objects;
 pub mod metrics;
 pub mod proxy;
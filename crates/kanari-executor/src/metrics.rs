@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use metrics::metrics_util::LATENCY_SEC_BUCKETS;
-use prometheus::{HistogramVec, Registry, register_histogram_vec_with_registry};
+use prometheus::{
+    HistogramVec, IntCounter, Registry, register_histogram_vec_with_registry,
+    register_int_counter_with_registry,
+};
 
 #[derive(Debug)]
 pub struct ExecutorMetrics {
@@ -10,6 +13,9 @@ pub struct ExecutorMetrics {
     pub executor_execute_tx_bytes: HistogramVec,
     pub executor_validate_tx_latency_seconds: HistogramVec,
     pub executor_validate_tx_bytes: HistogramVec,
+    pub executor_parallel_batch_txs: IntCounter,
+    pub executor_parallel_reexecuted_txs: IntCounter,
+    pub executor_parallel_serial_fallback_batches: IntCounter,
 }
 
 impl ExecutorMetrics {
@@ -51,6 +57,24 @@ impl ExecutorMetrics {
                 registry,
             )
             .unwrap(),
+            executor_parallel_batch_txs: register_int_counter_with_registry!(
+                "executor_parallel_batch_txs",
+                "Executor parallel execution txs",
+                registry,
+            )
+            .unwrap(),
+            executor_parallel_reexecuted_txs: register_int_counter_with_registry!(
+                "executor_parallel_reexecuted_txs",
+                "Executor parallel execution txs re-executed because of conflicts",
+                registry,
+            )
+            .unwrap(),
+            executor_parallel_serial_fallback_batches: register_int_counter_with_registry!(
+                "executor_parallel_serial_fallback_batches",
+                "Executor parallel execution batches executed serially because of module upgrades",
+                registry,
+            )
+            .unwrap(),
         }
     }
 }
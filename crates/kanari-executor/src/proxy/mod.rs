@@ -3,10 +3,11 @@
 
 use crate::actor::messages::{
     CheckStateChangeSetsMessage, ConvertL2TransactionData, DryRunTransactionResult,
-    GetAnnotatedEventsByEventIDsMessage, GetEventsByEventHandleMessage, GetEventsByEventIDsMessage,
-    GetStateChangeSetsMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
-    ListStatesMessage, RefreshStateMessage, SaveStateChangeSetMessage, ValidateL1BlockMessage,
-    ValidateL1TxMessage,
+    ExecuteBatchTransactionResult, GetAnnotatedEventsByEventIDsMessage,
+    GetEventsByEventHandleMessage, GetEventsByEventIDsMessage, GetStateChangeSetsMessage,
+    GetStateNodesMessage, GetTxExecutionInfosByHashMessage, ListAnnotatedStatesMessage,
+    ListStatesMessage, RefreshStateMessage, SaveStateChangeSetMessage, TraceL2TxMessage,
+    ValidateL1BlockMessage, ValidateL1TxMessage,
 };
 use crate::actor::reader_executor::ReaderExecutorActor;
 use crate::actor::{
@@ -22,6 +23,7 @@ use kanari_types::bitcoin::network::BitcoinNetwork;
 use kanari_types::framework::chain_id::ChainID;
 use kanari_types::transaction::{
     KanariTransaction, KanariTransactionData, L1BlockWithBody, L1Transaction,
+    TransactionSequenceInfo,
 };
 use move_core_types::account_address::AccountAddress;
 use moveos_types::function_return_value::{AnnotatedFunctionResult, FunctionResult};
@@ -89,7 +91,29 @@ impl ExecutorProxy {
         tx_data: KanariTransactionData,
     ) -> Result<VerifiedMoveOSTransaction> {
         self.actor
-            .send(ConvertL2TransactionData { tx_data })
+            .send(ConvertL2TransactionData {
+                tx_data,
+                root: None,
+            })
+            .await?
+    }
+
+    /// Replay the executed L2 transaction at the `root` before it with the tracer,
+    /// returns an error if the replay diverges from the `execution_info`.
+    pub async fn trace_l2_tx(
+        &self,
+        tx: KanariTransaction,
+        sequence_info: TransactionSequenceInfo,
+        root: ObjectMeta,
+        execution_info: TransactionExecutionInfo,
+    ) -> Result<DryRunTransactionResult> {
+        self.actor
+            .send(TraceL2TxMessage {
+                tx,
+                sequence_info,
+                root,
+                execution_info,
+            })
             .await?
     }
 
@@ -105,13 +129,39 @@ impl ExecutorProxy {
         Ok((result.output, result.transaction_info))
     }
 
+    pub async fn execute_batch_transactions(
+        &self,
+        txs: Vec<VerifiedMoveOSTransaction>,
+    ) -> Result<Vec<(TransactionOutput, TransactionExecutionInfo)>> {
+        let results = self
+            .execute_batch_transactions_until_failure(txs)
+            .await?
+            .into_result()?;
+        Ok(results
+            .into_iter()
+            .map(|result| (result.output, result.transaction_info))
+            .collect())
+    }
+
+    /// Execute the batch until the first failed transaction, the transactions before it are committed.
+    pub async fn execute_batch_transactions_until_failure(
+        &self,
+        txs: Vec<VerifiedMoveOSTransaction>,
+    ) -> Result<ExecuteBatchTransactionResult> {
+        Ok(self
+            .actor
+            .send(crate::actor::messages::ExecuteBatchTransactionMessage { txs })
+            .await?)
+    }
+
     pub async fn dry_run_transaction(
         &self,
         tx: VerifiedMoveOSTransaction,
+        trace: bool,
     ) -> Result<DryRunTransactionResult> {
         let result = self
             .actor
-            .send(crate::actor::messages::DryRunTransactionMessage { tx })
+            .send(crate::actor::messages::DryRunTransactionMessage { tx, trace })
             .await??;
         Ok(result)
     }
@@ -288,6 +338,12 @@ impl ExecutorProxy {
             .await?
     }
 
+    pub async fn get_state_nodes(&self, node_hashes: Vec<H256>) -> Result<Vec<Option<Vec<u8>>>> {
+        self.reader_actor
+            .send(GetStateNodesMessage { node_hashes })
+            .await?
+    }
+
     pub async fn check_state_change_sets(&self, tx_orders: Vec<u64>) -> Result<Vec<u64>> {
         self.reader_actor
             .send(CheckStateChangeSetsMessage { tx_orders })
@@ -0,0 +1,631 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test::RustBindingTest;
+use anyhow::{Context, Result, anyhow, ensure};
+use bitcoin::{
+    Amount, Block, BlockHash, CompactTarget, OutPoint, PublicKey, ScriptBuf, Sequence, Transaction,
+    TxIn, TxMerkleNode, TxOut, WPubkeyHash, Witness,
+    absolute::LockTime,
+    block::{self, Header},
+    hashes::Hash,
+    key::{Keypair, Secp256k1, TweakedPublicKey, XOnlyPublicKey},
+    opcodes::all::{OP_CHECKMULTISIG, OP_CHECKSIG, OP_PUSHNUM_2, OP_PUSHNUM_3},
+    script::{Builder, PushBytesBuf},
+    secp256k1::All,
+    transaction::Version,
+};
+use kanari_types::{
+    bitcoin::{
+        ord::{Inscription, InscriptionID, InscriptionRecord, InscriptionStore, OrdModule},
+        types,
+        utxo::{self, UTXO},
+    },
+    into_address::IntoAddress,
+    multichain_id::KanariMultiChainID,
+    transaction::{L1Block, L1BlockWithBody},
+};
+use move_core_types::account_address::AccountAddress;
+use moveos_types::{
+    access_path::AccessPath, module_binding::MoveFunctionCaller, move_std::string::MoveString,
+    state::MoveType, state_resolver::StateReader,
+};
+use rand::{Rng, SeedableRng, rngs::StdRng, seq::SliceRandom};
+use std::collections::{BTreeSet, HashMap, HashSet};
+use tracing::{debug, info};
+
+const BLOCK_SUBSIDY: u64 = 50 * 100_000_000;
+const MAX_FEE: u64 = 1_000;
+const GENESIS_TIME: u32 = 1_700_000_000;
+const SCHNORR_SIGNATURE_SIZE: usize = 64;
+const ECDSA_SIGNATURE_SIZE: usize = 72;
+const CONTENT_TYPES: [&str; 3] = ["text/plain;charset=utf-8", "application/json", "image/png"];
+
+#[derive(Debug, Clone)]
+pub struct FuzzConfig {
+    pub max_txs_per_block: usize,
+    pub max_inputs_per_tx: usize,
+    pub max_outputs_per_tx: usize,
+    /// The probability of a Taproot input revealing inscription envelopes
+    pub inscribe_probability: f64,
+    pub op_return_probability: f64,
+}
+
+impl Default for FuzzConfig {
+    fn default() -> Self {
+        Self {
+            max_txs_per_block: 6,
+            max_inputs_per_tx: 3,
+            max_outputs_per_tx: 4,
+            inscribe_probability: 0.4,
+            op_return_probability: 0.15,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum OutputKind {
+    Taproot,
+    P2wpkh,
+    /// P2WSH 2-of-3 multisig with the witness script
+    Multisig(ScriptBuf),
+    OpReturn,
+}
+
+#[derive(Debug, Clone)]
+struct GeneratedOutput {
+    txout: TxOut,
+    kind: OutputKind,
+}
+
+/// Generate synthetic Bitcoin blocks, execute them via `execute_l1_block`,
+/// and check the invariants of the UTXO set and the inscription store after every block.
+/// The generator keeps its own model of the UTXO set, so no real chain data is needed.
+/// A failure can be reproduced by the seed.
+pub struct BitcoinBlockFuzzer {
+    seed: u64,
+    rng: StdRng,
+    config: FuzzConfig,
+    secp: Secp256k1<All>,
+    binding_test: RustBindingTest,
+    next_height: u64,
+    prev_block_hash: BlockHash,
+    /// All the generated outputs
+    outputs: HashMap<OutPoint, GeneratedOutput>,
+    /// The outputs can be spent, the OP_RETURN outputs are excluded
+    unspent: Vec<OutPoint>,
+    spent: HashSet<OutPoint>,
+    /// The inscriptions revealed in the generated blocks
+    inscriptions: Vec<InscriptionID>,
+    initial_store: InscriptionStore,
+}
+
+impl BitcoinBlockFuzzer {
+    pub fn new(seed: u64, config: FuzzConfig) -> Result<Self> {
+        let binding_test = RustBindingTest::new()?;
+        let initial_store = Self::get_inscription_store(&binding_test)?;
+        Ok(Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            config,
+            secp: Secp256k1::new(),
+            binding_test,
+            next_height: 1,
+            prev_block_hash: BlockHash::all_zeros(),
+            outputs: HashMap::new(),
+            unspent: vec![],
+            spent: HashSet::new(),
+            inscriptions: vec![],
+            initial_store,
+        })
+    }
+
+    /// Generate and execute `block_count` blocks, check the invariants after every block
+    pub fn run(&mut self, block_count: u64) -> Result<()> {
+        for _ in 0..block_count {
+            let height = self.next_height;
+            let block = self.generate_block();
+            debug!(
+                "seed {}, execute block {} with {} txs",
+                self.seed,
+                height,
+                block.txdata.len()
+            );
+            self.execute_block(block)
+                .with_context(|| format!("seed {}, execute block {}", self.seed, height))?;
+            self.check_invariants()
+                .with_context(|| format!("seed {}, check block {}", self.seed, height))?;
+        }
+        info!(
+            "seed {}, executed {} blocks, utxos: {}, inscriptions: {}",
+            self.seed,
+            block_count,
+            self.unspent.len(),
+            self.inscriptions.len()
+        );
+        Ok(())
+    }
+
+    pub fn generate_block(&mut self) -> Block {
+        let height = self.next_height;
+        let mut txdata = vec![];
+        let mut fees = 0;
+        let tx_count = self.rng.gen_range(0..=self.config.max_txs_per_block);
+        for _ in 0..tx_count {
+            if self.unspent.is_empty() {
+                break;
+            }
+            let (tx, fee) = self.generate_tx();
+            fees += fee;
+            txdata.push(tx);
+        }
+        // The coinbase outputs can only be spent in the next blocks, so generate it at last
+        txdata.insert(0, self.generate_coinbase(height, BLOCK_SUBSIDY + fees));
+
+        let mut block = Block {
+            header: Header {
+                version: block::Version::TWO,
+                prev_blockhash: self.prev_block_hash,
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: GENESIS_TIME + height as u32 * 600,
+                bits: CompactTarget::from_consensus(0x207fffff),
+                nonce: self.rng.r#gen(),
+            },
+            txdata,
+        };
+        block.header.merkle_root = block
+            .compute_merkle_root()
+            .expect("block should have coinbase tx");
+        block
+    }
+
+    pub fn execute_block(&mut self, block: Block) -> Result<()> {
+        let block_hash = block.header.block_hash();
+        let move_block = types::Block::from(block.clone());
+        self.binding_test.execute_l1_block_and_tx(L1BlockWithBody {
+            block: L1Block {
+                chain_id: KanariMultiChainID::Bitcoin.multichain_id(),
+                block_height: self.next_height,
+                block_hash: block_hash.to_byte_array().to_vec(),
+            },
+            block_body: move_block.encode(),
+        })?;
+        self.prev_block_hash = block_hash;
+        self.next_height += 1;
+
+        let ord_module = self.binding_test.as_module_binding::<OrdModule>();
+        for tx in block.txdata {
+            let txid = tx.compute_txid().into_address();
+            let envelopes = ord_module.parse_inscription_from_tx(&types::Transaction::from(tx))?;
+            self.inscriptions
+                .extend((0..envelopes.len()).map(|index| InscriptionID::new(txid, index as u32)));
+        }
+        Ok(())
+    }
+
+    pub fn check_invariants(&self) -> Result<()> {
+        self.check_utxo_set()?;
+        self.check_inscriptions()
+    }
+
+    /// Every unspent output exists with the same value, spent and OP_RETURN outputs do not exist
+    fn check_utxo_set(&self) -> Result<()> {
+        let resolver = self.binding_test.resolver();
+        let utxo_ids = self
+            .unspent
+            .iter()
+            .map(|outpoint| utxo::derive_utxo_id(&(*outpoint).into()))
+            .collect::<Vec<_>>();
+        let states = resolver.get_states(AccessPath::objects(utxo_ids))?;
+        for (outpoint, state) in self.unspent.iter().zip(states) {
+            let state = state.ok_or_else(|| anyhow!("Missing utxo {}", outpoint))?;
+            let utxo = state.into_object::<UTXO>()?.value;
+            let expect_value = self.outputs[outpoint].txout.value.to_sat();
+            ensure!(
+                utxo.value == expect_value,
+                "Utxo {} value not match, expect: {}, actual: {}",
+                outpoint,
+                expect_value,
+                utxo.value
+            );
+        }
+
+        let removed_outpoints = self
+            .outputs
+            .iter()
+            .filter(|(outpoint, output)| {
+                self.spent.contains(outpoint) || matches!(output.kind, OutputKind::OpReturn)
+            })
+            .map(|(outpoint, _)| *outpoint)
+            .collect::<Vec<_>>();
+        let utxo_ids = removed_outpoints
+            .iter()
+            .map(|outpoint| utxo::derive_utxo_id(&(*outpoint).into()))
+            .collect::<Vec<_>>();
+        let states = resolver.get_states(AccessPath::objects(utxo_ids))?;
+        for (outpoint, state) in removed_outpoints.iter().zip(states) {
+            ensure!(
+                state.is_none(),
+                "Spent or OP_RETURN output {} should not be in utxo set",
+                outpoint
+            );
+        }
+        Ok(())
+    }
+
+    /// Every revealed envelope creates an inscription, the sequence and inscription numbers are contiguous,
+    /// and every inscription is sealed in an unspent output unless it is lost, unbound or burned.
+    fn check_inscriptions(&self) -> Result<()> {
+        let resolver = self.binding_test.resolver();
+        let store = Self::get_inscription_store(&self.binding_test)?;
+        let initial = &self.initial_store;
+        let created = store.next_sequence_number - initial.next_sequence_number;
+        let blessed = store.blessed_inscription_count - initial.blessed_inscription_count;
+        let cursed = store.cursed_inscription_count - initial.cursed_inscription_count;
+        ensure!(
+            created == blessed + cursed,
+            "Inscription count not match, created: {}, blessed: {}, cursed: {}",
+            created,
+            blessed,
+            cursed
+        );
+        ensure!(
+            created as usize == self.inscriptions.len(),
+            "Inscription count not match, created: {}, revealed envelopes: {}",
+            created,
+            self.inscriptions.len()
+        );
+
+        let object_ids = self
+            .inscriptions
+            .iter()
+            .map(|id| id.object_id())
+            .collect::<Vec<_>>();
+        let states = resolver.get_states(AccessPath::objects(object_ids))?;
+        let mut sequence_numbers = BTreeSet::new();
+        let mut blessed_numbers = BTreeSet::new();
+        let mut cursed_numbers = BTreeSet::new();
+        for (inscription_id, state) in self.inscriptions.iter().zip(states) {
+            let state = state.ok_or_else(|| anyhow!("Missing inscription {}", inscription_id))?;
+            let inscription = state.into_object::<Inscription>()?.value;
+            ensure!(
+                sequence_numbers.insert(inscription.sequence_number),
+                "Duplicate inscription sequence number {}",
+                inscription.sequence_number
+            );
+            let numbers = if inscription.is_cursed {
+                &mut cursed_numbers
+            } else {
+                &mut blessed_numbers
+            };
+            ensure!(
+                numbers.insert(inscription.inscription_number),
+                "Duplicate inscription number {}, cursed: {}",
+                inscription.inscription_number,
+                inscription.is_cursed
+            );
+            self.check_inscription_location(inscription_id, &inscription)?;
+        }
+
+        let expect_blessed_numbers = (initial.blessed_inscription_count
+            ..store.blessed_inscription_count)
+            .collect::<BTreeSet<_>>();
+        ensure!(
+            blessed_numbers == expect_blessed_numbers,
+            "Blessed inscription numbers are not contiguous"
+        );
+        // The cursed inscription number starts from 1, represents -1 in ord
+        let expect_cursed_numbers = (initial.cursed_inscription_count + 1
+            ..=store.cursed_inscription_count)
+            .collect::<BTreeSet<_>>();
+        ensure!(
+            cursed_numbers == expect_cursed_numbers,
+            "Cursed inscription numbers are not contiguous"
+        );
+        Ok(())
+    }
+
+    fn check_inscription_location(
+        &self,
+        inscription_id: &InscriptionID,
+        inscription: &Inscription,
+    ) -> Result<()> {
+        // The lost and unbound inscriptions use the zero txid
+        if inscription.location.outpoint.txid == AccountAddress::ZERO {
+            return Ok(());
+        }
+        let outpoint = inscription.location.outpoint();
+        ensure!(
+            !self.spent.contains(&outpoint),
+            "Inscription {} location {} has been spent",
+            inscription_id,
+            inscription.location
+        );
+        let output = self.outputs.get(&outpoint).ok_or_else(|| {
+            anyhow!(
+                "Inscription {} location {} is not a generated output",
+                inscription_id,
+                inscription.location
+            )
+        })?;
+        ensure!(
+            inscription.location.offset < output.txout.value.to_sat(),
+            "Inscription {} location {} offset out of the output value {}",
+            inscription_id,
+            inscription.location,
+            output.txout.value
+        );
+        if matches!(output.kind, OutputKind::OpReturn) {
+            return Ok(());
+        }
+        let utxo_id = utxo::derive_utxo_id(&inscription.location.outpoint);
+        let utxo = self
+            .binding_test
+            .resolver()
+            .get_states(AccessPath::object(utxo_id))?
+            .pop()
+            .flatten()
+            .ok_or_else(|| anyhow!("Missing utxo {}", outpoint))?
+            .into_object::<UTXO>()?
+            .value;
+        let sealed = utxo
+            .seals
+            .borrow(&MoveString::from(
+                Inscription::type_tag().to_canonical_string(),
+            ))
+            .map(|seals| seals.contains(&inscription_id.object_id()))
+            .unwrap_or(false);
+        ensure!(
+            sealed,
+            "Inscription {} is not sealed in utxo {}",
+            inscription_id,
+            outpoint
+        );
+        Ok(())
+    }
+
+    fn get_inscription_store(binding_test: &RustBindingTest) -> Result<InscriptionStore> {
+        let state = binding_test
+            .resolver()
+            .get_states(AccessPath::object(InscriptionStore::object_id()))?
+            .pop()
+            .flatten()
+            .ok_or_else(|| anyhow!("Missing InscriptionStore"))?;
+        Ok(state.into_object::<InscriptionStore>()?.value)
+    }
+
+    /// Spend random unspent outputs, the fee is always less than the first input value,
+    /// so the inscriptions do not go to the fee unless a pointer is set
+    fn generate_tx(&mut self) -> (Transaction, u64) {
+        let input_count = self
+            .rng
+            .gen_range(1..=self.config.max_inputs_per_tx.min(self.unspent.len()));
+        let mut spend_outputs = vec![];
+        for _ in 0..input_count {
+            let idx = self.rng.gen_range(0..self.unspent.len());
+            let outpoint = self.unspent.swap_remove(idx);
+            self.spent.insert(outpoint);
+            spend_outputs.push((outpoint, self.outputs[&outpoint].clone()));
+        }
+        let input_value = spend_outputs
+            .iter()
+            .map(|(_, output)| output.txout.value.to_sat())
+            .sum::<u64>();
+        let fee = self
+            .rng
+            .gen_range(0..=MAX_FEE.min(spend_outputs[0].1.txout.value.to_sat()));
+
+        let output_count = self.rng.gen_range(1..=self.config.max_outputs_per_tx);
+        let outputs = self.generate_outputs(output_count, input_value - fee, true);
+
+        let input = spend_outputs
+            .iter()
+            .map(|(outpoint, output)| TxIn {
+                previous_output: *outpoint,
+                script_sig: ScriptBuf::new(),
+                sequence: Sequence::ENABLE_RBF_NO_LOCKTIME,
+                witness: self.spend_witness(&output.kind, input_value),
+            })
+            .collect();
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input,
+            output: outputs.iter().map(|output| output.txout.clone()).collect(),
+        };
+        self.add_outputs(&tx, outputs);
+        (tx, fee)
+    }
+
+    /// The coinbase with BIP34 height, random outputs, and optional witness commitment and zero value output
+    fn generate_coinbase(&mut self, height: u64, value: u64) -> Transaction {
+        let extra_nonce = PushBytesBuf::from(self.rng.r#gen::<[u8; 8]>());
+        let script_sig = Builder::new()
+            .push_int(height as i64)
+            .push_slice(extra_nonce)
+            .into_script();
+        let output_count = self.rng.gen_range(1..=3);
+        let mut outputs = self.generate_outputs(output_count, value, false);
+        if self.rng.gen_bool(0.2) {
+            outputs.push(GeneratedOutput {
+                txout: TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: self.taproot_script(),
+                },
+                kind: OutputKind::Taproot,
+            });
+        }
+        if self.rng.gen_bool(0.5) {
+            let mut commitment = vec![0xaa, 0x21, 0xa9, 0xed];
+            commitment.extend(self.rng.r#gen::<[u8; 32]>());
+            outputs.push(GeneratedOutput {
+                txout: TxOut {
+                    value: Amount::ZERO,
+                    script_pubkey: ScriptBuf::new_op_return(
+                        PushBytesBuf::try_from(commitment).expect("commitment should be valid"),
+                    ),
+                },
+                kind: OutputKind::OpReturn,
+            });
+        }
+        let tx = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::null(),
+                script_sig,
+                sequence: Sequence::MAX,
+                witness: Witness::from_slice(&[[0u8; 32]]),
+            }],
+            output: outputs.iter().map(|output| output.txout.clone()).collect(),
+        };
+        self.add_outputs(&tx, outputs);
+        tx
+    }
+
+    fn add_outputs(&mut self, tx: &Transaction, outputs: Vec<GeneratedOutput>) {
+        let txid = tx.compute_txid();
+        for (vout, output) in outputs.into_iter().enumerate() {
+            let outpoint = OutPoint::new(txid, vout as u32);
+            if !matches!(output.kind, OutputKind::OpReturn) {
+                self.unspent.push(outpoint);
+            }
+            self.outputs.insert(outpoint, output);
+        }
+    }
+
+    /// Split the value to random outputs, some outputs may be zero value
+    fn generate_outputs(
+        &mut self,
+        output_count: usize,
+        value: u64,
+        allow_op_return: bool,
+    ) -> Vec<GeneratedOutput> {
+        let mut remaining = value;
+        let mut outputs = vec![];
+        for idx in 0..output_count {
+            let output_value = if idx + 1 == output_count {
+                remaining
+            } else {
+                self.rng.gen_range(0..=remaining)
+            };
+            remaining -= output_value;
+            let kind = if allow_op_return && self.rng.gen_bool(self.config.op_return_probability) {
+                OutputKind::OpReturn
+            } else {
+                match self.rng.gen_range(0..3) {
+                    0 => OutputKind::Taproot,
+                    1 => OutputKind::P2wpkh,
+                    _ => OutputKind::Multisig(self.multisig_script()),
+                }
+            };
+            let script_pubkey = match &kind {
+                OutputKind::Taproot => self.taproot_script(),
+                OutputKind::P2wpkh => {
+                    ScriptBuf::new_p2wpkh(&WPubkeyHash::from_byte_array(self.rng.r#gen()))
+                }
+                OutputKind::Multisig(script) => ScriptBuf::new_p2wsh(&script.wscript_hash()),
+                OutputKind::OpReturn => {
+                    let len = self.rng.gen_range(0..=80);
+                    let data = (0..len).map(|_| self.rng.r#gen()).collect::<Vec<u8>>();
+                    ScriptBuf::new_op_return(
+                        PushBytesBuf::try_from(data).expect("op_return data should be valid"),
+                    )
+                }
+            };
+            outputs.push(GeneratedOutput {
+                txout: TxOut {
+                    value: Amount::from_sat(output_value),
+                    script_pubkey,
+                },
+                kind,
+            });
+        }
+        outputs
+    }
+
+    fn spend_witness(&mut self, kind: &OutputKind, input_value: u64) -> Witness {
+        match kind {
+            OutputKind::Taproot if self.rng.gen_bool(self.config.inscribe_probability) => {
+                let (internal_key, script) = self.inscription_script(input_value);
+                let mut control_block = vec![0xc0];
+                control_block.extend(internal_key.serialize());
+                Witness::from_slice(&[
+                    self.random_bytes(SCHNORR_SIGNATURE_SIZE),
+                    script.into_bytes(),
+                    control_block,
+                ])
+            }
+            OutputKind::Taproot => {
+                Witness::from_slice(&[self.random_bytes(SCHNORR_SIGNATURE_SIZE)])
+            }
+            OutputKind::P2wpkh => {
+                let public_key = self.public_key();
+                Witness::from_slice(&[
+                    self.random_bytes(ECDSA_SIGNATURE_SIZE),
+                    public_key.to_bytes(),
+                ])
+            }
+            OutputKind::Multisig(script) => Witness::from_slice(&[
+                vec![],
+                self.random_bytes(ECDSA_SIGNATURE_SIZE),
+                self.random_bytes(ECDSA_SIGNATURE_SIZE),
+                script.to_bytes(),
+            ]),
+            OutputKind::OpReturn => unreachable!("OP_RETURN output can not be spent"),
+        }
+    }
+
+    /// The reveal script with one or two envelopes, the second envelope in the same input is cursed.
+    /// The pointer may be out of the output value, and the parent may be any revealed inscription.
+    fn inscription_script(&mut self, input_value: u64) -> (XOnlyPublicKey, ScriptBuf) {
+        let (internal_key, _) = Keypair::new(&self.secp, &mut self.rng).x_only_public_key();
+        let mut builder = Builder::new()
+            .push_slice(internal_key.serialize())
+            .push_opcode(OP_CHECKSIG);
+        let envelope_count = if self.rng.gen_bool(0.2) { 2 } else { 1 };
+        for _ in 0..envelope_count {
+            let mut record = InscriptionRecord::default();
+            let body_len = self.rng.gen_range(0..1200);
+            record.body = self.random_bytes(body_len);
+            let content_type = CONTENT_TYPES
+                .choose(&mut self.rng)
+                .expect("content types should not be empty");
+            record.set_content_type(content_type.to_string());
+            if self.rng.gen_bool(0.2) {
+                record.set_pointer(self.rng.gen_range(0..=input_value.saturating_mul(2)));
+            }
+            if self.rng.gen_bool(0.1) {
+                if let Some(parent) = self.inscriptions.choose(&mut self.rng) {
+                    record.set_parents(vec![*parent]);
+                }
+            }
+            builder = record.append_reveal_script_to_builder(builder);
+        }
+        (internal_key, builder.into_script())
+    }
+
+    fn multisig_script(&mut self) -> ScriptBuf {
+        let mut builder = Builder::new().push_opcode(OP_PUSHNUM_2);
+        for _ in 0..3 {
+            builder = builder.push_key(&self.public_key());
+        }
+        builder
+            .push_opcode(OP_PUSHNUM_3)
+            .push_opcode(OP_CHECKMULTISIG)
+            .into_script()
+    }
+
+    fn taproot_script(&mut self) -> ScriptBuf {
+        let (output_key, _) = Keypair::new(&self.secp, &mut self.rng).x_only_public_key();
+        ScriptBuf::new_p2tr_tweaked(TweakedPublicKey::dangerous_assume_tweaked(output_key))
+    }
+
+    fn public_key(&mut self) -> PublicKey {
+        PublicKey::new(Keypair::new(&self.secp, &mut self.rng).public_key())
+    }
+
+    fn random_bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.rng.r#gen()).collect()
+    }
+}
@@ -3,6 +3,7 @@
 
 mod bbn_tx_loader;
 pub mod binding_test;
+pub mod bitcoin_block_fuzzer;
 pub mod bitcoin_block_tester;
 #[cfg(test)]
 mod tests;
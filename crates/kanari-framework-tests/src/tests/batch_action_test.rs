@@ -0,0 +1,173 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use kanari_types::addresses::KANARI_FRAMEWORK_ADDRESS;
+use kanari_types::crypto::KanariKeyPair;
+use kanari_types::framework::account_coin_store::AccountCoinStoreModule;
+use kanari_types::framework::empty::Empty;
+use kanari_types::framework::gas_coin::KARI;
+use kanari_types::framework::transfer::TransferModule;
+use kanari_types::transaction::KanariTransactionData;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use move_core_types::u256::U256;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::move_types::FunctionId;
+use moveos_types::state::MoveStructType;
+use moveos_types::state_resolver::StateResolver;
+use moveos_types::transaction::{CallResultRef, FunctionCall, MoveAction};
+
+fn transfer_kari_call(to: AccountAddress, amount: U256) -> FunctionCall {
+    match TransferModule::create_transfer_coin_action(KARI::struct_tag(), to, amount) {
+        MoveAction::Function(call) => call,
+        _ => unreachable!(),
+    }
+}
+
+// `kari::faucet` is a friend function which mints KARI
+fn kari_faucet_call(to: AccountAddress, amount: U256) -> FunctionCall {
+    FunctionCall::new(
+        FunctionId::new(
+            ModuleId::new(KANARI_FRAMEWORK_ADDRESS, Identifier::new("kari").unwrap()),
+            Identifier::new("faucet").unwrap(),
+        ),
+        vec![],
+        vec![to.to_vec(), bcs::to_bytes(&amount).unwrap()],
+    )
+}
+
+fn create_kari_coin_store_call() -> FunctionCall {
+    FunctionCall::new(
+        FunctionId::new(
+            ModuleId::new(
+                KANARI_FRAMEWORK_ADDRESS,
+                Identifier::new("coin_store").unwrap(),
+            ),
+            Identifier::new("create_coin_store").unwrap(),
+        ),
+        vec![TypeTag::Struct(Box::new(KARI::struct_tag()))],
+        vec![],
+    )
+}
+
+#[tokio::test]
+async fn test_batch_action() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let kp = KanariKeyPair::generate_secp256k1();
+    let sender = kp.public().bitcoin_address().unwrap().to_kanari_address();
+    binding_test
+        .get_kari(sender.into(), U256::from(10000u64))
+        .unwrap();
+
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    let action = MoveAction::new_batch(vec![
+        Empty::empty_function_call(),
+        transfer_kari_call(alice, U256::from(100u64)),
+        transfer_kari_call(bob, U256::from(200u64)),
+    ]);
+    let tx = KanariTransactionData::new_for_test(sender, 0, action).sign(&kp);
+    binding_test.execute(tx).unwrap();
+
+    let resolver = binding_test.resolver();
+    for addr in [alice, bob] {
+        let coin_store_id = AccountCoinStoreModule::account_coin_store_id(addr, KARI::struct_tag());
+        assert!(resolver.get_object(&coin_store_id).unwrap().is_some());
+    }
+}
+
+#[tokio::test]
+async fn test_batch_action_is_atomic() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let kp = KanariKeyPair::generate_secp256k1();
+    let sender = kp.public().bitcoin_address().unwrap().to_kanari_address();
+    binding_test
+        .get_kari(sender.into(), U256::from(10000u64))
+        .unwrap();
+
+    // The second call aborts because of insufficient balance, the first transfer should be reverted
+    let alice = AccountAddress::random();
+    let action = MoveAction::new_batch(vec![
+        transfer_kari_call(alice, U256::from(100u64)),
+        transfer_kari_call(alice, U256::from(u64::MAX)),
+    ]);
+    let tx = KanariTransactionData::new_for_test(sender, 0, action).sign(&kp);
+    let result = binding_test.execute_as_result(tx).unwrap();
+    assert!(matches!(
+        result.transaction_info.status,
+        KeptVMStatus::MoveAbort(_, _)
+    ));
+
+    let coin_store_id = AccountCoinStoreModule::account_coin_store_id(alice, KARI::struct_tag());
+    assert!(
+        binding_test
+            .resolver()
+            .get_object(&coin_store_id)
+            .unwrap()
+            .is_none()
+    );
+}
+
+#[tokio::test]
+async fn test_batch_action_verify() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let kp = KanariKeyPair::generate_secp256k1();
+    let sender = kp.public().bitcoin_address().unwrap().to_kanari_address();
+
+    let invalid_batches = vec![
+        // Empty batch
+        vec![],
+        // The returned object is not consumed
+        vec![create_kari_coin_store_call()],
+        // Refer to the result of a later call
+        vec![
+            FunctionCall::new(
+                Empty::empty_function_call().function_id,
+                vec![],
+                vec![CallResultRef::new(1, 0).to_arg()],
+            ),
+            create_kari_coin_store_call(),
+        ],
+    ];
+    for calls in invalid_batches {
+        let tx =
+            KanariTransactionData::new_for_test(sender, 0, MoveAction::new_batch(calls)).sign(&kp);
+        assert!(binding_test.executor.validate_l2_tx(tx).is_err());
+    }
+}
+
+#[tokio::test]
+async fn test_dry_run_batch_action_verify() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let kp = KanariKeyPair::generate_secp256k1();
+    let sender = kp.public().bitcoin_address().unwrap().to_kanari_address();
+    binding_test
+        .get_kari(sender.into(), U256::from(10000u64))
+        .unwrap();
+
+    // The dry run skips the validation, the batch calls are verified when executing
+    let alice = AccountAddress::random();
+    for (call, executed) in [
+        (transfer_kari_call(alice, U256::from(100u64)), true),
+        (kari_faucet_call(alice, U256::from(100u64)), false),
+    ] {
+        let action = MoveAction::new_batch(vec![call]);
+        let tx_data = KanariTransactionData::new_for_test(sender, 0, action);
+        let verified_tx = binding_test
+            .executor
+            .convert_to_verified_tx_for_dry_run(tx_data, None)
+            .unwrap();
+        let result = binding_test.executor.dry_run(verified_tx, false).unwrap();
+        assert_eq!(result.raw_output.status == KeptVMStatus::Executed, executed);
+    }
+}
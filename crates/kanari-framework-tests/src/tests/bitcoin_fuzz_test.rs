@@ -0,0 +1,34 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::bitcoin_block_fuzzer::{BitcoinBlockFuzzer, FuzzConfig};
+
+const BLOCK_COUNT: u64 = 8;
+
+// Set BITCOIN_FUZZ_SEED to reproduce a failed seed
+fn fuzz_seeds() -> Vec<u64> {
+    match std::env::var("BITCOIN_FUZZ_SEED") {
+        Ok(seed) => vec![seed.parse().expect("BITCOIN_FUZZ_SEED should be a u64")],
+        Err(_) => vec![1, 2, 3],
+    }
+}
+
+#[tokio::test]
+async fn test_synthetic_blocks() {
+    let _ = tracing_subscriber::fmt::try_init();
+    for seed in fuzz_seeds() {
+        let mut fuzzer = BitcoinBlockFuzzer::new(seed, FuzzConfig::default()).unwrap();
+        fuzzer.run(BLOCK_COUNT).unwrap();
+    }
+}
+
+#[tokio::test]
+async fn test_synthetic_coinbase_only_blocks() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let config = FuzzConfig {
+        max_txs_per_block: 0,
+        ..Default::default()
+    };
+    let mut fuzzer = BitcoinBlockFuzzer::new(0, config).unwrap();
+    fuzzer.run(BLOCK_COUNT).unwrap();
+}
@@ -3,21 +3,29 @@
 
 use crate::binding_test;
 use crate::tests::bitcoin_data::load_block;
+use bitcoin::absolute::LockTime;
 use bitcoin::consensus::deserialize;
 use bitcoin::hashes::Hash;
-use bitcoin::{Block, OutPoint, Transaction, TxOut};
+use bitcoin::transaction::Version;
+use bitcoin::{
+    Amount, Block, BlockHash, CompactTarget, OutPoint, ScriptBuf, Sequence, Transaction, TxIn,
+    TxMerkleNode, TxOut, WPubkeyHash, Witness, block, script,
+};
 use hex::FromHex;
 use kanari_types::bitcoin::network::Network;
 use kanari_types::bitcoin::ord;
 use kanari_types::bitcoin::ord::{Inscription, InscriptionID};
+use kanari_types::bitcoin::pending_block::{PendingBlock, PendingBlockModule, PendingStore};
 use kanari_types::bitcoin::types::{self, Header};
 use kanari_types::bitcoin::utxo::{self, UTXO};
 use kanari_types::into_address::IntoAddress;
 use kanari_types::multichain_id::KanariMultiChainID;
-use kanari_types::transaction::L1BlockWithBody;
+use kanari_types::transaction::{L1BlockWithBody, L1Transaction};
+use move_core_types::ident_str;
+use move_core_types::value::MoveValue;
 use moveos_types::access_path::AccessPath;
-use moveos_types::module_binding::MoveFunctionCaller;
-use moveos_types::state_resolver::StateReader;
+use moveos_types::module_binding::{ModuleBinding, MoveFunctionCaller};
+use moveos_types::state_resolver::{StateReader, StateResolver};
 use std::collections::HashMap;
 use tracing::{debug, info};
 
@@ -210,3 +218,128 @@ async fn test_real_bocks() {
         test_block_process(blocks);
     }
 }
+
+fn coinbase_only_block(height: u64, prev_blockhash: BlockHash) -> Block {
+    let coinbase = Transaction {
+        version: Version::TWO,
+        lock_time: LockTime::ZERO,
+        input: vec![TxIn {
+            previous_output: OutPoint::null(),
+            script_sig: script::Builder::new().push_int(height as i64).into_script(),
+            sequence: Sequence::MAX,
+            witness: Witness::from_slice(&[[0u8; 32]]),
+        }],
+        output: vec![TxOut {
+            value: Amount::from_btc(50.0).unwrap(),
+            script_pubkey: ScriptBuf::new_p2wpkh(&WPubkeyHash::from_byte_array([height as u8; 20])),
+        }],
+    };
+    let mut block = Block {
+        header: block::Header {
+            version: block::Version::TWO,
+            prev_blockhash,
+            merkle_root: TxMerkleNode::all_zeros(),
+            time: 1_700_000_000 + height as u32 * 600,
+            bits: CompactTarget::from_consensus(0x207fffff),
+            nonce: 0,
+        },
+        txdata: vec![coinbase],
+    };
+    block.header.merkle_root = block.compute_merkle_root().unwrap();
+    block
+}
+
+fn execute_block_without_tx(
+    binding_test: &mut binding_test::RustBindingTest,
+    height: u64,
+    block: &Block,
+) -> Vec<L1Transaction> {
+    let move_block = kanari_types::bitcoin::types::Block::from(block.clone());
+    binding_test
+        .execute_l1_block(L1BlockWithBody {
+            block: kanari_types::transaction::L1Block {
+                chain_id: KanariMultiChainID::Bitcoin.multichain_id(),
+                block_height: height,
+                block_hash: block.header.block_hash().to_byte_array().to_vec(),
+            },
+            block_body: move_block.encode(),
+        })
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_pending_tx_confirmation() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+    let reorg_block_count = 2;
+    binding_test
+        .execute_function_call_via_sequencer(PendingBlockModule::create_function_call(
+            ident_str!("update_reorg_block_count_for_local"),
+            vec![],
+            vec![MoveValue::U64(reorg_block_count)],
+        ))
+        .unwrap();
+
+    let first_block = coinbase_only_block(1, BlockHash::all_zeros());
+    let first_block_hash = first_block.header.block_hash().into_address();
+    let txid = first_block.txdata[0].compute_txid().into_address();
+    let l1_txs = execute_block_without_tx(&mut binding_test, 1, &first_block);
+
+    // The tx is in the pending block, it can be reorged and its UTXO is not created
+    let pending_store = get_pending_store(&binding_test);
+    assert_eq!(pending_store.reorg_block_count, reorg_block_count);
+    assert!(
+        binding_test
+            .get_field(
+                &PendingBlock::object_id(first_block_hash),
+                &PendingBlock::tx_field_key(txid)
+            )
+            .unwrap()
+            .is_some()
+    );
+    let confirmation = pending_store.tx_confirmation(1, true);
+    assert_eq!(confirmation.confirmations, 1);
+    assert!(!confirmation.finalized);
+    let bitcoin_module = binding_test.as_module_binding::<kanari_types::bitcoin::BitcoinModule>();
+    assert_eq!(bitcoin_module.get_tx_height(txid).unwrap(), None);
+
+    let mut prev_blockhash = first_block.header.block_hash();
+    for height in 2..=reorg_block_count + 1 {
+        let block = coinbase_only_block(height, prev_blockhash);
+        execute_block_without_tx(&mut binding_test, height, &block);
+        prev_blockhash = block.header.block_hash();
+        // The tx is still pending until it is processed
+        let confirmation = get_pending_store(&binding_test).tx_confirmation(1, true);
+        assert_eq!(confirmation.confirmations, height);
+        assert!(!confirmation.finalized);
+    }
+
+    // The first block is ready after `reorg_block_count` blocks, process its txs
+    for l1_tx in l1_txs {
+        binding_test.execute_l1_tx(l1_tx).unwrap();
+    }
+    assert!(
+        binding_test
+            .get_field(
+                &PendingBlock::object_id(first_block_hash),
+                &PendingBlock::tx_field_key(txid)
+            )
+            .unwrap()
+            .is_none()
+    );
+    let bitcoin_module = binding_test.as_module_binding::<kanari_types::bitcoin::BitcoinModule>();
+    assert_eq!(bitcoin_module.get_tx_height(txid).unwrap(), Some(1));
+    let confirmation = get_pending_store(&binding_test).tx_confirmation(1, false);
+    assert_eq!(confirmation.confirmations, reorg_block_count + 1);
+    assert!(confirmation.finalized);
+    check_utxo(first_block.txdata, &binding_test);
+}
+
+fn get_pending_store(binding_test: &binding_test::RustBindingTest) -> PendingStore {
+    binding_test
+        .get_object(&PendingStore::object_id())
+        .unwrap()
+        .unwrap()
+        .value_as::<PendingStore>()
+        .unwrap()
+}
@@ -1,8 +1,10 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+mod batch_action_test;
 mod bbn_test;
 mod bitcoin_data;
+mod bitcoin_fuzz_test;
 mod bitcoin_multisign_validator_tests;
 mod bitcoin_test;
 mod bitcoin_tester_test;
@@ -14,5 +16,8 @@ mod empty_tests;
 mod ethereum_test;
 mod multisign_account_tests;
 mod ord_test;
+mod parallel_executor_test;
 mod session_validator_tests;
+mod taproot_validator_tests;
+mod trace_tx_test;
 mod view_function_gas;
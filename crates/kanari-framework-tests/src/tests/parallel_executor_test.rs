@@ -0,0 +1,179 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use kanari_types::crypto::KanariKeyPair;
+use kanari_types::framework::empty::Empty;
+use kanari_types::framework::oracle::OracleModule;
+use kanari_types::transaction::KanariTransactionData;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::tx_context::TxContext;
+use moveos_types::transaction::{MoveAction, MoveOSTransaction, VerifiedMoveOSTransaction};
+
+#[tokio::test]
+async fn test_parallel_execution_same_as_serial() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let txs = (0..8)
+        .map(|_| {
+            let kp = KanariKeyPair::generate_secp256k1();
+            let sender = kp.public().bitcoin_address().unwrap().to_kanari_address();
+            let action = MoveAction::Function(Empty::empty_function_call());
+            let tx = KanariTransactionData::new_for_test(sender, 0, action).sign(&kp);
+            binding_test.executor.validate_l2_tx(tx).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    // Execute the txs one by one, every tx is executed on the root of the previous tx
+    let base_root = binding_test.root().clone();
+    let mut root = base_root.clone();
+    let mut serial_results = vec![];
+    for mut tx in txs.clone() {
+        tx.root = root;
+        let result = binding_test.executor.execute(tx).unwrap();
+        root = result.transaction_info.root_metadata();
+        serial_results.push(result);
+    }
+
+    binding_test
+        .executor
+        .refresh_state(base_root, false)
+        .unwrap();
+    let parallel_results = binding_test
+        .executor
+        .execute_batch(txs)
+        .into_result()
+        .unwrap();
+
+    assert_eq!(serial_results.len(), parallel_results.len());
+    for (serial, parallel) in serial_results.iter().zip(parallel_results.iter()) {
+        assert_eq!(serial.output.status, parallel.output.status);
+        assert_eq!(serial.output.changeset, parallel.output.changeset);
+        assert_eq!(serial.output.events, parallel.output.events);
+        assert_eq!(serial.transaction_info, parallel.transaction_info);
+    }
+
+    // Every new sender binds its bitcoin address and gets the gas coin via the faucet,
+    // so every tx after the first conflicts and is re-executed
+    let metrics = binding_test.executor.metrics();
+    assert_eq!(metrics.executor_parallel_batch_txs.get(), 8);
+    assert_eq!(metrics.executor_parallel_reexecuted_txs.get(), 7);
+    assert_eq!(metrics.executor_parallel_serial_fallback_batches.get(), 0);
+}
+
+#[tokio::test]
+async fn test_parallel_execution_gas_paying_txs() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    // The first tx of a sender creates the account and binds the bitcoin address
+    let keypairs = (0..8)
+        .map(|_| KanariKeyPair::generate_secp256k1())
+        .collect::<Vec<_>>();
+    for kp in &keypairs {
+        let sender = kp.public().bitcoin_address().unwrap().to_kanari_address();
+        let action = MoveAction::Function(Empty::empty_function_call());
+        let tx = KanariTransactionData::new_for_test(sender, 0, action).sign(kp);
+        binding_test.execute(tx).unwrap();
+    }
+
+    let txs = keypairs
+        .iter()
+        .map(|kp| {
+            let sender = kp.public().bitcoin_address().unwrap().to_kanari_address();
+            let action = MoveAction::Function(Empty::empty_function_call());
+            let tx = KanariTransactionData::new_for_test(sender, 1, action).sign(kp);
+            binding_test.executor.validate_l2_tx(tx).unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let base_root = binding_test.root().clone();
+    let mut root = base_root.clone();
+    let mut serial_results = vec![];
+    for mut tx in txs.clone() {
+        tx.root = root;
+        let result = binding_test.executor.execute(tx).unwrap();
+        root = result.transaction_info.root_metadata();
+        serial_results.push(result);
+    }
+
+    binding_test
+        .executor
+        .refresh_state(base_root, false)
+        .unwrap();
+    let parallel_results = binding_test
+        .executor
+        .execute_batch(txs)
+        .into_result()
+        .unwrap();
+
+    assert_eq!(serial_results.len(), parallel_results.len());
+    for (serial, parallel) in serial_results.iter().zip(parallel_results.iter()) {
+        assert_eq!(serial.output.status, parallel.output.status);
+        assert_eq!(serial.output.changeset, parallel.output.changeset);
+        assert_eq!(serial.output.events, parallel.output.events);
+        assert_eq!(serial.transaction_info, parallel.transaction_info);
+    }
+
+    // The txs pay gas to the same fee pool and revenue stores, the deposits are rebased instead of re-executing
+    let metrics = binding_test.executor.metrics();
+    assert_eq!(metrics.executor_parallel_batch_txs.get(), 8);
+    assert!(metrics.executor_parallel_reexecuted_txs.get() < 7);
+    assert_eq!(metrics.executor_parallel_serial_fallback_batches.get(), 0);
+}
+
+#[tokio::test]
+async fn test_parallel_execution_disjoint_txs() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    // The system call txs skip the gas payment, and every tx creates new oracle objects,
+    // so the txs do not read or write the same objects.
+    let base_root = binding_test.root().clone();
+    let txs = (0..8)
+        .map(|i| {
+            let action = OracleModule::create_oracle_action(
+                format!("oracle{}", i),
+                "https://kanari.network".to_string(),
+                "parallel test oracle".to_string(),
+            );
+            let ctx = TxContext::new_system_call_ctx(H256::random(), 0);
+            binding_test
+                .executor
+                .moveos()
+                .verify(MoveOSTransaction::new(base_root.clone(), ctx, action))
+                .unwrap()
+        })
+        .collect::<Vec<VerifiedMoveOSTransaction>>();
+
+    let mut root = base_root.clone();
+    let mut serial_results = vec![];
+    for mut tx in txs.clone() {
+        tx.root = root;
+        let result = binding_test.executor.execute(tx).unwrap();
+        root = result.transaction_info.root_metadata();
+        serial_results.push(result);
+    }
+
+    binding_test
+        .executor
+        .refresh_state(base_root, false)
+        .unwrap();
+    let parallel_results = binding_test
+        .executor
+        .execute_batch(txs)
+        .into_result()
+        .unwrap();
+
+    assert_eq!(serial_results.len(), parallel_results.len());
+    for (serial, parallel) in serial_results.iter().zip(parallel_results.iter()) {
+        assert_eq!(serial.output.status, parallel.output.status);
+        assert_eq!(serial.output.changeset, parallel.output.changeset);
+        assert_eq!(serial.transaction_info, parallel.transaction_info);
+    }
+
+    let metrics = binding_test.executor.metrics();
+    assert_eq!(metrics.executor_parallel_batch_txs.get(), 8);
+    assert_eq!(metrics.executor_parallel_reexecuted_txs.get(), 0);
+}
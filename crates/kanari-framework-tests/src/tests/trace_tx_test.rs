@@ -0,0 +1,62 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::binding_test;
+use kanari_types::crypto::KanariKeyPair;
+use kanari_types::framework::gas_coin::KARI;
+use kanari_types::framework::transfer::TransferModule;
+use kanari_types::transaction::{KanariTransactionData, TransactionSequenceInfo};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::u256::U256;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos_types::h256::H256;
+use moveos_types::state::MoveStructType;
+
+#[tokio::test]
+async fn test_trace_l2_tx() {
+    let _ = tracing_subscriber::fmt::try_init();
+    let mut binding_test = binding_test::RustBindingTest::new().unwrap();
+
+    let kp = KanariKeyPair::generate_secp256k1();
+    let sender = kp.public().bitcoin_address().unwrap().to_kanari_address();
+    binding_test
+        .get_kari(sender.into(), U256::from(10000u64))
+        .unwrap();
+
+    let root = binding_test.root().clone();
+    let action = TransferModule::create_transfer_coin_action(
+        KARI::struct_tag(),
+        AccountAddress::random(),
+        U256::from(100u64),
+    );
+    let tx = KanariTransactionData::new_for_test(sender, 0, action).sign(&kp);
+    let sequence_info = TransactionSequenceInfo::random();
+    let mut verified_tx = binding_test.executor.validate_l2_tx(tx.clone()).unwrap();
+    verified_tx.ctx.add(sequence_info.clone()).unwrap();
+    let execution_info = binding_test
+        .execute_verified_tx(verified_tx)
+        .unwrap()
+        .transaction_info;
+
+    let result = binding_test
+        .executor
+        .trace_l2_tx(
+            tx.clone(),
+            sequence_info.clone(),
+            root.clone(),
+            execution_info.clone(),
+        )
+        .unwrap();
+    assert_eq!(result.raw_output.status, KeptVMStatus::Executed);
+    assert!(result.trace.is_some());
+
+    // The replay does not match a different execution
+    let mut diverged_execution_info = execution_info;
+    diverged_execution_info.state_root = H256::random();
+    assert!(
+        binding_test
+            .executor
+            .trace_l2_tx(tx, sequence_info, root, diverged_execution_info)
+            .is_err()
+    );
+}
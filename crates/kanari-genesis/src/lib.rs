@@ -13,6 +13,7 @@ use kanari_framework::natives::gas_parameter::gas_member::{
     FromOnChainGasSchedule, InitialGasSchedule, ToOnChainGasSchedule,
 };
 use kanari_indexer::store::traits::IndexerStoreTrait;
+use kanari_nursery::natives::wasm::WASMCostTableGasParameters;
 use kanari_store::state_store::StateStore;
 use kanari_types::bitcoin::genesis::BitcoinGenesisContext;
 use kanari_types::error::GenesisError;
@@ -35,18 +36,24 @@ use moveos::gas::table::VMGasParameters;
 use moveos::moveos::{MoveOS, MoveOSConfig};
 use moveos_stdlib::natives::moveos_stdlib::base64::EncodeDecodeGasParametersOption;
 use moveos_stdlib::natives::moveos_stdlib::event::EmitWithHandleGasParameters;
+use moveos_stdlib::natives::moveos_stdlib::move_module::VerifyPackageMetadataGasParameters;
 use moveos_stdlib::natives::moveos_stdlib::object::ListFieldsGasParametersOption;
+use moveos_stdlib::natives::moveos_stdlib::plonk::PlonkGasParameters;
 use moveos_store::MoveOSStore;
 use moveos_types::genesis_info::GenesisInfo;
 use moveos_types::h256::H256;
 use moveos_types::move_std::string::MoveString;
 use moveos_types::moveos_std::gas_schedule::{GasEntry, GasSchedule, GasScheduleConfig};
+use moveos_types::moveos_std::genesis::GenesisCalls;
+use moveos_types::moveos_std::module_store::PackageData;
 use moveos_types::moveos_std::object::ObjectMeta;
+use moveos_types::startup_info::StartupInfo;
 use moveos_types::state::{ObjectState, StateChangeSetExt};
 use moveos_types::transaction::{
-    GenesisRawTransactionOutput, MoveAction, MoveOSTransaction, RawTransactionOutput,
+    FunctionCall, GenesisRawTransactionOutput, MoveAction, MoveOSTransaction, RawTransactionOutput,
 };
 use moveos_types::{h256, state_resolver};
+use moveos_wasm::gas_config::WASMGasConfig;
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
@@ -56,6 +63,10 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use std::{fs::File, io::Write, path::Path};
 
+pub use spec::GenesisSpec;
+
+pub mod spec;
+
 pub static KANARI_LOCAL_GENESIS: Lazy<KanariGenesisV2> = Lazy::new(|| {
     let network: KanariNetwork = BuiltinChainID::Local.into();
     KanariGenesisV2::build(network).expect("build kanari genesis failed")
@@ -233,8 +244,83 @@ impl FrameworksGasParameters {
         v5_gas_parameter
     }
 
+    pub fn v7() -> Self {
+        let mut v6_gas_parameter = FrameworksGasParameters::v6();
+
+        v6_gas_parameter
+            .kanari_framework_gas_params
+            .moveos_stdlib
+            .move_module
+            .verify_package_metadata_inner =
+            VerifyPackageMetadataGasParameters::init(1000.into(), 30.into());
+
+        v6_gas_parameter
+    }
+
+    pub fn v8() -> Self {
+        let mut v7_gas_parameter = FrameworksGasParameters::v7();
+
+        // Charge the memory growth and the bulk memory operators more than the other operators
+        if let Some(kanari_nursery_gas_params) = v7_gas_parameter.kanari_nursery_gas_params.as_mut()
+        {
+            kanari_nursery_gas_params
+                .wasm
+                .create_instance_gas_parameter
+                .cost_table = WASMCostTableGasParameters::init(WASMGasConfig {
+                memory: 1000,
+                bulk_memory: 50,
+                ..WASMGasConfig::default()
+            });
+        }
+
+        v7_gas_parameter
+    }
+
+    pub fn v9() -> Self {
+        let mut v8_gas_parameter = FrameworksGasParameters::v8();
+
+        v8_gas_parameter.kanari_framework_gas_params.schnorr.verify =
+            kanari_framework::natives::kanari_framework::crypto::schnorr::SchnorrGasParameters::init(
+                1000.into(),
+                30.into(),
+            );
+
+        v8_gas_parameter
+            .kanari_framework_gas_params
+            .schnorr
+            .taproot_tweak_pubkey =
+            kanari_framework::natives::kanari_framework::crypto::schnorr::SchnorrGasParameters::init(
+                1000.into(),
+                30.into(),
+            );
+
+        v8_gas_parameter
+    }
+
+    pub fn v10() -> Self {
+        let mut v9_gas_parameter = FrameworksGasParameters::v9();
+
+        v9_gas_parameter
+            .kanari_framework_gas_params
+            .moveos_stdlib
+            .plonk
+            .prepare_verifying_key_internal = PlonkGasParameters::init(1000.into(), 30.into());
+        v9_gas_parameter
+            .kanari_framework_gas_params
+            .moveos_stdlib
+            .plonk
+            .verify_plonk_proof_internal = PlonkGasParameters::init(5000.into(), 30.into());
+        v9_gas_parameter
+            .kanari_framework_gas_params
+            .moveos_stdlib
+            .plonk
+            .verify_kzg_opening_internal = PlonkGasParameters::init(2000.into(), 30.into());
+
+        v9_gas_parameter
+    }
+
     pub fn latest() -> Self {
-        FrameworksGasParameters::v6()
+        FrameworksGasParameters::v10()
     }
 
     pub fn to_gas_schedule_config(&self, chain_id: ChainID) -> GasScheduleConfig {
@@ -397,6 +483,23 @@ impl KanariGenesis {
 
 impl KanariGenesisV2 {
     pub fn build(network: KanariNetwork) -> Result<Self> {
+        Self::build_with(network, vec![], None, vec![])
+    }
+
+    /// Build the genesis of a custom network from the declarative genesis spec
+    pub fn build_from_spec(spec: &GenesisSpec) -> Result<Self> {
+        let network = spec.network()?;
+        let packages = spec.load_packages()?;
+        let genesis_calls = spec.genesis_calls(&packages)?;
+        Self::build_with(network, packages, spec.gas_schedule.as_ref(), genesis_calls)
+    }
+
+    fn build_with(
+        network: KanariNetwork,
+        packages: Vec<PackageData>,
+        gas_schedule: Option<&spec::GenesisGasSchedule>,
+        genesis_calls: Vec<FunctionCall>,
+    ) -> Result<Self> {
         let genesis_config = network.genesis_config;
 
         let stdlib = Self::load_stdlib(genesis_config.stdlib_version)?;
@@ -421,11 +524,12 @@ impl KanariGenesisV2 {
         let genesis_tx = KanariTransaction::new_genesis_tx(
             KANARI_FRAMEWORK_ADDRESS.into(),
             network.chain_id.id,
-            //merge all the module bundles into one
+            //merge all the module bundles into one, the packages are published after the frameworks
             MoveAction::ModuleBundle(
                 bundles
                     .into_iter()
                     .flat_map(|(_, bundles)| bundles)
+                    .chain(packages.into_iter().flat_map(|package| package.modules))
                     .collect(),
             ),
         );
@@ -434,7 +538,7 @@ impl KanariGenesisV2 {
             .clone()
             .into_moveos_transaction(ObjectMeta::genesis_root());
 
-        let gas_parameter = if network.chain_id == BuiltinChainID::Main.chain_id() {
+        let mut gas_parameter = if network.chain_id == BuiltinChainID::Main.chain_id() {
             FrameworksGasParameters::initial()
         } else if network.chain_id == BuiltinChainID::Test.chain_id() {
             FrameworksGasParameters::v1()
@@ -442,11 +546,20 @@ impl KanariGenesisV2 {
             FrameworksGasParameters::latest()
         };
 
-        let gas_config = gas_parameter.to_gas_schedule_config(network.chain_id);
+        let mut gas_config = gas_parameter.to_gas_schedule_config(network.chain_id);
+        if let Some(gas_schedule) = gas_schedule {
+            gas_schedule.apply(&mut gas_config)?;
+            gas_parameter = FrameworksGasParameters::load_from_gas_config(&gas_config)?;
+        }
         genesis_moveos_tx.ctx.add(genesis_ctx.clone())?;
         genesis_moveos_tx.ctx.add(moveos_genesis_ctx.clone())?;
         genesis_moveos_tx.ctx.add(bitcoin_genesis_ctx.clone())?;
         genesis_moveos_tx.ctx.add(gas_config.clone())?;
+        if !genesis_calls.is_empty() {
+            genesis_moveos_tx
+                .ctx
+                .add(GenesisCalls::new(&genesis_calls)?)?;
+        }
 
         let vm_config = MoveOSConfig::default();
         let (moveos_store, _temp_dir) = MoveOSStore::mock_moveos_store()?;
@@ -488,6 +601,32 @@ impl KanariGenesisV2 {
         }
     }
 
+    /// Load the genesis built by `build_from_spec` from the genesis file, and check it is built for the network
+    pub fn load_for_network<P: AsRef<Path>>(
+        genesis_file: P,
+        network: &KanariNetwork,
+    ) -> Result<Self> {
+        let genesis = Self::load_from(genesis_file)?;
+        let genesis_ctx = genesis
+            .genesis_moveos_tx
+            .ctx
+            .get::<kanari_types::framework::genesis::GenesisContext>()?
+            .ok_or_else(|| anyhow::anyhow!("Genesis context should exist"))?;
+        ensure!(
+            genesis_ctx.chain_id == network.chain_id.id,
+            "The genesis chain id {} is not equal to the network chain id {}",
+            genesis_ctx.chain_id,
+            network.chain_id.id
+        );
+        ensure!(
+            genesis_ctx.sequencer == network.genesis_config.sequencer_account,
+            "The genesis sequencer {} is not equal to the sequencer {} in the genesis config",
+            genesis_ctx.sequencer,
+            network.genesis_config.sequencer_account
+        );
+        Ok(genesis)
+    }
+
     pub fn genesis_tx(&self) -> KanariTransaction {
         self.genesis_tx.clone()
     }
@@ -509,12 +648,17 @@ impl KanariGenesisV2 {
 
     /// Load the genesis from the kanari db, if not exist, build and init the genesis
     pub fn load_or_init(network: KanariNetwork, kanari_db: &KanariDB) -> Result<Self> {
+        Self::load_or_build(network)?.check_or_init(kanari_db)
+    }
+
+    /// Check the genesis in the kanari db is the same as this genesis, if not exist, init this genesis
+    pub fn check_or_init(self, kanari_db: &KanariDB) -> Result<Self> {
         let genesis_info = kanari_db.moveos_store.get_config_store().get_genesis()?;
         match genesis_info {
             Some(genesis_info_from_store) => {
                 //if the genesis_info in the store we should check the genesis version between the store and the binary
 
-                let genesis_from_binary = Self::load_or_build(network)?;
+                let genesis_from_binary = self;
                 let genesis_from_binary_v1 = KanariGenesis::from(genesis_from_binary.clone());
 
                 let genesis_info_from_binary = genesis_from_binary.genesis_info();
@@ -534,13 +678,37 @@ impl KanariGenesisV2 {
                 Self::decode(&genesis_info_from_store.genesis_bin)
             }
             None => {
-                let genesis = Self::load_or_build(network)?;
-                genesis.init_genesis(kanari_db)?;
-                Ok(genesis)
+                self.init_genesis(kanari_db)?;
+                Ok(self)
             }
         }
     }
 
+    /// Init the db on top of the `fork_root` of a remote chain instead of executing the genesis tx.
+    /// The genesis tx is still sequenced, so the local txs are sequenced after it.
+    /// The states of the `fork_root` are not in the local store and not indexed.
+    pub fn init_fork(&self, kanari_db: &KanariDB, fork_root: ObjectMeta) -> Result<()> {
+        ensure!(
+            kanari_db
+                .moveos_store
+                .get_config_store()
+                .get_genesis()?
+                .is_none(),
+            "Genesis already initialized"
+        );
+        self.sequence_genesis_tx(kanari_db)?;
+        kanari_db
+            .moveos_store
+            .get_config_store()
+            .save_startup_info(StartupInfo::new(fork_root.state_root(), fork_root.size()))?;
+        let genesis_info = GenesisInfo::new(self.genesis_hash(), self.encode());
+        kanari_db
+            .moveos_store
+            .get_config_store()
+            .save_genesis(genesis_info)?;
+        Ok(())
+    }
+
     pub fn init_genesis(&self, kanari_db: &KanariDB) -> Result<ObjectMeta> {
         ensure!(
             kanari_db
@@ -574,38 +742,7 @@ impl KanariGenesisV2 {
 
         // Save the genesis txs to sequencer
         let genesis_tx_order: u64 = 0;
-        let moveos_genesis_context = self
-            .genesis_moveos_tx()
-            .ctx
-            .get::<moveos_types::moveos_std::genesis::GenesisContext>()?
-            .expect("Moveos Genesis context should exist");
-        let mut tx_ledger_data = LedgerTxData::L2Tx(self.genesis_tx());
-        let tx_hash = tx_ledger_data.tx_hash();
-        // Init tx accumulator
-        let genesis_tx_accumulator = MerkleAccumulator::new_with_info(
-            AccumulatorInfo::default(),
-            kanari_db.kanari_store.get_transaction_accumulator_store(),
-        );
-        let _genesis_accumulator_root = genesis_tx_accumulator.append(vec![tx_hash].as_slice())?;
-        let genesis_accumulator_unsaved_nodes = genesis_tx_accumulator.pop_unsaved_nodes();
-
-        let genesis_tx_accmulator_info = genesis_tx_accumulator.get_info();
-        let ledger_tx = LedgerTransaction::build_ledger_transaction(
-            tx_ledger_data,
-            moveos_genesis_context.timestamp,
-            genesis_tx_order,
-            vec![],
-            genesis_tx_accmulator_info.clone(),
-        );
-        let sequencer_info = SequencerInfo::new(genesis_tx_order, genesis_tx_accmulator_info);
-        kanari_db.kanari_store.save_sequenced_tx(
-            tx_hash,
-            ledger_tx.clone(),
-            sequencer_info,
-            genesis_accumulator_unsaved_nodes,
-            true,
-        )?;
-        genesis_tx_accumulator.clear_after_save();
+        let ledger_tx = self.sequence_genesis_tx(kanari_db)?;
 
         let tx_hash = self.genesis_tx().tx_hash();
         let (output, genesis_execution_info) = kanari_db
@@ -671,6 +808,44 @@ impl KanariGenesisV2 {
         Ok(genesis_execution_info.root_metadata())
     }
 
+    /// Save the genesis tx to sequencer
+    fn sequence_genesis_tx(&self, kanari_db: &KanariDB) -> Result<LedgerTransaction> {
+        let genesis_tx_order: u64 = 0;
+        let moveos_genesis_context = self
+            .genesis_moveos_tx()
+            .ctx
+            .get::<moveos_types::moveos_std::genesis::GenesisContext>()?
+            .expect("Moveos Genesis context should exist");
+        let mut tx_ledger_data = LedgerTxData::L2Tx(self.genesis_tx());
+        let tx_hash = tx_ledger_data.tx_hash();
+        // Init tx accumulator
+        let genesis_tx_accumulator = MerkleAccumulator::new_with_info(
+            AccumulatorInfo::default(),
+            kanari_db.kanari_store.get_transaction_accumulator_store(),
+        );
+        let _genesis_accumulator_root = genesis_tx_accumulator.append(vec![tx_hash].as_slice())?;
+        let genesis_accumulator_unsaved_nodes = genesis_tx_accumulator.pop_unsaved_nodes();
+
+        let genesis_tx_accmulator_info = genesis_tx_accumulator.get_info();
+        let ledger_tx = LedgerTransaction::build_ledger_transaction(
+            tx_ledger_data,
+            moveos_genesis_context.timestamp,
+            genesis_tx_order,
+            vec![],
+            genesis_tx_accmulator_info.clone(),
+        );
+        let sequencer_info = SequencerInfo::new(genesis_tx_order, genesis_tx_accmulator_info);
+        kanari_db.kanari_store.save_sequenced_tx(
+            tx_hash,
+            ledger_tx.clone(),
+            sequencer_info,
+            genesis_accumulator_unsaved_nodes,
+            true,
+        )?;
+        genesis_tx_accumulator.clear_after_save();
+        Ok(ledger_tx)
+    }
+
     pub fn build_stdlib() -> Result<Stdlib> {
         framework_builder::stdlib_configs::build_stdlib(false)
     }
@@ -869,6 +1044,140 @@ mod tests {
         genesis_init_test_case(network, genesis);
     }
 
+    #[tokio::test]
+    async fn test_genesis_build_from_spec() {
+        use kanari_store::meta_store::MetaStore;
+        use kanari_types::framework::account_coin_store::AccountCoinStoreModule;
+        use kanari_types::framework::coin_store::CoinStore;
+        use kanari_types::framework::gas_coin::KARI;
+        use kanari_types::service_status::ServiceStatus;
+        use move_binary_format::file_format::empty_module;
+        use move_binary_format::file_format_common::VERSION_6;
+        use move_core_types::u256::U256;
+        use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+        use moveos_types::moveos_std::timestamp::Timestamp;
+        use moveos_types::state::{MoveState, MoveType};
+
+        let opt = KanariOpt::new_with_temp_store().expect("create kanari opt failed");
+        let alice = AccountAddress::random();
+
+        // A package with an empty module
+        let package_id = AccountAddress::random();
+        let mut module = empty_module();
+        module.version = VERSION_6;
+        module.address_identifiers[0] = package_id;
+        module.identifiers[0] = Identifier::new("demo").unwrap();
+        let mut module_bytes = vec![];
+        module.serialize(&mut module_bytes).unwrap();
+        std::fs::create_dir_all(opt.base().data_dir()).unwrap();
+        let package_file = opt.base().data_dir().join("demo.rpd");
+        PackageData::new(MoveString::from("demo"), package_id, vec![module_bytes])
+            .save_to_file(&package_file)
+            .unwrap();
+
+        let object_id = ObjectID::random();
+        let object = ObjectState::new_with_struct(
+            ObjectMeta::genesis_meta(object_id.clone(), Timestamp::type_tag()),
+            Timestamp { milliseconds: 42 },
+        )
+        .unwrap();
+
+        let spec = GenesisSpec {
+            chain_id: 100,
+            genesis_config: "test".to_owned(),
+            balances: vec![spec::GenesisBalance {
+                address: alice.to_hex_literal(),
+                amount: 1000,
+            }],
+            packages: vec![spec::GenesisPackage {
+                path: package_file,
+                upgrade_cap_owner: Some(alice.to_hex_literal()),
+            }],
+            objects: vec![(object, Timestamp::type_layout())],
+            gas_schedule: Some(spec::GenesisGasSchedule {
+                max_gas_amount: Some(GasScheduleConfig::INITIAL_MAX_GAS_AMOUNT * 2),
+                entries: BTreeMap::new(),
+            }),
+            service_status: ServiceStatus::Maintenance,
+        };
+        let network = spec.network().unwrap();
+        let genesis = KanariGenesisV2::build_from_spec(&spec).unwrap();
+        assert_eq!(
+            genesis.initial_gas_config.max_gas_amount,
+            GasScheduleConfig::INITIAL_MAX_GAS_AMOUNT * 2
+        );
+        // The genesis is reproducible
+        assert_eq!(
+            genesis.genesis_hash(),
+            KanariGenesisV2::build_from_spec(&spec)
+                .unwrap()
+                .genesis_hash()
+        );
+
+        let kanari_db = KanariDB::init_with_mock_metrics_for_test(opt.store_config())
+            .expect("init kanari db failed");
+        let genesis_file = opt.base().data_dir().join("genesis.bin");
+        genesis.save_to(&genesis_file).unwrap();
+        let genesis = KanariGenesisV2::load_for_network(&genesis_file, &network)
+            .unwrap()
+            .check_or_init(&kanari_db)
+            .unwrap();
+
+        let root = kanari_db.latest_root().unwrap().unwrap();
+        let resolver = RootObjectResolver::new(root, &kanari_db.moveos_store);
+        let coin_store = resolver
+            .get_object(&AccountCoinStoreModule::account_coin_store_id(
+                alice,
+                KARI::struct_tag(),
+            ))
+            .unwrap()
+            .unwrap()
+            .into_object::<CoinStore<KARI>>()
+            .unwrap();
+        assert_eq!(coin_store.value.balance(), U256::from(1000u64));
+
+        let module = resolver
+            .get_module(&ModuleId::new(package_id, Identifier::new("demo").unwrap()))
+            .unwrap();
+        assert!(module.is_some(), "the spec package should be published");
+        assert!(
+            resolver
+                .get_object(&Package::package_id(&package_id))
+                .unwrap()
+                .is_some()
+        );
+
+        let timestamp = resolver
+            .get_object(&object_id)
+            .unwrap()
+            .unwrap()
+            .into_object::<Timestamp>()
+            .unwrap();
+        assert_eq!(timestamp.value.milliseconds, 42);
+
+        // Init the same genesis again should pass the check
+        assert!(genesis.check_or_init(&kanari_db).is_ok());
+
+        // The service status is saved to the meta store by `genesis init`
+        kanari_db
+            .kanari_store
+            .save_service_status(spec.service_status)
+            .unwrap();
+        assert_eq!(
+            kanari_db.kanari_store.get_service_status().unwrap(),
+            Some(ServiceStatus::Maintenance)
+        );
+
+        let unknown_gas_entry = GenesisSpec {
+            gas_schedule: Some(spec::GenesisGasSchedule {
+                max_gas_amount: None,
+                entries: BTreeMap::from([("unknown.gas.entry".to_owned(), 1)]),
+            }),
+            ..spec
+        };
+        assert!(KanariGenesisV2::build_from_spec(&unknown_gas_entry).is_err());
+    }
+
     #[test]
     fn test_genesis_load_from_binary() {
         assert!(
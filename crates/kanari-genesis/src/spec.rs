@@ -0,0 +1,195 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+//! The declarative genesis file for custom networks.
+//! It extends a base `GenesisConfig` with initial balances, pre-published packages,
+//! pre-created objects, gas schedule overrides and the initial service status of the node.
+
+use anyhow::{Result, bail, ensure};
+use kanari_framework::KANARI_FRAMEWORK_ADDRESS;
+use kanari_types::address::{BitcoinAddress, KanariAddress};
+use kanari_types::framework::gas_coin;
+use kanari_types::genesis_config::GenesisConfig;
+use kanari_types::kanari_network::{BuiltinChainID, KanariNetwork};
+use kanari_types::service_status::ServiceStatus;
+use move_binary_format::access::ModuleAccess;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::ident_str;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::u256::U256;
+use move_core_types::value::MoveTypeLayout;
+use moveos_types::addresses::MOVEOS_STD_ADDRESS;
+use moveos_types::move_types::FunctionId;
+use moveos_types::moveos_std::gas_schedule::GasScheduleConfig;
+use moveos_types::moveos_std::module_store::{self, PackageData};
+use moveos_types::state::ObjectState;
+use moveos_types::transaction::FunctionCall;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenesisSpec {
+    /// The chain id of the custom network, it can not be a builtin chain id.
+    pub chain_id: u64,
+    /// The base genesis config, a builtin chain network name(local/dev/test/main) or a genesis config file path.
+    pub genesis_config: String,
+    /// The initial KARI balances
+    #[serde(default)]
+    pub balances: Vec<GenesisBalance>,
+    /// The packages published in the genesis transaction
+    #[serde(default)]
+    pub packages: Vec<GenesisPackage>,
+    /// The objects created before the genesis transaction, appended to the `genesis_objects` of the base config.
+    #[serde(default)]
+    pub objects: Vec<(ObjectState, MoveTypeLayout)>,
+    #[serde(default)]
+    pub gas_schedule: Option<GenesisGasSchedule>,
+    /// The service status of the node started with this genesis, it is not a part of the genesis state,
+    /// `kanari genesis init` saves it to the meta store of the node.
+    #[serde(default)]
+    pub service_status: ServiceStatus,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenesisBalance {
+    /// A Kanari address or a Bitcoin address
+    pub address: String,
+    /// The amount of KARI in the smallest unit
+    pub amount: u128,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct GenesisPackage {
+    /// The package data file exported by `kanari move build --export`
+    pub path: PathBuf,
+    /// The owner of the package `UpgradeCap`, if not set, the package can not be upgraded.
+    #[serde(default)]
+    pub upgrade_cap_owner: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct GenesisGasSchedule {
+    #[serde(default)]
+    pub max_gas_amount: Option<u64>,
+    /// Override the value of the existing gas schedule entries
+    #[serde(default)]
+    pub entries: BTreeMap<String, u64>,
+}
+
+impl GenesisGasSchedule {
+    pub fn apply(&self, gas_config: &mut GasScheduleConfig) -> Result<()> {
+        if let Some(max_gas_amount) = self.max_gas_amount {
+            gas_config.max_gas_amount = max_gas_amount;
+        }
+        for (key, val) in &self.entries {
+            match gas_config
+                .entries
+                .iter_mut()
+                .find(|entry| entry.key.as_str() == key)
+            {
+                Some(entry) => entry.val = *val,
+                None => bail!("Unknown gas schedule entry: {}", key),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GenesisSpec {
+    /// Load the genesis spec from a yaml file, the relative paths in the file are resolved from the file directory.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        let mut spec: GenesisSpec = serde_yaml::from_str(&content)?;
+        let base_dir = path.parent().unwrap_or_else(|| Path::new("."));
+        if BuiltinChainID::from_str(spec.genesis_config.trim()).is_err() {
+            spec.genesis_config = base_dir
+                .join(spec.genesis_config.trim())
+                .to_string_lossy()
+                .to_string();
+        }
+        for package in spec.packages.iter_mut() {
+            package.path = base_dir.join(&package.path);
+        }
+        Ok(spec)
+    }
+
+    pub fn network(&self) -> Result<KanariNetwork> {
+        ensure!(
+            BuiltinChainID::try_from(self.chain_id).is_err(),
+            "The genesis spec can not use the builtin chain id: {}",
+            self.chain_id
+        );
+        let mut genesis_config = match BuiltinChainID::from_str(self.genesis_config.trim()) {
+            Ok(builtin_id) => builtin_id.genesis_config().clone(),
+            Err(_) => GenesisConfig::load(self.genesis_config.trim())?,
+        };
+        genesis_config
+            .genesis_objects
+            .extend(self.objects.iter().cloned());
+        Ok(KanariNetwork::new(self.chain_id.into(), genesis_config))
+    }
+
+    pub fn load_packages(&self) -> Result<Vec<PackageData>> {
+        self.packages
+            .iter()
+            .map(|package| {
+                let package_data: PackageData = bcs::from_bytes(&std::fs::read(&package.path)?)?;
+                for module in package_data.compiled_modules()? {
+                    ensure!(
+                        *module.self_id().address() == package_data.package_id,
+                        "The module {} is not in the package {}",
+                        module.self_id(),
+                        package_data.package_id
+                    );
+                }
+                Ok(package_data)
+            })
+            .collect()
+    }
+
+    /// The function calls executed after the genesis modules published.
+    pub fn genesis_calls(&self, packages: &[PackageData]) -> Result<Vec<FunctionCall>> {
+        let mut calls = vec![];
+        for (package, package_data) in self.packages.iter().zip(packages) {
+            if let Some(owner) = &package.upgrade_cap_owner {
+                calls.push(FunctionCall::new(
+                    FunctionId::new(
+                        ModuleId::new(MOVEOS_STD_ADDRESS, module_store::MODULE_NAME.to_owned()),
+                        ident_str!("issue_upgrade_cap_by_system").to_owned(),
+                    ),
+                    vec![],
+                    vec![
+                        bcs::to_bytes(&package_data.package_id)?,
+                        bcs::to_bytes(&parse_address(owner)?)?,
+                    ],
+                ));
+            }
+        }
+        for balance in &self.balances {
+            calls.push(FunctionCall::new(
+                FunctionId::new(
+                    ModuleId::new(KANARI_FRAMEWORK_ADDRESS, gas_coin::MODULE_NAME.to_owned()),
+                    ident_str!("faucet").to_owned(),
+                ),
+                vec![],
+                vec![
+                    bcs::to_bytes(&parse_address(&balance.address)?)?,
+                    bcs::to_bytes(&U256::from(balance.amount))?,
+                ],
+            ));
+        }
+        Ok(calls)
+    }
+}
+
+fn parse_address(address: &str) -> Result<AccountAddress> {
+    match KanariAddress::from_str(address) {
+        Ok(address) => Ok(address.into()),
+        Err(_) => Ok(BitcoinAddress::from_str(address)?
+            .to_kanari_address()
+            .into()),
+    }
+}
@@ -0,0 +1 @@
+DROP TABLE IF EXISTS package_sources;
\ No newline at end of file
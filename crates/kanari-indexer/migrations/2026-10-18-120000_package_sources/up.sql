@@ -0,0 +1,10 @@
+CREATE TABLE package_sources
+(
+    package_address   VARCHAR        NOT NULL       PRIMARY KEY,
+    package_name      VARCHAR        NOT NULL,
+    package_digest    VARCHAR        NOT NULL,
+    source_digest     VARCHAR        NOT NULL,
+    submitted_at      BIGINT         NOT NULL
+);
+
+CREATE INDEX idx_package_sources_submitted_at ON package_sources (submitted_at);
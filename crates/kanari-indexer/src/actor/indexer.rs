@@ -3,8 +3,8 @@
 
 use crate::actor::messages::{
     IndexerApplyObjectStatesMessage, IndexerDeleteAnyObjectStatesMessage, IndexerEventsMessage,
-    IndexerPersistOrUpdateAnyObjectStatesMessage, IndexerRevertMessage, IndexerStatesMessage,
-    IndexerTransactionMessage, UpdateIndexerMessage,
+    IndexerPackageSourcesMessage, IndexerPersistOrUpdateAnyObjectStatesMessage,
+    IndexerRevertMessage, IndexerStatesMessage, IndexerTransactionMessage, UpdateIndexerMessage,
 };
 use crate::store::traits::IndexerStoreTrait;
 use crate::{IndexerStore, list_field_indexer_keys};
@@ -302,3 +302,18 @@ impl Handler<IndexerRevertMessage> for IndexerActor {
         Ok(())
     }
 }
+
+#[async_trait]
+impl Handler<IndexerPackageSourcesMessage> for IndexerActor {
+    async fn handle(
+        &mut self,
+        msg: IndexerPackageSourcesMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<()> {
+        let IndexerPackageSourcesMessage { package_sources } = msg;
+
+        self.indexer_store
+            .persist_or_update_package_sources(package_sources)?;
+        Ok(())
+    }
+}
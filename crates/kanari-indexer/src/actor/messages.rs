@@ -5,12 +5,14 @@ use anyhow::Result;
 use coerce::actor::message::Message;
 use kanari_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
 use kanari_types::indexer::field::{FieldFilter, IndexerField};
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::{
     IndexerObjectState, IndexerObjectStateChangeSet, IndexerStateID, ObjectStateFilter,
     ObjectStateType,
 };
 use kanari_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
 use kanari_types::transaction::LedgerTransaction;
+use move_core_types::account_address::AccountAddress;
 use moveos_types::moveos_std::event::Event;
 use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::moveos_std::tx_context::TxContext;
@@ -175,3 +177,23 @@ pub struct QueryIndexerFieldsMessage {
 impl Message for QueryIndexerFieldsMessage {
     type Result = Result<Vec<IndexerField>>;
 }
+
+/// Indexer Package Sources write Message
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IndexerPackageSourcesMessage {
+    pub package_sources: Vec<IndexerPackageSource>,
+}
+
+impl Message for IndexerPackageSourcesMessage {
+    type Result = Result<()>;
+}
+
+/// Query Indexer Package Sources Message
+#[derive(Debug, Serialize, Deserialize)]
+pub struct QueryIndexerPackageSourcesMessage {
+    pub package_addresses: Vec<AccountAddress>,
+}
+
+impl Message for QueryIndexerPackageSourcesMessage {
+    type Result = Result<Vec<IndexerPackageSource>>;
+}
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::actor::messages::{
-    QueryIndexerEventsMessage, QueryIndexerFieldsMessage, QueryIndexerTransactionsMessage,
-    QueryLastStateIndexByTxOrderMessage,
+    QueryIndexerEventsMessage, QueryIndexerFieldsMessage, QueryIndexerPackageSourcesMessage,
+    QueryIndexerTransactionsMessage, QueryLastStateIndexByTxOrderMessage,
 };
 use crate::indexer_reader::IndexerReader;
 use anyhow::{Result, anyhow};
@@ -11,6 +11,7 @@ use async_trait::async_trait;
 use coerce::actor::{Actor, context::ActorContext, message::Handler};
 use kanari_types::indexer::event::IndexerEvent;
 use kanari_types::indexer::field::IndexerField;
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::IndexerStateID;
 use kanari_types::indexer::transaction::IndexerTransaction;
 use moveos_types::moveos_std::object::ObjectID;
@@ -128,3 +129,17 @@ impl Handler<QueryIndexerFieldsMessage> for IndexerReaderActor {
             .map_err(|e| anyhow!(format!("Failed to query indexer fields: {:?}", e)))
     }
 }
+
+#[async_trait]
+impl Handler<QueryIndexerPackageSourcesMessage> for IndexerReaderActor {
+    async fn handle(
+        &mut self,
+        msg: QueryIndexerPackageSourcesMessage,
+        _ctx: &mut ActorContext,
+    ) -> Result<Vec<IndexerPackageSource>> {
+        let QueryIndexerPackageSourcesMessage { package_addresses } = msg;
+        self.indexer_reader
+            .query_package_sources(package_addresses)
+            .map_err(|e| anyhow!(format!("Failed to query indexer package sources: {:?}", e)))
+    }
+}
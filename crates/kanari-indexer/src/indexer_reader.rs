@@ -5,16 +5,17 @@ use crate::errors::IndexerError;
 use crate::metrics::IndexerReaderMetrics;
 use crate::models::events::StoredEvent;
 use crate::models::fields::StoredField;
+use crate::models::package_sources::StoredPackageSource;
 use crate::models::states::{StoredObjectStateInfo, StoredStateID};
 use crate::models::transactions::StoredTransaction;
-use crate::schema::{events, transactions};
+use crate::schema::{events, package_sources, transactions};
 use crate::utils::escape_sql_string;
 use crate::{
     DEFAULT_BUSY_TIMEOUT, INDEXER_EVENTS_TABLE_NAME, INDEXER_FIELDS_TABLE_NAME,
     INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME, INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME,
-    INDEXER_OBJECT_STATES_TABLE_NAME, INDEXER_TRANSACTIONS_TABLE_NAME, IndexerResult,
-    IndexerStoreMeta, IndexerTableName, SqliteConnectionConfig, SqliteConnectionPoolConfig,
-    SqlitePoolConnection,
+    INDEXER_OBJECT_STATES_TABLE_NAME, INDEXER_PACKAGE_SOURCES_TABLE_NAME,
+    INDEXER_TRANSACTIONS_TABLE_NAME, IndexerResult, IndexerStoreMeta, IndexerTableName,
+    SqliteConnectionConfig, SqliteConnectionPoolConfig, SqlitePoolConnection,
 };
 use anyhow::{Result, anyhow};
 use diesel::{
@@ -23,8 +24,10 @@ use diesel::{
 use function_name::named;
 use kanari_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
 use kanari_types::indexer::field::{FieldFilter, IndexerField};
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::{IndexerStateID, ObjectStateFilter, ObjectStateType};
 use kanari_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
+use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::StructTag;
 use moveos_types::moveos_std::object::ObjectID;
 use prometheus::Registry;
@@ -699,6 +702,42 @@ impl IndexerReader {
 
         Ok(result)
     }
+
+    #[named]
+    pub fn query_package_sources(
+        &self,
+        package_addresses: Vec<AccountAddress>,
+    ) -> IndexerResult<Vec<IndexerPackageSource>> {
+        let fn_name = function_name!();
+        let _timer = self
+            .metrics
+            .indexer_reader_query_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+
+        let package_addresses = package_addresses
+            .into_iter()
+            .map(|address| address.to_hex_literal())
+            .collect::<Vec<_>>();
+        let stored_package_sources = self
+            .get_inner_indexer_reader(INDEXER_PACKAGE_SOURCES_TABLE_NAME)?
+            .run_query_with_timeout(move |conn| {
+                package_sources::table
+                    .filter(package_sources::package_address.eq_any(package_addresses))
+                    .load::<StoredPackageSource>(conn)
+            })?;
+
+        stored_package_sources
+            .into_iter()
+            .map(|source| source.try_into_indexer_package_source())
+            .collect::<Result<Vec<_>>>()
+            .map_err(|e| {
+                IndexerError::SQLiteReadError(format!(
+                    "Cast indexer package sources failed: {:?}",
+                    e
+                ))
+            })
+    }
 }
 
 fn get_table_name_by_state_type(state_type: ObjectStateType) -> IndexerTableName {
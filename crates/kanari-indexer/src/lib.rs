@@ -15,6 +15,7 @@ use errors::IndexerError;
 use kanari_types::framework::indexer::{FieldIndexerData, IndexerModule};
 use kanari_types::indexer::event::IndexerEvent;
 use kanari_types::indexer::field::{IndexerField, IndexerFieldChanges};
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::{
     IndexerObjectState, IndexerObjectStateChangeSet, IndexerObjectStateChanges, ObjectStateType,
 };
@@ -54,6 +55,7 @@ pub const INDEXER_OBJECT_STATE_UTXOS_TABLE_NAME: IndexerTableName = "utxos";
 pub const INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME: IndexerTableName = "inscriptions";
 pub const INDEXER_TRANSACTIONS_TABLE_NAME: IndexerTableName = "transactions";
 pub const INDEXER_FIELDS_TABLE_NAME: IndexerTableName = "fields";
+pub const INDEXER_PACKAGE_SOURCES_TABLE_NAME: IndexerTableName = "package_sources";
 
 /// Please note that adding new indexer table needs to be added in vec simultaneously.
 static INDEXER_VEC_TABLE_NAME: Lazy<Vec<IndexerTableName>> = Lazy::new(|| {
@@ -64,6 +66,7 @@ static INDEXER_VEC_TABLE_NAME: Lazy<Vec<IndexerTableName>> = Lazy::new(|| {
         INDEXER_OBJECT_STATE_INSCRIPTIONS_TABLE_NAME,
         INDEXER_TRANSACTIONS_TABLE_NAME,
         INDEXER_FIELDS_TABLE_NAME,
+        INDEXER_PACKAGE_SOURCES_TABLE_NAME,
     ]
 });
 
@@ -252,6 +255,14 @@ impl IndexerStoreTrait for IndexerStore {
         self.delete_fields(field_changes.remove_fields)?;
         self.delete_fields_by_parent_id(field_changes.remove_fields_by_parent_id)
     }
+
+    fn persist_or_update_package_sources(
+        &self,
+        package_sources: Vec<IndexerPackageSource>,
+    ) -> Result<(), IndexerError> {
+        self.get_sqlite_store(INDEXER_PACKAGE_SOURCES_TABLE_NAME)?
+            .persist_or_update_package_sources(package_sources)
+    }
 }
 
 impl IndexerStore {
@@ -4,6 +4,7 @@
 pub mod events;
 pub mod fields;
 pub mod inscriptions;
+pub mod package_sources;
 pub mod states;
 pub mod transactions;
 pub mod utxos;
@@ -0,0 +1,52 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::schema::package_sources;
+use diesel::prelude::*;
+use kanari_types::indexer::package_source::IndexerPackageSource;
+use move_core_types::account_address::AccountAddress;
+use moveos_types::h256::H256;
+use std::str::FromStr;
+
+#[derive(Queryable, QueryableByName, Insertable, Debug, Clone)]
+#[diesel(table_name = package_sources)]
+pub struct StoredPackageSource {
+    /// The package address, hex literal format
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub package_address: String,
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub package_name: String,
+    /// The package digest, hex literal format
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub package_digest: String,
+    /// The source digest, hex literal format
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub source_digest: String,
+    /// The submitted timestamp in milliseconds
+    #[diesel(sql_type = diesel::sql_types::BigInt)]
+    pub submitted_at: i64,
+}
+
+impl From<IndexerPackageSource> for StoredPackageSource {
+    fn from(source: IndexerPackageSource) -> Self {
+        Self {
+            package_address: source.package_address.to_hex_literal(),
+            package_name: source.package_name,
+            package_digest: format!("{:?}", source.package_digest),
+            source_digest: format!("{:?}", source.source_digest),
+            submitted_at: source.submitted_at as i64,
+        }
+    }
+}
+
+impl StoredPackageSource {
+    pub fn try_into_indexer_package_source(&self) -> Result<IndexerPackageSource, anyhow::Error> {
+        Ok(IndexerPackageSource {
+            package_address: AccountAddress::from_hex_literal(self.package_address.as_str())?,
+            package_name: self.package_name.clone(),
+            package_digest: H256::from_str(self.package_digest.as_str())?,
+            source_digest: H256::from_str(self.source_digest.as_str())?,
+            submitted_at: self.submitted_at as u64,
+        })
+    }
+}
@@ -4,9 +4,10 @@
 use crate::actor::indexer::IndexerActor;
 use crate::actor::messages::{
     IndexerApplyObjectStatesMessage, IndexerDeleteAnyObjectStatesMessage, IndexerEventsMessage,
-    IndexerPersistOrUpdateAnyObjectStatesMessage, IndexerRevertMessage, IndexerStatesMessage,
-    IndexerTransactionMessage, QueryIndexerEventsMessage, QueryIndexerFieldsMessage,
-    QueryIndexerObjectIdsMessage, QueryIndexerTransactionsMessage,
+    IndexerPackageSourcesMessage, IndexerPersistOrUpdateAnyObjectStatesMessage,
+    IndexerRevertMessage, IndexerStatesMessage, IndexerTransactionMessage,
+    QueryIndexerEventsMessage, QueryIndexerFieldsMessage, QueryIndexerObjectIdsMessage,
+    QueryIndexerPackageSourcesMessage, QueryIndexerTransactionsMessage,
     QueryLastStateIndexByTxOrderMessage, UpdateIndexerMessage,
 };
 use crate::actor::reader_indexer::IndexerReaderActor;
@@ -14,12 +15,14 @@ use anyhow::{Ok, Result};
 use coerce::actor::ActorRef;
 use kanari_types::indexer::event::{EventFilter, IndexerEvent, IndexerEventID};
 use kanari_types::indexer::field::{FieldFilter, IndexerField};
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::{
     IndexerObjectState, IndexerObjectStateChangeSet, IndexerStateID, ObjectStateFilter,
     ObjectStateType,
 };
 use kanari_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
 use kanari_types::transaction::LedgerTransaction;
+use move_core_types::account_address::AccountAddress;
 use moveos_types::moveos_std::event::Event;
 use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
 use moveos_types::moveos_std::tx_context::TxContext;
@@ -247,4 +250,22 @@ impl IndexerProxy {
             })
             .await?
     }
+
+    pub async fn persist_or_update_package_sources(
+        &self,
+        package_sources: Vec<IndexerPackageSource>,
+    ) -> Result<()> {
+        self.actor
+            .send(IndexerPackageSourcesMessage { package_sources })
+            .await?
+    }
+
+    pub async fn query_package_sources(
+        &self,
+        package_addresses: Vec<AccountAddress>,
+    ) -> Result<Vec<IndexerPackageSource>> {
+        self.reader_actor
+            .send(QueryIndexerPackageSourcesMessage { package_addresses })
+            .await?
+    }
 }
@@ -76,6 +76,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    package_sources (package_address) {
+        package_address -> Text,
+        package_name -> Text,
+        package_digest -> Text,
+        source_digest -> Text,
+        submitted_at -> BigInt,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     events,
     object_states,
@@ -83,4 +93,5 @@ diesel::allow_tables_to_appear_in_same_query!(
     inscriptions,
     transactions,
     fields,
+    package_sources,
 );
@@ -8,6 +8,7 @@ use diesel::{ExpressionMethods, RunQueryDsl};
 use function_name::named;
 use kanari_types::indexer::event::IndexerEvent;
 use kanari_types::indexer::field::IndexerField;
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::IndexerObjectState;
 use kanari_types::indexer::transaction::IndexerTransaction;
 use std::sync::Arc;
@@ -15,10 +16,13 @@ use std::sync::Arc;
 use crate::models::events::StoredEvent;
 use crate::models::fields::StoredField;
 use crate::models::inscriptions::StoredInscription;
+use crate::models::package_sources::StoredPackageSource;
 use crate::models::states::StoredObjectState;
 use crate::models::transactions::{StoredTransaction, escape_transaction};
 use crate::models::utxos::StoredUTXO;
-use crate::schema::{events, fields, inscriptions, object_states, transactions, utxos};
+use crate::schema::{
+    events, fields, inscriptions, object_states, package_sources, transactions, utxos,
+};
 use crate::store::metrics::IndexerDBMetrics;
 use crate::utils::escape_sql_string;
 use crate::{SqliteConnectionPool, get_sqlite_pool_connection};
@@ -539,4 +543,37 @@ impl SqliteIndexerStore {
 
         Ok(())
     }
+
+    #[named]
+    pub fn persist_or_update_package_sources(
+        &self,
+        package_sources: Vec<IndexerPackageSource>,
+    ) -> Result<(), IndexerError> {
+        if package_sources.is_empty() {
+            return Ok(());
+        }
+
+        let fn_name = function_name!();
+        let _timer = self
+            .db_metrics
+            .indexer_store_metrics
+            .indexer_persist_or_update_or_delete_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+        let mut connection = get_sqlite_pool_connection(&self.connection_pool)?;
+        let package_sources = package_sources
+            .into_iter()
+            .map(StoredPackageSource::from)
+            .collect::<Vec<_>>();
+
+        // The source of an upgraded package replaces the previous one, the caller keeps the record
+        // of an unchanged package
+        diesel::replace_into(package_sources::table)
+            .values(package_sources.as_slice())
+            .execute(&mut connection)
+            .map_err(|e| IndexerError::SQLiteWriteError(e.to_string()))
+            .context("Failed to write or update package sources to SQLiteDB")?;
+
+        Ok(())
+    }
 }
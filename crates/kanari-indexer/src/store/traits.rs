@@ -5,6 +5,7 @@ use crate::errors::IndexerError;
 use anyhow::Result;
 use kanari_types::indexer::event::IndexerEvent;
 use kanari_types::indexer::field::{IndexerField, IndexerFieldChanges};
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::{IndexerObjectState, IndexerObjectStateChangeSet};
 use kanari_types::indexer::transaction::IndexerTransaction;
 
@@ -53,4 +54,9 @@ pub trait IndexerStoreTrait: Send + Sync {
     fn delete_fields_by_parent_id(&self, ids: Vec<String>) -> Result<(), IndexerError>;
 
     fn apply_fields(&self, field_changes: IndexerFieldChanges) -> Result<(), IndexerError>;
+
+    fn persist_or_update_package_sources(
+        &self,
+        package_sources: Vec<IndexerPackageSource>,
+    ) -> Result<(), IndexerError>;
 }
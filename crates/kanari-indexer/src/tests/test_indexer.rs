@@ -10,6 +10,7 @@ use kanari_types::framework::coin_store::CoinStore;
 use kanari_types::framework::gas_coin::KARI;
 use kanari_types::indexer::event::{EventFilter, IndexerEvent};
 use kanari_types::indexer::field::FieldFilter;
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::{IndexerObjectState, ObjectStateFilter, ObjectStateType};
 use kanari_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
 use kanari_types::test_utils::{
@@ -298,3 +299,46 @@ async fn test_field_store() -> Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_package_source_store() -> Result<()> {
+    let registry_service = RegistryService::default();
+    let tmpdir = moveos_config::temp_dir();
+    let indexer_db = tmpdir.path().join(DEFAULT_DB_INDEXER_SUBDIR);
+    let indexer_store =
+        IndexerStore::new(indexer_db.clone(), &registry_service.default_registry())?;
+    let indexer_reader = IndexerReader::new(indexer_db, &registry_service.default_registry())?;
+
+    let package_address = AccountAddress::random();
+    let package_source = IndexerPackageSource::new(
+        package_address,
+        "test_package".to_string(),
+        H256::random(),
+        H256::random(),
+        1,
+    );
+    indexer_store.persist_or_update_package_sources(vec![
+        package_source,
+        IndexerPackageSource::new(
+            AccountAddress::random(),
+            "other_package".to_string(),
+            H256::random(),
+            H256::random(),
+            1,
+        ),
+    ])?;
+
+    // submit the source again after the package is upgraded, the previous record is replaced
+    let upgraded_source = IndexerPackageSource::new(
+        package_address,
+        "test_package".to_string(),
+        H256::random(),
+        H256::random(),
+        2,
+    );
+    indexer_store.persist_or_update_package_sources(vec![upgraded_source.clone()])?;
+
+    let query_package_sources = indexer_reader.query_package_sources(vec![package_address])?;
+    assert_eq!(query_package_sources, vec![upgraded_source]);
+    Ok(())
+}
@@ -1,22 +1,44 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+use bitcoin::{
+    Amount, Block, BlockHash, CompactTarget, OutPoint, ScriptBuf, Sequence, Transaction, TxIn,
+    TxMerkleNode, TxOut, Txid, Witness,
+    absolute::LockTime,
+    block::{self, Header},
+    hashes::Hash,
+    script::{Builder, PushBytesBuf},
+    transaction::Version,
+};
 use clap::Parser;
 use codespan_reporting::diagnostic::Severity;
 use codespan_reporting::term::termcolor::Buffer;
 use kanari_genesis::{FrameworksGasParameters, KanariGenesisV2};
-use kanari_types::framework::auth_validator::TxValidateResult;
+use kanari_types::address::BitcoinAddress;
+use kanari_types::addresses::KANARI_FRAMEWORK_ADDRESS;
+use kanari_types::authentication_key::AuthenticationKey;
+use kanari_types::bitcoin::BitcoinModule;
+use kanari_types::bitcoin::utxo::derive_utxo_id;
+use kanari_types::framework::auth_validator::{BuiltinAuthValidator, TxValidateResult};
+use kanari_types::framework::session_key::SessionKeyModule;
 use kanari_types::function_arg::FunctionArg;
+use kanari_types::genesis_config;
+use kanari_types::into_address::IntoAddress;
+use kanari_types::multichain_id::KanariMultiChainID;
+use kanari_types::transaction::{L1BlockWithBody, L1Transaction};
 use move_command_line_common::files::{MOVE_EXTENSION, extension_equals, find_filenames};
 use move_command_line_common::parser::NumberFormat;
 use move_command_line_common::{
     address::{NumericalAddress, ParsedAddress},
     files::verify_and_create_named_address_mapping,
+    types::ParsedStructType,
     values::ParsableValue,
 };
 use move_compiler::FullyCompiledProgram;
 use move_compiler::shared::PackagePaths;
-use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, vm_status::KeptVMStatus,
+};
 use move_package::BuildConfig;
 use move_transactional_test_runner::{
     tasks::{InitCommand, SyntaxChoice},
@@ -27,11 +49,17 @@ use moveos::moveos::{MoveOS, MoveOSConfig};
 use moveos::moveos_test_runner::{CompiledState, MoveOSTestAdapter, TaskInput};
 use moveos_config::DataDirPath;
 use moveos_store::MoveOSStore;
+use moveos_types::function_return_value::FunctionResult;
+use moveos_types::h256::{self, H256};
+use moveos_types::module_binding::MoveFunctionCaller;
+use moveos_types::move_std::option::MoveOption;
 use moveos_types::move_std::string::MoveString;
+use moveos_types::moveos_std::event::Event;
 use moveos_types::moveos_std::module_store::PackageData;
 use moveos_types::moveos_std::object::ObjectMeta;
-use moveos_types::state_resolver::RootObjectResolver;
-use moveos_types::transaction::VerifiedMoveOSTransaction;
+use moveos_types::moveos_std::tx_context::TxContext;
+use moveos_types::state_resolver::{RootObjectResolver, StateResolver};
+use moveos_types::transaction::{FunctionCall, VerifiedMoveAction, VerifiedMoveOSTransaction};
 use moveos_types::{
     addresses::MOVEOS_STD_ADDRESS,
     move_types::FunctionId,
@@ -44,6 +72,7 @@ use moveos_verifier::metadata::run_extended_checks;
 use once_cell::sync::Lazy;
 use regex::Regex;
 use std::path::PathBuf;
+use std::str::FromStr;
 use std::{collections::BTreeMap, path::Path};
 use tracing::debug;
 
@@ -56,21 +85,289 @@ pub struct MoveOSTestRunner<'a> {
     moveos: MoveOS,
     moveos_store: MoveOSStore,
     root: ObjectMeta,
+    /// The events emitted by the transactions of the last task, checked by `assert_event`
+    last_events: Vec<Event>,
+    /// The height of the next synthetic Bitcoin block
+    bitcoin_block_height: u64,
+    /// The hash of the last synthetic Bitcoin block
+    bitcoin_block_hash: BlockHash,
+    /// The txids of the named Bitcoin txs, referred by `--input` and `--utxo` as `<name>:<vout>`
+    bitcoin_tx_names: BTreeMap<String, Txid>,
 }
 
 impl MoveOSTestRunner<'_> {
     pub fn validate_tx(&self, tx: MoveOSTransaction) -> anyhow::Result<VerifiedMoveOSTransaction> {
+        self.validate_tx_with_result(tx, TxValidateResult::new_for_test())
+    }
+
+    pub fn validate_tx_with_result(
+        &self,
+        tx: MoveOSTransaction,
+        validate_result: TxValidateResult,
+    ) -> anyhow::Result<VerifiedMoveOSTransaction> {
         let mut verified_tx = self.moveos.verify(tx)?;
-        verified_tx.ctx.add(TxValidateResult::new_for_test())?;
+        verified_tx.ctx.add(validate_result)?;
         Ok(verified_tx)
     }
+
+    fn execute_verified_tx(
+        &mut self,
+        tx_hash: H256,
+        verified_tx: VerifiedMoveOSTransaction,
+    ) -> anyhow::Result<TransactionOutput> {
+        let (raw_output, _) = self.moveos.execute_only(verified_tx)?;
+        let (output, _exe_info) = self.moveos_store.handle_tx_output(tx_hash, raw_output)?;
+        self.root = output.changeset.root_metadata();
+        self.last_events = output.events.clone();
+        Ok(output)
+    }
+
+    fn execute_system_call(
+        &mut self,
+        tx_hash: H256,
+        tx_size: u64,
+        call: FunctionCall,
+    ) -> anyhow::Result<TransactionOutput> {
+        let ctx = TxContext::new_system_call_ctx(tx_hash, tx_size);
+        let action = VerifiedMoveAction::Function {
+            call,
+            bypass_visibility: true,
+        };
+        let verified_tx = VerifiedMoveOSTransaction::new(self.root.clone(), ctx, action);
+        self.execute_verified_tx(tx_hash, verified_tx)
+    }
+
+    /// Execute a user transaction, the sender is the `--bitcoin-address` account if present,
+    /// otherwise the last of the `--signers`.
+    fn execute_user_action(
+        &mut self,
+        signers: Vec<ParsedAddress>,
+        action: MoveAction,
+        extra: MoveOSRunArgs,
+    ) -> anyhow::Result<TransactionOutput> {
+        let MoveOSRunArgs {
+            bitcoin_address,
+            session_key,
+        } = extra;
+        let mut validate_result = TxValidateResult::new_for_test();
+        let sender = match bitcoin_address {
+            Some(bitcoin_address) => {
+                let sender = bitcoin_address.to_kanari_address().into();
+                validate_result.bitcoin_address = bitcoin_address;
+                sender
+            }
+            None => {
+                let signer = signers
+                    .last()
+                    .ok_or_else(|| anyhow::anyhow!("Missing --signers or --bitcoin-address"))?;
+                self.compiled_state().resolve_address(signer)
+            }
+        };
+        if let Some(auth_key) = session_key {
+            let caller = RootFunctionCaller {
+                moveos: &self.moveos,
+                root: self.root.clone(),
+            };
+            let session_key = caller
+                .as_module_binding::<SessionKeyModule>()
+                .get_session_key(sender, &auth_key)?
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Session key {} not found for account {}", auth_key, sender)
+                })?;
+            if !session_key.is_scope_match_with_action(&action) {
+                anyhow::bail!("Session key {} scope does not match the action", auth_key);
+            }
+            validate_result.auth_validator_id = BuiltinAuthValidator::Session.flag().into();
+            validate_result.session_key = MoveOption::some(auth_key.into());
+        }
+
+        let tx = MoveOSTransaction::new_for_test(self.root.clone(), sender, action);
+        let tx_hash = tx.ctx.tx_hash();
+        let verified_tx = self.validate_tx_with_result(tx, validate_result)?;
+        self.execute_verified_tx(tx_hash, verified_tx)
+    }
+
+    /// Build a synthetic Bitcoin block on top of the last one, and execute it and its txs
+    /// in the same order as the relayer: the block first, then the txs with the coinbase last.
+    /// The `name` refers to the last tx of the block, or the coinbase if the block has no other tx.
+    fn execute_bitcoin_block(
+        &mut self,
+        coinbase_outputs: Vec<BitcoinOutputArg>,
+        mut txdata: Vec<Transaction>,
+        name: Option<String>,
+    ) -> anyhow::Result<String> {
+        let height = self.bitcoin_block_height;
+        let mut coinbase_outputs = coinbase_outputs
+            .into_iter()
+            .map(BitcoinOutputArg::into_tx_out)
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        if coinbase_outputs.is_empty() {
+            coinbase_outputs.push(TxOut {
+                value: Amount::ZERO,
+                script_pubkey: ScriptBuf::new_op_return(PushBytesBuf::new()),
+            });
+        }
+        let coinbase = Transaction {
+            version: Version::TWO,
+            lock_time: LockTime::ZERO,
+            input: vec![TxIn {
+                previous_output: OutPoint::null(),
+                // BIP34 height makes the coinbase txid unique per block
+                script_sig: Builder::new().push_int(height as i64).into_script(),
+                sequence: Sequence::MAX,
+                witness: Witness::from_slice(&[[0u8; 32]]),
+            }],
+            output: coinbase_outputs,
+        };
+        txdata.insert(0, coinbase);
+        let mut block = Block {
+            header: Header {
+                version: block::Version::TWO,
+                prev_blockhash: self.bitcoin_block_hash,
+                merkle_root: TxMerkleNode::all_zeros(),
+                time: (height * 600) as u32,
+                bits: CompactTarget::from_consensus(0x207fffff),
+                nonce: 0,
+            },
+            txdata,
+        };
+        block.header.merkle_root = block
+            .compute_merkle_root()
+            .expect("block should have coinbase tx");
+        let block_hash = block.block_hash();
+        if let Some(name) = name {
+            let named_tx = block.txdata.last().expect("block should have coinbase tx");
+            self.bitcoin_tx_names.insert(name, named_tx.compute_txid());
+        }
+        let mut txs = block.txdata.iter().cloned().enumerate().collect::<Vec<_>>();
+        let coinbase = txs.remove(0);
+        txs.push(coinbase);
+
+        let l1_block = L1BlockWithBody::new_bitcoin_block(height, block);
+        let call = BitcoinModule::create_execute_l1_block_call_bytes(
+            height,
+            l1_block.block.block_hash.clone(),
+            l1_block.block_body,
+        )?;
+        let output =
+            self.execute_system_call(l1_block.block.tx_hash(), l1_block.block.tx_size(), call)?;
+        if output.status != KeptVMStatus::Executed {
+            anyhow::bail!("Execute bitcoin block {} failed, {}", height, output.status);
+        }
+        self.bitcoin_block_height += 1;
+        self.bitcoin_block_hash = block_hash;
+
+        let mut events = vec![];
+        // The hashes are not printed, the txs are referred by the names in the test files
+        let mut lines = vec![format!("block {}", height)];
+        for (tx_index, tx) in txs {
+            let txid = tx.compute_txid();
+            let l1_tx = L1Transaction::new(
+                KanariMultiChainID::Bitcoin.multichain_id(),
+                l1_block.block.block_hash.clone(),
+                txid.into_address().to_vec(),
+            );
+            let call = BitcoinModule::create_execute_l1_tx_call(
+                l1_tx.block_hash.clone(),
+                l1_tx.txid.clone(),
+            )?;
+            let output = self.execute_system_call(l1_tx.tx_hash(), l1_tx.tx_size(), call)?;
+            events.extend(output.events.clone());
+            lines.push(format!("tx {} {}", tx_index, tx_output_to_str(output)));
+        }
+        self.last_events = events;
+        Ok(lines.join("\n"))
+    }
+
+    fn resolve_outpoint(&self, outpoint: &BitcoinOutPointArg) -> anyhow::Result<OutPoint> {
+        let txid = match Txid::from_str(&outpoint.tx) {
+            Ok(txid) => txid,
+            Err(_) => *self
+                .bitcoin_tx_names
+                .get(&outpoint.tx)
+                .ok_or_else(|| anyhow::anyhow!("Bitcoin tx {} not found", outpoint.tx))?,
+        };
+        Ok(OutPoint::new(txid, outpoint.vout))
+    }
+}
+
+/// Call readonly functions on the given state root
+struct RootFunctionCaller<'a> {
+    moveos: &'a MoveOS,
+    root: ObjectMeta,
+}
+
+impl MoveFunctionCaller for RootFunctionCaller<'_> {
+    fn call_function(&self, ctx: &TxContext, call: FunctionCall) -> anyhow::Result<FunctionResult> {
+        Ok(self
+            .moveos
+            .execute_readonly_function(self.root.clone(), ctx, call))
+    }
+}
+
+/// A Bitcoin tx output in the `<bitcoin_address>:<satoshis>` format
+#[derive(Debug, Clone)]
+pub struct BitcoinOutputArg {
+    pub address: BitcoinAddress,
+    pub amount: u64,
+}
+
+impl BitcoinOutputArg {
+    fn into_tx_out(self) -> anyhow::Result<TxOut> {
+        Ok(TxOut {
+            value: Amount::from_sat(self.amount),
+            script_pubkey: self.address.script_pubkey()?,
+        })
+    }
+}
+
+impl FromStr for BitcoinOutputArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, amount) = s.rsplit_once(':').ok_or_else(|| {
+            anyhow::anyhow!("Invalid output {}, expect <bitcoin_address>:<satoshis>", s)
+        })?;
+        Ok(Self {
+            address: BitcoinAddress::from_str(address)?,
+            amount: amount.parse()?,
+        })
+    }
+}
+
+/// A Bitcoin outpoint in the `<txid>:<vout>` format, the txid can be the name of a previous tx
+#[derive(Debug, Clone)]
+pub struct BitcoinOutPointArg {
+    pub tx: String,
+    pub vout: u32,
+}
+
+impl FromStr for BitcoinOutPointArg {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (tx, vout) = s.rsplit_once(':').ok_or_else(|| {
+            anyhow::anyhow!("Invalid outpoint {}, expect <txid_or_name>:<vout>", s)
+        })?;
+        Ok(Self {
+            tx: tx.to_owned(),
+            vout: vout.parse()?,
+        })
+    }
 }
 
 #[derive(Parser, Debug)]
 pub struct MoveOSPublishArgs {}
 
 #[derive(Parser, Debug)]
-pub struct MoveOSRunArgs {}
+pub struct MoveOSRunArgs {
+    /// Send the transaction as the account of the Bitcoin address, instead of the `--signers`
+    #[clap(long)]
+    pub bitcoin_address: Option<BitcoinAddress>,
+    /// Send the transaction via the sender's session key with the authentication key
+    #[clap(long)]
+    pub session_key: Option<AuthenticationKey>,
+}
 
 #[derive(Parser, Debug)]
 pub struct MoveOSExtraInitArgs {}
@@ -84,6 +381,56 @@ pub enum MoveOSSubcommands {
         #[clap(short, long)]
         object_id: ObjectID,
     },
+    /// Fast forward the global timestamp
+    #[clap(name = "advance_clock")]
+    AdvanceClock {
+        #[clap(long)]
+        seconds: u64,
+    },
+    /// Execute a Bitcoin block with only the coinbase tx
+    #[clap(name = "bitcoin_block")]
+    BitcoinBlock {
+        /// The coinbase outputs, `<bitcoin_address>:<satoshis>`
+        #[clap(long = "to")]
+        outputs: Vec<BitcoinOutputArg>,
+        /// The name of the coinbase tx, to refer its outputs as `<name>:<vout>`
+        #[clap(long)]
+        name: Option<String>,
+    },
+    /// Execute a Bitcoin block with a tx which spends the UTXOs
+    #[clap(name = "bitcoin_transfer")]
+    BitcoinTransfer {
+        /// The spent outpoints, `<txid>:<vout>` or `<name>:<vout>`
+        #[clap(long = "input", required = true)]
+        inputs: Vec<BitcoinOutPointArg>,
+        /// The tx outputs, `<bitcoin_address>:<satoshis>`
+        #[clap(long = "to", required = true)]
+        outputs: Vec<BitcoinOutputArg>,
+        /// The name of the tx, to refer its outputs as `<name>:<vout>`
+        #[clap(long)]
+        name: Option<String>,
+    },
+    /// Assert the transactions of the last task emitted the event
+    #[clap(name = "assert_event")]
+    AssertEvent {
+        #[clap(long = "event-type", value_parser = ParsedStructType::parse)]
+        event_type: ParsedStructType,
+        /// The expected number of events, at least one if not present
+        #[clap(long)]
+        count: Option<usize>,
+    },
+    /// Assert the owner of the object, or the UTXO object of the outpoint
+    #[clap(name = "assert_owner")]
+    AssertOwner {
+        #[clap(long, required_unless_present = "utxo", conflicts_with = "utxo")]
+        object_id: Option<ObjectID>,
+        /// The outpoint of the UTXO, `<txid>:<vout>` or `<name>:<vout>`
+        #[clap(long)]
+        utxo: Option<BitcoinOutPointArg>,
+        /// The owner address, or the Bitcoin address of the owner
+        #[clap(long)]
+        owner: String,
+    },
 }
 
 impl<'a> MoveOSTestAdapter<'a> for MoveOSTestRunner<'a> {
@@ -146,6 +493,7 @@ impl<'a> MoveOSTestAdapter<'a> for MoveOSTestRunner<'a> {
             named_address_mapping.insert(name, addr);
         }
 
+        let genesis_config = &genesis_config::G_LOCAL_CONFIG;
         let adapter = Self {
             compiled_state: CompiledState::new(named_address_mapping, pre_compiled_deps, None),
             default_syntax,
@@ -153,6 +501,10 @@ impl<'a> MoveOSTestAdapter<'a> for MoveOSTestRunner<'a> {
             moveos,
             moveos_store,
             root: exe_info.root_metadata(),
+            last_events: vec![],
+            bitcoin_block_height: genesis_config.bitcoin_block_height + 1,
+            bitcoin_block_hash: genesis_config.bitcoin_block_hash,
+            bitcoin_tx_names: BTreeMap::new(),
         };
         debug!("init moveos test adapter");
         (adapter, None)
@@ -194,9 +546,7 @@ impl<'a> MoveOSTestAdapter<'a> for MoveOSTestRunner<'a> {
         let tx = MoveOSTransaction::new_for_test(self.root.clone(), sender, action);
         let tx_hash = tx.ctx.tx_hash();
         let verified_tx = self.validate_tx(tx)?;
-        let (raw_output, _) = self.moveos.execute_only(verified_tx)?;
-        let (output, _exe_info) = self.moveos_store.handle_tx_output(tx_hash, raw_output)?;
-        self.root = output.changeset.root_metadata();
+        let output = self.execute_verified_tx(tx_hash, verified_tx)?;
         Ok((Some(tx_output_to_str(output)), module))
     }
 
@@ -209,7 +559,7 @@ impl<'a> MoveOSTestAdapter<'a> for MoveOSTestRunner<'a> {
             <<Self as MoveOSTestAdapter<'a>>::ExtraValueArgs as ParsableValue>::ConcreteValue,
         >,
         _gas_budget: Option<u64>,
-        _extra: Self::ExtraRunArgs,
+        extra: Self::ExtraRunArgs,
     ) -> anyhow::Result<(
         Option<String>,
         move_vm_runtime::session::SerializedReturnValues,
@@ -217,27 +567,16 @@ impl<'a> MoveOSTestAdapter<'a> for MoveOSTestRunner<'a> {
         let mut script_bytes = vec![];
         script.serialize(&mut script_bytes)?;
 
-        let mut signers: Vec<_> = signers
-            .into_iter()
-            .map(|addr| self.compiled_state().resolve_address(&addr))
-            .collect();
-
         let args = args
             .iter()
             .map(|arg| arg.simple_serialize().unwrap())
             .collect::<Vec<_>>();
 
-        let tx = MoveOSTransaction::new_for_test(
-            self.root.clone(),
-            signers.pop().unwrap(),
+        let output = self.execute_user_action(
+            signers,
             MoveAction::new_script_call(script_bytes, type_args, args),
-        );
-        let tx_hash = tx.ctx.tx_hash();
-        let verified_tx = self.validate_tx(tx)?;
-        let (raw_output, _) = self.moveos.execute_only(verified_tx)?;
-
-        let (output, _exe_info) = self.moveos_store.handle_tx_output(tx_hash, raw_output)?;
-        self.root = output.changeset.root_metadata();
+            extra,
+        )?;
         //TODO return values
         let value = SerializedReturnValues {
             mutable_reference_outputs: vec![],
@@ -256,32 +595,21 @@ impl<'a> MoveOSTestAdapter<'a> for MoveOSTestRunner<'a> {
             <<Self as MoveOSTestAdapter<'a>>::ExtraValueArgs as ParsableValue>::ConcreteValue,
         >,
         _gas_budget: Option<u64>,
-        _extra: Self::ExtraRunArgs,
+        extra: Self::ExtraRunArgs,
     ) -> anyhow::Result<(
         Option<String>,
         move_vm_runtime::session::SerializedReturnValues,
     )> {
-        let mut signers: Vec<_> = signers
-            .into_iter()
-            .map(|addr| self.compiled_state().resolve_address(&addr))
-            .collect();
-
         let args = args
             .iter()
             .map(|arg| arg.simple_serialize().unwrap())
             .collect::<Vec<_>>();
         let function_id = FunctionId::new(module.clone(), function.to_owned());
-        let tx = MoveOSTransaction::new_for_test(
-            self.root.clone(),
-            signers.pop().unwrap(),
+        let output = self.execute_user_action(
+            signers,
             MoveAction::new_function_call(function_id, type_args, args),
-        );
-        let tx_hash = tx.ctx.tx_hash();
-        let verified_tx = self.validate_tx(tx)?;
-        let (raw_output, _) = self.moveos.execute_only(verified_tx)?;
-
-        let (output, _exe_info) = self.moveos_store.handle_tx_output(tx_hash, raw_output)?;
-        self.root = output.changeset.root_metadata();
+            extra,
+        )?;
         debug_assert!(
             output.status == move_core_types::vm_status::KeptVMStatus::Executed,
             "{:?}",
@@ -321,6 +649,107 @@ impl<'a> MoveOSTestAdapter<'a> for MoveOSTestRunner<'a> {
                 //TODO should we bring the AnnotatedObjectView from jsonrpc to test adapter for better json output formatting?
                 Ok(Some(format!("{:?}", object)))
             }
+            MoveOSSubcommands::AdvanceClock { seconds } => {
+                let call = FunctionCall::new(
+                    FunctionId::new(
+                        ModuleId::new(
+                            KANARI_FRAMEWORK_ADDRESS,
+                            Identifier::new("timestamp".to_owned()).unwrap(),
+                        ),
+                        Identifier::new("fast_forward_seconds_for_local".to_owned()).unwrap(),
+                    ),
+                    vec![],
+                    vec![bcs::to_bytes(&seconds)?],
+                );
+                // Hash with the current state root, so advancing by the same seconds twice gets different tx hashes
+                let call_bytes = bcs::to_bytes(&(self.root.state_root(), &call))?;
+                let tx_hash = h256::sha3_256_of(&call_bytes);
+                let output = self.execute_system_call(tx_hash, call_bytes.len() as u64, call)?;
+                Ok(Some(tx_output_to_str(output)))
+            }
+            MoveOSSubcommands::BitcoinBlock { outputs, name } => {
+                Ok(Some(self.execute_bitcoin_block(outputs, vec![], name)?))
+            }
+            MoveOSSubcommands::BitcoinTransfer {
+                inputs,
+                outputs,
+                name,
+            } => {
+                let tx = Transaction {
+                    version: Version::TWO,
+                    lock_time: LockTime::ZERO,
+                    input: inputs
+                        .iter()
+                        .map(|input| {
+                            Ok(TxIn {
+                                previous_output: self.resolve_outpoint(input)?,
+                                script_sig: ScriptBuf::new(),
+                                sequence: Sequence::MAX,
+                                witness: Witness::new(),
+                            })
+                        })
+                        .collect::<anyhow::Result<Vec<_>>>()?,
+                    output: outputs
+                        .into_iter()
+                        .map(BitcoinOutputArg::into_tx_out)
+                        .collect::<anyhow::Result<Vec<_>>>()?,
+                };
+                Ok(Some(self.execute_bitcoin_block(vec![], vec![tx], name)?))
+            }
+            MoveOSSubcommands::AssertEvent { event_type, count } => {
+                let state = self.compiled_state();
+                let event_type =
+                    event_type.into_struct_tag(&|s| Some(state.resolve_named_address(s)))?;
+                let actual = self
+                    .last_events
+                    .iter()
+                    .filter(|event| event.event_type == event_type)
+                    .count();
+                match count {
+                    Some(count) if count != actual => anyhow::bail!(
+                        "Expect {} events of {}, but got {}",
+                        count,
+                        event_type,
+                        actual
+                    ),
+                    None if actual == 0 => {
+                        anyhow::bail!("Expect events of {}, but got none", event_type)
+                    }
+                    _ => Ok(None),
+                }
+            }
+            MoveOSSubcommands::AssertOwner {
+                object_id,
+                utxo,
+                owner,
+            } => {
+                let object_id = match (object_id, utxo) {
+                    (Some(object_id), _) => object_id,
+                    (None, Some(utxo)) => derive_utxo_id(&self.resolve_outpoint(&utxo)?.into()),
+                    (None, None) => anyhow::bail!("Missing --object-id or --utxo"),
+                };
+                let owner = match BitcoinAddress::from_str(&owner) {
+                    Ok(bitcoin_address) => bitcoin_address.to_kanari_address().into(),
+                    Err(_) => {
+                        let owner = ParsedAddress::parse(&owner)?;
+                        self.compiled_state().resolve_address(&owner)
+                    }
+                };
+                let resolver =
+                    RootObjectResolver::new(self.root.clone(), self.moveos.moveos_store());
+                let object = resolver
+                    .get_object(&object_id)?
+                    .ok_or_else(|| anyhow::anyhow!("Object with id {} not found", object_id))?;
+                if object.metadata.owner != owner {
+                    anyhow::bail!(
+                        "Expect the owner of object {} is {}, but got {}",
+                        object_id,
+                        owner,
+                        object.metadata.owner
+                    );
+                }
+                Ok(None)
+            }
         }
     }
 }
@@ -480,6 +909,22 @@ pub fn kanari_framework_info() -> (Vec<String>, BTreeMap<String, NumericalAddres
     (files, named_addresses)
 }
 
+pub fn bitcoin_move_info() -> (Vec<String>, BTreeMap<String, NumericalAddress>) {
+    let bitcoin_move_path = PathBuf::from("../../frameworks/bitcoin-move/");
+    let named_addresses = resolve_package_named_addresses(bitcoin_move_path.clone());
+
+    let binding = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join(bitcoin_move_path.join("sources"))
+        .canonicalize()
+        .unwrap();
+    let bitcoin_move_path = binding.as_path();
+    let files = find_filenames(&[bitcoin_move_path], |p| {
+        extension_equals(p, MOVE_EXTENSION)
+    })
+    .unwrap();
+    (files, named_addresses)
+}
+
 pub const KANARI_FRAMEWORK_ADDRESS_NAME: &str = "kanari_framework";
 pub const KANARI_FRAMEWORK_ADDRESS_LITERAL: &str = "0x3";
 
@@ -514,11 +959,19 @@ pub fn all_pre_compiled_libs() -> Option<FullyCompiledProgram> {
         named_address_map: addresses,
     };
 
+    let (bitcoin_move_files, bitcoin_move_named_addresses) = bitcoin_move_info();
+    let bitcoin_move_package = PackagePaths {
+        name: None,
+        paths: bitcoin_move_files,
+        named_address_map: bitcoin_move_named_addresses,
+    };
+
     let program_res = move_compiler::construct_pre_compiled_lib(
         vec![
             stdlib_package,
             moveos_stdlib_package,
             kanari_framework_package,
+            bitcoin_move_package,
         ],
         None,
         move_compiler::Flags::empty(),
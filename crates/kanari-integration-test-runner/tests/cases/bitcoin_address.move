@@ -0,0 +1,18 @@
+//# init --addresses tester=0x42
+
+//# run --signers tester --bitcoin-address bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+script {
+    use std::option;
+    use std::signer;
+    use std::string;
+    use kanari_framework::address_mapping;
+    use kanari_framework::bitcoin_address;
+
+    fun main(s: signer) {
+        let btc_address = bitcoin_address::from_string(&string::utf8(b"bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"));
+        let sender = signer::address_of(&s);
+        assert!(sender == bitcoin_address::to_kanari_address(&btc_address), 0);
+        let mapped = address_mapping::resolve_bitcoin(sender);
+        assert!(option::destroy_some(mapped) == btc_address, 1);
+    }
+}
@@ -0,0 +1,22 @@
+//# init --addresses tester=0x42
+
+//# bitcoin_block --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4:5000000000 --to bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0:2500000000 --name first
+
+//# assert_owner --utxo first:0 --owner bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+
+//# assert_owner --utxo first:1 --owner bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0
+
+//# bitcoin_block
+
+//# run --signers tester
+script {
+    use std::option;
+    use bitcoin_move::bitcoin;
+    use bitcoin_move::types;
+
+    fun main() {
+        let latest_block = option::destroy_some(bitcoin::get_latest_block());
+        let (height, _hash) = types::unpack_block_height_hash(latest_block);
+        assert!(height == 2, 0);
+    }
+}
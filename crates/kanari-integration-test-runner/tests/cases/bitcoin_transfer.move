@@ -0,0 +1,13 @@
+//# init --addresses tester=0x42
+
+//# bitcoin_block --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4:5000000000 --name funding
+
+//# assert_owner --utxo funding:0 --owner bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+
+//# bitcoin_transfer --input funding:0 --to bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0:4000000000 --to bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4:999990000 --name spend
+
+//# assert_owner --utxo spend:0 --owner bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0
+
+//# assert_owner --utxo spend:1 --owner bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4
+
+//# bitcoin_block
@@ -0,0 +1,30 @@
+//# init --addresses tester=0x42
+
+//# publish
+module tester::m {
+    use moveos_std::event;
+
+    struct TestEvent has copy, drop {
+        value: u64,
+    }
+
+    public entry fun emit_twice(value: u64) {
+        event::emit(TestEvent { value });
+        event::emit(TestEvent { value });
+    }
+}
+
+//# run tester::m::emit_twice --signers tester --args 1u64
+
+//# assert_event --event-type tester::m::TestEvent --count 2
+
+//# advance_clock --seconds 100
+
+//# run --signers tester
+script {
+    use moveos_std::timestamp;
+
+    fun main() {
+        assert!(timestamp::now_seconds() >= 100, 0);
+    }
+}
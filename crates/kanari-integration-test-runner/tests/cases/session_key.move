@@ -0,0 +1,50 @@
+//# init --addresses tester=0x42
+
+//# publish
+module tester::counter {
+    use std::signer;
+    use moveos_std::account;
+
+    struct Counter has key {
+        value: u64,
+    }
+
+    public entry fun increase(sender: &signer) {
+        let addr = signer::address_of(sender);
+        if (!account::exists_resource<Counter>(addr)) {
+            account::move_resource_to(sender, Counter { value: 0 });
+        };
+        let counter = account::borrow_mut_resource<Counter>(addr);
+        counter.value = counter.value + 1;
+    }
+
+    public entry fun reset(sender: &signer) {
+        let counter = account::borrow_mut_resource<Counter>(signer::address_of(sender));
+        counter.value = 0;
+    }
+}
+
+//# run --signers tester
+script {
+    use std::string;
+    use kanari_framework::session_key;
+
+    fun main(s: signer) {
+        session_key::create_session_key_entry(
+            &s,
+            string::utf8(b"test"),
+            string::utf8(b"https://test.kanari.network"),
+            x"0ef8f1ebd8e1a38c6afd6e1b2c97b1a4bd8fa9fb4d8bde7e9f5cbb6b1bb12c3d",
+            @tester,
+            string::utf8(b"counter"),
+            string::utf8(b"increase"),
+            0,
+        );
+    }
+}
+
+//# run tester::counter::increase --signers tester --session-key 0x0ef8f1ebd8e1a38c6afd6e1b2c97b1a4bd8fa9fb4d8bde7e9f5cbb6b1bb12c3d
+
+//# view --address 0x42 --resource 0x42::counter::Counter
+
+//# run tester::counter::reset --signers tester --session-key 0x0ef8f1ebd8e1a38c6afd6e1b2c97b1a4bd8fa9fb4d8bde7e9f5cbb6b1bb12c3d
@@ -3,15 +3,19 @@
 
 use argon2::Argon2;
 use argon2::PasswordVerifier;
+use argon2::password_hash::rand_core::OsRng;
 use argon2::password_hash::{PasswordHash, PasswordHasher, SaltString};
-use bip32::{ChildNumber, DerivationPath, XPrv};
+use bip32::{ChildNumber, DerivationPath, Prefix, XPrv};
 use bip39::{Language, Mnemonic, MnemonicType, Seed};
 use fastcrypto::secp256k1::{Secp256k1KeyPair, Secp256k1PrivateKey};
 use fastcrypto::traits::ToFromBytes;
-use kanari_types::crypto::KanariKeyPair;
+use kanari_types::address::{BitcoinAddress, KanariAddress};
+use kanari_types::crypto::{KanariKeyPair, PublicKey};
 use kanari_types::error::KanariError;
-use kanari_types::key_struct::{EncryptionData, GenerateNewKeyPair, GeneratedKeyPair};
+use kanari_types::key_struct::{EncryptionData, GenerateNewKeyPair, GeneratedKeyPair, KdfParams};
 use kanari_types::multichain_id::KanariMultiChainID;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::str::FromStr;
 use std::string::String;
 
@@ -20,8 +24,206 @@ pub const DERIVATION_PATH_PURPOSE_ED25519: u32 = 44;
 pub const DERIVATION_PATH_PURPOSE_SCHNORR: u32 = 44;
 pub const DERIVATION_PATH_PURPOSE_ECDSA: u32 = 54;
 pub const DERIVATION_PATH_PURPOSE_SECP256R1: u32 = 74;
+pub const DERIVATION_PATH_PURPOSE_BIP44: u32 = 44;
+pub const DERIVATION_PATH_PURPOSE_BIP49: u32 = 49;
+pub const DERIVATION_PATH_PURPOSE_BIP84: u32 = 84;
 pub const DERIVATION_PATH_PURPOSE_BIP86: u32 = 86;
 
+// The SLIP-44 coin type shared by all Bitcoin test networks
+pub const BITCOIN_TESTNET_COIN_TYPE: u32 = 1;
+
+// The number of consecutive unused addresses after which the account discovery stops, as BIP-44
+pub const DEFAULT_ADDRESS_GAP_LIMIT: u32 = 20;
+
+/// The standard Bitcoin derivation schemes, the purpose of a path decides the address type
+/// a Bitcoin wallet restores from it.
+/// The Kanari address of a key is always derived from its P2TR address, whatever the scheme.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DerivationScheme {
+    /// P2PKH, https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
+    Bip44,
+    /// P2SH-P2WPKH, https://github.com/bitcoin/bips/blob/master/bip-0049.mediawiki
+    Bip49,
+    /// P2WPKH, https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki
+    Bip84,
+    /// P2TR, https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki
+    Bip86,
+}
+
+impl DerivationScheme {
+    pub const ALL: [DerivationScheme; 4] = [
+        DerivationScheme::Bip44,
+        DerivationScheme::Bip49,
+        DerivationScheme::Bip84,
+        DerivationScheme::Bip86,
+    ];
+
+    pub fn purpose(&self) -> u32 {
+        match self {
+            DerivationScheme::Bip44 => DERIVATION_PATH_PURPOSE_BIP44,
+            DerivationScheme::Bip49 => DERIVATION_PATH_PURPOSE_BIP49,
+            DerivationScheme::Bip84 => DERIVATION_PATH_PURPOSE_BIP84,
+            DerivationScheme::Bip86 => DERIVATION_PATH_PURPOSE_BIP86,
+        }
+    }
+
+    pub fn from_purpose(purpose: u32) -> Result<Self, anyhow::Error> {
+        match purpose {
+            DERIVATION_PATH_PURPOSE_BIP44 => Ok(DerivationScheme::Bip44),
+            DERIVATION_PATH_PURPOSE_BIP49 => Ok(DerivationScheme::Bip49),
+            DERIVATION_PATH_PURPOSE_BIP84 => Ok(DerivationScheme::Bip84),
+            DERIVATION_PATH_PURPOSE_BIP86 => Ok(DerivationScheme::Bip86),
+            _ => Err(anyhow::anyhow!(
+                "Unsupported derivation purpose: {}",
+                purpose
+            )),
+        }
+    }
+
+    pub fn from_path(path: &DerivationPath) -> Result<Self, anyhow::Error> {
+        match path.iter().next() {
+            Some(purpose) if purpose.is_hardened() => Self::from_purpose(purpose.index()),
+            _ => Err(anyhow::anyhow!("Invalid derivation path: {}", path)),
+        }
+    }
+
+    /// The Bitcoin address of the public key in the address type of the scheme
+    pub fn bitcoin_address(&self, public_key: &PublicKey) -> Result<BitcoinAddress, anyhow::Error> {
+        let network = bitcoin::Network::Bitcoin;
+        let pk = match public_key {
+            PublicKey::Secp256k1(_) => {
+                bitcoin::CompressedPublicKey::from_slice(public_key.as_ref())?
+            }
+            _ => anyhow::bail!("Only secp256k1 public key can be converted to bitcoin address"),
+        };
+        // Kanari BitcoinAddress do not distinguish between network
+        let address = match self {
+            DerivationScheme::Bip44 => bitcoin::Address::p2pkh(pk, network),
+            DerivationScheme::Bip49 => bitcoin::Address::p2shwpkh(&pk, network),
+            DerivationScheme::Bip84 => bitcoin::Address::p2wpkh(&pk, network),
+            DerivationScheme::Bip86 => return public_key.bitcoin_address(),
+        };
+        Ok(BitcoinAddress::from(address))
+    }
+
+    fn descriptor(&self, key: &str) -> String {
+        match self {
+            DerivationScheme::Bip44 => format!("pkh({})", key),
+            DerivationScheme::Bip49 => format!("sh(wpkh({}))", key),
+            DerivationScheme::Bip84 => format!("wpkh({})", key),
+            DerivationScheme::Bip86 => format!("tr({})", key),
+        }
+    }
+}
+
+impl fmt::Display for DerivationScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DerivationScheme::Bip44 => write!(f, "bip44"),
+            DerivationScheme::Bip49 => write!(f, "bip49"),
+            DerivationScheme::Bip84 => write!(f, "bip84"),
+            DerivationScheme::Bip86 => write!(f, "bip86"),
+        }
+    }
+}
+
+impl FromStr for DerivationScheme {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "bip44" | "44" => Ok(DerivationScheme::Bip44),
+            "bip49" | "49" => Ok(DerivationScheme::Bip49),
+            "bip84" | "84" => Ok(DerivationScheme::Bip84),
+            "bip86" | "86" => Ok(DerivationScheme::Bip86),
+            _ => Err(anyhow::anyhow!("Invalid derivation scheme: {}", s)),
+        }
+    }
+}
+
+/// The metadata of the derivation path of an account, it is enough to restore
+/// the account in a Bitcoin wallet which supports output descriptors
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DerivationPathInfo {
+    pub derivation_path: String,
+    /// The hex encoded fingerprint of the master key
+    pub master_fingerprint: String,
+    /// The extended public key of the account level of the path, m / purpose' / coin_type' / account'
+    pub account_xpub: String,
+}
+
+impl DerivationPathInfo {
+    pub fn new(seed: &[u8], path: &DerivationPath) -> Result<Self, anyhow::Error> {
+        validate_derivation_path(path)?;
+        let master_fingerprint = XPrv::new(seed)
+            .map_err(|e| KanariError::SignatureKeyGenError(e.to_string()))?
+            .public_key()
+            .fingerprint()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>();
+        let account_path = DerivationPath::from_str(&format!(
+            "m/{}",
+            path.iter()
+                .take(3)
+                .map(|c| c.to_string())
+                .collect::<Vec<_>>()
+                .join("/")
+        ))?;
+        let account_xpub = XPrv::derive_from_path(seed, &account_path)
+            .map_err(|e| KanariError::SignatureKeyGenError(e.to_string()))?
+            .public_key()
+            .to_string(Prefix::XPUB);
+        Ok(Self {
+            derivation_path: path.to_string(),
+            master_fingerprint,
+            account_xpub,
+        })
+    }
+
+    pub fn from_mnemonic(
+        mnemonic_phrase: &str,
+        path: &DerivationPath,
+    ) -> Result<Self, anyhow::Error> {
+        let mnemonic = Mnemonic::from_phrase(mnemonic_phrase, Language::English)?;
+        Self::new(Seed::new(&mnemonic, "").as_bytes(), path)
+    }
+
+    pub fn path(&self) -> Result<DerivationPath, anyhow::Error> {
+        parse_derivation_path(&self.derivation_path)
+    }
+
+    pub fn scheme(&self) -> Result<DerivationScheme, anyhow::Error> {
+        DerivationScheme::from_path(&self.path()?)
+    }
+
+    /// The output descriptor with the BIP-380 checksum of the chain the address belongs to,
+    /// https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki
+    pub fn descriptor(&self) -> Result<String, anyhow::Error> {
+        let path = self.path()?;
+        let components = path.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+        let key = format!(
+            "[{}/{}]{}/{}/*",
+            self.master_fingerprint,
+            components[..3].join("/"),
+            self.account_xpub,
+            components[3]
+        );
+        let descriptor = self.scheme()?.descriptor(&key);
+        let checksum = descriptor_checksum(&descriptor)?;
+        Ok(format!("{}#{}", descriptor, checksum))
+    }
+}
+
+/// An address derived from the mnemonic, used to discover the accounts of a mnemonic
+#[derive(Debug, Clone)]
+pub struct DerivedAddress {
+    pub derivation_path: DerivationPath,
+    pub address: KanariAddress,
+    pub bitcoin_address: BitcoinAddress,
+}
+
 pub fn verify_password(
     password: Option<String>,
     password_hash: String,
@@ -40,10 +242,13 @@ pub fn verify_password(
         .is_ok())
 }
 
-pub fn hash_password(nonce: &[u8], password: Option<String>) -> Result<String, KanariError> {
-    let salt = SaltString::encode_b64(nonce)
+/// Hash the keystore password with Argon2id and a random salt, the PHC string keeps the parameters,
+/// so the hashes of the older keystores are still verified by `verify_password`
+pub fn hash_password(password: Option<String>) -> Result<String, KanariError> {
+    let salt = SaltString::generate(&mut OsRng);
+    let argon2 = KdfParams::default()
+        .argon2()
         .map_err(|e| KanariError::KeyConversionError(e.to_string()))?;
-    let argon2 = Argon2::default();
     let password_hash = argon2
         .hash_password(password.unwrap_or_default().as_bytes(), &salt)
         .map_err(|e| KanariError::KeyConversionError(e.to_string()))?
@@ -66,14 +271,15 @@ pub fn derive_bitcoin_private_key_from_path(
 }
 
 fn validate_derivation_path(path: &DerivationPath) -> Result<(), anyhow::Error> {
-    let (purpose, coin_type) = (
-        DERIVATION_PATH_PURPOSE_BIP86,
-        KanariMultiChainID::Bitcoin as u32,
-    );
+    let coin_types = [
+        ChildNumber::new(KanariMultiChainID::Bitcoin as u32, true)?,
+        ChildNumber::new(BITCOIN_TESTNET_COIN_TYPE, true)?,
+    ];
 
     if let &[p_purpose, p_coin_type, account, change, address] = path.as_ref() {
-        if p_purpose == ChildNumber::new(purpose, true)?
-            && p_coin_type == ChildNumber::new(coin_type, true)?
+        if p_purpose.is_hardened()
+            && DerivationScheme::from_purpose(p_purpose.index()).is_ok()
+            && coin_types.contains(&p_coin_type)
             && account.is_hardened()
             && !change.is_hardened()
             && !address.is_hardened()
@@ -92,23 +298,122 @@ fn validate_derivation_path(path: &DerivationPath) -> Result<(), anyhow::Error>
     }
 }
 
+/// Parse and validate a derivation path of one of the supported schemes
+pub fn parse_derivation_path(path: &str) -> Result<DerivationPath, anyhow::Error> {
+    let path = DerivationPath::from_str(path)
+        .map_err(|e| anyhow::anyhow!("Invalid derivation path: {}, {}", path, e))?;
+    validate_derivation_path(&path)?;
+    Ok(path)
+}
+
 /// Derivation path template
 /// Which tells a wallet how to derive a specific key within a tree of keys
 /// https://github.com/bitcoin/bips/blob/master/bip-0044.mediawiki
 /// https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki
 /// m / purpose' / coin_type' / account' / change / address_index
 pub(crate) fn generate_derivation_path(account_index: u32) -> Result<DerivationPath, KanariError> {
-    let (purpose, coin_type) = (
-        DERIVATION_PATH_PURPOSE_BIP86,
-        KanariMultiChainID::Bitcoin as u32,
-    );
+    generate_derivation_path_with_scheme(DerivationScheme::Bip86, 0, 0, account_index)
+}
+
+pub fn generate_derivation_path_with_scheme(
+    scheme: DerivationScheme,
+    account: u32,
+    change: u32,
+    address_index: u32,
+) -> Result<DerivationPath, KanariError> {
+    let (purpose, coin_type) = (scheme.purpose(), KanariMultiChainID::Bitcoin as u32);
 
     DerivationPath::from_str(
-        format!("m/{}'/{}'/0'/0/{}", purpose, coin_type, account_index).as_str(),
+        format!(
+            "m/{}'/{}'/{}'/{}/{}",
+            purpose, coin_type, account, change, address_index
+        )
+        .as_str(),
     )
     .map_err(|_| KanariError::SignatureKeyGenError("Cannot parse derivation path".to_owned()))
 }
 
+/// Derive the addresses of the indices of a chain, the Bitcoin address is in the address type of the scheme
+pub fn derive_addresses(
+    mnemonic_phrase: &str,
+    scheme: DerivationScheme,
+    account: u32,
+    change: u32,
+    indices: std::ops::Range<u32>,
+) -> Result<Vec<DerivedAddress>, anyhow::Error> {
+    let mnemonic = Mnemonic::from_phrase(mnemonic_phrase, Language::English)?;
+    let seed = Seed::new(&mnemonic, "");
+    indices
+        .map(|index| {
+            let derivation_path =
+                generate_derivation_path_with_scheme(scheme, account, change, index)?;
+            let sk =
+                derive_bitcoin_private_key_from_path(seed.as_bytes(), derivation_path.clone())?;
+            let public_key = KanariKeyPair::Secp256k1(sk).public();
+            Ok(DerivedAddress {
+                derivation_path,
+                address: public_key.kanari_address()?,
+                bitcoin_address: scheme.bitcoin_address(&public_key)?,
+            })
+        })
+        .collect()
+}
+
+const DESCRIPTOR_INPUT_CHARSET: &str = "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const DESCRIPTOR_CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+fn descriptor_poly_mod(mut c: u64, val: u64) -> u64 {
+    let c0 = c >> 35;
+    c = ((c & 0x7ffffffff) << 5) ^ val;
+    if c0 & 1 != 0 {
+        c ^= 0xf5dee51989;
+    }
+    if c0 & 2 != 0 {
+        c ^= 0xa9fdca3312;
+    }
+    if c0 & 4 != 0 {
+        c ^= 0x1bab10e32d;
+    }
+    if c0 & 8 != 0 {
+        c ^= 0x3706b1677a;
+    }
+    if c0 & 16 != 0 {
+        c ^= 0x644d626ffd;
+    }
+    c
+}
+
+/// The BIP-380 checksum of an output descriptor
+pub fn descriptor_checksum(descriptor: &str) -> Result<String, anyhow::Error> {
+    let mut c = 1u64;
+    let mut cls = 0u64;
+    let mut cls_count = 0;
+    for ch in descriptor.chars() {
+        let pos = DESCRIPTOR_INPUT_CHARSET
+            .find(ch)
+            .ok_or_else(|| anyhow::anyhow!("Invalid descriptor character: {}", ch))?
+            as u64;
+        c = descriptor_poly_mod(c, pos & 31);
+        cls = cls * 3 + (pos >> 5);
+        cls_count += 1;
+        if cls_count == 3 {
+            c = descriptor_poly_mod(c, cls);
+            cls = 0;
+            cls_count = 0;
+        }
+    }
+    if cls_count > 0 {
+        c = descriptor_poly_mod(c, cls);
+    }
+    for _ in 0..8 {
+        c = descriptor_poly_mod(c, 0);
+    }
+    c ^= 1;
+    Ok((0..8)
+        .map(|j| DESCRIPTOR_CHECKSUM_CHARSET[((c >> (5 * (7 - j))) & 31) as usize] as char)
+        .collect())
+}
+
 pub(crate) fn generate_new_key_pair(
     mnemonic_phrase: Option<String>,
     derivation_path: DerivationPath,
@@ -131,9 +436,11 @@ pub(crate) fn generate_new_key_pair(
     let mnemonic_phrase_encryption =
         EncryptionData::encrypt(mnemonic.phrase().as_bytes(), password)?;
 
-    let address = kanari_kp.public().kanari_address()?;
+    let public_key = kanari_kp.public();
+    let address = public_key.kanari_address()?;
 
     let result = GenerateNewKeyPair {
+        public_key,
         private_key_encryption,
         mnemonic_phrase_encryption,
         mnemonic_phrase: mnemonic.phrase().to_string(),
@@ -192,4 +499,69 @@ mod tests {
         assert_eq!(key_pair0.address, recovery_key_pair0.address);
         assert_eq!(key_pair1.address, recovery_key_pair1.address);
     }
+
+    const TEST_MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_derive_standard_paths() {
+        // The test vectors of BIP-44/49/84/86
+        let cases = [
+            (
+                DerivationScheme::Bip44,
+                "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+                "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj",
+            ),
+            (
+                DerivationScheme::Bip49,
+                "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+                "xpub6C6nQwHaWbSrzs5tZ1q7m5R9cPK9eYpNMFesiXsYrgc1P8bvLLAet9JfHjYXKjToD8cBRswJXXbbFpXgwsswVPAZzKMa1jUp2kVkGVUaJa7",
+            ),
+            (
+                DerivationScheme::Bip84,
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+                "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V",
+            ),
+            (
+                DerivationScheme::Bip86,
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+                "xpub6BgBgsespWvERF3LHQu6CnqdvfEvtMcQjYrcRzx53QJjSxarj2afYWcLteoGVky7D3UKDP9QyrLprQ3VCECoY49yfdDEHGCtMMj92pReUsQ",
+            ),
+        ];
+        for (scheme, bitcoin_address, account_xpub) in cases {
+            let derived = derive_addresses(TEST_MNEMONIC, scheme, 0, 0, 0..1).unwrap();
+            assert_eq!(derived[0].bitcoin_address.to_string(), bitcoin_address);
+
+            let path = generate_derivation_path_with_scheme(scheme, 0, 0, 0).unwrap();
+            assert_eq!(DerivationScheme::from_path(&path).unwrap(), scheme);
+            let info = DerivationPathInfo::from_mnemonic(TEST_MNEMONIC, &path).unwrap();
+            assert_eq!(info.master_fingerprint, "73c5da0a");
+            assert_eq!(info.account_xpub, account_xpub);
+            assert!(info.descriptor().unwrap().contains(&format!(
+                "[73c5da0a/{}'/0'/0']{}/0/*)",
+                scheme.purpose(),
+                account_xpub
+            )));
+
+            // The Kanari address is derived from the P2TR address of the key for every scheme
+            let key_pair =
+                generate_new_key_pair(Some(TEST_MNEMONIC.to_owned()), path, None, None).unwrap();
+            assert_eq!(key_pair.address, derived[0].address);
+        }
+    }
+
+    #[test]
+    fn test_validate_derivation_path() {
+        assert!(parse_derivation_path("m/84'/0'/0'/0/0").is_ok());
+        assert!(parse_derivation_path("m/86'/1'/2'/1/5").is_ok());
+        assert!(parse_derivation_path("m/54'/0'/0'/0/0").is_err());
+        assert!(parse_derivation_path("m/86'/60'/0'/0/0").is_err());
+        assert!(parse_derivation_path("m/86'/0'/0/0/0").is_err());
+        assert!(parse_derivation_path("m/86'/0'/0'/0").is_err());
+    }
+
+    #[test]
+    fn test_descriptor_checksum() {
+        assert_eq!(descriptor_checksum("raw(deadbeef)").unwrap(), "89f8spxm");
+        assert!(descriptor_checksum("raw(deadbeef)\u{e9}").is_err());
+    }
 }
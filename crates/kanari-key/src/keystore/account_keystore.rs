@@ -2,13 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::types::LocalAccount;
-use crate::key_derive::{generate_derivation_path, generate_new_key_pair};
+use crate::key_derive::{
+    DEFAULT_ADDRESS_GAP_LIMIT, DerivationPathInfo, DerivationScheme, DerivedAddress,
+    derive_addresses, generate_derivation_path, generate_new_key_pair,
+};
+use bip32::DerivationPath;
 use kanari_types::framework::session_key::SessionKey;
 use kanari_types::key_struct::{MnemonicData, MnemonicResult};
 use kanari_types::{
     address::KanariAddress,
     authentication_key::AuthenticationKey,
-    crypto::{KanariKeyPair, Signature},
+    crypto::{KanariKeyPair, PublicKey, Signature},
     key_struct::{EncryptionData, GeneratedKeyPair},
     transaction::kanari::{KanariTransaction, KanariTransactionData},
 };
@@ -27,6 +31,7 @@ pub trait AccountKeystore {
         let new_address = result.address;
         self.add_address_encryption_data_to_keys(
             new_address,
+            result.key_pair_data.public_key.clone(),
             result.key_pair_data.private_key_encryption.clone(),
         )?;
         let mnemonic_data = MnemonicData {
@@ -34,6 +39,11 @@ pub trait AccountKeystore {
             mnemonic_phrase_encryption: result.key_pair_data.mnemonic_phrase_encryption.clone(),
         };
         self.init_mnemonic_data(mnemonic_data)?;
+        let info = DerivationPathInfo::from_mnemonic(
+            &result.key_pair_data.mnemonic_phrase,
+            &generate_derivation_path(0)?,
+        )?;
+        self.add_derivation_path_info(new_address, info)?;
         Ok(result)
     }
 
@@ -52,8 +62,34 @@ pub trait AccountKeystore {
     ) -> Result<GeneratedKeyPair, anyhow::Error> {
         // load mnemonic phrase from keystore
         let mnemonic = self.get_mnemonic(password.clone())?;
-        let account_index = mnemonic.mnemonic_data.addresses.len() as u32;
-        let derivation_path = generate_derivation_path(account_index)?;
+        // Skip the indices whose addresses are already in the keystore, such as the ones
+        // imported by the account discovery
+        let mut account_index = mnemonic.mnemonic_data.addresses.len() as u32;
+        loop {
+            let derived = derive_addresses(
+                &mnemonic.mnemonic_phrase,
+                DerivationScheme::Bip86,
+                0,
+                0,
+                account_index..account_index + 1,
+            )?;
+            if !self.contains_address(&derived[0].address) {
+                break;
+            }
+            account_index += 1;
+        }
+        self.generate_and_add_new_key_with_path(generate_derivation_path(account_index)?, password)
+    }
+
+    /// Derive a new key of the keystore mnemonic with the derivation path of one of the BIP-44/49/84/86 schemes
+    fn generate_and_add_new_key_with_path(
+        &mut self,
+        derivation_path: DerivationPath,
+        password: Option<String>,
+    ) -> Result<GeneratedKeyPair, anyhow::Error> {
+        // load mnemonic phrase from keystore
+        let mnemonic = self.get_mnemonic(password.clone())?;
+        let info = DerivationPathInfo::from_mnemonic(&mnemonic.mnemonic_phrase, &derivation_path)?;
         let result = generate_new_key_pair(
             Some(mnemonic.mnemonic_phrase),
             derivation_path,
@@ -63,12 +99,101 @@ pub trait AccountKeystore {
         let new_address = result.address;
         self.add_address_encryption_data_to_keys(
             new_address,
+            result.key_pair_data.public_key.clone(),
             result.key_pair_data.private_key_encryption.clone(),
         )?;
-        self.add_addresses_to_mnemonic_data(new_address)?;
+        if !mnemonic.mnemonic_data.addresses.contains(&new_address) {
+            self.add_addresses_to_mnemonic_data(new_address)?;
+        }
+        self.add_derivation_path_info(new_address, info)?;
         Ok(result)
     }
 
+    /// Import the key of the derivation path of an external mnemonic, only the key and its path metadata are kept
+    fn import_mnemonic_account(
+        &mut self,
+        mnemonic_phrase: String,
+        derivation_path: DerivationPath,
+        password: Option<String>,
+    ) -> Result<KanariAddress, anyhow::Error> {
+        let info = DerivationPathInfo::from_mnemonic(&mnemonic_phrase, &derivation_path)?;
+        let result = generate_new_key_pair(Some(mnemonic_phrase), derivation_path, None, password)?;
+        let address = result.address;
+        self.add_address_encryption_data_to_keys(
+            address,
+            result.key_pair_data.public_key,
+            result.key_pair_data.private_key_encryption,
+        )?;
+        self.add_derivation_path_info(address, info)?;
+        Ok(address)
+    }
+
+    /// Derive the addresses of a chain of the keystore mnemonic, without adding them to the keystore
+    fn derive_addresses(
+        &self,
+        scheme: DerivationScheme,
+        account: u32,
+        change: u32,
+        indices: std::ops::Range<u32>,
+        password: Option<String>,
+    ) -> Result<Vec<DerivedAddress>, anyhow::Error> {
+        let mnemonic = self.get_mnemonic(password)?;
+        derive_addresses(&mnemonic.mnemonic_phrase, scheme, account, change, indices)
+    }
+
+    fn add_derivation_path_info(
+        &mut self,
+        address: KanariAddress,
+        info: DerivationPathInfo,
+    ) -> Result<(), anyhow::Error>;
+
+    fn get_derivation_path_info(&self, address: &KanariAddress) -> Option<DerivationPathInfo>;
+
+    /// Add an account with only its public key, it can be used as the sender to build transactions,
+    /// which are signed offline by the machine with the private key
+    fn add_watch_only_account(
+        &mut self,
+        address: KanariAddress,
+        public_key: PublicKey,
+    ) -> Result<(), anyhow::Error>;
+
+    fn get_watch_only_public_key(&self, address: &KanariAddress) -> Option<PublicKey>;
+
+    fn is_watch_only(&self, address: &KanariAddress) -> bool {
+        self.get_watch_only_public_key(address).is_some()
+    }
+
+    /// Get the derivation path metadata of an account of the keystore mnemonic.
+    /// The accounts created before the metadata was kept are looked up on the default BIP-86 chain.
+    fn export_derivation_path_info(
+        &self,
+        address: &KanariAddress,
+        password: Option<String>,
+    ) -> Result<DerivationPathInfo, anyhow::Error> {
+        if let Some(info) = self.get_derivation_path_info(address) {
+            return Ok(info);
+        }
+        let mnemonic = self.get_mnemonic(password)?;
+        anyhow::ensure!(
+            mnemonic.mnemonic_data.addresses.contains(address),
+            "Address {} is not derived from the keystore mnemonic",
+            address
+        );
+        let end = mnemonic.mnemonic_data.addresses.len() as u32 + DEFAULT_ADDRESS_GAP_LIMIT;
+        let derived = derive_addresses(
+            &mnemonic.mnemonic_phrase,
+            DerivationScheme::Bip86,
+            0,
+            0,
+            0..end,
+        )?;
+        let derived = derived
+            .into_iter()
+            .find(|derived| derived.address == *address)
+            .ok_or_else(|| anyhow::anyhow!("Cannot find the derivation path of {}", address))?;
+        DerivationPathInfo::from_mnemonic(&mnemonic.mnemonic_phrase, &derived.derivation_path)
+    }
+
     fn export_mnemonic_phrase(
         &mut self,
         password: Option<String>,
@@ -86,12 +211,12 @@ pub trait AccountKeystore {
         password: Option<String>,
     ) -> Result<(), anyhow::Error> {
         let private_key_encryption = EncryptionData::encrypt_with_type(&kp, password)?;
-        self.add_address_encryption_data_to_keys(address, private_key_encryption)?;
+        self.add_address_encryption_data_to_keys(address, kp.public(), private_key_encryption)?;
         Ok(())
     }
 
-    /// Get all local accounts
-    //TODO refactor the keystore, save the public key out of the encryption data, so that we don't need to require password to get the public key
+    /// Get all local accounts, the password is only required by the keys of the legacy keystores
+    /// whose public keys are not saved out of the encryption data
     fn get_accounts(&self, password: Option<String>) -> Result<Vec<LocalAccount>, anyhow::Error>;
 
     /// Get local account by address
@@ -110,6 +235,7 @@ pub trait AccountKeystore {
     fn add_address_encryption_data_to_keys(
         &mut self,
         address: KanariAddress,
+        public_key: PublicKey,
         encryption: EncryptionData,
     ) -> Result<(), anyhow::Error>;
 
@@ -129,6 +255,22 @@ pub trait AccountKeystore {
         is_password_empty: bool,
     ) -> Result<(), anyhow::Error>;
 
+    /// Whether the keystore or any of its encrypted data is in a format older than the current one
+    fn needs_migration(&self) -> bool;
+
+    /// Re-encrypt all the keys, the mnemonic and the session keys with the new password in the
+    /// current format, and replace the password hash
+    fn change_password(
+        &mut self,
+        old_password: Option<String>,
+        new_password: Option<String>,
+    ) -> Result<(), anyhow::Error>;
+
+    /// Upgrade the keystore to the current format with the same password
+    fn migrate(&mut self, password: Option<String>) -> Result<(), anyhow::Error> {
+        self.change_password(password.clone(), password)
+    }
+
     fn nullify(&mut self, address: &KanariAddress) -> Result<(), anyhow::Error>;
 
     fn sign_hashed(
@@ -145,6 +287,14 @@ pub trait AccountKeystore {
         password: Option<String>,
     ) -> Result<KanariTransaction, anyhow::Error>;
 
+    /// Sign the transaction with the Taproot tweaked key of the address via the Taproot validator
+    fn sign_transaction_via_taproot(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error>;
+
     fn sign_secure<T>(
         &self,
         address: &KanariAddress,
@@ -181,6 +331,20 @@ pub trait AccountKeystore {
         password: Option<String>,
     ) -> Result<Option<KanariKeyPair>, anyhow::Error>;
 
+    /// Get the on-chain SessionKey bound to the LocalSessionKey
+    fn get_bound_session_key(
+        &self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Option<SessionKey>;
+
+    /// Remove the LocalSessionKey after the session key is removed on-chain
+    fn remove_session_key(
+        &mut self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Result<(), anyhow::Error>;
+
     fn sign_transaction_via_session_key(
         &self,
         address: &KanariAddress,
@@ -188,4 +352,21 @@ pub trait AccountKeystore {
         authentication_key: &AuthenticationKey,
         password: Option<String>,
     ) -> Result<KanariTransaction, anyhow::Error>;
+
+    /// Generate a P-256 passkey credential of the software WebAuthn authenticator.
+    /// The passkey is kept as a session key, the authentication key is derived as the on-chain
+    /// session key of a `EcdsaSecp256r1VerificationKey2019` DID verification method.
+    fn generate_passkey(
+        &mut self,
+        address: &KanariAddress,
+        password: Option<String>,
+    ) -> Result<AuthenticationKey, anyhow::Error>;
+
+    fn sign_transaction_via_webauthn(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        authentication_key: &AuthenticationKey,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error>;
 }
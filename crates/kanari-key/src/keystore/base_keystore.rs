@@ -2,15 +2,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::types::{LocalAccount, LocalSessionKey};
+use crate::key_derive::{DerivationPathInfo, hash_password, verify_password};
 use crate::keystore::account_keystore::AccountKeystore;
 use anyhow::{Ok, ensure};
-use kanari_types::framework::session_key::SessionKey;
+use kanari_types::framework::session_key::{
+    SessionKey, secp256r1_public_key_to_authentication_key,
+};
 use kanari_types::key_struct::{MnemonicData, MnemonicResult};
 use kanari_types::to_bech32::ToBech32;
 use kanari_types::{
     address::KanariAddress,
     authentication_key::AuthenticationKey,
-    crypto::{KanariKeyPair, Signature},
+    crypto::{KanariKeyPair, PublicKey, Signature},
     error::KanariError,
     key_struct::EncryptionData,
     transaction::{
@@ -22,11 +25,20 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use std::collections::BTreeMap;
 
+/// The version of the keystore format, the keystores without a version are the legacy format,
+/// whose keys are encrypted by Argon2 with the default parameters and the AEAD nonce as the salt
+pub const KEYSTORE_VERSION: u32 = 1;
+
 #[derive(Default, Debug, Serialize, Deserialize)]
 #[serde_as]
 pub(crate) struct BaseKeyStore {
+    #[serde(default)]
+    pub(crate) version: u32,
     #[serde(default)]
     pub(crate) keys: BTreeMap<KanariAddress, EncryptionData>,
+    /// The public keys of the encrypted keys, so the accounts can be listed without decrypting the keys
+    #[serde(default)]
+    pub(crate) public_keys: BTreeMap<KanariAddress, PublicKey>,
     #[serde(default)]
     pub(crate) mnemonic: Option<MnemonicData>,
     #[serde(default)]
@@ -36,20 +48,40 @@ pub(crate) struct BaseKeyStore {
     pub(crate) password_hash: Option<String>,
     #[serde(default)]
     pub(crate) is_password_empty: bool,
+    #[serde(default)]
+    pub(crate) derivation_paths: BTreeMap<KanariAddress, DerivationPathInfo>,
+    #[serde(default)]
+    pub(crate) watch_only_accounts: BTreeMap<KanariAddress, PublicKey>,
 }
 
 impl BaseKeyStore {
     pub fn new() -> Self {
         Self {
+            version: KEYSTORE_VERSION,
             keys: BTreeMap::new(),
+            public_keys: BTreeMap::new(),
             mnemonic: None,
             session_keys: BTreeMap::new(),
             password_hash: None,
             is_password_empty: true,
+            derivation_paths: BTreeMap::new(),
+            watch_only_accounts: BTreeMap::new(),
         }
     }
 }
 
+impl BaseKeyStore {
+    fn ensure_not_watch_only(&self, address: &KanariAddress) -> Result<(), anyhow::Error> {
+        ensure!(
+            !self.watch_only_accounts.contains_key(address),
+            KanariError::SignMessageError(format!(
+                "The address [{address}] is a watch-only account, build a signing request with `kanari tx build --signing-request` and sign it on the machine with the private key"
+            ))
+        );
+        Ok(())
+    }
+}
+
 impl AccountKeystore for BaseKeyStore {
     fn init_mnemonic_data(&mut self, mnemonic_data: MnemonicData) -> Result<(), anyhow::Error> {
         ensure!(self.mnemonic.is_none(), "Mnemonic data already exists");
@@ -72,8 +104,13 @@ impl AccountKeystore for BaseKeyStore {
     fn get_accounts(&self, password: Option<String>) -> Result<Vec<LocalAccount>, anyhow::Error> {
         let mut accounts = BTreeMap::new();
         for (address, encryption) in &self.keys {
-            let keypair: KanariKeyPair = encryption.decrypt_with_type(password.clone())?;
-            let public_key = keypair.public();
+            let public_key = match self.public_keys.get(address) {
+                Some(public_key) => public_key.clone(),
+                // The legacy keystores do not save the public keys until they are migrated
+                None => encryption
+                    .decrypt_with_type::<KanariKeyPair>(password.clone())?
+                    .public(),
+            };
             let bitcoin_address = public_key.bitcoin_address()?;
             let nostr_bech32_public_key = public_key.xonly_public_key()?.to_bech32()?;
             let has_session_key = self.session_keys.contains_key(address);
@@ -83,6 +120,18 @@ impl AccountKeystore for BaseKeyStore {
                 nostr_bech32_public_key,
                 public_key,
                 has_session_key,
+                is_watch_only: false,
+            };
+            accounts.insert(*address, local_account);
+        }
+        for (address, public_key) in &self.watch_only_accounts {
+            let local_account = LocalAccount {
+                address: *address,
+                bitcoin_address: public_key.bitcoin_address()?,
+                nostr_bech32_public_key: public_key.xonly_public_key()?.to_bech32()?,
+                public_key: public_key.clone(),
+                has_session_key: false,
+                is_watch_only: true,
             };
             accounts.insert(*address, local_account);
         }
@@ -103,6 +152,7 @@ impl AccountKeystore for BaseKeyStore {
             let keypair: KanariKeyPair = encryption.decrypt_with_type::<KanariKeyPair>(password)?;
             Ok(keypair)
         } else {
+            self.ensure_not_watch_only(address)?;
             Err(anyhow::Error::new(KanariError::CommandArgumentError(
                 format!("Cannot find key for address: [{:?}]", address),
             )))
@@ -139,6 +189,7 @@ impl AccountKeystore for BaseKeyStore {
         msg: KanariTransactionData,
         password: Option<String>,
     ) -> Result<KanariTransaction, anyhow::Error> {
+        self.ensure_not_watch_only(address)?;
         let kp = self.get_key_pair(address, password).ok().ok_or_else(|| {
             KanariError::SignMessageError(format!("Cannot find key for address: [{address}]"))
         })?;
@@ -146,17 +197,76 @@ impl AccountKeystore for BaseKeyStore {
         Ok(KanariTransaction::new(msg, auth))
     }
 
+    fn sign_transaction_via_taproot(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error> {
+        self.ensure_not_watch_only(address)?;
+        let kp = self.get_key_pair(address, password).ok().ok_or_else(|| {
+            KanariError::SignMessageError(format!("Cannot find key for address: [{address}]"))
+        })?;
+        let auth = authenticator::Authenticator::taproot(&kp, &msg)?;
+        Ok(KanariTransaction::new(msg, auth))
+    }
+
     fn add_address_encryption_data_to_keys(
         &mut self,
         address: KanariAddress,
+        public_key: PublicKey,
         encryption: EncryptionData,
     ) -> Result<(), anyhow::Error> {
-        self.keys.entry(address).or_insert(encryption);
+        if !self.keys.contains_key(&address) {
+            self.keys.insert(address, encryption);
+            self.public_keys.insert(address, public_key);
+        }
+        // The imported private key turns the watch-only account into a signing account
+        self.watch_only_accounts.remove(&address);
+        Ok(())
+    }
+
+    fn add_derivation_path_info(
+        &mut self,
+        address: KanariAddress,
+        info: DerivationPathInfo,
+    ) -> Result<(), anyhow::Error> {
+        self.derivation_paths.insert(address, info);
         Ok(())
     }
 
+    fn get_derivation_path_info(&self, address: &KanariAddress) -> Option<DerivationPathInfo> {
+        self.derivation_paths.get(address).cloned()
+    }
+
+    fn add_watch_only_account(
+        &mut self,
+        address: KanariAddress,
+        public_key: PublicKey,
+    ) -> Result<(), anyhow::Error> {
+        ensure!(
+            public_key.kanari_address()? == address,
+            "The public key does not match the address {}",
+            address
+        );
+        ensure!(
+            !self.keys.contains_key(&address),
+            "The address {} already has a private key in the keystore",
+            address
+        );
+        self.watch_only_accounts.insert(address, public_key);
+        Ok(())
+    }
+
+    fn get_watch_only_public_key(&self, address: &KanariAddress) -> Option<PublicKey> {
+        self.watch_only_accounts.get(address).cloned()
+    }
+
     fn nullify(&mut self, address: &KanariAddress) -> Result<(), anyhow::Error> {
         self.keys.remove(address);
+        self.public_keys.remove(address);
+        self.derivation_paths.remove(address);
+        self.watch_only_accounts.remove(address);
         let mnemonic_data = match &self.mnemonic {
             Some(mnemonic) => mnemonic,
             // For None, this could be indicating that there's no internal account address in the mnemonic addresses
@@ -237,6 +347,41 @@ impl AccountKeystore for BaseKeyStore {
         Ok(KanariTransaction::new(msg, auth))
     }
 
+    fn generate_passkey(
+        &mut self,
+        address: &KanariAddress,
+        password: Option<String>,
+    ) -> Result<AuthenticationKey, anyhow::Error> {
+        let kp: KanariKeyPair = KanariKeyPair::generate_ecdsa_r1();
+        let authentication_key = secp256r1_public_key_to_authentication_key(kp.public().as_ref());
+        let inner_map = self.session_keys.entry(*address).or_default();
+        let private_key_encryption = EncryptionData::encrypt_with_type(&kp, password)?;
+        let local_session_key = LocalSessionKey {
+            session_key: None,
+            private_key: private_key_encryption,
+        };
+        inner_map.insert(authentication_key.clone(), local_session_key);
+        Ok(authentication_key)
+    }
+
+    fn sign_transaction_via_webauthn(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        authentication_key: &AuthenticationKey,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error> {
+        let kp = self
+            .get_session_key(address, authentication_key, password)?
+            .ok_or_else(|| {
+                KanariError::SignMessageError(format!(
+                    "Cannot find passkey for authentication_key: [{authentication_key}]"
+                ))
+            })?;
+        let auth = authenticator::Authenticator::webauthn(&kp, &msg)?;
+        Ok(KanariTransaction::new(msg, auth))
+    }
+
     fn get_session_key(
         &self,
         address: &KanariAddress,
@@ -250,6 +395,32 @@ impl AccountKeystore for BaseKeyStore {
         .map(|local_session_key| local_session_key.private_key.decrypt_with_type(password).map_err(signature::Error::from_source)).transpose()?)
     }
 
+    fn get_bound_session_key(
+        &self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Option<SessionKey> {
+        self.session_keys
+            .get(address)?
+            .get(authentication_key)?
+            .session_key
+            .clone()
+    }
+
+    fn remove_session_key(
+        &mut self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(inner_map) = self.session_keys.get_mut(address) {
+            inner_map.remove(authentication_key);
+            if inner_map.is_empty() {
+                self.session_keys.remove(address);
+            }
+        }
+        Ok(())
+    }
+
     fn addresses(&self) -> Vec<KanariAddress> {
         // Create an empty Vec to store the addresses.
         let mut addresses = Vec::with_capacity(self.keys.len() + self.session_keys.len());
@@ -284,6 +455,92 @@ impl AccountKeystore for BaseKeyStore {
         self.is_password_empty
     }
 
+    fn needs_migration(&self) -> bool {
+        self.version < KEYSTORE_VERSION
+            || self.keys.values().any(EncryptionData::is_legacy)
+            || self
+                .keys
+                .keys()
+                .any(|address| !self.public_keys.contains_key(address))
+            || self
+                .mnemonic
+                .as_ref()
+                .is_some_and(|mnemonic| mnemonic.mnemonic_phrase_encryption.is_legacy())
+            || self
+                .session_keys
+                .values()
+                .flat_map(|session_keys| session_keys.values())
+                .any(|session_key| session_key.private_key.is_legacy())
+    }
+
+    fn change_password(
+        &mut self,
+        old_password: Option<String>,
+        new_password: Option<String>,
+    ) -> Result<(), anyhow::Error> {
+        if let Some(password_hash) = &self.password_hash {
+            ensure!(
+                verify_password(old_password.clone(), password_hash.clone())?,
+                KanariError::InvalidPasswordError("Password is invalid".to_owned())
+            );
+        }
+        // Re-encrypt everything before replacing any of them, so a failure leaves the keystore untouched
+        let mut keys = BTreeMap::new();
+        let mut public_keys = BTreeMap::new();
+        for (address, encryption) in &self.keys {
+            let keypair: KanariKeyPair = encryption.decrypt_with_type(old_password.clone())?;
+            public_keys.insert(*address, keypair.public());
+            keys.insert(
+                *address,
+                EncryptionData::encrypt_with_type(&keypair, new_password.clone())?,
+            );
+        }
+        let mnemonic_phrase_encryption = self
+            .mnemonic
+            .as_ref()
+            .map(|mnemonic| {
+                mnemonic
+                    .mnemonic_phrase_encryption
+                    .reencrypt(old_password.clone(), new_password.clone())
+            })
+            .transpose()?;
+        let session_keys = self
+            .session_keys
+            .iter()
+            .map(|(address, session_keys)| {
+                let session_keys = session_keys
+                    .iter()
+                    .map(|(authentication_key, local_session_key)| {
+                        Ok((
+                            authentication_key.clone(),
+                            LocalSessionKey {
+                                session_key: local_session_key.session_key.clone(),
+                                private_key: local_session_key
+                                    .private_key
+                                    .reencrypt(old_password.clone(), new_password.clone())?,
+                            },
+                        ))
+                    })
+                    .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?;
+                Ok((*address, session_keys))
+            })
+            .collect::<Result<BTreeMap<_, _>, anyhow::Error>>()?;
+        let password_hash = hash_password(new_password.clone())?;
+
+        self.keys = keys;
+        self.public_keys = public_keys;
+        if let (Some(mnemonic), Some(mnemonic_phrase_encryption)) =
+            (self.mnemonic.as_mut(), mnemonic_phrase_encryption)
+        {
+            mnemonic.mnemonic_phrase_encryption = mnemonic_phrase_encryption;
+        }
+        self.session_keys = session_keys;
+        self.password_hash = Some(password_hash);
+        self.is_password_empty = new_password.unwrap_or_default().is_empty();
+        self.version = KEYSTORE_VERSION;
+        Ok(())
+    }
+
     fn get_mnemonic(&self, password: Option<String>) -> Result<MnemonicResult, anyhow::Error> {
         match &self.mnemonic {
             Some(mnemonic_data) => {
@@ -302,3 +559,37 @@ impl AccountKeystore for BaseKeyStore {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kanari_types::framework::empty::Empty;
+
+    #[test]
+    fn test_import_private_key_of_watch_only_account() {
+        let mut keystore = BaseKeyStore::new();
+        let kp = KanariKeyPair::generate_secp256k1();
+        let public_key = kp.public();
+        let address = public_key.kanari_address().unwrap();
+        let tx_data =
+            KanariTransactionData::new_for_test(address, 0, Empty::empty_function_call().into());
+
+        keystore
+            .add_watch_only_account(address, public_key.clone())
+            .unwrap();
+        assert!(
+            keystore
+                .sign_transaction(&address, tx_data.clone(), None)
+                .is_err()
+        );
+
+        keystore.import_external_account(address, kp, None).unwrap();
+        assert!(keystore.get_watch_only_public_key(&address).is_none());
+        let account = keystore.get_account(&address, None).unwrap().unwrap();
+        assert!(!account.is_watch_only);
+        assert_eq!(account.public_key, public_key);
+
+        let tx = keystore.sign_transaction(&address, tx_data, None).unwrap();
+        assert_eq!(tx.sender(), address);
+    }
+}
@@ -2,21 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::types::LocalAccount;
+use crate::key_derive::DerivationPathInfo;
 use crate::keystore::account_keystore::AccountKeystore;
-use crate::keystore::base_keystore::BaseKeyStore;
-use anyhow::anyhow;
+use crate::keystore::base_keystore::{BaseKeyStore, KEYSTORE_VERSION};
+use anyhow::{anyhow, ensure};
 use kanari_types::key_struct::{MnemonicData, MnemonicResult};
 use kanari_types::{
     address::KanariAddress,
     authentication_key::AuthenticationKey,
-    crypto::{KanariKeyPair, Signature},
+    crypto::{KanariKeyPair, PublicKey, Signature},
     key_struct::EncryptionData,
     transaction::kanari::{KanariTransaction, KanariTransactionData},
 };
 use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
 use std::fs;
 use std::fs::File;
-use std::io::BufReader;
+use std::io::{BufReader, Write};
 use std::path::{Path, PathBuf};
 
 #[derive(Default, Serialize, Deserialize, Debug)]
@@ -52,14 +54,43 @@ impl AccountKeystore for FileBasedKeystore {
     fn add_address_encryption_data_to_keys(
         &mut self,
         address: KanariAddress,
+        public_key: PublicKey,
         encryption: EncryptionData,
     ) -> Result<(), anyhow::Error> {
         self.keystore
-            .add_address_encryption_data_to_keys(address, encryption)?;
+            .add_address_encryption_data_to_keys(address, public_key, encryption)?;
         self.save()?;
         Ok(())
     }
 
+    fn add_derivation_path_info(
+        &mut self,
+        address: KanariAddress,
+        info: DerivationPathInfo,
+    ) -> Result<(), anyhow::Error> {
+        self.keystore.add_derivation_path_info(address, info)?;
+        self.save()?;
+        Ok(())
+    }
+
+    fn get_derivation_path_info(&self, address: &KanariAddress) -> Option<DerivationPathInfo> {
+        self.keystore.get_derivation_path_info(address)
+    }
+
+    fn add_watch_only_account(
+        &mut self,
+        address: KanariAddress,
+        public_key: PublicKey,
+    ) -> Result<(), anyhow::Error> {
+        self.keystore.add_watch_only_account(address, public_key)?;
+        self.save()?;
+        Ok(())
+    }
+
+    fn get_watch_only_public_key(&self, address: &KanariAddress) -> Option<PublicKey> {
+        self.keystore.get_watch_only_public_key(address)
+    }
+
     fn get_key_pair(
         &self,
         address: &KanariAddress,
@@ -92,6 +123,16 @@ impl AccountKeystore for FileBasedKeystore {
         self.keystore.sign_transaction(address, msg, password)
     }
 
+    fn sign_transaction_via_taproot(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error> {
+        self.keystore
+            .sign_transaction_via_taproot(address, msg, password)
+    }
+
     fn sign_secure<T>(
         &self,
         address: &KanariAddress,
@@ -151,6 +192,26 @@ impl AccountKeystore for FileBasedKeystore {
             .get_session_key(address, authentication_key, password)
     }
 
+    fn get_bound_session_key(
+        &self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Option<kanari_types::framework::session_key::SessionKey> {
+        self.keystore
+            .get_bound_session_key(address, authentication_key)
+    }
+
+    fn remove_session_key(
+        &mut self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Result<(), anyhow::Error> {
+        self.keystore
+            .remove_session_key(address, authentication_key)?;
+        self.save()?;
+        Ok(())
+    }
+
     fn sign_transaction_via_session_key(
         &self,
         address: &KanariAddress,
@@ -162,6 +223,27 @@ impl AccountKeystore for FileBasedKeystore {
             .sign_transaction_via_session_key(address, msg, authentication_key, password)
     }
 
+    fn generate_passkey(
+        &mut self,
+        address: &KanariAddress,
+        password: Option<String>,
+    ) -> Result<AuthenticationKey, anyhow::Error> {
+        let auth_key = self.keystore.generate_passkey(address, password)?;
+        self.save()?;
+        Ok(auth_key)
+    }
+
+    fn sign_transaction_via_webauthn(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        authentication_key: &AuthenticationKey,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error> {
+        self.keystore
+            .sign_transaction_via_webauthn(address, msg, authentication_key, password)
+    }
+
     fn set_password_hash_with_indicator(
         &mut self,
         password_hash: String,
@@ -177,6 +259,23 @@ impl AccountKeystore for FileBasedKeystore {
         self.keystore.password_hash.clone().unwrap_or_default()
     }
 
+    fn needs_migration(&self) -> bool {
+        self.keystore.needs_migration()
+    }
+
+    fn change_password(
+        &mut self,
+        old_password: Option<String>,
+        new_password: Option<String>,
+    ) -> Result<(), anyhow::Error> {
+        if self.keystore.needs_migration() {
+            self.backup()?;
+        }
+        self.keystore.change_password(old_password, new_password)?;
+        self.save()?;
+        Ok(())
+    }
+
     fn get_if_password_is_empty(&self) -> bool {
         self.keystore.is_password_empty
     }
@@ -196,13 +295,15 @@ impl FileBasedKeystore {
                     e
                 )
             })?);
-            serde_json::from_reader(reader).map_err(|e| {
+            let keystore: BaseKeyStore = serde_json::from_reader(reader).map_err(|e| {
                 anyhow!(
                     "Can't deserialize FileBasedKeystore from Kanari path {:?}: {}",
                     path,
                     e
                 )
-            })?
+            })?;
+            Self::check_version(&keystore, path)?;
+            keystore
         } else {
             BaseKeyStore::new()
         };
@@ -222,13 +323,14 @@ impl FileBasedKeystore {
                     e
                 )
             })?);
-            let keystore = serde_json::from_reader(reader).map_err(|e| {
+            let keystore: BaseKeyStore = serde_json::from_reader(reader).map_err(|e| {
                 anyhow!(
                     "Can't deserialize FileBasedKeystore from Kanari path {:?}: {}",
                     path,
                     e
                 )
             })?;
+            Self::check_version(&keystore, path)?;
             Ok(Self {
                 keystore,
                 path: Some(path.to_path_buf()),
@@ -242,14 +344,48 @@ impl FileBasedKeystore {
         self.path = Some(path.to_path_buf());
     }
 
+    fn check_version(keystore: &BaseKeyStore, path: &Path) -> Result<(), anyhow::Error> {
+        ensure!(
+            keystore.version <= KEYSTORE_VERSION,
+            "The keystore {:?} is in version {}, which is newer than the supported version {}, please upgrade kanari",
+            path,
+            keystore.version,
+            KEYSTORE_VERSION
+        );
+        Ok(())
+    }
+
+    /// Save the keystore atomically, it is written to a temporary file in the same directory
+    /// and renamed to the keystore path, so the keystore is never left partially written
     pub fn save(&self) -> Result<(), anyhow::Error> {
         if let Some(path) = &self.path {
             let store = serde_json::to_string_pretty(&self.keystore)?;
-            fs::write(path, store)?;
+            let tmp_path = Self::sibling_path(path, ".tmp");
+            {
+                let mut file = File::create(&tmp_path)?;
+                file.write_all(store.as_bytes())?;
+                file.sync_all()?;
+            }
+            fs::rename(&tmp_path, path)?;
         }
         Ok(())
     }
 
+    /// Keep a copy of the keystore file before migrating it, as `<path>.v<version>.bak`
+    fn backup(&self) -> Result<(), anyhow::Error> {
+        if let Some(path) = self.path.as_ref().filter(|path| path.exists()) {
+            let backup_path = Self::sibling_path(path, &format!(".v{}.bak", self.keystore.version));
+            fs::copy(path, backup_path)?;
+        }
+        Ok(())
+    }
+
+    fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+        let mut file_name = OsString::from(path.as_os_str());
+        file_name.push(suffix);
+        PathBuf::from(file_name)
+    }
+
     pub fn key_pairs(
         &self,
         _address: &KanariAddress,
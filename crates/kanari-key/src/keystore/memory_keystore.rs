@@ -2,13 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::types::LocalAccount;
+use crate::key_derive::DerivationPathInfo;
 use crate::keystore::account_keystore::AccountKeystore;
 use crate::keystore::base_keystore::BaseKeyStore;
 use kanari_types::key_struct::{MnemonicData, MnemonicResult};
 use kanari_types::{
     address::KanariAddress,
     authentication_key::AuthenticationKey,
-    crypto::{KanariKeyPair, Signature},
+    crypto::{KanariKeyPair, PublicKey, Signature},
     key_struct::EncryptionData,
     transaction::kanari::{KanariTransaction, KanariTransactionData},
 };
@@ -31,10 +32,35 @@ impl AccountKeystore for InMemKeystore {
     fn add_address_encryption_data_to_keys(
         &mut self,
         address: KanariAddress,
+        public_key: PublicKey,
         encryption: EncryptionData,
     ) -> Result<(), anyhow::Error> {
         self.keystore
-            .add_address_encryption_data_to_keys(address, encryption)
+            .add_address_encryption_data_to_keys(address, public_key, encryption)
+    }
+
+    fn add_derivation_path_info(
+        &mut self,
+        address: KanariAddress,
+        info: DerivationPathInfo,
+    ) -> Result<(), anyhow::Error> {
+        self.keystore.add_derivation_path_info(address, info)
+    }
+
+    fn get_derivation_path_info(&self, address: &KanariAddress) -> Option<DerivationPathInfo> {
+        self.keystore.get_derivation_path_info(address)
+    }
+
+    fn add_watch_only_account(
+        &mut self,
+        address: KanariAddress,
+        public_key: PublicKey,
+    ) -> Result<(), anyhow::Error> {
+        self.keystore.add_watch_only_account(address, public_key)
+    }
+
+    fn get_watch_only_public_key(&self, address: &KanariAddress) -> Option<PublicKey> {
+        self.keystore.get_watch_only_public_key(address)
     }
 
     fn get_key_pair(
@@ -67,6 +93,16 @@ impl AccountKeystore for InMemKeystore {
         self.keystore.sign_transaction(address, msg, password)
     }
 
+    fn sign_transaction_via_taproot(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error> {
+        self.keystore
+            .sign_transaction_via_taproot(address, msg, password)
+    }
+
     fn sign_secure<T>(
         &self,
         address: &KanariAddress,
@@ -122,6 +158,24 @@ impl AccountKeystore for InMemKeystore {
             .get_session_key(address, authentication_key, password)
     }
 
+    fn get_bound_session_key(
+        &self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Option<kanari_types::framework::session_key::SessionKey> {
+        self.keystore
+            .get_bound_session_key(address, authentication_key)
+    }
+
+    fn remove_session_key(
+        &mut self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Result<(), anyhow::Error> {
+        self.keystore
+            .remove_session_key(address, authentication_key)
+    }
+
     fn sign_transaction_via_session_key(
         &self,
         address: &KanariAddress,
@@ -133,6 +187,25 @@ impl AccountKeystore for InMemKeystore {
             .sign_transaction_via_session_key(address, msg, authentication_key, password)
     }
 
+    fn generate_passkey(
+        &mut self,
+        address: &KanariAddress,
+        password: Option<String>,
+    ) -> Result<AuthenticationKey, anyhow::Error> {
+        self.keystore.generate_passkey(address, password)
+    }
+
+    fn sign_transaction_via_webauthn(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        authentication_key: &AuthenticationKey,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error> {
+        self.keystore
+            .sign_transaction_via_webauthn(address, msg, authentication_key, password)
+    }
+
     fn set_password_hash_with_indicator(
         &mut self,
         password_hash: String,
@@ -147,6 +220,18 @@ impl AccountKeystore for InMemKeystore {
         self.keystore.password_hash.clone().unwrap_or_default()
     }
 
+    fn needs_migration(&self) -> bool {
+        self.keystore.needs_migration()
+    }
+
+    fn change_password(
+        &mut self,
+        old_password: Option<String>,
+        new_password: Option<String>,
+    ) -> Result<(), anyhow::Error> {
+        self.keystore.change_password(old_password, new_password)
+    }
+
     fn get_if_password_is_empty(&self) -> bool {
         self.keystore.is_password_empty
     }
@@ -1,6 +1,7 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::key_derive::DerivationPathInfo;
 use crate::keystore::account_keystore::AccountKeystore;
 use crate::keystore::file_keystore::FileBasedKeystore;
 use enum_dispatch::enum_dispatch;
@@ -8,7 +9,7 @@ use kanari_types::key_struct::{GeneratedKeyPair, MnemonicData, MnemonicResult};
 use kanari_types::{
     address::KanariAddress,
     authentication_key::AuthenticationKey,
-    crypto::{KanariKeyPair, Signature},
+    crypto::{KanariKeyPair, PublicKey, Signature},
     key_struct::EncryptionData,
     transaction::kanari::{KanariTransaction, KanariTransactionData},
 };
@@ -115,19 +116,62 @@ impl AccountKeystore for Keystore {
     fn add_address_encryption_data_to_keys(
         &mut self,
         address: KanariAddress,
+        public_key: PublicKey,
         encryption: EncryptionData,
     ) -> Result<(), anyhow::Error> {
         // Implement this method to add a key pair to the appropriate variant (File or InMem)
         match self {
             Keystore::File(file_keystore) => {
-                file_keystore.add_address_encryption_data_to_keys(address, encryption)
+                file_keystore.add_address_encryption_data_to_keys(address, public_key, encryption)
             }
             Keystore::InMem(inmem_keystore) => {
-                inmem_keystore.add_address_encryption_data_to_keys(address, encryption)
+                inmem_keystore.add_address_encryption_data_to_keys(address, public_key, encryption)
             }
         }
     }
 
+    fn add_derivation_path_info(
+        &mut self,
+        address: KanariAddress,
+        info: DerivationPathInfo,
+    ) -> Result<(), anyhow::Error> {
+        match self {
+            Keystore::File(file_keystore) => file_keystore.add_derivation_path_info(address, info),
+            Keystore::InMem(inmem_keystore) => {
+                inmem_keystore.add_derivation_path_info(address, info)
+            }
+        }
+    }
+
+    fn get_derivation_path_info(&self, address: &KanariAddress) -> Option<DerivationPathInfo> {
+        match self {
+            Keystore::File(file_keystore) => file_keystore.get_derivation_path_info(address),
+            Keystore::InMem(inmem_keystore) => inmem_keystore.get_derivation_path_info(address),
+        }
+    }
+
+    fn add_watch_only_account(
+        &mut self,
+        address: KanariAddress,
+        public_key: PublicKey,
+    ) -> Result<(), anyhow::Error> {
+        match self {
+            Keystore::File(file_keystore) => {
+                file_keystore.add_watch_only_account(address, public_key)
+            }
+            Keystore::InMem(inmem_keystore) => {
+                inmem_keystore.add_watch_only_account(address, public_key)
+            }
+        }
+    }
+
+    fn get_watch_only_public_key(&self, address: &KanariAddress) -> Option<PublicKey> {
+        match self {
+            Keystore::File(file_keystore) => file_keystore.get_watch_only_public_key(address),
+            Keystore::InMem(inmem_keystore) => inmem_keystore.get_watch_only_public_key(address),
+        }
+    }
+
     fn get_key_pair(
         &self,
         address: &KanariAddress,
@@ -176,6 +220,22 @@ impl AccountKeystore for Keystore {
         }
     }
 
+    fn sign_transaction_via_taproot(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error> {
+        match self {
+            Keystore::File(file_keystore) => {
+                file_keystore.sign_transaction_via_taproot(address, msg, password)
+            }
+            Keystore::InMem(inmem_keystore) => {
+                inmem_keystore.sign_transaction_via_taproot(address, msg, password)
+            }
+        }
+    }
+
     fn sign_secure<T>(
         &self,
         address: &KanariAddress,
@@ -206,6 +266,40 @@ impl AccountKeystore for Keystore {
         }
     }
 
+    fn generate_passkey(
+        &mut self,
+        address: &KanariAddress,
+        password: Option<String>,
+    ) -> Result<AuthenticationKey, anyhow::Error> {
+        match self {
+            Keystore::File(file_keystore) => file_keystore.generate_passkey(address, password),
+            Keystore::InMem(inmem_keystore) => inmem_keystore.generate_passkey(address, password),
+        }
+    }
+
+    fn sign_transaction_via_webauthn(
+        &self,
+        address: &KanariAddress,
+        msg: KanariTransactionData,
+        authentication_key: &AuthenticationKey,
+        password: Option<String>,
+    ) -> Result<KanariTransaction, anyhow::Error> {
+        match self {
+            Keystore::File(file_keystore) => file_keystore.sign_transaction_via_webauthn(
+                address,
+                msg,
+                authentication_key,
+                password,
+            ),
+            Keystore::InMem(inmem_keystore) => inmem_keystore.sign_transaction_via_webauthn(
+                address,
+                msg,
+                authentication_key,
+                password,
+            ),
+        }
+    }
+
     fn binding_session_key(
         &mut self,
         address: KanariAddress,
@@ -237,6 +331,36 @@ impl AccountKeystore for Keystore {
         }
     }
 
+    fn get_bound_session_key(
+        &self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Option<kanari_types::framework::session_key::SessionKey> {
+        match self {
+            Keystore::File(file_keystore) => {
+                file_keystore.get_bound_session_key(address, authentication_key)
+            }
+            Keystore::InMem(inmem_keystore) => {
+                inmem_keystore.get_bound_session_key(address, authentication_key)
+            }
+        }
+    }
+
+    fn remove_session_key(
+        &mut self,
+        address: &KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Result<(), anyhow::Error> {
+        match self {
+            Keystore::File(file_keystore) => {
+                file_keystore.remove_session_key(address, authentication_key)
+            }
+            Keystore::InMem(inmem_keystore) => {
+                inmem_keystore.remove_session_key(address, authentication_key)
+            }
+        }
+    }
+
     fn addresses(&self) -> Vec<KanariAddress> {
         match self {
             Keystore::File(file_keystore) => file_keystore.addresses(),
@@ -266,6 +390,28 @@ impl AccountKeystore for Keystore {
         }
     }
 
+    fn needs_migration(&self) -> bool {
+        match self {
+            Keystore::File(file_keystore) => file_keystore.needs_migration(),
+            Keystore::InMem(inmem_keystore) => inmem_keystore.needs_migration(),
+        }
+    }
+
+    fn change_password(
+        &mut self,
+        old_password: Option<String>,
+        new_password: Option<String>,
+    ) -> Result<(), anyhow::Error> {
+        match self {
+            Keystore::File(file_keystore) => {
+                file_keystore.change_password(old_password, new_password)
+            }
+            Keystore::InMem(inmem_keystore) => {
+                inmem_keystore.change_password(old_password, new_password)
+            }
+        }
+    }
+
     fn get_if_password_is_empty(&self) -> bool {
         match self {
             Keystore::File(file_keystore) => file_keystore.get_if_password_is_empty(),
@@ -22,4 +22,7 @@ pub struct LocalAccount {
     pub nostr_bech32_public_key: String,
     pub public_key: PublicKey,
     pub has_session_key: bool,
+    /// The account only has the public key in the keystore, its transactions are signed offline
+    #[serde(default)]
+    pub is_watch_only: bool,
 }
@@ -47,6 +47,14 @@ pub struct InscriptionInfo {
     pub value: Option<u64>,
 }
 
+/// The index status of the ord server, only the fields we need
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub struct OrdStatus {
+    pub height: Option<u32>,
+    pub blessed_inscriptions: u64,
+    pub cursed_inscriptions: u64,
+}
+
 impl OrdClient {
     pub fn new(ord_rpc_url: String) -> Self {
         let http_client = Client::new();
@@ -115,6 +123,12 @@ impl OrdClient {
         })
     }
 
+    pub async fn get_status(&self) -> Result<OrdStatus> {
+        self.get("status")
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("Ord server status not found"))
+    }
+
     async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<Option<T>> {
         let url = format!("{}/{}", self.ord_rpc_url, path);
         debug!("GET {}", url);
@@ -136,3 +150,68 @@ impl OrdClient {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// A minimal ord http server which responds the json by the request path
+    async fn start_mock_ord_server(routes: Vec<(&'static str, &'static str)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let n = stream.read(&mut buf).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..n]);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let response = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    ),
+                    None => {
+                        "HTTP/1.1 404 Not Found\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+                            .to_string()
+                    }
+                };
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_mock_ord_server() {
+        let url = start_mock_ord_server(vec![
+            (
+                "/status",
+                r#"{"height":100,"blessed_inscriptions":10,"cursed_inscriptions":2,"chain":"regtest"}"#,
+            ),
+            (
+                "/inscriptions/block/100/0",
+                r#"{"ids":["6fb976ab49dcec017f1e201e84395983204ae1a7c2abf7ced0a85d692e442799i0"],"more":false,"page_index":0}"#,
+            ),
+        ])
+        .await;
+        let client = OrdClient::new(url);
+        let status = client.get_status().await.unwrap();
+        assert_eq!(status.height, Some(100));
+        assert_eq!(status.blessed_inscriptions, 10);
+        assert_eq!(status.cursed_inscriptions, 2);
+
+        let ids = client.get_inscriptions_by_block(100).await.unwrap();
+        assert_eq!(ids.len(), 1);
+        assert!(
+            client
+                .get_inscriptions_by_block(101)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,237 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::ord_client::{InscriptionInfo, OrdStatus};
+use kanari_types::bitcoin::ord::{Inscription, InscriptionID, InscriptionStore, SatPoint};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// The difference of one inscription between Kanari and the ord index
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InscriptionMismatch {
+    /// The inscription is indexed by ord but not found in Kanari
+    MissingInKanari { inscription_id: InscriptionID },
+    /// The inscription is listed in the ord block but ord can not return the detail
+    MissingInOrd { inscription_id: InscriptionID },
+    /// The inscription is created in the block by Kanari but not listed in the ord block
+    MissingInOrdBlock { inscription_id: InscriptionID },
+    NumberMismatch {
+        inscription_id: InscriptionID,
+        ord_number: i32,
+        kanari_number: i32,
+    },
+    LocationMismatch {
+        inscription_id: InscriptionID,
+        ord_location: SatPoint,
+        kanari_location: SatPoint,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMismatch {
+    pub block_height: u64,
+    pub mismatch: InscriptionMismatch,
+}
+
+/// The blessed and cursed inscription counts of Kanari and ord.
+/// The counts are only comparable when both sides have indexed the same block height.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InscriptionCountReport {
+    pub ord_height: Option<u64>,
+    pub kanari_height: Option<u64>,
+    pub ord_blessed_inscriptions: u64,
+    pub kanari_blessed_inscriptions: u64,
+    pub ord_cursed_inscriptions: u64,
+    pub kanari_cursed_inscriptions: u64,
+    /// Whether the counts are compared, false if the heights are different
+    pub compared: bool,
+    pub matched: bool,
+}
+
+impl InscriptionCountReport {
+    pub fn new(
+        ord_status: &OrdStatus,
+        kanari_height: Option<u64>,
+        inscription_store: &InscriptionStore,
+    ) -> Self {
+        let ord_height = ord_status.height.map(u64::from);
+        let kanari_blessed_inscriptions = inscription_store.blessed_inscription_count as u64;
+        let kanari_cursed_inscriptions = inscription_store.cursed_inscription_count as u64;
+        let compared = ord_height.is_some() && ord_height == kanari_height;
+        let matched = compared
+            && ord_status.blessed_inscriptions == kanari_blessed_inscriptions
+            && ord_status.cursed_inscriptions == kanari_cursed_inscriptions;
+        Self {
+            ord_height,
+            kanari_height,
+            ord_blessed_inscriptions: ord_status.blessed_inscriptions,
+            kanari_blessed_inscriptions,
+            ord_cursed_inscriptions: ord_status.cursed_inscriptions,
+            kanari_cursed_inscriptions,
+            compared,
+            matched,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifyReport {
+    pub start_block: u64,
+    pub end_block: u64,
+    /// How many inscriptions listed by ord in the block range are checked
+    pub checked_inscriptions: u64,
+    pub mismatches: Vec<BlockMismatch>,
+    pub counts: Option<InscriptionCountReport>,
+}
+
+impl VerifyReport {
+    pub fn new(start_block: u64, end_block: u64) -> Self {
+        Self {
+            start_block,
+            end_block,
+            checked_inscriptions: 0,
+            mismatches: vec![],
+            counts: None,
+        }
+    }
+
+    pub fn add_mismatches(&mut self, block_height: u64, mismatches: Vec<InscriptionMismatch>) {
+        self.mismatches
+            .extend(mismatches.into_iter().map(|mismatch| BlockMismatch {
+                block_height,
+                mismatch,
+            }));
+    }
+
+    pub fn is_consistent(&self) -> bool {
+        self.mismatches.is_empty()
+            && self
+                .counts
+                .as_ref()
+                .map(|counts| !counts.compared || counts.matched)
+                .unwrap_or(true)
+    }
+}
+
+/// Convert the Kanari inscription number to the ord number,
+/// ord represents the cursed inscription number as negative number start from -1,
+/// Kanari keeps the absolute value of the cursed number
+pub fn ord_inscription_number(inscription_number: u32, is_cursed: bool) -> i32 {
+    if is_cursed {
+        -(inscription_number as i32)
+    } else {
+        inscription_number as i32
+    }
+}
+
+/// Compare the inscription from ord with the inscription in Kanari.
+/// The location is the current location, so it is only comparable when both sides are on the same height.
+pub fn compare_inscription(
+    ord_inscription: &InscriptionInfo,
+    kanari_inscription: &Inscription,
+) -> Vec<InscriptionMismatch> {
+    let mut mismatches = vec![];
+    let inscription_id = ord_inscription.id;
+    let kanari_number = ord_inscription_number(
+        kanari_inscription.inscription_number,
+        kanari_inscription.is_cursed,
+    );
+    if ord_inscription.number != kanari_number {
+        mismatches.push(InscriptionMismatch::NumberMismatch {
+            inscription_id,
+            ord_number: ord_inscription.number,
+            kanari_number,
+        });
+    }
+    if ord_inscription.satpoint != kanari_inscription.location {
+        mismatches.push(InscriptionMismatch::LocationMismatch {
+            inscription_id,
+            ord_location: ord_inscription.satpoint.clone(),
+            kanari_location: kanari_inscription.location.clone(),
+        });
+    }
+    mismatches
+}
+
+/// Find the inscriptions created by Kanari in a block but not listed by ord in the same block
+pub fn find_missing_in_ord(
+    ord_inscription_ids: &[InscriptionID],
+    kanari_inscription_ids: &[InscriptionID],
+) -> Vec<InscriptionMismatch> {
+    let ord_inscription_ids = ord_inscription_ids.iter().collect::<HashSet<_>>();
+    kanari_inscription_ids
+        .iter()
+        .filter(|inscription_id| !ord_inscription_ids.contains(inscription_id))
+        .map(|inscription_id| InscriptionMismatch::MissingInOrdBlock {
+            inscription_id: *inscription_id,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ord_inscription_number() {
+        assert_eq!(ord_inscription_number(0, false), 0);
+        assert_eq!(ord_inscription_number(10, false), 10);
+        assert_eq!(ord_inscription_number(1, true), -1);
+        assert_eq!(ord_inscription_number(10, true), -10);
+    }
+
+    #[test]
+    fn test_find_missing_in_ord() {
+        let ids = (0..3)
+            .map(|index| InscriptionID {
+                index,
+                ..Default::default()
+            })
+            .collect::<Vec<_>>();
+        assert!(find_missing_in_ord(&ids, &ids).is_empty());
+        assert!(find_missing_in_ord(&ids, &ids[..1]).is_empty());
+        assert_eq!(
+            find_missing_in_ord(&ids[..1], &ids),
+            vec![
+                InscriptionMismatch::MissingInOrdBlock {
+                    inscription_id: ids[1]
+                },
+                InscriptionMismatch::MissingInOrdBlock {
+                    inscription_id: ids[2]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_report() {
+        let ord_status = OrdStatus {
+            height: Some(100),
+            blessed_inscriptions: 10,
+            cursed_inscriptions: 2,
+        };
+        let store = InscriptionStore {
+            cursed_inscription_count: 2,
+            blessed_inscription_count: 10,
+            unbound_inscription_count: 0,
+            lost_sats: 0,
+            next_sequence_number: 12,
+        };
+        let report = InscriptionCountReport::new(&ord_status, Some(100), &store);
+        assert!(report.compared && report.matched);
+
+        let report = InscriptionCountReport::new(&ord_status, Some(99), &store);
+        assert!(!report.compared && !report.matched);
+
+        let store = InscriptionStore {
+            cursed_inscription_count: 3,
+            ..store
+        };
+        let report = InscriptionCountReport::new(&ord_status, Some(100), &store);
+        assert!(report.compared && !report.matched);
+        let mut verify_report = VerifyReport::new(100, 100);
+        verify_report.counts = Some(report);
+        assert!(!verify_report.is_consistent());
+    }
+}
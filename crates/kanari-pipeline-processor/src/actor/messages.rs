@@ -17,6 +17,16 @@ impl Message for ExecuteL2TxMessage {
     type Result = Result<ExecuteTransactionResponse>;
 }
 
+/// Execute the L2 txs with the parallel executor, the results are in the order of the txs
+#[derive(Clone)]
+pub struct ExecuteL2TxBatchMessage {
+    pub txs: Vec<KanariTransaction>,
+}
+
+impl Message for ExecuteL2TxBatchMessage {
+    type Result = Vec<Result<ExecuteTransactionResponse>>;
+}
+
 #[derive(Clone)]
 pub struct ExecuteL1BlockMessage {
     pub tx: L1BlockWithBody,
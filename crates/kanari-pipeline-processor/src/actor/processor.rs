@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::messages::{
-    ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL2TxMessage, GetServiceStatusMessage,
+    ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL2TxBatchMessage, ExecuteL2TxMessage,
+    GetServiceStatusMessage,
 };
 use crate::metrics::PipelineProcessorMetrics;
 use anyhow::{Error, Result, anyhow};
@@ -14,6 +15,7 @@ use function_name::named;
 use kanari_da::actor::messages::{AppendTransactionMessage, RevertTransactionMessage};
 use kanari_da::proxy::DAServerProxy;
 use kanari_db::KanariDB;
+use kanari_executor::actor::messages::ExecuteBatchTransactionResult;
 use kanari_executor::proxy::ExecutorProxy;
 use kanari_indexer::proxy::IndexerProxy;
 use kanari_notify::actor::NotifyActor;
@@ -33,8 +35,11 @@ use moveos_types::h256::H256;
 use moveos_types::moveos_std::event::Event;
 use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::state::StateChangeSetExt;
-use moveos_types::transaction::VerifiedMoveOSTransaction;
+use moveos_types::transaction::{
+    TransactionExecutionInfo, TransactionOutput, VerifiedMoveOSTransaction,
+};
 use prometheus::Registry;
+use std::collections::HashSet;
 use std::io;
 use std::sync::Arc;
 use tracing::{debug, info};
@@ -172,31 +177,9 @@ impl PipelineProcessorActor {
 
     // sequence tx and public tx to DA
     async fn sequence_and_public_tx(&mut self, tx_data: LedgerTxData) -> Result<LedgerTransaction> {
-        let ledger_tx_ret = self.sequencer.sequence_transaction(tx_data).await;
-        let mut ledger_tx = match ledger_tx_ret {
-            Ok(v) => v,
-            Err(err) => {
-                if let Some(_io_err) = err.downcast_ref::<io::Error>() {
-                    tracing::error!(
-                        "Sequence transaction failed while io_error occurred then \
-                        set service to Maintenance mode and pause the relayer. error: {:?}",
-                        err
-                    );
-                    self.update_service_status(ServiceStatus::Maintenance).await;
-                }
-                return Err(err);
-            }
-        };
-
+        let mut ledger_tx = self.sequence_tx(tx_data).await?;
         let tx_order = ledger_tx.sequence_info.tx_order;
-        let public_ret = self
-            .da_server
-            .append_tx(AppendTransactionMessage {
-                tx_order: ledger_tx.sequence_info.tx_order,
-                tx_timestamp: ledger_tx.sequence_info.tx_timestamp,
-            })
-            .await;
-        match public_ret {
+        match self.public_tx(&ledger_tx).await {
             Ok(_) => Ok(ledger_tx),
             Err(err) => {
                 tracing::error!(
@@ -218,6 +201,33 @@ impl PipelineProcessorActor {
         }
     }
 
+    async fn sequence_tx(&mut self, tx_data: LedgerTxData) -> Result<LedgerTransaction> {
+        let ledger_tx_ret = self.sequencer.sequence_transaction(tx_data).await;
+        match ledger_tx_ret {
+            Ok(v) => Ok(v),
+            Err(err) => {
+                if let Some(_io_err) = err.downcast_ref::<io::Error>() {
+                    tracing::error!(
+                        "Sequence transaction failed while io_error occurred then \
+                        set service to Maintenance mode and pause the relayer. error: {:?}",
+                        err
+                    );
+                    self.update_service_status(ServiceStatus::Maintenance).await;
+                }
+                Err(err)
+            }
+        }
+    }
+
+    async fn public_tx(&mut self, ledger_tx: &LedgerTransaction) -> Result<()> {
+        self.da_server
+            .append_tx(AppendTransactionMessage {
+                tx_order: ledger_tx.sequence_info.tx_order,
+                tx_timestamp: ledger_tx.sequence_info.tx_timestamp,
+            })
+            .await
+    }
+
     // for non-VM panic error, revert tx avoiding hole between executed tx:
     // e.g., executed_tx_0, failed_tx_1, executed_tx_3, executed_tx_4.
     // executed_tx_3 may start with wrong state.
@@ -426,6 +436,164 @@ impl PipelineProcessorActor {
         Ok(result)
     }
 
+    /// Execute the L2 txs with the parallel executor, the results are in the order of `txs`.
+    /// The txs are split into the batches in which every sender has one tx at most,
+    /// so every tx is validated after the previous txs of its sender are executed, same as the serial execution.
+    pub async fn execute_l2_tx_batch(
+        &mut self,
+        txs: Vec<KanariTransaction>,
+    ) -> Vec<Result<ExecuteTransactionResponse>> {
+        let mut results = Vec::with_capacity(txs.len());
+        let mut batch = vec![];
+        let mut senders = HashSet::new();
+        for tx in txs {
+            if !senders.insert(tx.sender()) {
+                let batch = std::mem::take(&mut batch);
+                results.extend(self.execute_parallel_l2_txs(batch).await);
+                senders = HashSet::from([tx.sender()]);
+            }
+            batch.push(tx);
+        }
+        results.extend(self.execute_parallel_l2_txs(batch).await);
+        results
+    }
+
+    // The txs are sequenced before the execution and published to DA after the execution.
+    // If a tx fails, it and the txs after it are reverted, then the txs after it are executed one by one.
+    #[named]
+    async fn execute_parallel_l2_txs(
+        &mut self,
+        txs: Vec<KanariTransaction>,
+    ) -> Vec<Result<ExecuteTransactionResponse>> {
+        let fn_name = function_name!();
+        let _timer = self
+            .metrics
+            .pipeline_processor_execution_tx_latency_seconds
+            .with_label_values(&[fn_name])
+            .start_timer();
+
+        let mut results = txs.iter().map(|_| None).collect::<Vec<_>>();
+        let mut sequenced_txs = vec![];
+        for (index, tx) in txs.iter().enumerate() {
+            let ret = match self.executor.validate_l2_tx(tx.clone()).await {
+                Ok(moveos_tx) => self
+                    .sequence_tx(LedgerTxData::L2Tx(tx.clone()))
+                    .await
+                    .map(|ledger_tx| (ledger_tx, moveos_tx)),
+                Err(err) => Err(err),
+            };
+            match ret {
+                Ok((ledger_tx, moveos_tx)) => sequenced_txs.push((index, ledger_tx, moveos_tx)),
+                Err(err) => results[index] = Some(Err(err)),
+            }
+        }
+
+        let mut failure = None;
+        let mut moveos_txs = vec![];
+        for (_, ledger_tx, moveos_tx) in sequenced_txs.iter_mut() {
+            // Add sequence info to tx context, let the Move contract can get the sequence info
+            if let Err(err) = moveos_tx.ctx.add(ledger_tx.sequence_info.clone()) {
+                failure = Some(err);
+                break;
+            }
+            moveos_txs.push(moveos_tx.clone());
+        }
+        let batch_result = if moveos_txs.is_empty() {
+            ExecuteBatchTransactionResult {
+                results: vec![],
+                error: None,
+            }
+        } else {
+            self.executor
+                .execute_batch_transactions_until_failure(moveos_txs)
+                .await
+                .unwrap_or_else(|err| ExecuteBatchTransactionResult {
+                    results: vec![],
+                    error: Some(err),
+                })
+        };
+        // The failed tx is the first one after the executed txs
+        let failure = batch_result.error.or(failure);
+
+        let mut sequenced_txs = sequenced_txs.into_iter();
+        let mut da_failed = false;
+        for (result, (index, ledger_tx, moveos_tx)) in
+            batch_result.results.into_iter().zip(sequenced_txs.by_ref())
+        {
+            // The tx is executed and can not be reverted like the serial execution
+            if !da_failed {
+                if let Err(err) = self.public_tx(&ledger_tx).await {
+                    tracing::error!(
+                        "Public executed transaction(tx_order: {}) failed, set service to Maintenance mode and pause the relayer. error: {:?}",
+                        ledger_tx.sequence_info.tx_order,
+                        err
+                    );
+                    self.update_service_status(ServiceStatus::Maintenance).await;
+                    da_failed = true;
+                }
+            }
+            self.metrics
+                .pipeline_processor_l2_tx_gas_used
+                .inc_by(result.output.gas_used);
+            self.metrics
+                .pipeline_processor_execution_tx_bytes
+                .with_label_values(&[fn_name])
+                .observe(moveos_tx.ctx.tx_size as f64);
+            results[index] = Some(
+                self.process_executed_tx(
+                    ledger_tx,
+                    moveos_tx,
+                    result.output,
+                    result.transaction_info,
+                )
+                .await,
+            );
+        }
+
+        if let Some(err) = failure {
+            let mut reverted_txs = sequenced_txs.collect::<Vec<_>>();
+            let tx_hashes = reverted_txs
+                .iter_mut()
+                .map(|(_, ledger_tx, _)| ledger_tx.tx_hash())
+                .collect::<Vec<_>>();
+            // The txs are not published to DA yet, only revert them in the db
+            match self.kanari_db.revert_last_txs(&tx_hashes) {
+                Ok(_) => {
+                    let mut reverted_txs = reverted_txs.into_iter();
+                    if let Some((index, ledger_tx, _)) = reverted_txs.next() {
+                        tracing::error!(
+                            "Execute L2Tx failed in the parallel batch. error: {:?}, tx_order: {}, tx_hash: {:?}",
+                            err,
+                            ledger_tx.sequence_info.tx_order,
+                            tx_hashes[0]
+                        );
+                        results[index] = Some(Err(err));
+                    }
+                    for (index, _, _) in reverted_txs {
+                        results[index] = Some(self.execute_l2_tx(txs[index].clone(), None).await);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Revert the failed parallel batch failed, set service to Maintenance mode and pause the relayer. error: {:?}, revert error: {:?}",
+                        err,
+                        e
+                    );
+                    self.update_service_status(ServiceStatus::Maintenance).await;
+                }
+            }
+        }
+
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(anyhow!("The tx is not executed, the parallel batch failed"))
+                })
+            })
+            .collect()
+    }
+
     #[named]
     pub async fn execute_tx(
         &mut self,
@@ -445,7 +613,26 @@ impl PipelineProcessorActor {
         // Then execute
         let size = moveos_tx.ctx.tx_size;
         let (output, execution_info) = self.executor.execute_transaction(moveos_tx.clone()).await?;
+        let response = self
+            .process_executed_tx(tx, moveos_tx, output, execution_info)
+            .await?;
+
+        self.metrics
+            .pipeline_processor_execution_tx_bytes
+            .with_label_values(&[fn_name])
+            .observe(size as f64);
 
+        Ok(response)
+    }
+
+    // sync the state, save the state change set, update the indexer and process the subscription of an executed tx
+    async fn process_executed_tx(
+        &mut self,
+        tx: LedgerTransaction,
+        moveos_tx: VerifiedMoveOSTransaction,
+        output: TransactionOutput,
+        execution_info: TransactionExecutionInfo,
+    ) -> Result<ExecuteTransactionResponse> {
         let root = execution_info.root_metadata();
         // Sync the latest state root from writer executor to reader executor
         self.executor
@@ -489,11 +676,6 @@ impl PipelineProcessorActor {
             Err(error) => tracing::error!("Process subscription error: {}", error),
         };
 
-        self.metrics
-            .pipeline_processor_execution_tx_bytes
-            .with_label_values(&[fn_name])
-            .observe(size as f64);
-
         Ok(ExecuteTransactionResponse {
             sequence_info,
             execution_info,
@@ -539,6 +721,17 @@ impl Handler<ExecuteL2TxMessage> for PipelineProcessorActor {
     }
 }
 
+#[async_trait]
+impl Handler<ExecuteL2TxBatchMessage> for PipelineProcessorActor {
+    async fn handle(
+        &mut self,
+        msg: ExecuteL2TxBatchMessage,
+        _ctx: &mut ActorContext,
+    ) -> Vec<Result<ExecuteTransactionResponse>> {
+        self.execute_l2_tx_batch(msg.txs).await
+    }
+}
+
 #[async_trait]
 impl Handler<ExecuteL1BlockMessage> for PipelineProcessorActor {
     async fn handle(
@@ -3,7 +3,8 @@
 
 use crate::actor::{
     messages::{
-        ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL2TxMessage, GetServiceStatusMessage,
+        ExecuteL1BlockMessage, ExecuteL1TxMessage, ExecuteL2TxBatchMessage, ExecuteL2TxMessage,
+        GetServiceStatusMessage,
     },
     processor::PipelineProcessorActor,
 };
@@ -30,6 +31,13 @@ impl PipelineProcessorProxy {
         self.actor.send(ExecuteL2TxMessage { tx }).await?
     }
 
+    pub async fn execute_l2_tx_batch(
+        &self,
+        txs: Vec<KanariTransaction>,
+    ) -> Result<Vec<Result<ExecuteTransactionResponse>>> {
+        Ok(self.actor.send(ExecuteL2TxBatchMessage { txs }).await?)
+    }
+
     pub async fn execute_l1_block(
         &self,
         tx: L1BlockWithBody,
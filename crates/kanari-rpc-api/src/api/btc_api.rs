@@ -3,9 +3,13 @@
 
 use crate::RpcResult;
 use crate::jsonrpc_types::btc::ord::InscriptionFilterView;
+use crate::jsonrpc_types::btc::pending_block::{
+    PendingBlocksView, StateConfirmationView, TxConfirmationView,
+};
+use crate::jsonrpc_types::btc::transaction::TxidView;
 use crate::jsonrpc_types::btc::utxo::UTXOFilterView;
 use crate::jsonrpc_types::{
-    BytesView, IndexerStateIDView, InscriptionPageView, StrView, UTXOPageView,
+    BytesView, IndexerStateIDView, InscriptionPageView, ObjectIDVecView, StrView, UTXOPageView,
 };
 use jsonrpsee::proc_macros::rpc;
 use kanari_open_rpc_macros::open_rpc;
@@ -44,4 +48,23 @@ pub trait BtcAPI {
         maxfeerate: Option<f64>,
         maxburnamount: Option<f64>,
     ) -> RpcResult<String>;
+
+    /// Get the Bitcoin blocks which are waiting for enough confirmations to be processed
+    #[method(name = "getPendingBlocks")]
+    async fn get_pending_blocks(&self) -> RpcResult<PendingBlocksView>;
+
+    /// Get the Bitcoin confirmation depth and finality of UTXO or Inscription states
+    #[method(name = "getStateConfirmations")]
+    async fn get_state_confirmations(
+        &self,
+        object_ids: ObjectIDVecView,
+    ) -> RpcResult<Vec<Option<StateConfirmationView>>>;
+
+    /// Get the Bitcoin confirmation depth and finality of txs, including the txs in the pending blocks
+    /// which are not processed yet and can still be reorged
+    #[method(name = "getTxConfirmations")]
+    async fn get_tx_confirmations(
+        &self,
+        txids: Vec<TxidView>,
+    ) -> RpcResult<Vec<Option<TxConfirmationView>>>;
 }
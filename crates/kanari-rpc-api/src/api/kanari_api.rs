@@ -6,6 +6,7 @@ use crate::jsonrpc_types::account_view::BalanceInfoView;
 use crate::jsonrpc_types::address::UnitedAddressView;
 use crate::jsonrpc_types::event_view::{EventFilterView, IndexerEventIDView, IndexerEventView};
 use crate::jsonrpc_types::field_view::FieldFilterView;
+use crate::jsonrpc_types::package_source_view::{PackageSourceView, SubmitPackageSourceView};
 use crate::jsonrpc_types::repair_view::{RepairIndexerParamsView, RepairIndexerTypeView};
 use crate::jsonrpc_types::transaction_view::{TransactionFilterView, TransactionWithInfoView};
 use crate::jsonrpc_types::{
@@ -44,8 +45,21 @@ pub trait KanariAPI {
         tx_option: Option<TxOptions>,
     ) -> RpcResult<ExecuteTransactionResponseView>;
 
+    /// Dry run the transaction, the execution trace is returned if `trace` is true.
     #[method(name = "dryRunRawTransaction")]
-    async fn dry_run(&self, tx_bcs_hex: BytesView) -> RpcResult<DryRunTransactionResponseView>;
+    async fn dry_run(
+        &self,
+        tx_bcs_hex: BytesView,
+        trace: Option<bool>,
+    ) -> RpcResult<DryRunTransactionResponseView>;
+
+    /// Replay an executed L2 transaction at the state before it, and return the execution trace.
+    /// The transaction is validated and replayed with its sequence info, it fails if the replayed status or state root diverges from the execution.
+    #[method(name = "traceTransaction")]
+    async fn trace_transaction(
+        &self,
+        tx_hash: H256View,
+    ) -> RpcResult<DryRunTransactionResponseView>;
 
     /// Execute a read-only function call
     /// The function do not change the state of Application
@@ -205,6 +219,23 @@ pub trait KanariAPI {
         query_option: Option<QueryOptions>,
     ) -> RpcResult<FieldPageView>;
 
+    /// Submit the source digest of a published package to the indexer registry, it is only accepted
+    /// if the node enables the package source submission. The package digest is checked against the package
+    /// published on chain, but the source digest is recorded as submitted without a rebuild.
+    /// A later submission replaces the record of the package.
+    #[method(name = "submitPackageSource")]
+    async fn submit_package_source(
+        &self,
+        package_source: SubmitPackageSourceView,
+    ) -> RpcResult<PackageSourceView>;
+
+    /// Query the submitted sources of the packages from the indexer registry
+    #[method(name = "queryPackageSources")]
+    async fn query_package_sources(
+        &self,
+        package_addresses: Vec<KanariAddressView>,
+    ) -> RpcResult<Vec<PackageSourceView>>;
+
     /// Repair indexer by sync from states
     #[method(name = "repairIndexer")]
     async fn repair_indexer(
@@ -224,6 +255,14 @@ pub trait KanariAPI {
         query_option: Option<QueryOptions>,
     ) -> RpcResult<StateChangeSetPageView>;
 
+    /// Get the state tree nodes by node hashes, the node is null if it does not exist.
+    /// It is used by the fork mode node to read the state tree lazily.
+    #[method(name = "getStateNodes")]
+    async fn get_state_nodes(
+        &self,
+        node_hashes: Vec<H256View>,
+    ) -> RpcResult<Vec<Option<BytesView>>>;
+
     /// Get the chain and service status
     #[method(name = "status")]
     async fn status(&self) -> RpcResult<Status>;
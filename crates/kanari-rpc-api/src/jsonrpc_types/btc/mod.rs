@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod ord;
+pub mod pending_block;
 pub mod transaction;
 pub mod utxo;
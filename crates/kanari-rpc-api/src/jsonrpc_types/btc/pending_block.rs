@@ -0,0 +1,82 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::btc::transaction::TxidView;
+use crate::jsonrpc_types::{BlockHeightHashView, ObjectIDView, StrView};
+use bitcoin::BlockHash;
+use kanari_types::bitcoin::pending_block::{PendingBlock, TxConfirmation, confirmations};
+use kanari_types::into_address::FromAddress;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PendingBlockView {
+    pub block_height: StrView<u64>,
+    pub block_hash: String,
+    /// How many txs of the block have been processed
+    pub processed_tx: StrView<u64>,
+    pub next_block_hash: Option<String>,
+    /// How many blocks on top of this block, the block itself is counted
+    pub confirmations: StrView<u64>,
+}
+
+impl PendingBlockView {
+    pub fn new(pending_block: PendingBlock, best_block_height: u64) -> Self {
+        let next_block_hash: Option<_> = pending_block.next_block_hash.into();
+        PendingBlockView {
+            block_height: pending_block.block_height.into(),
+            block_hash: BlockHash::from_address(pending_block.block_hash).to_string(),
+            processed_tx: pending_block.processed_tx.into(),
+            next_block_hash: next_block_hash.map(|hash| BlockHash::from_address(hash).to_string()),
+            confirmations: confirmations(best_block_height, pending_block.block_height).into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PendingBlocksView {
+    /// The best block the relayer has submitted
+    pub best_block: Option<BlockHeightHashView>,
+    /// How many blocks a block should wait before it can be processed
+    pub reorg_block_count: StrView<u64>,
+    /// The pending blocks order by block height
+    pub blocks: Vec<PendingBlockView>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TxConfirmationView {
+    pub txid: TxidView,
+    /// The Bitcoin block height of the tx
+    pub block_height: StrView<u64>,
+    /// How many blocks on top of the tx's block from the relayer's best block, the block itself is counted
+    pub confirmations: StrView<u64>,
+    /// The tx is in a pending block, its block can still be reorged and its states are not created yet
+    pub pending: bool,
+    /// Whether the states of the tx can not be reverted by a Bitcoin reorg
+    pub finalized: bool,
+}
+
+impl TxConfirmationView {
+    pub fn new(txid: TxidView, confirmation: TxConfirmation) -> Self {
+        TxConfirmationView {
+            txid,
+            block_height: confirmation.block_height.into(),
+            confirmations: confirmation.confirmations.into(),
+            pending: confirmation.pending,
+            finalized: confirmation.finalized,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StateConfirmationView {
+    pub object_id: ObjectIDView,
+    /// The Bitcoin txid which created the current state of the UTXO or Inscription
+    pub txid: String,
+    /// The Bitcoin block height of the txid, None if the tx is imported from genesis
+    pub block_height: Option<StrView<u64>>,
+    /// How many blocks on top of the tx's block from the relayer's best block, the block itself is counted
+    pub confirmations: Option<StrView<u64>>,
+    /// Whether the state can not be reverted by a Bitcoin reorg
+    pub finalized: bool,
+}
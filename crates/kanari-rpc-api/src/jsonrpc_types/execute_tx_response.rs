@@ -1,7 +1,7 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
-use super::BytesView;
+use super::{BytesView, TxTraceView};
 use super::{HumanReadableDisplay, ModuleIdView, StateChangeSetView, StrView};
 use crate::jsonrpc_types::H256View;
 use crate::jsonrpc_types::event_view::EventView;
@@ -227,6 +227,9 @@ pub struct RawTransactionOutputView {
 pub struct DryRunTransactionResponseView {
     pub raw_output: RawTransactionOutputView,
     pub vm_error_info: VMErrorInfo,
+    /// The execution trace, only returned if the trace is requested.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<TxTraceView>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
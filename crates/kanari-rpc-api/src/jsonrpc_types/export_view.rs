@@ -1,6 +1,7 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+use super::BitcoinAddressView;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -8,6 +9,19 @@ use serde::{Deserialize, Serialize};
 pub struct ExportInfoView {
     pub mnemonic_phrase: Option<String>,
     pub encoded_private_key: Option<String>,
+    pub derivation_path: Option<DerivationPathView>,
+}
+
+/// The derivation path metadata of an account, to restore it from the mnemonic in a Bitcoin wallet
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DerivationPathView {
+    pub derivation_path: String,
+    pub scheme: String,
+    pub bitcoin_address: BitcoinAddressView,
+    pub master_fingerprint: String,
+    pub account_xpub: String,
+    /// The output descriptor of the chain of the address, with the checksum
+    pub descriptor: String,
 }
 
 impl ExportInfoView {
@@ -15,6 +29,7 @@ impl ExportInfoView {
         Self {
             mnemonic_phrase: Some(mnemonic_phrase),
             encoded_private_key: None,
+            derivation_path: None,
         }
     }
 
@@ -22,6 +37,15 @@ impl ExportInfoView {
         Self {
             mnemonic_phrase: None,
             encoded_private_key: Some(encoded_private_key),
+            derivation_path: None,
+        }
+    }
+
+    pub fn new_derivation_path(derivation_path: DerivationPathView) -> Self {
+        Self {
+            mnemonic_phrase: None,
+            encoded_private_key: None,
+            derivation_path: Some(derivation_path),
         }
     }
 }
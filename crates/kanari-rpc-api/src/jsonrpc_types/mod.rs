@@ -12,6 +12,7 @@ mod move_types;
 mod rpc_options;
 mod state_view;
 mod status;
+mod trace_view;
 
 #[cfg(test)]
 mod tests;
@@ -28,6 +29,7 @@ pub mod transaction_view;
 pub mod address;
 pub mod btc;
 pub mod field_view;
+pub mod package_source_view;
 pub mod repair_view;
 
 pub use self::kanari_types::*;
@@ -40,4 +42,5 @@ pub use rpc_options::*;
 pub use state_view::*;
 pub use status::*;
 pub use str_view::*;
+pub use trace_view::*;
 pub use transaction_argument_view::*;
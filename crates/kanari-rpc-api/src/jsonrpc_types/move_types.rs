@@ -425,6 +425,8 @@ pub struct MoveActionView {
     pub script_call: Option<ScriptCallView>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub module_bundle: Option<Vec<BytesView>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub batch: Option<Vec<FunctionCallView>>,
 }
 
 impl From<MoveAction> for MoveActionView {
@@ -434,16 +436,25 @@ impl From<MoveAction> for MoveActionView {
                 script_call: Some(script.into()),
                 function_call: None,
                 module_bundle: None,
+                batch: None,
             },
             MoveAction::Function(fun) => Self {
                 script_call: None,
                 function_call: Some(fun.into()),
                 module_bundle: None,
+                batch: None,
             },
             MoveAction::ModuleBundle(module) => Self {
                 script_call: None,
                 function_call: None,
                 module_bundle: Some(module.into_iter().map(StrView).collect()),
+                batch: None,
+            },
+            MoveAction::Batch(calls) => Self {
+                script_call: None,
+                function_call: None,
+                module_bundle: None,
+                batch: Some(calls.into_iter().map(Into::into).collect()),
             },
         }
     }
@@ -457,6 +468,8 @@ impl From<MoveActionView> for MoveAction {
             MoveAction::Function(function_call.into())
         } else if let Some(module_bundle) = action.module_bundle {
             MoveAction::ModuleBundle(module_bundle.into_iter().map(StrView::into).collect())
+        } else if let Some(calls) = action.batch {
+            MoveAction::Batch(calls.into_iter().map(Into::into).collect())
         } else {
             panic!("Invalid MoveActionView")
         }
@@ -469,6 +482,7 @@ pub enum MoveActionTypeView {
     ScriptCall,
     FunctionCall,
     ModuleBundle,
+    Batch,
 }
 
 impl From<MoveAction> for MoveActionTypeView {
@@ -477,6 +491,7 @@ impl From<MoveAction> for MoveActionTypeView {
             MoveAction::Script(_) => Self::ScriptCall,
             MoveAction::Function(_) => Self::FunctionCall,
             MoveAction::ModuleBundle(_) => Self::ModuleBundle,
+            MoveAction::Batch(_) => Self::Batch,
         }
     }
 }
@@ -0,0 +1,42 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::jsonrpc_types::{H256View, KanariAddressView, StrView};
+use kanari_types::address::KanariAddress;
+use kanari_types::indexer::package_source::IndexerPackageSource;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The source digest of a package submitted by the node operator who rebuilt it from sources.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct SubmitPackageSourceView {
+    pub package_address: KanariAddressView,
+    pub package_name: String,
+    /// The digest of the rebuilt package modules, it must equal the digest of the modules on chain
+    pub package_digest: H256View,
+    /// The digest of the package sources and manifest which the modules are rebuilt from
+    pub source_digest: H256View,
+}
+
+/// The source digest of a package as it is submitted, the node does not rebuild the package from the sources.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct PackageSourceView {
+    pub package_address: KanariAddressView,
+    pub package_name: String,
+    pub package_digest: H256View,
+    pub source_digest: H256View,
+    /// The submitted timestamp in milliseconds
+    pub submitted_at: StrView<u64>,
+}
+
+impl From<IndexerPackageSource> for PackageSourceView {
+    fn from(source: IndexerPackageSource) -> Self {
+        PackageSourceView {
+            package_address: KanariAddress::from(source.package_address).into(),
+            package_name: source.package_name,
+            package_digest: source.package_digest.into(),
+            source_digest: source.source_digest.into(),
+            submitted_at: source.submitted_at.into(),
+        }
+    }
+}
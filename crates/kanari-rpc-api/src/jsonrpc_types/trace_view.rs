@@ -0,0 +1,163 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    AccountAddressView, BytesView, FunctionIdView, ObjectIDView, StrView, StructTagView,
+    TypeTagView,
+};
+use move_core_types::u256::U256;
+use moveos_types::moveos_std::event::TransactionEvent;
+use moveos_types::trace::{CallTrace, ObjectWriteKind, ObjectWriteTrace, TraceValue, TxTrace};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A Move value observed by the tracer, the struct fields are positional.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum TraceValueView {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(StrView<u64>),
+    U128(StrView<u128>),
+    U256(StrView<U256>),
+    Address(AccountAddressView),
+    Bytes(BytesView),
+    Vector(Vec<TraceValueView>),
+    Struct(Vec<TraceValueView>),
+    Reference {
+        is_global: bool,
+        value: Box<TraceValueView>,
+    },
+    /// The BCS encoded argument of a transaction entry call
+    Raw(BytesView),
+}
+
+impl From<TraceValue> for TraceValueView {
+    fn from(value: TraceValue) -> Self {
+        match value {
+            TraceValue::Bool(v) => Self::Bool(v),
+            TraceValue::U8(v) => Self::U8(v),
+            TraceValue::U16(v) => Self::U16(v),
+            TraceValue::U32(v) => Self::U32(v),
+            TraceValue::U64(v) => Self::U64(StrView(v)),
+            TraceValue::U128(v) => Self::U128(StrView(v)),
+            TraceValue::U256(v) => Self::U256(StrView(v)),
+            TraceValue::Address(v) => Self::Address(v.into()),
+            TraceValue::Bytes(v) => Self::Bytes(StrView(v)),
+            TraceValue::Vector(v) => Self::Vector(v.into_iter().map(Into::into).collect()),
+            TraceValue::Struct(v) => Self::Struct(v.into_iter().map(Into::into).collect()),
+            TraceValue::Reference { is_global, value } => Self::Reference {
+                is_global,
+                value: Box::new((*value).into()),
+            },
+            TraceValue::Raw(v) => Self::Raw(StrView(v)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CallTraceView {
+    /// The called function, null if it is a script or a module init function.
+    pub function: Option<FunctionIdView>,
+    pub ty_args: Vec<TypeTagView>,
+    pub args: Vec<TraceValueView>,
+    pub is_native: bool,
+    /// The return values, null if the call aborted or did not finish.
+    pub return_values: Option<Vec<TraceValueView>>,
+    pub calls: Vec<CallTraceView>,
+}
+
+impl From<CallTrace> for CallTraceView {
+    fn from(call: CallTrace) -> Self {
+        Self {
+            function: call.function.map(Into::into),
+            ty_args: call.ty_args.into_iter().map(Into::into).collect(),
+            args: call.args.into_iter().map(Into::into).collect(),
+            is_native: call.is_native,
+            return_values: call
+                .return_values
+                .map(|values| values.into_iter().map(Into::into).collect()),
+            calls: call.calls.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ObjectWriteKindView {
+    New,
+    Modify,
+    Delete,
+    Fields,
+}
+
+impl From<ObjectWriteKind> for ObjectWriteKindView {
+    fn from(kind: ObjectWriteKind) -> Self {
+        match kind {
+            ObjectWriteKind::New => Self::New,
+            ObjectWriteKind::Modify => Self::Modify,
+            ObjectWriteKind::Delete => Self::Delete,
+            ObjectWriteKind::Fields => Self::Fields,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ObjectWriteTraceView {
+    pub object_id: ObjectIDView,
+    pub object_type: TypeTagView,
+    pub owner: AccountAddressView,
+    pub kind: ObjectWriteKindView,
+}
+
+impl From<ObjectWriteTrace> for ObjectWriteTraceView {
+    fn from(write: ObjectWriteTrace) -> Self {
+        Self {
+            object_id: write.object_id.into(),
+            object_type: write.object_type.into(),
+            owner: write.owner.into(),
+            kind: write.kind.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TraceEventView {
+    pub event_handle_id: ObjectIDView,
+    pub event_type: StructTagView,
+    pub event_data: BytesView,
+    pub event_index: StrView<u64>,
+}
+
+impl From<TransactionEvent> for TraceEventView {
+    fn from(event: TransactionEvent) -> Self {
+        Self {
+            event_handle_id: event.event_handle_id.into(),
+            event_type: event.event_type.into(),
+            event_data: StrView(event.event_data),
+            event_index: event.event_index.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TxTraceView {
+    /// The call tree of the transaction action.
+    pub calls: Vec<CallTraceView>,
+    pub object_reads: Vec<ObjectIDView>,
+    pub object_writes: Vec<ObjectWriteTraceView>,
+    pub events: Vec<TraceEventView>,
+}
+
+impl From<TxTrace> for TxTraceView {
+    fn from(trace: TxTrace) -> Self {
+        Self {
+            calls: trace.calls.into_iter().map(Into::into).collect(),
+            object_reads: trace.object_reads.into_iter().map(Into::into).collect(),
+            object_writes: trace.object_writes.into_iter().map(Into::into).collect(),
+            events: trace.events.into_iter().map(Into::into).collect(),
+        }
+    }
+}
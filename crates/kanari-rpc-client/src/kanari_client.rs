@@ -2,12 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::{Ok, Result};
+use bitcoin::Txid;
 use bitcoincore_rpc::RawTx;
 use jsonrpsee::http_client::HttpClient;
 use kanari_rpc_api::api::btc_api::BtcAPIClient;
 use kanari_rpc_api::api::kanari_api::KanariAPIClient;
 use kanari_rpc_api::jsonrpc_types::btc::ord::{InscriptionFilterView, InscriptionObjectView};
+use kanari_rpc_api::jsonrpc_types::btc::pending_block::{
+    PendingBlocksView, StateConfirmationView, TxConfirmationView,
+};
 use kanari_rpc_api::jsonrpc_types::btc::utxo::{UTXOFilterView, UTXOObjectView};
+use kanari_rpc_api::jsonrpc_types::package_source_view::{
+    PackageSourceView, SubmitPackageSourceView,
+};
 use kanari_rpc_api::jsonrpc_types::transaction_view::TransactionFilterView;
 use kanari_rpc_api::jsonrpc_types::{
     AccessPathView, AnnotatedFunctionResultView, BalanceInfoPageView, BytesView, EventOptions,
@@ -26,8 +33,10 @@ use kanari_rpc_api::jsonrpc_types::{
     StateChangeSetPageView, StrView, SyncStateFilterView, TransactionWithInfoPageView, TxOptions,
 };
 use kanari_types::address::BitcoinAddress;
+use kanari_types::authentication_key::AuthenticationKey;
 use kanari_types::bitcoin::multisign_account::MultisignAccountInfo;
 use kanari_types::framework::address_mapping::KanariToBitcoinAddressMapping;
+use kanari_types::framework::session_key::{SessionKey, SessionKeys};
 use kanari_types::indexer::state::IndexerStateID;
 use kanari_types::{address::KanariAddress, transaction::kanari::KanariTransaction};
 use move_core_types::account_address::AccountAddress;
@@ -106,6 +115,17 @@ impl KanariRpcClient {
             .await?)
     }
 
+    pub async fn get_state_nodes(&self, node_hashes: Vec<H256>) -> Result<Vec<Option<Vec<u8>>>> {
+        let node_hashes = node_hashes.into_iter().map(StrView).collect();
+        Ok(self
+            .http
+            .get_state_nodes(node_hashes)
+            .await?
+            .into_iter()
+            .map(|node| node.map(|node| node.0))
+            .collect())
+    }
+
     pub async fn get_decoded_states(
         &self,
         access_path: AccessPath,
@@ -382,6 +402,30 @@ impl KanariRpcClient {
             .await?)
     }
 
+    pub async fn get_pending_blocks(&self) -> Result<PendingBlocksView> {
+        Ok(self.http.get_pending_blocks().await?)
+    }
+
+    pub async fn get_state_confirmations(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> Result<Vec<Option<StateConfirmationView>>> {
+        Ok(self
+            .http
+            .get_state_confirmations(ObjectIDVecView::from(object_ids))
+            .await?)
+    }
+
+    pub async fn get_tx_confirmations(
+        &self,
+        txids: Vec<Txid>,
+    ) -> Result<Vec<Option<TxConfirmationView>>> {
+        Ok(self
+            .http
+            .get_tx_confirmations(txids.into_iter().map(StrView).collect())
+            .await?)
+    }
+
     pub async fn get_resource<T: MoveStructState>(
         &self,
         account: KanariAddress,
@@ -413,6 +457,41 @@ impl KanariRpcClient {
             })?)
     }
 
+    /// Get all the session keys of the account with their scopes, last active time and expiry
+    pub async fn get_session_keys(&self, address: KanariAddress) -> Result<Vec<SessionKey>> {
+        let Some(session_keys) = self.get_resource::<SessionKeys>(address).await? else {
+            return Ok(vec![]);
+        };
+        let mut result = vec![];
+        let mut cursor = None;
+        loop {
+            let page = self
+                .list_field_states(session_keys.keys.clone().into(), cursor, None, None)
+                .await?;
+            for field in page.data {
+                let state = ObjectState::from(field.state);
+                result.push(state.value_as_df::<Vec<u8>, SessionKey>()?.value);
+            }
+            if !page.has_next_page {
+                break;
+            }
+            cursor = page.next_cursor;
+        }
+        Ok(result)
+    }
+
+    pub async fn get_session_key(
+        &self,
+        address: KanariAddress,
+        authentication_key: &AuthenticationKey,
+    ) -> Result<Option<SessionKey>> {
+        Ok(self
+            .get_session_keys(address)
+            .await?
+            .into_iter()
+            .find(|session_key| &session_key.authentication_key() == authentication_key))
+    }
+
     pub async fn broadcast_bitcoin_tx<T: RawTx>(
         &self,
         raw_tx: T,
@@ -445,4 +524,19 @@ impl KanariRpcClient {
     pub async fn status(&self) -> Result<Status> {
         Ok(self.http.status().await?)
     }
+
+    pub async fn submit_package_source(
+        &self,
+        package_source: SubmitPackageSourceView,
+    ) -> Result<PackageSourceView> {
+        Ok(self.http.submit_package_source(package_source).await?)
+    }
+
+    pub async fn query_package_sources(
+        &self,
+        package_addresses: Vec<KanariAddress>,
+    ) -> Result<Vec<PackageSourceView>> {
+        let package_addresses = package_addresses.into_iter().map(StrView).collect();
+        Ok(self.http.query_package_sources(package_addresses).await?)
+    }
 }
@@ -21,9 +21,12 @@ use moveos_types::{
     function_return_value::FunctionResult, module_binding::MoveFunctionCaller,
     moveos_std::tx_context::TxContext, transaction::FunctionCall,
 };
-use std::sync::Arc;
+use smt::NodeReader;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, LazyLock, Mutex};
 use std::time::Duration;
-use tokio::runtime::Handle;
+use tokio::runtime::Runtime;
 
 pub mod client_config;
 pub mod kanari_client;
@@ -112,19 +115,19 @@ impl ModuleResolver for &Client {
     }
 
     fn get_module(&self, id: &ModuleId) -> Result<Option<Vec<u8>>> {
-        tokio::task::block_in_place(|| {
-            Handle::current().block_on(async {
-                let mut states = self.kanari.get_states(AccessPath::module(id), None).await?;
-                states
-                    .pop()
-                    .flatten()
-                    .map(|state_view| {
-                        let state = ObjectState::from(state_view);
-                        let module = state.value_as_df::<MoveString, MoveModule>()?;
-                        Ok(module.value.byte_codes)
-                    })
-                    .transpose()
-            })
+        let client = (*self).clone();
+        let access_path = AccessPath::module(id);
+        block_on(async move {
+            let mut states = client.kanari.get_states(access_path, None).await?;
+            states
+                .pop()
+                .flatten()
+                .map(|state_view| {
+                    let state = ObjectState::from(state_view);
+                    let module = state.value_as_df::<MoveString, MoveModule>()?;
+                    Ok(module.value.byte_codes)
+                })
+                .transpose()
         })
     }
 }
@@ -190,19 +193,17 @@ impl ModuleResolver for ClientResolver {
 
 impl StatelessResolver for ClientResolver {
     fn get_field_at(&self, state_root: H256, key: &FieldKey) -> Result<Option<ObjectState>, Error> {
-        tokio::task::block_in_place(|| {
-            Handle::current().block_on(async {
-                let access_path = AccessPath::object(ObjectID::new(key.0));
-                let mut object_state_view_list = self
-                    .client
-                    .kanari
-                    .get_states(access_path, Some(state_root))
-                    .await?;
-                Ok(object_state_view_list.pop().flatten().map(|state_view| {
-                    let v: ObjectState = state_view.into();
-                    v
-                }))
-            })
+        let client = self.client.clone();
+        let access_path = AccessPath::object(ObjectID::new(key.0));
+        block_on(async move {
+            let mut object_state_view_list = client
+                .kanari
+                .get_states(access_path, Some(state_root))
+                .await?;
+            Ok(object_state_view_list.pop().flatten().map(|state_view| {
+                let v: ObjectState = state_view.into();
+                v
+            }))
         })
     }
 
@@ -212,21 +213,19 @@ impl StatelessResolver for ClientResolver {
         cursor: Option<FieldKey>,
         limit: usize,
     ) -> Result<Vec<StateKV>> {
-        tokio::task::block_in_place(|| {
-            Handle::current().block_on(async {
-                let object_id = ObjectID::new(state_root.0);
-                let field_cursor = cursor.map(|field_key| field_key.to_hex_literal());
-                let fields_states = self
-                    .client
-                    .kanari
-                    .list_field_states(object_id.into(), field_cursor, Some(limit as u64), None)
-                    .await?;
-                Ok(fields_states
-                    .data
-                    .iter()
-                    .map(|item| StateKV::from((item.field_key.into(), item.state.clone().into())))
-                    .collect())
-            })
+        let client = self.client.clone();
+        let object_id = ObjectID::new(state_root.0);
+        let field_cursor = cursor.map(|field_key| field_key.to_hex_literal());
+        block_on(async move {
+            let fields_states = client
+                .kanari
+                .list_field_states(object_id.into(), field_cursor, Some(limit as u64), None)
+                .await?;
+            Ok(fields_states
+                .data
+                .iter()
+                .map(|item| StateKV::from((item.field_key.into(), item.state.clone().into())))
+                .collect())
         })
     }
 }
@@ -236,3 +235,124 @@ impl StateResolver for ClientResolver {
         &self.root
     }
 }
+
+/// The max number of the prefetched nodes kept in the `ClientNodeReader`.
+const MAX_PREFETCHED_NODES: usize = 100_000;
+
+/// The runtime to send the requests of the synchronous resolvers and readers.
+static BLOCKING_RUNTIME: LazyLock<Runtime> = LazyLock::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("rpc-client-blocking")
+        .enable_all()
+        .build()
+        .expect("Create the rpc client blocking runtime failed")
+});
+
+/// Run the future on a dedicated runtime and wait for the result, so the synchronous resolvers can be
+/// called from both the async context and the threads outside the runtime without blocking the runtime.
+fn block_on<T, F>(future: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: Future<Output = Result<T>> + Send + 'static,
+{
+    let (sender, receiver) = std::sync::mpsc::channel();
+    BLOCKING_RUNTIME.spawn(async move {
+        let _ = sender.send(future.await);
+    });
+    receiver
+        .recv()
+        .map_err(|_| anyhow::anyhow!("The rpc client blocking runtime is shutdown"))?
+}
+
+/// Read the state tree nodes from the remote node, it is used by the fork mode node.
+/// When an internal node is read, its children are prefetched in one batch request in the background,
+/// so the iteration of the fields mostly reads the nodes from the prefetched nodes.
+pub struct ClientNodeReader {
+    client: Client,
+    prefetched: Arc<Mutex<HashMap<H256, Vec<u8>>>>,
+}
+
+impl ClientNodeReader {
+    pub fn new(client: Client) -> Self {
+        Self {
+            client,
+            prefetched: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+async fn prefetch_state_nodes(
+    client: Client,
+    prefetched: Arc<Mutex<HashMap<H256, Vec<u8>>>>,
+    hashes: Vec<H256>,
+) {
+    let hashes = {
+        let prefetched = prefetched
+            .lock()
+            .expect("The prefetched nodes lock is poisoned");
+        if prefetched.len() >= MAX_PREFETCHED_NODES {
+            return;
+        }
+        hashes
+            .into_iter()
+            .filter(|hash| !prefetched.contains_key(hash))
+            .collect::<Vec<_>>()
+    };
+    if hashes.is_empty() {
+        return;
+    }
+    // The prefetch is an optimization, the failed nodes are read again when they are needed
+    if let Ok(nodes) = client.kanari.get_state_nodes(hashes.clone()).await {
+        let mut prefetched = prefetched
+            .lock()
+            .expect("The prefetched nodes lock is poisoned");
+        for (hash, node) in hashes.into_iter().zip(nodes) {
+            // The mismatched nodes are dropped, and they are read and checked again when they are needed
+            if let Some(node) = node
+                .filter(|node| smt::decode_node_hash(node).is_ok_and(|node_hash| node_hash == hash))
+            {
+                prefetched.insert(hash, node);
+            }
+        }
+    }
+}
+
+impl NodeReader for ClientNodeReader {
+    fn get(&self, hash: &H256) -> Result<Option<Vec<u8>>> {
+        // The node is cached in the local store after it is read, so the prefetched node is read only once
+        if let Some(node) = self
+            .prefetched
+            .lock()
+            .expect("The prefetched nodes lock is poisoned")
+            .remove(hash)
+        {
+            return Ok(Some(node));
+        }
+        let client = self.client.clone();
+        let prefetched = self.prefetched.clone();
+        let hash = *hash;
+        block_on(async move {
+            let node = client
+                .kanari
+                .get_state_nodes(vec![hash])
+                .await?
+                .pop()
+                .flatten();
+            if let Some(node) = &node {
+                let node_hash = smt::decode_node_hash(node)?;
+                ensure!(
+                    node_hash == hash,
+                    "The hash {:?} of the remote node mismatches the requested hash {:?}",
+                    node_hash,
+                    hash
+                );
+                let children = smt::decode_node_children(node)?;
+                if !children.is_empty() {
+                    tokio::spawn(prefetch_state_nodes(client, prefetched, children));
+                }
+            }
+            Ok(node)
+        })
+    }
+}
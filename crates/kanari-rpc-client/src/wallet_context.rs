@@ -10,6 +10,7 @@ use bitcoin::psbt::{GetKey, KeyRequest};
 use bitcoin::secp256k1::Signing;
 use kanari_config::config::{Config, PersistedConfig};
 use kanari_config::{KANARI_CLIENT_CONFIG, kanari_config_dir};
+use kanari_key::key_derive::verify_password;
 use kanari_key::keystore::Keystore;
 use kanari_key::keystore::account_keystore::AccountKeystore;
 use kanari_key::keystore::file_keystore::FileBasedKeystore;
@@ -28,10 +29,10 @@ use moveos_types::moveos_std::gas_schedule::GasScheduleConfig;
 use moveos_types::transaction::MoveAction;
 use std::collections::BTreeMap;
 use std::path::PathBuf;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 #[derive(Debug)]
 pub struct WalletContext {
@@ -39,7 +40,41 @@ pub struct WalletContext {
     pub client_config: PersistedConfig<ClientConfig>,
     pub keystore: Keystore,
     pub address_mapping: BTreeMap<String, AccountAddress>,
+    password: Mutex<KeystoreLock>,
+}
+
+/// Asks the keystore password again when the keystore is locked
+pub type UnlockPromptFn = fn() -> Option<String>;
+
+/// The keystore password of the session, it is forgotten after the lock timeout of inactivity.
+/// There is no timer behind the timeout, it is checked on the next use of the password or the
+/// lock state, so the password stays in memory until then or until the wallet context is dropped.
+/// When the keystore is locked, the unlock prompt is used to ask the password again.
+#[derive(Debug, Default)]
+struct KeystoreLock {
     password: Option<String>,
+    last_used: Option<Instant>,
+    timeout: Option<Duration>,
+    unlock_prompt: Option<UnlockPromptFn>,
+}
+
+impl KeystoreLock {
+    fn is_expired(&self) -> bool {
+        match (self.timeout, self.last_used) {
+            (Some(timeout), Some(last_used)) => last_used.elapsed() > timeout,
+            _ => false,
+        }
+    }
+
+    /// Forget the password if the lock timeout has expired, returns whether it expired
+    fn forget_if_expired(&mut self) -> bool {
+        let expired = self.is_expired();
+        if expired {
+            self.password = None;
+            self.last_used = None;
+        }
+        expired
+    }
 }
 
 pub type AddressMappingFn = Box<dyn Fn(&str) -> Option<AccountAddress> + Send + Sync>;
@@ -68,7 +103,9 @@ impl WalletContext {
 
         //TODO support account name alias name.
         if let Some(active_address) = &client_config.active_address {
-            let active_address = if !keystore.contains_address(active_address) {
+            let active_address = if !keystore.contains_address(active_address)
+                && !keystore.is_watch_only(active_address)
+            {
                 //The active address is not in the keystore, maybe the user reset the keystore.
                 //We auto change the active address to the first address in the keystore.
                 let first_address = keystore
@@ -93,7 +130,7 @@ impl WalletContext {
             client_config,
             keystore,
             address_mapping,
-            password: None,
+            password: Default::default(),
         })
     }
 
@@ -137,7 +174,7 @@ impl WalletContext {
             ParsedAddress::Bitcoin(address) => Ok(address),
             _ => {
                 let address = self.resolve_kanari_address(parsed_address)?;
-                let account = self.keystore.get_account(&address, self.password.clone())?;
+                let account = self.keystore.get_account(&address, self.password()?)?;
                 if let Some(account) = account {
                     let bitcoin_address = account.bitcoin_address;
                     Ok(bitcoin_address)
@@ -230,7 +267,7 @@ impl WalletContext {
 
     pub fn generate_session_key(&mut self, address: &KanariAddress) -> Result<AuthenticationKey> {
         self.keystore
-            .generate_session_key(address, self.password.clone())
+            .generate_session_key(address, self.password()?)
     }
 
     pub fn get_session_key(
@@ -239,7 +276,7 @@ impl WalletContext {
         authentication_key: &AuthenticationKey,
     ) -> Result<Option<KanariKeyPair>> {
         self.keystore
-            .get_session_key(address, authentication_key, self.password.clone())
+            .get_session_key(address, authentication_key, self.password()?)
     }
 
     pub fn sign_transaction_via_session_key(
@@ -248,11 +285,28 @@ impl WalletContext {
         tx_data: KanariTransactionData,
         authentication_key: &AuthenticationKey,
     ) -> KanariResult<KanariTransaction> {
+        if let Some(session_key) = self
+            .keystore
+            .get_bound_session_key(signer, authentication_key)
+            .filter(|session_key| !session_key.is_scope_match_with_action(&tx_data.action))
+        {
+            warn!(
+                "The action {} is out of the scopes [{}] of the session key {}, the transaction will be rejected by the session validator",
+                tx_data.action,
+                session_key
+                    .scopes
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                authentication_key
+            );
+        }
         let tx = self.keystore.sign_transaction_via_session_key(
             signer,
             tx_data,
             authentication_key,
-            self.password.clone(),
+            self.password()?,
         )?;
         Ok(tx)
     }
@@ -264,7 +318,37 @@ impl WalletContext {
     ) -> KanariResult<KanariTransaction> {
         let tx = self
             .keystore
-            .sign_transaction(&signer, tx_data, self.password.clone())?;
+            .sign_transaction(&signer, tx_data, self.password()?)?;
+        Ok(tx)
+    }
+
+    pub fn sign_transaction_via_taproot(
+        &self,
+        signer: KanariAddress,
+        tx_data: KanariTransactionData,
+    ) -> KanariResult<KanariTransaction> {
+        let tx = self
+            .keystore
+            .sign_transaction_via_taproot(&signer, tx_data, self.password()?)?;
+        Ok(tx)
+    }
+
+    pub fn generate_passkey(&mut self, address: &KanariAddress) -> Result<AuthenticationKey> {
+        self.keystore.generate_passkey(address, self.password()?)
+    }
+
+    pub fn sign_transaction_via_webauthn(
+        &self,
+        signer: &KanariAddress,
+        tx_data: KanariTransactionData,
+        authentication_key: &AuthenticationKey,
+    ) -> KanariResult<KanariTransaction> {
+        let tx = self.keystore.sign_transaction_via_webauthn(
+            signer,
+            tx_data,
+            authentication_key,
+            self.password()?,
+        )?;
         Ok(tx)
     }
 
@@ -296,7 +380,7 @@ impl WalletContext {
     }
 
     pub fn get_key_pair(&self, address: &KanariAddress) -> Result<KanariKeyPair> {
-        self.keystore.get_key_pair(address, self.password.clone())
+        self.keystore.get_key_pair(address, self.password()?)
     }
 
     pub fn assert_execute_success(
@@ -314,11 +398,77 @@ impl WalletContext {
     }
 
     pub fn set_password(&mut self, password: Option<String>) {
-        self.password = password;
+        let lock = self.password.get_mut().unwrap_or_else(|e| e.into_inner());
+        lock.password = password;
+        lock.last_used = Some(Instant::now());
     }
 
+    /// Get the password of the session, it is `None` if the keystore is locked
     pub fn get_password(&self) -> Option<String> {
-        self.password.clone()
+        self.password().ok().flatten()
+    }
+
+    /// Lock the keystore after the timeout of inactivity, `None` keeps it unlocked for the session.
+    /// The timeout takes effect on the next use of the keystore after it expires.
+    pub fn set_lock_timeout(&mut self, timeout: Option<Duration>) {
+        let lock = self.password.get_mut().unwrap_or_else(|e| e.into_inner());
+        lock.timeout = timeout;
+    }
+
+    /// Ask the password with the prompt to unlock the keystore when it is locked,
+    /// otherwise the use of the keys fails until `unlock` is called
+    pub fn set_unlock_prompt(&mut self, unlock_prompt: UnlockPromptFn) {
+        let lock = self.password.get_mut().unwrap_or_else(|e| e.into_inner());
+        lock.unlock_prompt = Some(unlock_prompt);
+    }
+
+    /// Forget the password of the session
+    pub fn lock(&self) {
+        let mut lock = self.password.lock().unwrap_or_else(|e| e.into_inner());
+        lock.password = None;
+        lock.last_used = None;
+    }
+
+    pub fn unlock(&self, password: Option<String>) -> Result<()> {
+        let mut lock = self.password.lock().unwrap_or_else(|e| e.into_inner());
+        self.unlock_with(&mut lock, password)
+    }
+
+    pub fn is_locked(&self) -> bool {
+        if self.keystore.get_if_password_is_empty() {
+            return false;
+        }
+        let mut lock = self.password.lock().unwrap_or_else(|e| e.into_inner());
+        lock.forget_if_expired() || lock.password.is_none()
+    }
+
+    fn unlock_with(&self, lock: &mut KeystoreLock, password: Option<String>) -> Result<()> {
+        if !verify_password(password.clone(), self.keystore.get_password_hash())? {
+            return Err(KanariError::InvalidPasswordError("Password is invalid".to_owned()).into());
+        }
+        lock.password = password;
+        lock.last_used = Some(Instant::now());
+        Ok(())
+    }
+
+    /// The password to decrypt the keys, every use of it extends the lock timeout
+    fn password(&self) -> Result<Option<String>> {
+        let mut lock = self.password.lock().unwrap_or_else(|e| e.into_inner());
+        if self.keystore.get_if_password_is_empty() {
+            return Ok(lock.password.clone());
+        }
+        if lock.forget_if_expired() {
+            let unlock_prompt = lock.unlock_prompt.ok_or_else(|| {
+                KanariError::InvalidPasswordError(
+                    "The keystore is locked after the timeout of inactivity, unlock it with the password"
+                        .to_owned(),
+                )
+            })?;
+            self.unlock_with(&mut lock, unlock_prompt())?;
+        } else if lock.last_used.is_some() {
+            lock.last_used = Some(Instant::now());
+        }
+        Ok(lock.password.clone())
     }
 
     pub async fn get_kanari_network(&self) -> Result<KanariNetwork> {
@@ -358,9 +508,7 @@ impl GetKey for WalletContext {
             _ => anyhow::bail!("Unsupported key request: {:?}", key_request),
         };
         debug!("Get key for address: {:?}", address);
-        let kp = self
-            .keystore
-            .get_key_pair(&address, self.password.clone())?;
+        let kp = self.keystore.get_key_pair(&address, self.password()?)?;
         Ok(Some(PrivateKey::from_slice(
             kp.private(),
             bitcoin::Network::Bitcoin,
@@ -40,14 +40,19 @@ use kanari_relayer::actor::messages::RelayTick;
 use kanari_relayer::actor::relayer::RelayerActor;
 use kanari_rpc_api::RpcError;
 use kanari_rpc_api::api::KanariRpcModule;
+use kanari_rpc_api::jsonrpc_types::{QueryOptions, SyncStateFilterView};
+use kanari_rpc_client::{Client, ClientBuilder, ClientNodeReader};
 use kanari_sequencer::actor::sequencer::SequencerActor;
 use kanari_sequencer::proxy::SequencerProxy;
 use kanari_store::da_store::DAMetaStore;
+use kanari_store::meta_store::MetaStore;
 use kanari_types::address::KanariAddress;
 use kanari_types::error::{GenesisError, KanariError};
 use kanari_types::kanari_network::BuiltinChainID;
 use kanari_types::service_type::ServiceType;
 use moveos_eventbus::bus::EventBus;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::object::ObjectMeta;
 use raw_store::errors::RawStoreError;
 use serde_json::json;
 use std::fmt::Debug;
@@ -174,6 +179,58 @@ pub async fn start_server(opt: KanariOpt, server_opt: ServerOpt) -> Result<Serve
     }
 }
 
+/// The max number of the latest remote state change sets to search for the fork state root.
+const MAX_FORK_ROOT_SEARCH_DEPTH: usize = 1000;
+
+/// Get the root object metadata of the fork state root from the remote node.
+/// The size of the root object is not in the state tree, so it is got from the remote state change sets.
+async fn resolve_fork_root(client: &Client, fork_state_root: Option<H256>) -> Result<ObjectMeta> {
+    let root_state = client.kanari.status().await?.kanari_status.root_state;
+    let latest_root = ObjectMeta::root_metadata(root_state.state_root.0, root_state.size.0);
+    let fork_state_root = match fork_state_root {
+        Some(fork_state_root) if fork_state_root != latest_root.state_root() => fork_state_root,
+        _ => return Ok(latest_root),
+    };
+
+    let query_option = QueryOptions {
+        descending: true,
+        ..Default::default()
+    };
+    let mut cursor = None;
+    let mut searched = 0;
+    while searched < MAX_FORK_ROOT_SEARCH_DEPTH {
+        let page = client
+            .kanari
+            .sync_states(
+                SyncStateFilterView::All,
+                cursor,
+                None,
+                Some(query_option.clone()),
+            )
+            .await?;
+        if let Some(change_set) = page
+            .data
+            .iter()
+            .find(|change_set| change_set.state_change_set.state_root.0 == fork_state_root)
+        {
+            return Ok(ObjectMeta::root_metadata(
+                fork_state_root,
+                change_set.state_change_set.global_size.0,
+            ));
+        }
+        searched += page.data.len();
+        if !page.has_next_page {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    Err(anyhow::anyhow!(
+        "The fork state root {:?} is not found in the latest {} state change sets of the remote node",
+        fork_state_root,
+        searched
+    ))
+}
+
 // run json-rpc server
 pub async fn run_start_server(opt: KanariOpt, server_opt: ServerOpt) -> Result<ServerHandle> {
     // We may call `start_server` multiple times in testing scenarios
@@ -204,7 +261,18 @@ pub async fn run_start_server(opt: KanariOpt, server_opt: ServerOpt) -> Result<S
     // Init store
     let store_config = opt.store_config();
 
-    let kanari_db = KanariDB::init(store_config, &prometheus_registry)?;
+    let fork_client = match &opt.fork_url {
+        Some(fork_url) => Some(ClientBuilder::default().build(fork_url).await?),
+        None => None,
+    };
+    let kanari_db = match &fork_client {
+        Some(fork_client) => {
+            info!("Fork the state from {:?}", opt.fork_url);
+            let remote = Arc::new(ClientNodeReader::new(fork_client.clone()));
+            KanariDB::init_fork(store_config, &prometheus_registry, remote)?
+        }
+        None => KanariDB::init(store_config, &prometheus_registry)?,
+    };
     let (kanari_store, moveos_store, indexer_store, indexer_reader) = (
         kanari_db.kanari_store.clone(),
         kanari_db.moveos_store.clone(),
@@ -223,7 +291,10 @@ pub async fn run_start_server(opt: KanariOpt, server_opt: ServerOpt) -> Result<S
     let sequencer_account = sequencer_keypair.public().kanari_address()?;
     let sequencer_bitcoin_address = sequencer_keypair.public().bitcoin_address()?;
 
-    let service_status = opt.service_status;
+    let service_status = match opt.service_status {
+        Some(service_status) => service_status,
+        None => kanari_store.get_service_status()?.unwrap_or_default(),
+    };
 
     let mut network = opt.network();
     if network.chain_id == BuiltinChainID::Local.chain_id() {
@@ -232,7 +303,8 @@ pub async fn run_start_server(opt: KanariOpt, server_opt: ServerOpt) -> Result<S
         let kanari_dao_address = kanari_dao_bitcoin_address.to_kanari_address();
         println!("Kanari DAO address: {:?}", kanari_dao_address);
         println!("Kanari DAO Bitcoin address: {}", kanari_dao_bitcoin_address);
-    } else {
+    } else if fork_client.is_none() {
+        // In fork mode, the local txs are sequenced by the local sequencer
         ensure!(
             network.genesis_config.sequencer_account == sequencer_bitcoin_address,
             "Sequencer({:?}) in genesis config is not equal to sequencer({:?}) in cli config",
@@ -241,7 +313,35 @@ pub async fn run_start_server(opt: KanariOpt, server_opt: ServerOpt) -> Result<S
         );
     }
 
-    let genesis = KanariGenesisV2::load_or_init(network.clone(), &kanari_db)?;
+    let genesis = match &opt.genesis_file {
+        Some(genesis_file) => KanariGenesisV2::load_for_network(genesis_file, &network)?,
+        None => KanariGenesisV2::load_or_build(network.clone())?,
+    };
+    if let Some(fork_client) = &fork_client {
+        let remote_chain_id = fork_client.kanari.get_chain_id().await?;
+        ensure!(
+            remote_chain_id == network.chain_id.id,
+            "The chain id of the fork url({}) is not equal to the chain id of the network({})",
+            remote_chain_id,
+            network.chain_id.id
+        );
+        // The fork root is only resolved on the first start, the store keeps going from the local root after.
+        if kanari_db
+            .moveos_store
+            .get_config_store()
+            .get_genesis()?
+            .is_none()
+        {
+            let fork_root = resolve_fork_root(fork_client, opt.fork_state_root).await?;
+            info!(
+                "Fork from the state root: {:?}, size: {}",
+                fork_root.state_root(),
+                fork_root.size()
+            );
+            genesis.init_fork(&kanari_db, fork_root)?;
+        }
+    }
+    let genesis = genesis.check_or_init(&kanari_db)?;
 
     let root = kanari_db
         .latest_root()?
@@ -312,7 +412,7 @@ pub async fn run_start_server(opt: KanariOpt, server_opt: ServerOpt) -> Result<S
         false,
         opt.da_config().da_min_block_to_submit,
         false,
-        opt.service_status.is_sync_mode(),
+        service_status.is_sync_mode(),
     )?;
     info!("DA meta issues: {:?}, fixed: {:?}", da_issues, da_fixed);
     let da_config = opt.da_config().clone();
@@ -442,6 +542,8 @@ pub async fn run_start_server(opt: KanariOpt, server_opt: ServerOpt) -> Result<S
         da_proxy,
         subscription_handle.clone(),
         None,
+        opt.parallel_batch_size,
+        opt.accept_package_sources,
     );
     let aggregate_service = AggregateService::new(rpc_service.clone());
 
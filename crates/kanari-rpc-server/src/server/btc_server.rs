@@ -5,15 +5,20 @@ use crate::service::rpc_service::RpcService;
 use anyhow::Result;
 use bitcoincore_rpc::bitcoin::Txid;
 use jsonrpsee::{RpcModule, core::async_trait};
-use kanari_rpc_api::RpcResult;
 use kanari_rpc_api::api::btc_api::BtcAPIServer;
 use kanari_rpc_api::api::{DEFAULT_RESULT_LIMIT_USIZE, KanariRpcModule, MAX_RESULT_LIMIT_USIZE};
 use kanari_rpc_api::jsonrpc_types::btc::ord::{InscriptionFilterView, InscriptionStateView};
+use kanari_rpc_api::jsonrpc_types::btc::pending_block::{
+    PendingBlocksView, StateConfirmationView, TxConfirmationView,
+};
+use kanari_rpc_api::jsonrpc_types::btc::transaction::TxidView;
 use kanari_rpc_api::jsonrpc_types::btc::utxo::{UTXOFilterView, UTXOStateView};
 use kanari_rpc_api::jsonrpc_types::{
-    BytesView, IndexerStateIDView, InscriptionPageView, StrView, UTXOPageView,
+    BytesView, IndexerStateIDView, InscriptionPageView, ObjectIDVecView, StrView, UTXOPageView,
 };
+use kanari_rpc_api::{RpcError, RpcResult};
 use kanari_types::indexer::state::ObjectStateType;
+use moveos_types::moveos_std::object::{MAX_OBJECT_IDS_PER_QUERY, ObjectID};
 use std::cmp::min;
 
 pub struct BtcServer {
@@ -130,6 +135,38 @@ impl BtcAPIServer for BtcServer {
 
         Ok(txid.to_string())
     }
+
+    async fn get_pending_blocks(&self) -> RpcResult<PendingBlocksView> {
+        Ok(self.rpc_service.get_pending_blocks().await?)
+    }
+
+    async fn get_state_confirmations(
+        &self,
+        object_ids: ObjectIDVecView,
+    ) -> RpcResult<Vec<Option<StateConfirmationView>>> {
+        let object_ids: Vec<ObjectID> = object_ids.into();
+        if object_ids.len() > MAX_OBJECT_IDS_PER_QUERY {
+            return Err(RpcError::UnexpectedError(format!(
+                "object_ids should be less than {}",
+                MAX_OBJECT_IDS_PER_QUERY
+            )));
+        }
+        Ok(self.rpc_service.get_state_confirmations(object_ids).await?)
+    }
+
+    async fn get_tx_confirmations(
+        &self,
+        txids: Vec<TxidView>,
+    ) -> RpcResult<Vec<Option<TxConfirmationView>>> {
+        if txids.len() > MAX_OBJECT_IDS_PER_QUERY {
+            return Err(RpcError::UnexpectedError(format!(
+                "txids should be less than {}",
+                MAX_OBJECT_IDS_PER_QUERY
+            )));
+        }
+        let txids = txids.into_iter().map(Into::into).collect();
+        Ok(self.rpc_service.get_tx_confirmations(txids).await?)
+    }
 }
 
 impl KanariRpcModule for BtcServer {
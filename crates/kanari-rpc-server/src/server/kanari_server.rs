@@ -6,8 +6,12 @@ use crate::service::rpc_service::RpcService;
 use anyhow::Result;
 use jsonrpsee::core::SubscriptionResult;
 use jsonrpsee::{PendingSubscriptionSink, RpcModule, core::async_trait};
+use kanari_executor::actor::messages::DryRunTransactionResult;
 use kanari_rpc_api::api::MAX_INTERNAL_LIMIT_USIZE;
 use kanari_rpc_api::jsonrpc_types::field_view::FieldFilterView;
+use kanari_rpc_api::jsonrpc_types::package_source_view::{
+    PackageSourceView, SubmitPackageSourceView,
+};
 use kanari_rpc_api::jsonrpc_types::{
     AccessPathView, BalanceInfoPageView, DryRunTransactionResponseView,
     EnumStructTagOrObjectIDView, EventOptions, EventPageView, ExecuteTransactionResponseView,
@@ -170,23 +174,27 @@ impl KanariAPIServer for KanariServer {
         Ok(result)
     }
 
-    async fn dry_run(&self, payload: BytesView) -> RpcResult<DryRunTransactionResponseView> {
+    async fn dry_run(
+        &self,
+        payload: BytesView,
+        trace: Option<bool>,
+    ) -> RpcResult<DryRunTransactionResponseView> {
         let tx = bcs::from_bytes::<KanariTransactionData>(&payload.0)?;
         let tx_hash = tx.tx_hash();
-        let tx_result = self.rpc_service.dry_run_tx(tx).await?;
-        let raw_output = tx_result.raw_output;
-        let raw_output_view = RawTransactionOutputView {
-            tx_hash: tx_hash.into(),
-            state_root: raw_output.changeset.state_root.into(),
-            status: raw_output.status.into(),
-            gas_used: raw_output.gas_used.into(),
-            is_upgrade: raw_output.is_upgrade,
-        };
-        let tx_response = DryRunTransactionResponseView {
-            raw_output: raw_output_view,
-            vm_error_info: tx_result.vm_error_info.unwrap_or_default(),
-        };
-        Ok(tx_response)
+        let tx_result = self
+            .rpc_service
+            .dry_run_tx(tx, trace.unwrap_or(false))
+            .await?;
+        Ok(dry_run_response_view(tx_hash, tx_result))
+    }
+
+    async fn trace_transaction(
+        &self,
+        tx_hash: H256View,
+    ) -> RpcResult<DryRunTransactionResponseView> {
+        let tx_hash = tx_hash.into();
+        let tx_result = self.rpc_service.trace_tx(tx_hash).await?;
+        Ok(dry_run_response_view(tx_hash, tx_result))
     }
 
     async fn execute_view_function(
@@ -841,6 +849,51 @@ impl KanariAPIServer for KanariServer {
         })
     }
 
+    async fn submit_package_source(
+        &self,
+        package_source: SubmitPackageSourceView,
+    ) -> RpcResult<PackageSourceView> {
+        let SubmitPackageSourceView {
+            package_address,
+            package_name,
+            package_digest,
+            source_digest,
+        } = package_source;
+        let package_source = self
+            .rpc_service
+            .submit_package_source(
+                AccountAddress::from(package_address.0),
+                package_name,
+                package_digest.0,
+                source_digest.0,
+            )
+            .await?;
+        Ok(package_source.into())
+    }
+
+    async fn query_package_sources(
+        &self,
+        package_addresses: Vec<KanariAddressView>,
+    ) -> RpcResult<Vec<PackageSourceView>> {
+        if package_addresses.len() > MAX_RESULT_LIMIT_USIZE {
+            return Err(RpcError::UnexpectedError(format!(
+                "Too many package addresses requested. Maximum allowed: {}",
+                MAX_RESULT_LIMIT_USIZE
+            )));
+        }
+        let package_addresses = package_addresses
+            .into_iter()
+            .map(|address| AccountAddress::from(address.0))
+            .collect();
+        Ok(self
+            .rpc_service
+            .query_package_sources(package_addresses)
+            .await?
+            .into_iter()
+            .map(Into::into)
+            .collect())
+    }
+
     async fn repair_indexer(
         &self,
         repair_type: RepairIndexerTypeView,
@@ -909,6 +962,24 @@ impl KanariAPIServer for KanariServer {
         })
     }
 
+    async fn get_state_nodes(
+        &self,
+        node_hashes: Vec<H256View>,
+    ) -> RpcResult<Vec<Option<BytesView>>> {
+        if node_hashes.len() > MAX_RESULT_LIMIT_USIZE {
+            return Err(RpcError::UnexpectedError(format!(
+                "node_hashes size is too large, max is {}",
+                MAX_RESULT_LIMIT_USIZE
+            )));
+        }
+        let node_hashes = node_hashes.into_iter().map(Into::into).collect();
+        let nodes = self.rpc_service.get_state_nodes(node_hashes).await?;
+        Ok(nodes
+            .into_iter()
+            .map(|node| node.map(BytesView::from))
+            .collect())
+    }
+
     async fn status(&self) -> RpcResult<Status> {
         let status = self.rpc_service.status().await?;
         Ok(status)
@@ -980,3 +1051,22 @@ impl KanariRpcModule for KanariServer {
         self.into_rpc()
     }
 }
+
+fn dry_run_response_view(
+    tx_hash: H256,
+    tx_result: DryRunTransactionResult,
+) -> DryRunTransactionResponseView {
+    let raw_output = tx_result.raw_output;
+    let raw_output_view = RawTransactionOutputView {
+        tx_hash: tx_hash.into(),
+        state_root: raw_output.changeset.state_root.into(),
+        status: raw_output.status.into(),
+        gas_used: raw_output.gas_used.into(),
+        is_upgrade: raw_output.is_upgrade,
+    };
+    DryRunTransactionResponseView {
+        raw_output: raw_output_view,
+        vm_error_info: tx_result.vm_error_info.unwrap_or_default(),
+        trace: tx_result.trace.map(Into::into),
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Result, anyhow};
+use kanari_pipeline_processor::proxy::PipelineProcessorProxy;
+use kanari_types::transaction::{ExecuteTransactionResponse, KanariTransaction};
+use metrics::spawn_monitored_task;
+use tokio::sync::{mpsc, oneshot};
+
+type PendingTx = (
+    KanariTransaction,
+    oneshot::Sender<Result<ExecuteTransactionResponse>>,
+);
+
+/// L2TxBatcher collects the L2 transactions submitted concurrently, and sends them to the pipeline processor
+/// as a batch of `batch_size` at most, which is executed by the parallel executor.
+/// The transactions are batched in the submission order, a transaction does not wait for a full batch.
+#[derive(Clone)]
+pub struct L2TxBatcher {
+    sender: mpsc::UnboundedSender<PendingTx>,
+}
+
+impl L2TxBatcher {
+    pub fn new(pipeline_processor: PipelineProcessorProxy, batch_size: usize) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<PendingTx>();
+        spawn_monitored_task!(async move {
+            while let Some(pending_tx) = receiver.recv().await {
+                let mut batch = vec![pending_tx];
+                while batch.len() < batch_size {
+                    match receiver.try_recv() {
+                        Ok(pending_tx) => batch.push(pending_tx),
+                        Err(_) => break,
+                    }
+                }
+                let (txs, responders): (Vec<_>, Vec<_>) = batch.into_iter().unzip();
+                match pipeline_processor.execute_l2_tx_batch(txs).await {
+                    Ok(results) => {
+                        for (responder, result) in responders.into_iter().zip(results) {
+                            let _ = responder.send(result);
+                        }
+                    }
+                    Err(err) => {
+                        for responder in responders {
+                            let _ =
+                                responder.send(Err(anyhow!("Execute L2 tx batch error: {}", err)));
+                        }
+                    }
+                }
+            }
+        });
+        Self { sender }
+    }
+
+    pub async fn execute_l2_tx(&self, tx: KanariTransaction) -> Result<ExecuteTransactionResponse> {
+        let (responder, receiver) = oneshot::channel();
+        self.sender
+            .send((tx, responder))
+            .map_err(|_| anyhow!("The L2 tx batcher is stopped"))?;
+        receiver
+            .await
+            .map_err(|_| anyhow!("The L2 tx batcher dropped the tx"))?
+    }
+}
@@ -4,6 +4,7 @@
 pub mod aggregate_service;
 // pub mod rpc_logger;
 pub mod error;
+pub mod l2_tx_batcher;
 pub mod rpc_service;
 
 pub mod routing;
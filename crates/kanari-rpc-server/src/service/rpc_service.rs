@@ -1,6 +1,7 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::service::l2_tx_batcher::L2TxBatcher;
 use anyhow::{Result, bail, format_err};
 use bitcoin_client::proxy::BitcoinClientProxy;
 use bitcoincore_rpc::bitcoin::Txid;
@@ -13,6 +14,9 @@ use kanari_executor::proxy::ExecutorProxy;
 use kanari_indexer::proxy::IndexerProxy;
 use kanari_notify::subscription_handler::SubscriptionHandler;
 use kanari_pipeline_processor::proxy::PipelineProcessorProxy;
+use kanari_rpc_api::jsonrpc_types::btc::pending_block::{
+    PendingBlockView, PendingBlocksView, StateConfirmationView, TxConfirmationView,
+};
 use kanari_rpc_api::jsonrpc_types::event_view::EventFilterView;
 use kanari_rpc_api::jsonrpc_types::field_view::IndexerFieldView;
 use kanari_rpc_api::jsonrpc_types::transaction_view::TransactionFilterView;
@@ -22,21 +26,27 @@ use kanari_rpc_api::jsonrpc_types::{
 use kanari_sequencer::proxy::SequencerProxy;
 use kanari_types::address::{BitcoinAddress, KanariAddress};
 use kanari_types::bitcoin::BitcoinModule;
-use kanari_types::bitcoin::pending_block::PendingBlockModule;
+use kanari_types::bitcoin::ord::Inscription;
+use kanari_types::bitcoin::pending_block::{PendingBlock, PendingBlockModule, PendingStore};
+use kanari_types::bitcoin::types::BlockHeightHash;
+use kanari_types::bitcoin::utxo::UTXO;
 use kanari_types::framework::address_mapping::KanariToBitcoinAddressMapping;
 use kanari_types::indexer::event::{
     AnnotatedIndexerEvent, EventFilter, IndexerEvent, IndexerEventID,
 };
 use kanari_types::indexer::field::{FieldFilter, IndexerField};
+use kanari_types::indexer::package_source::IndexerPackageSource;
 use kanari_types::indexer::state::{
     INSCRIPTION_TYPE_TAG, IndexerObjectState, IndexerStateID, ObjectStateFilter, ObjectStateType,
     UTXO_TYPE_TAG,
 };
 use kanari_types::indexer::transaction::{IndexerTransaction, TransactionFilter};
+use kanari_types::into_address::{FromAddress, IntoAddress};
 use kanari_types::repair::{RepairIndexerParams, RepairIndexerType};
 use kanari_types::state::{StateChangeSetWithTxOrder, SyncStateFilter};
 use kanari_types::transaction::{
     ExecuteTransactionResponse, KanariTransaction, KanariTransactionData, LedgerTransaction,
+    LedgerTxData,
 };
 use metrics::spawn_monitored_task;
 use move_core_types::account_address::AccountAddress;
@@ -48,13 +58,15 @@ use moveos_types::module_binding::MoveFunctionCaller;
 use moveos_types::move_types::type_tag_match;
 use moveos_types::moveos_std::display::{RawDisplay, get_object_display_id};
 use moveos_types::moveos_std::event::{AnnotatedEvent, Event, EventID};
-use moveos_types::moveos_std::object::{MAX_OBJECT_IDS_PER_QUERY, ObjectID};
-use moveos_types::state::{AnnotatedState, FieldKey, ObjectState, StateChangeSet};
+use moveos_types::moveos_std::module_store::PackageInfo;
+use moveos_types::moveos_std::object::{MAX_OBJECT_IDS_PER_QUERY, ObjectID, ObjectMeta};
+use moveos_types::state::{AnnotatedState, FieldKey, MoveStructType, ObjectState, StateChangeSet};
 use moveos_types::state_resolver::{AnnotatedStateKV, StateKV};
 use moveos_types::transaction::{FunctionCall, TransactionExecutionInfo};
 use serde::Serialize;
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 
 pub fn spawn_subscription<S, T>(
@@ -114,6 +126,8 @@ pub struct RpcService {
     // pub(crate) notify: NotifyProxy,
     pub(crate) subscription_handler: Arc<SubscriptionHandler>,
     pub(crate) subscription_semaphore: Arc<Semaphore>,
+    pub(crate) l2_tx_batcher: Option<L2TxBatcher>,
+    pub(crate) accept_package_sources: bool,
 }
 
 impl RpcService {
@@ -128,8 +142,13 @@ impl RpcService {
         da_server: DAServerProxy,
         subscription_handler: Arc<SubscriptionHandler>,
         max_subscriptions: Option<usize>,
+        parallel_batch_size: Option<usize>,
+        accept_package_sources: bool,
     ) -> Self {
         let max_subscriptions = max_subscriptions.unwrap_or(DEFAULT_MAX_SUBSCRIPTIONS);
+        let l2_tx_batcher = parallel_batch_size
+            .filter(|batch_size| *batch_size > 1)
+            .map(|batch_size| L2TxBatcher::new(pipeline_processor.clone(), batch_size));
         Self {
             chain_id,
             bitcoin_network,
@@ -141,6 +160,8 @@ impl RpcService {
             da_server,
             subscription_handler,
             subscription_semaphore: Arc::new(Semaphore::new(max_subscriptions)),
+            l2_tx_batcher,
+            accept_package_sources,
         }
     }
 }
@@ -161,12 +182,62 @@ impl RpcService {
     }
 
     pub async fn execute_tx(&self, tx: KanariTransaction) -> Result<ExecuteTransactionResponse> {
-        self.pipeline_processor.execute_l2_tx(tx).await
+        match &self.l2_tx_batcher {
+            Some(l2_tx_batcher) => l2_tx_batcher.execute_l2_tx(tx).await,
+            None => self.pipeline_processor.execute_l2_tx(tx).await,
+        }
     }
 
-    pub async fn dry_run_tx(&self, tx: KanariTransactionData) -> Result<DryRunTransactionResult> {
+    pub async fn dry_run_tx(
+        &self,
+        tx: KanariTransactionData,
+        trace: bool,
+    ) -> Result<DryRunTransactionResult> {
         let verified_tx = self.executor.convert_to_verified_tx(tx).await?;
-        self.executor.dry_run_transaction(verified_tx).await
+        self.executor.dry_run_transaction(verified_tx, trace).await
+    }
+
+    /// Replay the executed L2 transaction at the root after its previous transaction, with the tracer.
+    /// Returns an error if the replay diverges from the execution.
+    pub async fn trace_tx(&self, tx_hash: H256) -> Result<DryRunTransactionResult> {
+        let ledger_tx = self
+            .get_transaction_by_hash(tx_hash)
+            .await?
+            .ok_or_else(|| format_err!("Transaction {:?} not found", tx_hash))?;
+        let tx = match ledger_tx.data {
+            LedgerTxData::L2Tx(tx) => tx,
+            _ => bail!(
+                "Only the L2 transaction can be traced, {:?} is not",
+                tx_hash
+            ),
+        };
+        let tx_order = ledger_tx.sequence_info.tx_order;
+        if tx_order == 0 {
+            bail!("The genesis transaction can not be traced");
+        }
+        let pre_tx_hash = self
+            .get_tx_hashes(vec![tx_order - 1])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| format_err!("Transaction of order {} not found", tx_order - 1))?;
+        let pre_execution_info = self
+            .get_transaction_execution_infos_by_hash(vec![pre_tx_hash])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| format_err!("Execution info of {:?} not found", pre_tx_hash))?;
+        let execution_info = self
+            .get_transaction_execution_infos_by_hash(vec![tx_hash])
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| format_err!("Execution info of {:?} not found", tx_hash))?;
+        let root =
+            ObjectMeta::root_metadata(pre_execution_info.state_root, pre_execution_info.size);
+        self.executor
+            .trace_l2_tx(tx, ledger_tx.sequence_info, root, execution_info)
+            .await
     }
 
     pub async fn execute_view_function(
@@ -807,6 +878,10 @@ impl RpcService {
         }
     }
 
+    pub async fn get_state_nodes(&self, node_hashes: Vec<H256>) -> Result<Vec<Option<Vec<u8>>>> {
+        self.executor.get_state_nodes(node_hashes).await
+    }
+
     pub async fn sync_states(
         &self,
         tx_orders: Vec<u64>,
@@ -894,6 +969,137 @@ impl RpcService {
         })
     }
 
+    async fn get_pending_store(&self) -> Result<PendingStore> {
+        self.get_states(AccessPath::object(PendingStore::object_id()), None)
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| format_err!("The PendingStore object is not found"))?
+            .value_as::<PendingStore>()
+    }
+
+    pub async fn get_pending_blocks(&self) -> Result<PendingBlocksView> {
+        let pending_store = self.get_pending_store().await?;
+        let best_block_height = pending_store.best_block_height().unwrap_or_default();
+
+        let block_ids = pending_store
+            .pending_blocks
+            .values()
+            .into_iter()
+            .map(|block_hash| PendingBlock::object_id(*block_hash))
+            .collect::<Vec<_>>();
+        let mut blocks = if block_ids.is_empty() {
+            vec![]
+        } else {
+            self.get_states(AccessPath::objects(block_ids), None)
+                .await?
+                .into_iter()
+                .flatten()
+                .map(|state| {
+                    Ok(PendingBlockView::new(
+                        state.value_as::<PendingBlock>()?,
+                        best_block_height,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+        blocks.sort_by_key(|block| block.block_height.0);
+
+        let best_block: Option<BlockHeightHash> = pending_store.best_block.into();
+        Ok(PendingBlocksView {
+            best_block: best_block.map(Into::into),
+            reorg_block_count: pending_store.reorg_block_count.into(),
+            blocks,
+        })
+    }
+
+    pub async fn get_state_confirmations(
+        &self,
+        object_ids: Vec<ObjectID>,
+    ) -> Result<Vec<Option<StateConfirmationView>>> {
+        let states = self
+            .get_states(AccessPath::objects(object_ids), None)
+            .await?;
+
+        let pending_store = self.get_pending_store().await?;
+        let bitcoin_module = self.executor.as_module_binding::<BitcoinModule>();
+
+        states
+            .into_iter()
+            .map(|state_opt| {
+                state_opt
+                    .map(|state| {
+                        let txid = if state.match_struct_type(&UTXO::struct_tag()) {
+                            state.value_as::<UTXO>()?.txid
+                        } else if state.match_struct_type(&Inscription::struct_tag()) {
+                            state.value_as::<Inscription>()?.location.outpoint.txid
+                        } else {
+                            bail!(
+                                "The object {} is not a UTXO or Inscription",
+                                state.metadata.id
+                            )
+                        };
+                        // The states are only created by the processed txs
+                        let confirmation = bitcoin_module
+                            .get_tx_height(txid)?
+                            .map(|height| pending_store.tx_confirmation(height, false));
+                        Ok(StateConfirmationView {
+                            object_id: state.metadata.id.clone().into(),
+                            txid: Txid::from_address(txid).to_string(),
+                            block_height: confirmation.map(|c| c.block_height.into()),
+                            confirmations: confirmation.map(|c| c.confirmations.into()),
+                            // The tx without height is imported from the genesis, it can not be reorged
+                            finalized: confirmation.is_none_or(|c| c.finalized),
+                        })
+                    })
+                    .transpose()
+            })
+            .collect()
+    }
+
+    pub async fn get_tx_confirmations(
+        &self,
+        txids: Vec<Txid>,
+    ) -> Result<Vec<Option<TxConfirmationView>>> {
+        let pending_store = self.get_pending_store().await?;
+        let bitcoin_module = self.executor.as_module_binding::<BitcoinModule>();
+        let tx_keys = txids
+            .iter()
+            .map(|txid| PendingBlock::tx_field_key(txid.into_address()))
+            .collect::<Vec<_>>();
+
+        // The unprocessed txs are kept in the pending blocks, which are in the reorg-able range
+        let mut pending_tx_heights = vec![None; txids.len()];
+        for block in &pending_store.pending_blocks.data {
+            let fields = self
+                .get_states(
+                    AccessPath::fields(PendingBlock::object_id(block.value), tx_keys.clone()),
+                    None,
+                )
+                .await?;
+            for (height, field) in pending_tx_heights.iter_mut().zip(fields) {
+                if field.is_some() {
+                    *height = Some(block.key);
+                }
+            }
+        }
+
+        txids
+            .into_iter()
+            .zip(pending_tx_heights)
+            .map(|(txid, pending_height)| {
+                let confirmation = match pending_height {
+                    Some(height) => Some(pending_store.tx_confirmation(height, true)),
+                    None => bitcoin_module
+                        .get_tx_height(txid.into_address())?
+                        .map(|height| pending_store.tx_confirmation(height, false)),
+                };
+                Ok(confirmation
+                    .map(|confirmation| TxConfirmationView::new(txid.into(), confirmation)))
+            })
+            .collect()
+    }
+
     pub async fn query_fields(
         &self,
         filter: FieldFilter,
@@ -967,6 +1173,70 @@ impl RpcService {
         Ok((fields, result))
     }
 
+    /// Get the digest of the package recorded by the module store, None if the package is not published
+    /// or is a system package, whose digest is not recorded.
+    pub async fn get_package_digest(
+        &self,
+        package_address: AccountAddress,
+    ) -> Result<Option<H256>> {
+        let package_info_id = PackageInfo::package_info_id(package_address);
+        let package_info = self
+            .get_states(AccessPath::object(package_info_id), None)
+            .await?
+            .pop()
+            .flatten()
+            .map(|state| state.value_as::<PackageInfo>())
+            .transpose()?;
+        Ok(package_info.map(|info| H256::from_slice(&info.digest)))
+    }
+
+    /// Record the source digest of a package in the indexer after its package digest is checked
+    /// against the package published on chain. The source digest is recorded as submitted, so the
+    /// submission is only accepted if the node is started with `--accept-package-sources`,
+    /// and a later submission replaces the record of the package.
+    pub async fn submit_package_source(
+        &self,
+        package_address: AccountAddress,
+        package_name: String,
+        package_digest: H256,
+        source_digest: H256,
+    ) -> Result<IndexerPackageSource> {
+        if !self.accept_package_sources {
+            bail!("The package source submission is disabled on this node");
+        }
+        let onchain_digest = self
+            .get_package_digest(package_address)
+            .await?
+            .ok_or_else(|| format_err!("The digest of package {} is not found", package_address))?;
+        if onchain_digest != package_digest {
+            bail!(
+                "The package digest {:?} does not match the digest {:?} of the package published at {}",
+                package_digest,
+                onchain_digest,
+                package_address
+            );
+        }
+        let submitted_at = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis() as u64;
+        let package_source = IndexerPackageSource::new(
+            package_address,
+            package_name,
+            package_digest,
+            source_digest,
+            submitted_at,
+        );
+        self.indexer
+            .persist_or_update_package_sources(vec![package_source.clone()])
+            .await?;
+        Ok(package_source)
+    }
+
+    pub async fn query_package_sources(
+        &self,
+        package_addresses: Vec<AccountAddress>,
+    ) -> Result<Vec<IndexerPackageSource>> {
+        self.indexer.query_package_sources(package_addresses).await
+    }
+
     fn acquire_subscribe_permit(&self) -> anyhow::Result<OwnedSemaphorePermit> {
         match self.subscription_semaphore.clone().try_acquire_owned() {
             Ok(p) => Ok(p),
@@ -11,6 +11,7 @@ use accumulator::{AccumulatorNode, AccumulatorTreeStore};
 use anyhow::Result;
 use kanari_types::da::batch::{BlockRange, BlockSubmitState};
 use kanari_types::sequencer::SequencerInfo;
+use kanari_types::service_status::ServiceStatus;
 use kanari_types::transaction::LedgerTransaction;
 use moveos_common::utils::to_bytes;
 use moveos_config::DataDirPath;
@@ -330,6 +331,14 @@ impl MetaStore for KanariStore {
     fn remove_sequencer_info(&self) -> Result<()> {
         self.get_meta_store().remove_sequence_info()
     }
+
+    fn get_service_status(&self) -> Result<Option<ServiceStatus>> {
+        self.get_meta_store().get_service_status()
+    }
+
+    fn save_service_status(&self, service_status: ServiceStatus) -> Result<()> {
+        self.get_meta_store().save_service_status(service_status)
+    }
 }
 
 impl StateStore for KanariStore {
@@ -4,10 +4,12 @@
 use crate::META_SEQUENCER_INFO_COLUMN_FAMILY_NAME;
 use anyhow::Result;
 use kanari_types::sequencer::SequencerInfo;
+use kanari_types::service_status::ServiceStatus;
 use raw_store::{CodecKVStore, StoreInstance, derive_store};
 use std::string::ToString;
 
 pub const SEQUENCER_INFO_KEY: &str = "sequencer_info";
+pub const SERVICE_STATUS_KEY: &str = "service_status";
 
 derive_store!(
     SequencerInfoStore,
@@ -16,22 +18,36 @@ derive_store!(
     META_SEQUENCER_INFO_COLUMN_FAMILY_NAME
 );
 
+// The initial service status declared by the genesis spec, shares the column family with the sequencer info.
+derive_store!(
+    ServiceStatusStore,
+    String,
+    ServiceStatus,
+    META_SEQUENCER_INFO_COLUMN_FAMILY_NAME
+);
+
 pub trait MetaStore {
     fn get_sequencer_info(&self) -> Result<Option<SequencerInfo>>;
 
     fn save_sequencer_info(&self, sequencer_info: SequencerInfo) -> Result<()>;
     fn remove_sequencer_info(&self) -> Result<()>;
+
+    fn get_service_status(&self) -> Result<Option<ServiceStatus>>;
+
+    fn save_service_status(&self, service_status: ServiceStatus) -> Result<()>;
 }
 
 #[derive(Clone)]
 pub struct MetaDBStore {
     sequencer_info_store: SequencerInfoStore,
+    service_status_store: ServiceStatusStore,
 }
 
 impl MetaDBStore {
     pub fn new(instance: StoreInstance) -> Self {
         MetaDBStore {
-            sequencer_info_store: SequencerInfoStore::new(instance),
+            sequencer_info_store: SequencerInfoStore::new(instance.clone()),
+            service_status_store: ServiceStatusStore::new(instance),
         }
     }
 
@@ -66,4 +82,14 @@ impl MetaDBStore {
         self.sequencer_info_store
             .put_sync(SEQUENCER_INFO_KEY.to_string(), sequencer_info)
     }
+
+    pub fn get_service_status(&self) -> Result<Option<ServiceStatus>> {
+        self.service_status_store
+            .kv_get(SERVICE_STATUS_KEY.to_string())
+    }
+
+    pub fn save_service_status(&self, service_status: ServiceStatus) -> Result<()> {
+        self.service_status_store
+            .put_sync(SERVICE_STATUS_KEY.to_string(), service_status)
+    }
 }
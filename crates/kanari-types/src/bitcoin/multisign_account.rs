@@ -155,6 +155,47 @@ fn create_multisig_script(threshold: usize, public_keys: &Vec<XOnlyPublicKey>) -
     builder.into_script()
 }
 
+/// Parse the threshold and the public keys of a multisig script created by `create_multisig_script`,
+/// so the multisign account can be recovered from a PSBT without querying the chain
+pub fn parse_multisig_script(script: &bitcoin::Script) -> Result<(usize, Vec<XOnlyPublicKey>)> {
+    let instructions = script
+        .instructions()
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    ensure!(
+        instructions.len() >= 4 && instructions.len() % 2 == 0,
+        "Invalid multisig script: {}",
+        script
+    );
+    let (key_instructions, threshold_instructions) = instructions.split_at(instructions.len() - 2);
+
+    let mut public_keys = Vec::with_capacity(key_instructions.len() / 2);
+    for (idx, pair) in key_instructions.chunks(2).enumerate() {
+        let expected_opcode = if idx == 0 {
+            bitcoin::opcodes::all::OP_CHECKSIG
+        } else {
+            bitcoin::opcodes::all::OP_CHECKSIGADD
+        };
+        let public_key = pair[0]
+            .push_bytes()
+            .filter(|_| pair[1].opcode() == Some(expected_opcode))
+            .ok_or_else(|| anyhow::anyhow!("Invalid multisig script: {}", script))?;
+        public_keys.push(XOnlyPublicKey::from_slice(public_key.as_bytes())?);
+    }
+
+    let threshold = threshold_instructions[0]
+        .script_num()
+        .filter(|_| {
+            threshold_instructions[1].opcode() == Some(bitcoin::opcodes::all::OP_GREATERTHANOREQUAL)
+        })
+        .ok_or_else(|| anyhow::anyhow!("Invalid multisig script: {}", script))?;
+    ensure!(
+        threshold > 0 && threshold as usize <= public_keys.len(),
+        "Invalid threshold: {}",
+        threshold
+    );
+    Ok((threshold as usize, public_keys))
+}
+
 pub fn update_multisig_psbt(
     psbt_input: &mut bitcoin::psbt::Input,
     account_info: &MultisignAccountInfo,
@@ -439,9 +480,38 @@ mod tests {
             let script = create_multisig_script(threshold, &pubkeys);
             //println!("script: {:?}", script.to_hex_string());
             assert_eq!(script, expected_script);
+            assert_eq!(
+                parse_multisig_script(&script).unwrap(),
+                (threshold, pubkeys)
+            );
         }
     }
 
+    #[test]
+    fn test_parse_multisig_script() {
+        let pubkeys = (0..20)
+            .map(|_| {
+                KanariKeyPair::generate_secp256k1()
+                    .public()
+                    .xonly_public_key()
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        for threshold in [1, 2, 16, 17, 20] {
+            let script = create_multisig_script(threshold, &pubkeys);
+            assert_eq!(
+                parse_multisig_script(&script).unwrap(),
+                (threshold, pubkeys.clone())
+            );
+        }
+
+        let single_key_script = bitcoin::script::Builder::new()
+            .push_x_only_key(&pubkeys[0])
+            .push_opcode(bitcoin::opcodes::all::OP_CHECKSIG)
+            .into_script();
+        assert!(parse_multisig_script(&single_key_script).is_err());
+    }
+
     #[test]
     fn test_multisign_bitcoin_address_from_less_than_eight_pubkeys() {
         let cases = vec![(
@@ -1,16 +1,19 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
-use super::types::BlockHeightHash;
+use super::types::{BlockHeightHash, Header};
 use crate::addresses::BITCOIN_MOVE_ADDRESS;
 use anyhow::Result;
 use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
 use moveos_types::{
     module_binding::{ModuleBinding, MoveFunctionCaller},
     move_std::option::MoveOption,
-    moveos_std::tx_context::TxContext,
-    state::MoveStructState,
-    state::MoveStructType,
+    moveos_std::{
+        object::{self, ObjectID},
+        simple_map::SimpleMap,
+        tx_context::TxContext,
+    },
+    state::{FieldKey, MoveState, MoveStructState, MoveStructType},
 };
 use serde::{Deserialize, Serialize};
 
@@ -39,6 +42,119 @@ impl MoveStructState for PendingTxs {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingStore {
+    /// block_height -> block_hash
+    pub pending_blocks: SimpleMap<u64, AccountAddress>,
+    /// The best block height and hash
+    pub best_block: MoveOption<BlockHeightHash>,
+    /// How many blocks we should pending for reorg
+    pub reorg_block_count: u64,
+}
+
+impl PendingStore {
+    pub fn object_id() -> ObjectID {
+        object::named_object_id(&Self::struct_tag())
+    }
+
+    /// The best block height the relayer has submitted
+    pub fn best_block_height(&self) -> Option<u64> {
+        self.best_block.as_ref().map(|block| block.block_height)
+    }
+
+    /// Calculate the confirmation of a tx in the block at `block_height` from the relayer's best block.
+    /// `pending` means the tx is still in a pending block and has not been processed.
+    pub fn tx_confirmation(&self, block_height: u64, pending: bool) -> TxConfirmation {
+        let confirmations = self
+            .best_block_height()
+            .map(|best_block_height| confirmations(best_block_height, block_height))
+            .unwrap_or_default();
+        TxConfirmation {
+            block_height,
+            confirmations,
+            pending,
+            finalized: !pending && confirmations > self.reorg_block_count,
+        }
+    }
+}
+
+/// The confirmation depth of a Bitcoin tx on Kanari
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TxConfirmation {
+    pub block_height: u64,
+    /// How many blocks on top of the tx's block, the block itself is counted
+    pub confirmations: u64,
+    /// The tx is in a pending block, its block can still be reorged and its states are not created
+    pub pending: bool,
+    /// The states of the tx can not be reverted by a Bitcoin reorg
+    pub finalized: bool,
+}
+
+/// Calculate the confirmation depth of the block at `block_height`
+pub fn confirmations(best_block_height: u64, block_height: u64) -> u64 {
+    if best_block_height < block_height {
+        0
+    } else {
+        best_block_height - block_height + 1
+    }
+}
+
+impl MoveStructType for PendingStore {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("PendingStore");
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+}
+
+impl MoveStructState for PendingStore {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            SimpleMap::<u64, AccountAddress>::type_layout(),
+            MoveOption::<BlockHeightHash>::type_layout(),
+            u64::type_layout(),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PendingBlock {
+    pub block_height: u64,
+    pub block_hash: AccountAddress,
+    pub header: Header,
+    pub processed_tx: u64,
+    pub next_block_hash: MoveOption<AccountAddress>,
+}
+
+impl PendingBlock {
+    /// The pending block object id, same as `pending_block::pending_block_obj_id` in Move
+    pub fn object_id(block_hash: AccountAddress) -> ObjectID {
+        object::custom_object_id(&block_hash, &Self::struct_tag())
+    }
+
+    /// The unprocessed txs are the fields of the pending block keyed by txid,
+    /// a tx field is removed when the tx is processed
+    pub fn tx_field_key(txid: AccountAddress) -> FieldKey {
+        FieldKey::derive_from_address(&txid)
+    }
+}
+
+impl MoveStructType for PendingBlock {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("PendingBlock");
+    const ADDRESS: AccountAddress = BITCOIN_MOVE_ADDRESS;
+}
+
+impl MoveStructState for PendingBlock {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            u64::type_layout(),
+            AccountAddress::type_layout(),
+            Header::type_layout(),
+            u64::type_layout(),
+            MoveOption::<AccountAddress>::type_layout(),
+        ])
+    }
+}
+
 /// Rust bindings for BitcoinMove bitcoin module
 pub struct PendingBlockModule<'a> {
     caller: &'a dyn MoveFunctionCaller,
@@ -110,3 +226,37 @@ impl<'a> ModuleBinding<'a> for PendingBlockModule<'a> {
         Self { caller }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tx_confirmation() {
+        assert_eq!(confirmations(100, 101), 0);
+        assert_eq!(confirmations(100, 100), 1);
+        assert_eq!(confirmations(100, 90), 11);
+
+        let store = PendingStore {
+            pending_blocks: SimpleMap::create(),
+            best_block: MoveOption::some(BlockHeightHash {
+                block_height: 100,
+                block_hash: AccountAddress::ZERO,
+            }),
+            reorg_block_count: 3,
+        };
+        let pending = store.tx_confirmation(98, true);
+        assert_eq!(pending.confirmations, 3);
+        assert!(!pending.finalized);
+        // A pending tx is not finalized even if its block is ready to be processed
+        assert!(!store.tx_confirmation(97, true).finalized);
+        assert!(store.tx_confirmation(97, false).finalized);
+        assert!(!store.tx_confirmation(98, false).finalized);
+
+        let empty_store = PendingStore {
+            best_block: MoveOption::none(),
+            ..store
+        };
+        assert_eq!(empty_store.tx_confirmation(1, true).confirmations, 0);
+    }
+}
@@ -12,6 +12,7 @@ use crate::{
 };
 use anyhow::{anyhow, bail};
 use bech32::{Bech32, EncodeError, encode};
+use bitcoin::key::TapTweak;
 use bitcoin::secp256k1::SecretKey;
 use derive_more::{AsRef, From};
 pub use enum_dispatch::enum_dispatch;
@@ -182,6 +183,26 @@ impl KanariKeyPair {
         }
     }
 
+    /// Get the secp256k1 keypair tweaked for the Taproot key path spending without script tree
+    pub fn taproot_tweaked_keypair(&self) -> Option<bitcoin::key::TweakedKeypair> {
+        self.secp256k1_keypair().map(|keypair| {
+            keypair.tap_tweak(&bitcoin::secp256k1::Secp256k1::verification_only(), None)
+        })
+    }
+
+    /// Sign the 32 bytes digest with the Taproot tweaked key, return the BIP-340 Schnorr signature
+    pub fn sign_taproot(
+        &self,
+        digest: &[u8],
+    ) -> Result<bitcoin::secp256k1::schnorr::Signature, anyhow::Error> {
+        let Some(tweaked_keypair) = self.taproot_tweaked_keypair() else {
+            bail!("Only secp256k1 key pair can sign with the taproot tweaked key")
+        };
+        let message = bitcoin::secp256k1::Message::from_digest_slice(digest)?;
+        let secp = bitcoin::secp256k1::Secp256k1::signing_only();
+        Ok(secp.sign_schnorr(&message, &tweaked_keypair.to_inner()))
+    }
+
     /// Authentication key is the hash of the public key
     pub fn authentication_key(&self) -> AuthenticationKey {
         self.public().authentication_key()
@@ -461,6 +482,13 @@ impl PublicKey {
         }
     }
 
+    /// The x-only output key of the P2TR address of the public key, it verifies the Taproot key path signature
+    pub fn taproot_output_key(&self) -> Result<bitcoin::XOnlyPublicKey, anyhow::Error> {
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        let (output_key, _parity) = self.xonly_public_key()?.tap_tweak(&secp, None);
+        Ok(output_key.to_inner())
+    }
+
     pub fn to_hex(&self) -> String {
         hex::encode(self.as_ref())
     }
@@ -1109,6 +1137,30 @@ mod tests {
         );
     }
 
+    // the Taproot output key is the witness program of the P2TR address of the public key
+    #[test]
+    fn test_taproot_sign() {
+        let private_key = Secp256k1PrivateKey::from_bytes(&[0xcd; 32]).unwrap();
+        let kanari_keypair = KanariKeyPair::Secp256k1(Secp256k1KeyPair::from(private_key));
+        let output_key = kanari_keypair.public().taproot_output_key().unwrap();
+        assert_eq!(
+            hex::encode(output_key.serialize()),
+            "cc09f9512db9acbc4ff1aaca3287f97f67d581d7eceeb85e3180839b82e85f50"
+        );
+
+        let digest = [0x01; 32];
+        let signature = kanari_keypair.sign_taproot(&digest).unwrap();
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        let message = bitcoin::secp256k1::Message::from_digest(digest);
+        assert!(
+            secp.verify_schnorr(&signature, &message, &output_key)
+                .is_ok()
+        );
+
+        let ed25519_keypair = KanariKeyPair::generate_ed25519();
+        assert!(ed25519_keypair.sign_taproot(&digest).is_err());
+    }
+
     // this test is to ensure that the ECDSA recoverable algorithm works for Ethereum public key to address
     #[test]
     fn test_ethereum_public_key_to_address() {
@@ -8,6 +8,7 @@ use crate::{
 use anyhow::{Result, ensure};
 use bitcoin::consensus::{Decodable, Encodable};
 use bitcoin::io::{Read, Write};
+use bitcoin::key::TapTweak;
 
 use fastcrypto::{
     hash::Sha256,
@@ -180,6 +181,23 @@ impl AuthPayload {
         }
     }
 
+    /// The payload of the Taproot key path signature, the public key is the x-only internal key
+    pub fn new_taproot(
+        sign_data: SignData,
+        signature: bitcoin::secp256k1::schnorr::Signature,
+        internal_key: bitcoin::XOnlyPublicKey,
+        bitcoin_address: String,
+    ) -> Self {
+        let message_info = sign_data.message_info_without_tx_hash();
+        AuthPayload {
+            signature: signature.serialize().to_vec(),
+            message_prefix: sign_data.message_prefix,
+            message_info,
+            public_key: internal_key.serialize().to_vec(),
+            from_address: bitcoin_address.into_bytes(),
+        }
+    }
+
     pub fn verify(&self, tx_data: &KanariTransactionData) -> Result<()> {
         let pk = Secp256k1PublicKey::from_bytes(&self.public_key)?;
         let sign_data = SignData::new(
@@ -205,6 +223,23 @@ impl AuthPayload {
         Ok(())
     }
 
+    /// Verify the Taproot key path signature against the output key tweaked from the internal key
+    pub fn verify_taproot(&self, tx_data: &KanariTransactionData) -> Result<()> {
+        let internal_key = bitcoin::XOnlyPublicKey::from_slice(&self.public_key)?;
+        let sign_data = SignData::new(
+            self.message_prefix.clone(),
+            self.message_info.clone(),
+            tx_data,
+        );
+        let digest = sha2_256_of(sign_data.data_hash().as_bytes());
+        let secp = bitcoin::secp256k1::Secp256k1::verification_only();
+        let (output_key, _parity) = internal_key.tap_tweak(&secp, None);
+        let signature = bitcoin::secp256k1::schnorr::Signature::from_slice(&self.signature)?;
+        let message = bitcoin::secp256k1::Message::from_digest(digest.0);
+        secp.verify_schnorr(&signature, &message, &output_key.to_inner())?;
+        Ok(())
+    }
+
     pub fn from_address(&self) -> Result<String> {
         Ok(String::from_utf8(self.from_address.to_vec())?)
     }
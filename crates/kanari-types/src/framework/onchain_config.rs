@@ -44,3 +44,29 @@ impl OnchainConfig {
         object::named_object_id(&Self::struct_tag())
     }
 }
+
+/// ConfigUpdateCap is the capability for admin operations, the owner of it is the admin.
+#[derive(Clone, Debug, Deserialize, Serialize, Eq, PartialEq, Default)]
+pub struct ConfigUpdateCap {
+    // Move VM will auto add a bool field to the empty struct
+    // So we manually add a bool field to the struct
+    _placeholder: bool,
+}
+
+impl MoveStructType for ConfigUpdateCap {
+    const ADDRESS: AccountAddress = KANARI_FRAMEWORK_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("ConfigUpdateCap");
+}
+
+impl MoveStructState for ConfigUpdateCap {
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout::new(vec![MoveTypeLayout::Bool])
+    }
+}
+
+impl ConfigUpdateCap {
+    pub fn config_update_cap_object_id() -> ObjectID {
+        object::named_object_id(&Self::struct_tag())
+    }
+}
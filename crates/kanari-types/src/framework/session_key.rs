@@ -3,9 +3,11 @@
 
 use crate::addresses::KANARI_FRAMEWORK_ADDRESS;
 use crate::authentication_key::AuthenticationKey;
+use crate::crypto::SignatureScheme;
 use anyhow::Result;
 use move_core_types::value::MoveValue;
 use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::h256::sha2_256_of;
 use moveos_types::moveos_std::object::ObjectID;
 use moveos_types::{
     module_binding::{ModuleBinding, MoveFunctionCaller},
@@ -25,6 +27,14 @@ use std::str::FromStr;
 
 pub const MODULE_NAME: &IdentStr = ident_str!("session_key");
 
+/// Same as `session_key::secp256r1_public_key_to_authentication_key` in Move,
+/// the authentication key is `scheme || sha256(public_key)`.
+pub fn secp256r1_public_key_to_authentication_key(public_key: &[u8]) -> AuthenticationKey {
+    let mut authentication_key = vec![SignatureScheme::EcdsaR1.flag()];
+    authentication_key.extend_from_slice(sha2_256_of(public_key).as_bytes());
+    AuthenticationKey::new(authentication_key)
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq, Hash)]
 pub struct SessionScope {
     pub module_address: AccountAddress,
@@ -138,14 +148,29 @@ impl SessionKey {
         AuthenticationKey::new(self.authentication_key.clone())
     }
     pub fn is_expired(&self) -> bool {
-        if self.max_inactive_interval == 0 {
-            return false;
-        }
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .expect("should get system time")
             .as_secs();
-        now - self.last_active_time > self.max_inactive_interval
+        self.is_expired_at(now)
+    }
+
+    /// The time in seconds after which the session key expires if it is not active,
+    /// `None` if the session key never expires.
+    pub fn expiration_time(&self) -> Option<u64> {
+        if self.max_inactive_interval == 0 {
+            return None;
+        }
+        Some(
+            self.last_active_time
+                .saturating_add(self.max_inactive_interval),
+        )
+    }
+
+    /// Same as `session_key::is_expired` in Move, `now_seconds` is the current timestamp in seconds.
+    pub fn is_expired_at(&self, now_seconds: u64) -> bool {
+        self.expiration_time()
+            .is_some_and(|expiration_time| expiration_time < now_seconds)
     }
 
     pub fn is_scope_match(&self, function: &FunctionCall) -> bool {
@@ -159,6 +184,8 @@ impl SessionKey {
             MoveAction::Script(_) => false,
             MoveAction::ModuleBundle(_) => false,
             MoveAction::Function(function) => self.is_scope_match(function),
+            // The session key can only be used to execute a single function call
+            MoveAction::Batch(_) => false,
         }
     }
 }
@@ -183,6 +210,25 @@ impl MoveStructState for SessionKey {
     }
 }
 
+/// The session keys of an account, the `keys` is the handle of the `Table<vector<u8>, SessionKey>`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionKeys {
+    pub keys: ObjectID,
+}
+
+impl MoveStructType for SessionKeys {
+    const ADDRESS: AccountAddress = KANARI_FRAMEWORK_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("SessionKeys");
+}
+
+impl MoveStructState for SessionKeys {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        // The `Table` is a struct with an `Object<TablePlaceholder>` handle, which has the same BCS encoding as `ObjectID`.
+        move_core_types::value::MoveStructLayout::new(vec![ObjectID::type_layout()])
+    }
+}
+
 /// Rust bindings for Kanari Framework session_key module
 pub struct SessionKeyModule<'a> {
     caller: &'a dyn MoveFunctionCaller,
@@ -194,6 +240,10 @@ impl<'a> SessionKeyModule<'a> {
         ident_str!("create_session_key_entry");
     pub const GET_SESSION_KEYS_HANDLE_FUNCTION_NAME: &'static IdentStr =
         ident_str!("get_session_keys_handle");
+    pub const CREATE_SESSION_KEY_WITH_MULTI_SCOPE_ENTRY_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("create_session_key_with_multi_scope_entry");
+    pub const REMOVE_SESSION_KEY_ENTRY_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("remove_session_key_entry");
 
     pub fn get_session_key(
         &self,
@@ -274,6 +324,93 @@ impl<'a> SessionKeyModule<'a> {
             ],
         )
     }
+
+    pub fn create_session_key_with_multi_scope_call(
+        app_name: MoveString,
+        app_url: MoveString,
+        authentication_key: Vec<u8>,
+        scopes: Vec<SessionScope>,
+        max_inactive_interval: u64,
+    ) -> FunctionCall {
+        let mut module_addresses = Vec::with_capacity(scopes.len());
+        let mut module_names = Vec::with_capacity(scopes.len());
+        let mut function_names = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            module_addresses.push(scope.module_address.to_move_value());
+            module_names.push(scope.module_name.to_move_value());
+            function_names.push(scope.function_name.to_move_value());
+        }
+        Self::create_function_call(
+            Self::CREATE_SESSION_KEY_WITH_MULTI_SCOPE_ENTRY_FUNCTION_NAME,
+            vec![],
+            vec![
+                app_name.to_move_value(),
+                app_url.to_move_value(),
+                MoveValue::vector_u8(authentication_key),
+                MoveValue::Vector(module_addresses),
+                MoveValue::Vector(module_names),
+                MoveValue::Vector(function_names),
+                MoveValue::U64(max_inactive_interval),
+            ],
+        )
+    }
+
+    pub fn remove_session_key_call(authentication_key: Vec<u8>) -> FunctionCall {
+        Self::create_function_call(
+            Self::REMOVE_SESSION_KEY_ENTRY_FUNCTION_NAME,
+            vec![],
+            vec![MoveValue::vector_u8(authentication_key)],
+        )
+    }
+
+    /// Remove the session keys in one transaction
+    pub fn remove_session_keys_action(authentication_keys: Vec<Vec<u8>>) -> MoveAction {
+        MoveAction::new_batch(
+            authentication_keys
+                .into_iter()
+                .map(Self::remove_session_key_call)
+                .collect(),
+        )
+    }
+
+    /// Renew the session key by removing and re-creating it with the same authentication key and scopes
+    /// in one transaction, which resets its create and last active time to now.
+    pub fn renew_session_key_action(
+        session_key: SessionKey,
+        max_inactive_interval: u64,
+    ) -> MoveAction {
+        MoveAction::new_batch(vec![
+            Self::remove_session_key_call(session_key.authentication_key.clone()),
+            Self::create_session_key_with_multi_scope_call(
+                session_key.app_name,
+                session_key.app_url,
+                session_key.authentication_key,
+                session_key.scopes,
+                max_inactive_interval,
+            ),
+        ])
+    }
+
+    /// Replace each session key with the new authentication key in one transaction,
+    /// the new session key keeps the app, the scopes and the max inactive interval of the old one.
+    pub fn rotate_session_keys_action(
+        rotations: Vec<(SessionKey, AuthenticationKey)>,
+    ) -> MoveAction {
+        let mut calls = Vec::with_capacity(rotations.len() * 2);
+        for (session_key, new_authentication_key) in rotations {
+            calls.push(Self::remove_session_key_call(
+                session_key.authentication_key,
+            ));
+            calls.push(Self::create_session_key_with_multi_scope_call(
+                session_key.app_name,
+                session_key.app_url,
+                new_authentication_key.as_ref().to_vec(),
+                session_key.scopes,
+                session_key.max_inactive_interval,
+            ));
+        }
+        MoveAction::new_batch(calls)
+    }
 }
 
 impl<'a> ModuleBinding<'a> for SessionKeyModule<'a> {
@@ -292,7 +429,7 @@ impl<'a> ModuleBinding<'a> for SessionKeyModule<'a> {
 mod tests {
     use std::str::FromStr;
 
-    use super::SessionScope;
+    use super::{SessionKey, SessionScope};
     use move_core_types::{account_address::AccountAddress, ident_str, language_storage::ModuleId};
     use moveos_types::{
         move_std::string::MoveString, move_types::FunctionId, transaction::FunctionCall,
@@ -366,6 +503,26 @@ mod tests {
             .for_each(|c| do_test_scope_match(&session_scope, &c.0, c.1));
     }
 
+    #[test]
+    fn test_session_key_expiration() {
+        let mut session_key = SessionKey {
+            app_name: MoveString::from_str("test").unwrap(),
+            app_url: MoveString::from_str("https://test.app").unwrap(),
+            authentication_key: vec![0u8; 33],
+            scopes: vec![],
+            create_time: 100,
+            last_active_time: 200,
+            max_inactive_interval: 3600,
+        };
+        assert_eq!(session_key.expiration_time(), Some(3800));
+        assert!(!session_key.is_expired_at(3800));
+        assert!(session_key.is_expired_at(3801));
+
+        session_key.max_inactive_interval = 0;
+        assert_eq!(session_key.expiration_time(), None);
+        assert!(!session_key.is_expired_at(u64::MAX));
+    }
+
     #[test]
     fn test_check_scope_match_asterisk() {
         let session_scope = SessionScope {
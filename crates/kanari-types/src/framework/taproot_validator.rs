@@ -0,0 +1,24 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use super::auth_validator::BuiltinAuthValidator;
+use crate::addresses::KANARI_FRAMEWORK_ADDRESS;
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::state::MoveStructType;
+
+const MODULE_NAME: &IdentStr = ident_str!("taproot_validator");
+
+/// Taproot key path Auth Validator
+pub struct TaprootValidator {}
+
+impl TaprootValidator {
+    pub fn auth_validator_id() -> u64 {
+        BuiltinAuthValidator::Taproot.flag().into()
+    }
+}
+
+impl MoveStructType for TaprootValidator {
+    const ADDRESS: AccountAddress = KANARI_FRAMEWORK_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("TaprootValidator");
+}
@@ -0,0 +1,38 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::addresses::KANARI_FRAMEWORK_ADDRESS;
+use crate::framework::coin_store::CoinStore;
+use crate::framework::gas_coin::KARI;
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::moveos_std::object::{self, Object, ObjectID};
+use moveos_types::state::{MoveState, MoveStructState, MoveStructType};
+use serde::{Deserialize, Serialize};
+
+pub const MODULE_NAME: &IdentStr = ident_str!("transaction_fee");
+
+/// The gas fee pool, the gas revenue stores are the fields of the pool, keyed by the revenue address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionFeePool {
+    pub fee: Object<CoinStore<KARI>>,
+}
+
+impl TransactionFeePool {
+    pub fn object_id() -> ObjectID {
+        object::named_object_id(&Self::struct_tag())
+    }
+}
+
+impl MoveStructType for TransactionFeePool {
+    const ADDRESS: AccountAddress = KANARI_FRAMEWORK_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("TransactionFeePool");
+}
+
+impl MoveStructState for TransactionFeePool {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(
+            vec![Object::<CoinStore<KARI>>::type_layout()],
+        )
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::addresses::KANARI_FRAMEWORK_ADDRESS;
+use crate::crypto::SignatureScheme;
+use crate::transaction::KanariTransactionData;
+use anyhow::{Result, ensure};
+use fastcrypto::{
+    encoding::{Base64, Encoding},
+    hash::Sha256,
+    secp256r1::{Secp256r1PublicKey, Secp256r1Signature},
+    traits::ToFromBytes,
+};
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::{h256::sha2_256_of, state::MoveStructType};
+use serde::{Deserialize, Serialize};
+
+const MODULE_NAME: &IdentStr = ident_str!("webauthn_validator");
+
+/// The WebAuthn validator id, must stay in sync with `builtin_validators.move`
+pub const WEBAUTHN_AUTH_VALIDATOR_ID: u64 = 3;
+
+/// The relying party id of the software passkey authenticator, the origin is `https://{rp_id}`
+pub const DEFAULT_WEBAUTHN_RP_ID: &str = "localhost";
+
+/// The `type` of the clientDataJSON for an assertion
+pub const CLIENT_DATA_TYPE_GET: &str = "webauthn.get";
+
+/// User Present flag of the authenticatorData
+pub const AUTHENTICATOR_DATA_FLAG_UP: u8 = 0x01;
+/// User Verified flag of the authenticatorData
+pub const AUTHENTICATOR_DATA_FLAG_UV: u8 = 0x04;
+
+/// WebAuthn (P-256) Auth Validator
+pub struct WebauthnValidator {}
+
+impl WebauthnValidator {
+    pub fn auth_validator_id() -> u64 {
+        WEBAUTHN_AUTH_VALIDATOR_ID
+    }
+}
+
+impl MoveStructType for WebauthnValidator {
+    const ADDRESS: AccountAddress = KANARI_FRAMEWORK_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("WebauthnValidator");
+}
+
+/// The clientDataJSON of an assertion, the `challenge` is the base64 encoded tx hash
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientData {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub challenge: String,
+    pub origin: String,
+}
+
+impl ClientData {
+    pub fn new_for_assertion(tx_data: &KanariTransactionData, origin: &str) -> Self {
+        Self {
+            type_: CLIENT_DATA_TYPE_GET.to_owned(),
+            challenge: Base64::encode(tx_data.tx_hash().as_bytes()),
+            origin: origin.to_owned(),
+        }
+    }
+
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        serde_json::to_vec(self).expect("Serialize ClientData should success")
+    }
+}
+
+/// Build the authenticatorData of an assertion without attested credential data and extensions:
+/// `sha256(rp_id) || flags || sign_count(big endian)`
+pub fn authenticator_data(rp_id: &str, flags: u8, sign_count: u32) -> Vec<u8> {
+    let mut data = sha2_256_of(rp_id.as_bytes()).0.to_vec();
+    data.push(flags);
+    data.extend_from_slice(&sign_count.to_be_bytes());
+    data
+}
+
+/// The BCS serialised payload of the WebAuthn validator, same as `WebauthnAuthPayload` in Move
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WebauthnAuthPayload {
+    pub scheme: u8,
+    /// 64 bytes raw signature (r || s)
+    pub signature: Vec<u8>,
+    /// 33 bytes compressed P-256 public key
+    pub public_key: Vec<u8>,
+    pub authenticator_data: Vec<u8>,
+    pub client_data_json: Vec<u8>,
+}
+
+impl WebauthnAuthPayload {
+    pub fn new(
+        signature: Vec<u8>,
+        public_key: Vec<u8>,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self {
+            scheme: SignatureScheme::EcdsaR1.flag(),
+            signature,
+            public_key,
+            authenticator_data,
+            client_data_json,
+        }
+    }
+
+    /// The message signed by the authenticator: `authenticatorData || sha256(clientDataJSON)`
+    pub fn signed_message(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+        let mut message = authenticator_data.to_vec();
+        message.extend_from_slice(sha2_256_of(client_data_json).as_bytes());
+        message
+    }
+
+    /// Verify the payload as the WebAuthn validator, except the on-chain session key and DID checks
+    pub fn verify(&self, tx_data: &KanariTransactionData) -> Result<()> {
+        ensure!(
+            self.scheme == SignatureScheme::EcdsaR1.flag(),
+            "Invalid WebAuthn signature scheme: {}",
+            self.scheme
+        );
+        let pk = Secp256r1PublicKey::from_bytes(&self.public_key)?;
+        let signature = Secp256r1Signature::from_bytes(&self.signature)?;
+        let message = Self::signed_message(&self.authenticator_data, &self.client_data_json);
+        pk.verify_with_hash::<Sha256>(&message, &signature)?;
+
+        let client_data: ClientData = serde_json::from_slice(&self.client_data_json)?;
+        let challenge = Base64::decode(&client_data.challenge)?;
+        ensure!(
+            challenge == tx_data.tx_hash().as_bytes(),
+            "The WebAuthn challenge does not match the transaction hash"
+        );
+        Ok(())
+    }
+}
@@ -3,6 +3,7 @@
 
 pub mod event;
 pub mod field;
+pub mod package_source;
 pub mod state;
 pub mod transaction;
 
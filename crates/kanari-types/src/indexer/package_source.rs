@@ -0,0 +1,39 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use move_core_types::account_address::AccountAddress;
+use moveos_types::h256::H256;
+use serde::{Deserialize, Serialize};
+
+/// The sources of a package submitted to the indexer registry.
+/// The `source_digest` is recorded as submitted by the node operator who rebuilt the package,
+/// only the `package_digest` is checked against the package published on chain.
+#[derive(Eq, PartialEq, Clone, Debug, Serialize, Deserialize)]
+pub struct IndexerPackageSource {
+    pub package_address: AccountAddress,
+    pub package_name: String,
+    /// The digest of the package modules, see `moveos_types::moveos_std::module_store::package_digest`
+    pub package_digest: H256,
+    /// The digest of the package sources and manifest which the bytecode is rebuilt from
+    pub source_digest: H256,
+    /// The submitted timestamp in milliseconds
+    pub submitted_at: u64,
+}
+
+impl IndexerPackageSource {
+    pub fn new(
+        package_address: AccountAddress,
+        package_name: String,
+        package_digest: H256,
+        source_digest: H256,
+        submitted_at: u64,
+    ) -> Self {
+        IndexerPackageSource {
+            package_address,
+            package_name,
+            package_digest,
+            source_digest,
+            submitted_at,
+        }
+    }
+}
@@ -1,26 +1,79 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{address::KanariAddress, error::KanariError};
+use crate::{address::KanariAddress, crypto::PublicKey, error::KanariError};
 use anyhow::Result;
-use argon2::Argon2;
+use argon2::{Algorithm, Argon2, Params, Version};
 use chacha20poly1305::AeadCore;
 use chacha20poly1305::ChaCha20Poly1305;
 use chacha20poly1305::KeyInit;
-use chacha20poly1305::aead::Aead;
+use chacha20poly1305::aead::{Aead, Payload};
 use fastcrypto::encoding::{Base64, Encoding};
+use rand::RngCore;
 use rand::rngs::OsRng;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+/// The legacy format, the key is derived by Argon2 with the default parameters and the nonce as the salt
+pub const ENCRYPTION_DATA_VERSION_LEGACY: u32 = 0;
+/// The key is derived by Argon2id with a random salt and the parameters kept in the data,
+/// the version, salt and parameters are authenticated as the associated data of the AEAD
+pub const ENCRYPTION_DATA_VERSION_V1: u32 = 1;
+pub const ENCRYPTION_DATA_VERSION: u32 = ENCRYPTION_DATA_VERSION_V1;
+
+const KDF_SALT_LENGTH: usize = 16;
+const KDF_OUTPUT_LENGTH: usize = 32;
+const ASSOCIATED_DATA_DOMAIN: &[u8] = b"kanari-keystore";
+
+/// The Argon2id parameters of the key derivation, default to the second recommended option of RFC 9106
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// The memory size in KiB
+    pub m_cost: u32,
+    /// The number of iterations
+    pub t_cost: u32,
+    /// The degree of parallelism
+    pub p_cost: u32,
+}
+
+impl Default for KdfParams {
+    fn default() -> Self {
+        Self {
+            m_cost: 64 * 1024,
+            t_cost: 3,
+            p_cost: 4,
+        }
+    }
+}
+
+impl KdfParams {
+    pub fn argon2(&self) -> Result<Argon2<'static>> {
+        let params = Params::new(
+            self.m_cost,
+            self.t_cost,
+            self.p_cost,
+            Some(KDF_OUTPUT_LENGTH),
+        )
+        .map_err(|e| KanariError::KeyConversionError(e.to_string()))?;
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EncryptionData {
+    #[serde(default)]
+    pub version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf_params: Option<KdfParams>,
     pub nonce: String,
     pub ciphertext: String,
     pub tag: String,
 }
 pub struct GenerateNewKeyPair {
     pub mnemonic_phrase: String,
+    pub public_key: PublicKey,
     pub private_key_encryption: EncryptionData,
     pub mnemonic_phrase_encryption: EncryptionData,
 }
@@ -44,19 +97,38 @@ impl EncryptionData {
         .to_vec();
 
         EncryptionData {
+            version: ENCRYPTION_DATA_VERSION_LEGACY,
+            salt: None,
+            kdf_params: None,
             nonce: Base64::encode(nonce),
             ciphertext: Base64::encode(ciphertext),
             tag: Base64::encode(tag),
         }
     }
 
+    /// Whether the data is encrypted in a format older than the current one
+    pub fn is_legacy(&self) -> bool {
+        self.version < ENCRYPTION_DATA_VERSION
+    }
+
     pub fn encrypt(data: &[u8], password: Option<String>) -> Result<Self> {
+        Self::encrypt_with_params(data, password, KdfParams::default())
+    }
+
+    pub fn encrypt_with_params(
+        data: &[u8],
+        password: Option<String>,
+        kdf_params: KdfParams,
+    ) -> Result<Self> {
+        let mut salt = [0u8; KDF_SALT_LENGTH];
+        OsRng.fill_bytes(&mut salt);
         let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
-        let mut output_key_material = [0u8; 32];
-        Argon2::default()
+        let mut output_key_material = [0u8; KDF_OUTPUT_LENGTH];
+        kdf_params
+            .argon2()?
             .hash_password_into(
                 password.unwrap_or_default().as_bytes(),
-                &nonce,
+                &salt,
                 &mut output_key_material,
             )
             .map_err(|e| KanariError::KeyConversionError(e.to_string()))?;
@@ -64,20 +136,40 @@ impl EncryptionData {
         let cipher = ChaCha20Poly1305::new_from_slice(&output_key_material)
             .map_err(|e| KanariError::KeyConversionError(e.to_string()))?;
 
+        let aad = Self::associated_data(ENCRYPTION_DATA_VERSION_V1, &salt, &kdf_params);
         let ciphertext_with_tag = cipher
-            .encrypt(&nonce, data)
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: data,
+                    aad: &aad,
+                },
+            )
             .map_err(|e| KanariError::KeyConversionError(e.to_string()))?;
 
         let ciphertext = ciphertext_with_tag[..ciphertext_with_tag.len() - 16].to_vec();
         let tag = ciphertext_with_tag[ciphertext_with_tag.len() - 16..].to_vec();
 
         Ok(EncryptionData {
+            version: ENCRYPTION_DATA_VERSION_V1,
+            salt: Some(Base64::encode(salt)),
+            kdf_params: Some(kdf_params),
             nonce: Base64::encode(nonce),
             ciphertext: Base64::encode(ciphertext),
             tag: Base64::encode(tag),
         })
     }
 
+    fn associated_data(version: u32, salt: &[u8], kdf_params: &KdfParams) -> Vec<u8> {
+        let mut aad = ASSOCIATED_DATA_DOMAIN.to_vec();
+        aad.extend(version.to_le_bytes());
+        aad.extend(salt);
+        aad.extend(kdf_params.m_cost.to_le_bytes());
+        aad.extend(kdf_params.t_cost.to_le_bytes());
+        aad.extend(kdf_params.p_cost.to_le_bytes());
+        aad
+    }
+
     pub fn decrypt(&self, password: Option<String>) -> Result<Vec<u8>> {
         let nonce = Base64::decode(&self.nonce)
             .map_err(|e| anyhow::Error::new(KanariError::KeyConversionError(e.to_string())))?;
@@ -85,12 +177,37 @@ impl EncryptionData {
             .map_err(|e| anyhow::Error::new(KanariError::KeyConversionError(e.to_string())))?;
         let tag = Base64::decode(&self.tag)
             .map_err(|e| anyhow::Error::new(KanariError::KeyConversionError(e.to_string())))?;
+        if nonce.len() != 12 {
+            return Err(KanariError::KeyConversionError("Invalid nonce length".to_owned()).into());
+        }
 
-        let mut output_key_material = [0u8; 32];
-        Argon2::default()
+        let (argon2, salt, aad) = match self.version {
+            ENCRYPTION_DATA_VERSION_LEGACY => (Argon2::default(), nonce.clone(), vec![]),
+            ENCRYPTION_DATA_VERSION_V1 => {
+                let salt =
+                    Base64::decode(self.salt.as_deref().unwrap_or_default()).map_err(|e| {
+                        anyhow::Error::new(KanariError::KeyConversionError(e.to_string()))
+                    })?;
+                let kdf_params = self.kdf_params.ok_or_else(|| {
+                    KanariError::KeyConversionError("Missing KDF parameters".to_owned())
+                })?;
+                let aad = Self::associated_data(self.version, &salt, &kdf_params);
+                (kdf_params.argon2()?, salt, aad)
+            }
+            version => {
+                return Err(KanariError::KeyConversionError(format!(
+                    "Unsupported encryption data version: {}",
+                    version
+                ))
+                .into());
+            }
+        };
+
+        let mut output_key_material = [0u8; KDF_OUTPUT_LENGTH];
+        argon2
             .hash_password_into(
                 password.unwrap_or_default().as_bytes(),
-                nonce.as_slice(),
+                salt.as_slice(),
                 &mut output_key_material,
             )
             .map_err(|e| KanariError::KeyConversionError(e.to_string()))?;
@@ -103,11 +220,27 @@ impl EncryptionData {
         ciphertext_with_tag.extend(tag);
 
         let data = cipher
-            .decrypt(nonce.as_slice().into(), &*ciphertext_with_tag)
+            .decrypt(
+                nonce.as_slice().into(),
+                Payload {
+                    msg: &ciphertext_with_tag,
+                    aad: &aad,
+                },
+            )
             .map_err(|e| KanariError::KeyConversionError(e.to_string()))?;
         Ok(data)
     }
 
+    /// Re-encrypt the data with the password in the current format
+    pub fn reencrypt(
+        &self,
+        old_password: Option<String>,
+        new_password: Option<String>,
+    ) -> Result<Self> {
+        let data = self.decrypt(old_password)?;
+        Self::encrypt(&data, new_password)
+    }
+
     pub fn encrypt_with_type<T>(data: &T, password: Option<String>) -> Result<Self>
     where
         T: Serialize,
@@ -156,4 +289,52 @@ mod tests {
         let result = encryption_data.decrypt(Some(wrong_password));
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_encryption_data_kdf_params_are_authenticated() {
+        let data = b"test data";
+        let password = Some("password".to_string());
+        let mut encryption_data = EncryptionData::encrypt(data, password.clone()).unwrap();
+        assert_eq!(encryption_data.version, ENCRYPTION_DATA_VERSION);
+        assert!(!encryption_data.is_legacy());
+
+        let kdf_params = encryption_data.kdf_params.unwrap();
+        encryption_data.kdf_params = Some(KdfParams {
+            t_cost: kdf_params.t_cost + 1,
+            ..kdf_params
+        });
+        assert!(encryption_data.decrypt(password).is_err());
+    }
+
+    #[test]
+    fn test_legacy_encryption_data_migration() {
+        let data = b"test data";
+        let password = Some("password".to_string());
+        // Encrypt in the legacy format, the nonce is the salt of the default Argon2
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let mut output_key_material = [0u8; 32];
+        Argon2::default()
+            .hash_password_into(b"password", &nonce, &mut output_key_material)
+            .unwrap();
+        let ciphertext_with_tag = ChaCha20Poly1305::new_from_slice(&output_key_material)
+            .unwrap()
+            .encrypt(&nonce, data.as_slice())
+            .unwrap();
+        let legacy: EncryptionData = serde_json::from_value(serde_json::json!({
+            "nonce": Base64::encode(nonce),
+            "ciphertext": Base64::encode(&ciphertext_with_tag[..data.len()]),
+            "tag": Base64::encode(&ciphertext_with_tag[data.len()..]),
+        }))
+        .unwrap();
+        assert!(legacy.is_legacy());
+        assert_eq!(legacy.decrypt(password.clone()).unwrap(), data.to_vec());
+
+        let new_password = Some("new_password".to_string());
+        let migrated = legacy
+            .reencrypt(password.clone(), new_password.clone())
+            .unwrap();
+        assert!(!migrated.is_legacy());
+        assert_eq!(migrated.decrypt(new_password).unwrap(), data.to_vec());
+        assert!(migrated.decrypt(password).is_err());
+    }
 }
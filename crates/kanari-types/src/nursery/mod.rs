@@ -1,2 +1,4 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
+
+pub mod multisign_wallet;
@@ -0,0 +1,193 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::addresses::KANARI_NURSERY_ADDRESS;
+use anyhow::Result;
+use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
+use moveos_types::moveos_std::tx_context::TxContext;
+use moveos_types::state::{MoveStructState, MoveStructType};
+use moveos_types::{
+    module_binding::{ModuleBinding, MoveFunctionCaller},
+    state::MoveState,
+    transaction::MoveAction,
+};
+use serde::{Deserialize, Serialize};
+
+pub const MODULE_NAME: &IdentStr = ident_str!("multisign_wallet");
+
+pub const PROPOSAL_STATUS_PENDING: u8 = 0;
+pub const PROPOSAL_STATUS_APPROVED: u8 = 1;
+pub const PROPOSAL_STATUS_REJECTED: u8 = 2;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BitcoinProposalInfo {
+    pub multisign_address: AccountAddress,
+    pub proposal_id: u64,
+    /// The Bitcoin txid of the proposal transaction
+    pub tx_id: AccountAddress,
+    /// The PSBT of the proposal transaction
+    pub tx_data: Vec<u8>,
+    pub status: u8,
+    /// The participants who have signed the proposal
+    pub participants: Vec<AccountAddress>,
+    /// The partially signed PSBTs of the participants, in the same order as `participants`
+    pub signed_psbts: Vec<Vec<u8>>,
+}
+
+impl BitcoinProposalInfo {
+    pub fn is_pending(&self) -> bool {
+        self.status == PROPOSAL_STATUS_PENDING
+    }
+
+    pub fn is_approved(&self) -> bool {
+        self.status == PROPOSAL_STATUS_APPROVED
+    }
+}
+
+impl MoveStructType for BitcoinProposalInfo {
+    const ADDRESS: AccountAddress = KANARI_NURSERY_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("BitcoinProposalInfo");
+}
+
+impl MoveStructState for BitcoinProposalInfo {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            AccountAddress::type_layout(),
+            u64::type_layout(),
+            AccountAddress::type_layout(),
+            Vec::<u8>::type_layout(),
+            u8::type_layout(),
+            Vec::<AccountAddress>::type_layout(),
+            Vec::<Vec<u8>>::type_layout(),
+        ])
+    }
+}
+
+/// The message the participant signs to approve a Bitcoin proposal,
+/// the `multisign_wallet` module verifies the ecdsa signature over the sha256 of it
+pub fn bitcoin_proposal_sign_message(tx_id: AccountAddress) -> Vec<u8> {
+    bcs::to_bytes(&tx_id).expect("bcs serialize address should success")
+}
+
+/// Rust bindings for kanari_nursery::multisign_wallet module
+pub struct MultisignWalletModule<'a> {
+    caller: &'a dyn MoveFunctionCaller,
+}
+
+impl<'a> MultisignWalletModule<'a> {
+    const SUBMIT_BITCOIN_PROPOSAL_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("submit_bitcoin_proposal");
+    const SIGN_BITCOIN_PROPOSAL_WITH_PSBT_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("sign_bitcoin_proposal_with_psbt");
+    const BITCOIN_PROPOSAL_COUNT_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("bitcoin_proposal_count");
+    const BITCOIN_PROPOSAL_INFO_FUNCTION_NAME: &'static IdentStr =
+        ident_str!("bitcoin_proposal_info");
+
+    pub fn submit_bitcoin_proposal_action(
+        multisign_address: AccountAddress,
+        tx_id: AccountAddress,
+        tx_data: Vec<u8>,
+    ) -> MoveAction {
+        Self::create_move_action(
+            Self::SUBMIT_BITCOIN_PROPOSAL_FUNCTION_NAME,
+            vec![],
+            vec![
+                multisign_address.to_move_value(),
+                tx_id.to_move_value(),
+                tx_data.to_move_value(),
+            ],
+        )
+    }
+
+    pub fn sign_bitcoin_proposal_with_psbt_action(
+        multisign_address: AccountAddress,
+        proposal_id: u64,
+        signature: Vec<u8>,
+        signed_psbt: Vec<u8>,
+    ) -> MoveAction {
+        Self::create_move_action(
+            Self::SIGN_BITCOIN_PROPOSAL_WITH_PSBT_FUNCTION_NAME,
+            vec![],
+            vec![
+                multisign_address.to_move_value(),
+                proposal_id.to_move_value(),
+                signature.to_move_value(),
+                signed_psbt.to_move_value(),
+            ],
+        )
+    }
+
+    pub fn bitcoin_proposal_count(&self, multisign_address: AccountAddress) -> Result<u64> {
+        let function_call = Self::create_function_call(
+            Self::BITCOIN_PROPOSAL_COUNT_FUNCTION_NAME,
+            vec![],
+            vec![multisign_address.to_move_value()],
+        );
+        let ctx = TxContext::new_readonly_ctx(AccountAddress::ZERO);
+        let count = self
+            .caller
+            .call_function(&ctx, function_call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<u64>(&value.value).expect("should be a valid u64")
+            })?;
+        Ok(count)
+    }
+
+    pub fn bitcoin_proposal_info(
+        &self,
+        multisign_address: AccountAddress,
+        proposal_id: u64,
+    ) -> Result<BitcoinProposalInfo> {
+        let function_call = Self::create_function_call(
+            Self::BITCOIN_PROPOSAL_INFO_FUNCTION_NAME,
+            vec![],
+            vec![
+                multisign_address.to_move_value(),
+                proposal_id.to_move_value(),
+            ],
+        );
+        let ctx = TxContext::new_readonly_ctx(AccountAddress::ZERO);
+        let info = self
+            .caller
+            .call_function(&ctx, function_call)?
+            .into_result()
+            .map(|mut values| {
+                let value = values.pop().expect("should have one return value");
+                bcs::from_bytes::<BitcoinProposalInfo>(&value.value)
+                    .expect("should be a valid BitcoinProposalInfo")
+            })?;
+        Ok(info)
+    }
+
+    /// Get all the proposals of the multisign account which are still waiting for signatures
+    pub fn pending_bitcoin_proposals(
+        &self,
+        multisign_address: AccountAddress,
+    ) -> Result<Vec<BitcoinProposalInfo>> {
+        let count = self.bitcoin_proposal_count(multisign_address)?;
+        let mut proposals = vec![];
+        for proposal_id in 0..count {
+            let info = self.bitcoin_proposal_info(multisign_address, proposal_id)?;
+            if info.is_pending() {
+                proposals.push(info);
+            }
+        }
+        Ok(proposals)
+    }
+}
+
+impl<'a> ModuleBinding<'a> for MultisignWalletModule<'a> {
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const MODULE_ADDRESS: AccountAddress = KANARI_NURSERY_ADDRESS;
+
+    fn new(caller: &'a impl MoveFunctionCaller) -> Self
+    where
+        Self: Sized,
+    {
+        Self { caller }
+    }
+}
@@ -7,11 +7,12 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 #[cfg(any(test, feature = "fuzzing"))]
 use fastcrypto::ed25519::Ed25519KeyPair;
 #[cfg(any(test, feature = "fuzzing"))]
 use fastcrypto::traits::KeyPair;
+use moveos_types::h256::sha2_256_of;
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest::{collection::vec, prelude::*};
 #[cfg(any(test, feature = "fuzzing"))]
@@ -20,10 +21,14 @@ use serde::{Deserialize, Serialize};
 use std::{fmt, str::FromStr};
 
 use crate::{
-    crypto::{KanariKeyPair, Signature, SignatureScheme},
+    crypto::{KanariKeyPair, KanariSignature, Signature, SignatureScheme},
     framework::{
         auth_payload::{AuthPayload, MultisignAuthPayload, SignData},
         auth_validator::BuiltinAuthValidator,
+        webauthn_validator::{
+            AUTHENTICATOR_DATA_FLAG_UP, AUTHENTICATOR_DATA_FLAG_UV, ClientData,
+            DEFAULT_WEBAUTHN_RP_ID, WebauthnAuthPayload, WebauthnValidator, authenticator_data,
+        },
     },
     kanari_network::{BuiltinChainID, KanariNetwork},
 };
@@ -144,6 +149,96 @@ impl BuiltinAuthenticator for BitcoinAuthenticator {
     }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TaprootAuthenticator {
+    pub payload: AuthPayload,
+}
+
+impl TaprootAuthenticator {
+    pub fn new(payload: AuthPayload) -> Self {
+        Self { payload }
+    }
+
+    /// Sign the same digest as the Bitcoin authenticator with the Taproot tweaked key
+    pub fn sign(kp: &KanariKeyPair, tx_data: &KanariTransactionData) -> Result<Self> {
+        let sign_data = SignData::new_with_default(tx_data);
+        let digest = sha2_256_of(sign_data.data_hash().as_bytes());
+        let signature = kp.sign_taproot(digest.as_bytes())?;
+        let public_key = kp.public();
+        let internal_key = public_key.xonly_public_key()?;
+        let bitcoin_address = public_key.bitcoin_address()?;
+        //TODO handle custom network
+        let kanari_network = KanariNetwork::from(
+            BuiltinChainID::try_from(tx_data.chain_id).unwrap_or(BuiltinChainID::default()),
+        );
+        let bitcoin_address_str =
+            bitcoin_address.format(kanari_network.genesis_config.bitcoin_network)?;
+        Ok(TaprootAuthenticator {
+            payload: AuthPayload::new_taproot(
+                sign_data,
+                signature,
+                internal_key,
+                bitcoin_address_str,
+            ),
+        })
+    }
+}
+
+impl BuiltinAuthenticator for TaprootAuthenticator {
+    fn auth_validator_id(&self) -> u64 {
+        BuiltinAuthValidator::Taproot.flag().into()
+    }
+    fn payload(&self) -> Vec<u8> {
+        bcs::to_bytes(&self.payload).expect("Serialize TaprootAuthenticator should success")
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct WebauthnAuthenticator {
+    pub payload: WebauthnAuthPayload,
+}
+
+impl WebauthnAuthenticator {
+    pub fn new(payload: WebauthnAuthPayload) -> Self {
+        Self { payload }
+    }
+
+    /// Sign as a software passkey authenticator with the P-256 credential,
+    /// the challenge of the clientDataJSON is the tx hash.
+    /// The software authenticator does not keep a signature counter, so the sign count is always 0.
+    pub fn sign(kp: &KanariKeyPair, tx_data: &KanariTransactionData, rp_id: &str) -> Result<Self> {
+        if kp.scheme() != SignatureScheme::EcdsaR1 {
+            bail!("Only ecdsa r1 key pair can sign as a WebAuthn authenticator")
+        }
+        let authenticator_data = authenticator_data(
+            rp_id,
+            AUTHENTICATOR_DATA_FLAG_UP | AUTHENTICATOR_DATA_FLAG_UV,
+            0,
+        );
+        let origin = format!("https://{}", rp_id);
+        let client_data_json = ClientData::new_for_assertion(tx_data, &origin).to_json_bytes();
+        let message = WebauthnAuthPayload::signed_message(&authenticator_data, &client_data_json);
+        let signature = kp.sign(&message);
+        Ok(WebauthnAuthenticator {
+            payload: WebauthnAuthPayload::new(
+                signature.signature_bytes().to_vec(),
+                kp.public().as_ref().to_vec(),
+                authenticator_data,
+                client_data_json,
+            ),
+        })
+    }
+}
+
+impl BuiltinAuthenticator for WebauthnAuthenticator {
+    fn auth_validator_id(&self) -> u64 {
+        WebauthnValidator::auth_validator_id()
+    }
+    fn payload(&self) -> Vec<u8> {
+        bcs::to_bytes(&self.payload).expect("Serialize WebauthnAuthenticator should success")
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct BitcoinMultisignAuthenticator {
     pub payload: MultisignAuthPayload,
@@ -213,6 +308,18 @@ impl Authenticator {
         BitcoinAuthenticator::sign(kp, tx_data).into()
     }
 
+    /// Create a taproot key path authenticator for KanariTransaction
+    /// The BIP-340 Schnorr signature is signed by the Taproot tweaked key
+    pub fn taproot(kp: &KanariKeyPair, tx_data: &KanariTransactionData) -> Result<Self> {
+        TaprootAuthenticator::sign(kp, tx_data).map(Into::into)
+    }
+
+    /// Create a WebAuthn authenticator for KanariTransaction
+    /// We simulate a passkey assertion with the P-256 key pair of the default relying party
+    pub fn webauthn(kp: &KanariKeyPair, tx_data: &KanariTransactionData) -> Result<Self> {
+        WebauthnAuthenticator::sign(kp, tx_data, DEFAULT_WEBAUTHN_RP_ID).map(Into::into)
+    }
+
     /// Create a bitcoin multisign authenticator for KanariTransaction
     pub fn bitcoin_multisign(authenticators: Vec<BitcoinAuthenticator>) -> Result<Self> {
         BitcoinMultisignAuthenticator::build_multisig_authenticator(authenticators).map(Into::into)
@@ -250,8 +357,68 @@ impl fmt::Display for Authenticator {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::framework::empty::Empty;
+    use moveos_types::transaction::MoveAction;
     use proptest::prelude::*;
 
+    #[test]
+    fn test_taproot_authenticator() {
+        let kp = KanariKeyPair::generate_secp256k1();
+        let sender = kp.public().kanari_address().unwrap();
+        let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+        let tx_data = KanariTransactionData::new_for_test(sender, 0, action.clone());
+
+        let authenticator = TaprootAuthenticator::sign(&kp, &tx_data).unwrap();
+        assert!(authenticator.payload.verify_taproot(&tx_data).is_ok());
+        // The Taproot signature is not a valid ECDSA signature of the Bitcoin authenticator
+        assert!(authenticator.payload.verify(&tx_data).is_err());
+
+        let other_tx_data = KanariTransactionData::new_for_test(sender, 1, action);
+        assert!(
+            authenticator
+                .payload
+                .verify_taproot(&other_tx_data)
+                .is_err()
+        );
+
+        let ed25519_kp = KanariKeyPair::generate_ed25519();
+        assert!(TaprootAuthenticator::sign(&ed25519_kp, &tx_data).is_err());
+    }
+
+    #[test]
+    fn test_webauthn_authenticator() {
+        let kp = KanariKeyPair::generate_ecdsa_r1();
+        let sender = KanariKeyPair::generate_secp256k1()
+            .public()
+            .kanari_address()
+            .unwrap();
+        let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+        let tx_data = KanariTransactionData::new_for_test(sender, 0, action.clone());
+
+        let authenticator = WebauthnAuthenticator::sign(&kp, &tx_data, "localhost").unwrap();
+        assert!(authenticator.payload.verify(&tx_data).is_ok());
+        assert_eq!(authenticator.payload.signature.len(), 64);
+        assert_eq!(authenticator.payload.public_key.len(), 33);
+        // rp id hash || flags || sign count
+        assert_eq!(authenticator.payload.authenticator_data.len(), 37);
+        assert_eq!(authenticator.payload.authenticator_data[32], 0x05);
+
+        let client_data: ClientData =
+            serde_json::from_slice(&authenticator.payload.client_data_json).unwrap();
+        assert_eq!(client_data.type_, "webauthn.get");
+        assert_eq!(client_data.origin, "https://localhost");
+
+        let payload: WebauthnAuthPayload = bcs::from_bytes(&authenticator.payload()).unwrap();
+        assert_eq!(payload, authenticator.payload);
+
+        let other_tx_data = KanariTransactionData::new_for_test(sender, 1, action);
+        assert!(authenticator.payload.verify(&other_tx_data).is_err());
+
+        let secp256k1_kp = KanariKeyPair::generate_secp256k1();
+        assert!(WebauthnAuthenticator::sign(&secp256k1_kp, &tx_data, "localhost").is_err());
+    }
+
     proptest! {
         #[test]
         fn test_kanari_authenticator_serialize_deserialize(authenticator in any::<super::SessionAuthenticator>()) {
@@ -14,6 +14,7 @@ use serde::{Deserialize, Serialize};
 pub mod authenticator;
 pub mod kanari;
 mod ledger_transaction;
+pub mod signing_request;
 
 use crate::test_utils::random_accumulator_info;
 pub use authenticator::Authenticator;
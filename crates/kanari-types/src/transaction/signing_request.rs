@@ -0,0 +1,119 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use super::KanariTransactionData;
+use crate::address::KanariAddress;
+use anyhow::{Result, bail, ensure};
+use moveos_types::h256::H256;
+use serde::{Deserialize, Serialize};
+
+/// The magic bytes of an encoded signing request, `ksr` followed by 0xff as PSBT.
+pub const SIGNING_REQUEST_MAGIC: [u8; 4] = [b'k', b's', b'r', 0xff];
+pub const SIGNING_REQUEST_VERSION: u8 = 1;
+
+/// The multisign account info of the sender, which the offline signer can not query from the chain.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SigningRequestMultisign {
+    pub threshold: u64,
+    pub participants: Vec<KanariAddress>,
+}
+
+/// SigningRequest is a transaction built by an online machine, such as a watch-only wallet,
+/// with all the chain state the signer needs, so that it can be signed on an air-gapped machine.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SigningRequest {
+    pub version: u8,
+    pub data: KanariTransactionData,
+    /// The multisign info if the sender is a multisign account.
+    pub multisign: Option<SigningRequestMultisign>,
+}
+
+impl SigningRequest {
+    pub fn new(data: KanariTransactionData, multisign: Option<SigningRequestMultisign>) -> Self {
+        Self {
+            version: SIGNING_REQUEST_VERSION,
+            data,
+            multisign,
+        }
+    }
+
+    pub fn sender(&self) -> KanariAddress {
+        self.data.sender
+    }
+
+    pub fn tx_hash(&self) -> H256 {
+        self.data.tx_hash()
+    }
+
+    pub fn is_signing_request(bytes: &[u8]) -> bool {
+        bytes.starts_with(&SIGNING_REQUEST_MAGIC)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = SIGNING_REQUEST_MAGIC.to_vec();
+        bytes.extend(bcs::to_bytes(self).expect("encode signing request should success"));
+        bytes
+    }
+
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let Some(payload) = bytes.strip_prefix(&SIGNING_REQUEST_MAGIC) else {
+            bail!("Invalid signing request magic");
+        };
+        let request = bcs::from_bytes::<Self>(payload)?;
+        ensure!(
+            request.version == SIGNING_REQUEST_VERSION,
+            "Unsupported signing request version: {}",
+            request.version
+        );
+        if let Some(multisign) = &request.multisign {
+            ensure!(
+                multisign.threshold > 0
+                    && multisign.threshold <= multisign.participants.len() as u64,
+                "Invalid multisign threshold {} of {} participants",
+                multisign.threshold,
+                multisign.participants.len()
+            );
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::KanariSupportedAddress;
+    use crate::framework::empty::Empty;
+    use moveos_types::transaction::MoveAction;
+
+    #[test]
+    fn test_signing_request_encode_decode() {
+        let action = MoveAction::new_function_call(Empty::empty_function_id(), vec![], vec![]);
+        let tx_data = KanariTransactionData::new_for_test(KanariAddress::random(), 0, action);
+        let multisign = SigningRequestMultisign {
+            threshold: 2,
+            participants: vec![
+                KanariAddress::random(),
+                KanariAddress::random(),
+                KanariAddress::random(),
+            ],
+        };
+        let request = SigningRequest::new(tx_data.clone(), Some(multisign));
+        let bytes = request.encode();
+        assert!(SigningRequest::is_signing_request(&bytes));
+        assert_eq!(SigningRequest::decode(&bytes).unwrap(), request);
+        assert_eq!(request.tx_hash(), tx_data.tx_hash());
+
+        // The raw transaction data is not a signing request
+        assert!(!SigningRequest::is_signing_request(&tx_data.encode()));
+        assert!(SigningRequest::decode(&tx_data.encode()).is_err());
+
+        let invalid = SigningRequest::new(
+            tx_data,
+            Some(SigningRequestMultisign {
+                threshold: 3,
+                participants: vec![KanariAddress::random()],
+            }),
+        );
+        assert!(SigningRequest::decode(&invalid.encode()).is_err());
+    }
+}
@@ -18,6 +18,8 @@ use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
+use tracing::info;
 
 pub use kanari_types::function_arg::{FunctionArg, FunctionArgType};
 
@@ -105,6 +107,16 @@ pub struct TransactionOptions {
     /// This option conflicts with `authenticator`
     #[clap(long, conflicts_with = "authenticator")]
     pub(crate) session_key: Option<AuthenticationKey>,
+
+    /// Sign the transaction with the Taproot tweaked key via the Taproot validator
+    /// This option conflicts with `authenticator` and `session_key`
+    #[clap(long, conflicts_with_all = ["authenticator", "session_key"])]
+    pub(crate) taproot: bool,
+
+    /// Sign the transaction with the passkey of `session_key` via the WebAuthn validator
+    /// The passkey is a P-256 session key generated by `kanari did manage add-vm --passkey`
+    #[clap(long, requires = "session_key", conflicts_with = "taproot")]
+    pub(crate) webauthn: bool,
 }
 
 #[derive(Debug, Parser)]
@@ -134,6 +146,7 @@ pub struct TransactionFilterOptions {
 }
 
 pub const KANARI_PASSWORD_ENV: &str = "KANARI_PASSWORD";
+pub const KANARI_LOCK_TIMEOUT_ENV: &str = "KANARI_LOCK_TIMEOUT";
 
 #[derive(Debug, Parser)]
 pub struct WalletContextOptions {
@@ -143,6 +156,10 @@ pub struct WalletContextOptions {
     /// kanari config path
     #[clap(long)]
     pub config_dir: Option<PathBuf>,
+    /// Lock the keystore after the seconds of inactivity, the password is asked again to unlock it.
+    /// For the long-running commands, such as the faucet server and the oracle reporter
+    #[clap(long, env = KANARI_LOCK_TIMEOUT_ENV)]
+    pub lock_timeout: Option<u64>,
 }
 
 impl WalletContextOptions {
@@ -153,6 +170,7 @@ impl WalletContextOptions {
     pub fn build_require_password(&self) -> KanariResult<WalletContext> {
         let mut ctx = WalletContext::new(self.config_dir.clone()).map_err(KanariError::from)?;
         if ctx.keystore.get_if_password_is_empty() {
+            Self::migrate_keystore(&mut ctx, None)?;
             Ok(ctx)
         } else {
             let password = self.password.clone().or_else(|| {
@@ -160,11 +178,7 @@ impl WalletContextOptions {
                 //then prompt password
                 match env::var(KANARI_PASSWORD_ENV) {
                     Ok(val) => Some(val),
-                    _ => {
-                        let password = prompt_password("Enter the keystore password:").ok();
-                        println!();
-                        password
-                    }
+                    _ => prompt_keystore_password(),
                 }
             });
             let is_verified = verify_password(password.clone(), ctx.keystore.get_password_hash())?;
@@ -173,10 +187,28 @@ impl WalletContextOptions {
                     "Password is invalid".to_owned(),
                 ));
             }
+            Self::migrate_keystore(&mut ctx, password.clone())?;
             ctx.set_password(password);
+            ctx.set_lock_timeout(self.lock_timeout.map(Duration::from_secs));
+            ctx.set_unlock_prompt(prompt_keystore_password);
             Ok(ctx)
         }
     }
+
+    /// Upgrade the keystore in the legacy format once the password is known
+    fn migrate_keystore(ctx: &mut WalletContext, password: Option<String>) -> KanariResult<()> {
+        if ctx.keystore.needs_migration() {
+            ctx.keystore.migrate(password)?;
+            info!("The keystore is migrated to the current format");
+        }
+        Ok(())
+    }
+}
+
+fn prompt_keystore_password() -> Option<String> {
+    let password = prompt_password("Enter the keystore password:").ok();
+    println!();
+    password
 }
 
 #[derive(Debug, Clone)]
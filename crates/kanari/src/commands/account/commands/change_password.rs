@@ -0,0 +1,68 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use kanari_key::keystore::account_keystore::AccountKeystore;
+use kanari_types::error::{KanariError, KanariResult};
+use rpassword::prompt_password;
+
+/// Change the password of kanari.keystore.
+///
+/// All the keys, the mnemonic and the session keys are re-encrypted with the new password.
+/// The new password is prompted if --new-password is not specified, an empty password leaves
+/// the keystore unencrypted by a password.
+#[derive(Debug, Parser)]
+pub struct ChangePasswordCommand {
+    /// The new key store password
+    #[clap(long)]
+    new_password: Option<String>,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+
+    /// Return command outputs in json format
+    #[clap(long, default_value = "false")]
+    json: bool,
+}
+
+#[async_trait]
+impl CommandAction<Option<bool>> for ChangePasswordCommand {
+    async fn execute(self) -> KanariResult<Option<bool>> {
+        let mut context = self.context_options.build_require_password()?;
+        let old_password = context.get_password();
+        let new_password = match self.new_password {
+            Some(new_password) => new_password,
+            None => {
+                let new_password = prompt_password(
+                    "Enter the new keystore password. Press enter to leave it an empty password: ",
+                )?;
+                let confirmed_password = prompt_password("Confirm the new keystore password: ")?;
+                if new_password != confirmed_password {
+                    return Err(KanariError::InvalidPasswordError(
+                        "The passwords do not match".to_owned(),
+                    ));
+                }
+                new_password
+            }
+        };
+        let new_password = if new_password.is_empty() {
+            None
+        } else {
+            Some(new_password)
+        };
+
+        context
+            .keystore
+            .change_password(old_password, new_password.clone())?;
+        context.set_password(new_password);
+
+        if self.json {
+            Ok(Some(true))
+        } else {
+            println!("The keystore password is changed");
+            Ok(None)
+        }
+    }
+}
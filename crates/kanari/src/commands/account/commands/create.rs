@@ -3,7 +3,9 @@
 
 use crate::cli_types::{CommandAction, WalletContextOptions};
 use async_trait::async_trait;
+use bip32::DerivationPath;
 use clap::Parser;
+use kanari_key::key_derive::parse_derivation_path;
 use kanari_key::keystore::account_keystore::AccountKeystore;
 use kanari_rpc_api::jsonrpc_types::KanariAddressView;
 use kanari_types::error::KanariResult;
@@ -14,8 +16,15 @@ use kanari_types::error::KanariResult;
 /// An account can be created by transferring coins, or by making an explicit
 /// call to create an account.  This will create an account with no coins, and
 /// any coins will have to transferred afterwards.
+///
+/// The key is derived from the keystore mnemonic on the next BIP-86 path by default,
+/// a BIP-44/49/84/86 path can be specified with --derivation-path.
 #[derive(Debug, Parser)]
 pub struct CreateCommand {
+    /// The derivation path of the key, m/{44,49,84,86}'/{0,1}'/account'/change/index
+    #[clap(long, value_parser = parse_derivation_path)]
+    derivation_path: Option<DerivationPath>,
+
     #[clap(flatten)]
     pub context_options: WalletContextOptions,
 
@@ -29,7 +38,12 @@ impl CommandAction<Option<KanariAddressView>> for CreateCommand {
     async fn execute(self) -> KanariResult<Option<KanariAddressView>> {
         let mut context = self.context_options.build_require_password()?;
         let password = context.get_password();
-        let result = context.keystore.generate_and_add_new_key(password)?;
+        let result = match self.derivation_path {
+            Some(derivation_path) => context
+                .keystore
+                .generate_and_add_new_key_with_path(derivation_path, password)?,
+            None => context.keystore.generate_and_add_new_key(password)?,
+        };
 
         if self.json {
             Ok(Some(result.address.into()))
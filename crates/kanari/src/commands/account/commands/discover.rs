@@ -0,0 +1,178 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use kanari_key::key_derive::{
+    DEFAULT_ADDRESS_GAP_LIMIT, DerivationScheme, DerivedAddress, derive_addresses,
+};
+use kanari_key::keystore::account_keystore::AccountKeystore;
+use kanari_rpc_api::jsonrpc_types::btc::utxo::UTXOFilterView;
+use kanari_rpc_api::jsonrpc_types::{BitcoinAddressView, KanariAddressView};
+use kanari_rpc_client::Client;
+use kanari_types::error::{KanariError, KanariResult};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Discover the accounts of a mnemonic with on-chain activity.
+///
+/// The addresses of the external chain of each scheme are derived in order, an address is active
+/// if its account has sent a transaction or its Bitcoin address of the scheme owns UTXOs.
+/// The scan of a scheme stops after --gap-limit consecutive inactive addresses, as BIP-44.
+/// The active addresses are imported into the keystore unless --dry-run is specified.
+#[derive(Debug, Parser)]
+pub struct DiscoverCommand {
+    /// The derivation scheme to scan, bip44, bip49, bip84 or bip86, default to all of them
+    #[clap(long)]
+    scheme: Option<DerivationScheme>,
+
+    /// The account index of the derivation path
+    #[clap(long, default_value_t = 0)]
+    account: u32,
+
+    /// The number of consecutive inactive addresses after which the scan of a scheme stops
+    #[clap(long, default_value_t = DEFAULT_ADDRESS_GAP_LIMIT)]
+    gap_limit: u32,
+
+    /// Discover the accounts of an external mnemonic instead of the keystore mnemonic
+    #[clap(long)]
+    mnemonic: Option<String>,
+
+    /// Only show the active addresses, do not import them into the keystore
+    #[clap(long)]
+    dry_run: bool,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+
+    /// Return command outputs in json format
+    #[clap(long, default_value = "false")]
+    json: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DiscoveredAccountView {
+    pub address: KanariAddressView,
+    pub bitcoin_address: BitcoinAddressView,
+    pub derivation_path: String,
+    pub sequence_number: u64,
+    pub has_utxo: bool,
+    /// Whether the address is added to the keystore by this discovery
+    pub imported: bool,
+}
+
+#[async_trait]
+impl CommandAction<Option<Vec<DiscoveredAccountView>>> for DiscoverCommand {
+    async fn execute(self) -> KanariResult<Option<Vec<DiscoveredAccountView>>> {
+        if self.gap_limit == 0 {
+            return Err(KanariError::CommandArgumentError(
+                "The gap limit must be greater than 0".to_owned(),
+            ));
+        }
+        let mut context = self.context_options.build_require_password()?;
+        let password = context.get_password();
+        let client = context.get_client().await?;
+        let external_mnemonic = self.mnemonic.is_some();
+        let mnemonic_phrase = match self.mnemonic {
+            Some(mnemonic_phrase) => mnemonic_phrase,
+            None => {
+                context
+                    .keystore
+                    .get_mnemonic(password.clone())?
+                    .mnemonic_phrase
+            }
+        };
+        let schemes = match self.scheme {
+            Some(scheme) => vec![scheme],
+            None => DerivationScheme::ALL.to_vec(),
+        };
+
+        let mut discovered = vec![];
+        for scheme in schemes {
+            let mut start = 0u32;
+            let mut inactive_count = 0u32;
+            while inactive_count < self.gap_limit {
+                let end = start.checked_add(self.gap_limit).ok_or_else(|| {
+                    KanariError::CommandArgumentError("The address index overflows".to_owned())
+                })?;
+                for derived in
+                    derive_addresses(&mnemonic_phrase, scheme, self.account, 0, start..end)?
+                {
+                    let (sequence_number, has_utxo) = query_activity(&client, &derived).await?;
+                    if sequence_number == 0 && !has_utxo {
+                        inactive_count += 1;
+                        if inactive_count >= self.gap_limit {
+                            break;
+                        }
+                        continue;
+                    }
+                    inactive_count = 0;
+                    let imported =
+                        !self.dry_run && !context.keystore.contains_address(&derived.address);
+                    if imported {
+                        let derivation_path = derived.derivation_path.clone();
+                        if external_mnemonic {
+                            context.keystore.import_mnemonic_account(
+                                mnemonic_phrase.clone(),
+                                derivation_path,
+                                password.clone(),
+                            )?;
+                        } else {
+                            context.keystore.generate_and_add_new_key_with_path(
+                                derivation_path,
+                                password.clone(),
+                            )?;
+                        }
+                    }
+                    discovered.push(DiscoveredAccountView {
+                        address: derived.address.into(),
+                        bitcoin_address: derived.bitcoin_address.clone().into(),
+                        derivation_path: derived.derivation_path.to_string(),
+                        sequence_number,
+                        has_utxo,
+                        imported,
+                    });
+                }
+                start = end;
+            }
+        }
+
+        if self.json {
+            Ok(Some(discovered))
+        } else {
+            if discovered.is_empty() {
+                println!("No active address is discovered");
+            }
+            for account in &discovered {
+                println!(
+                    "{} {} [{}] sequence number: {}, has UTXO: {}{}",
+                    account.derivation_path,
+                    account.address,
+                    account.bitcoin_address,
+                    account.sequence_number,
+                    account.has_utxo,
+                    if account.imported { ", imported" } else { "" }
+                );
+            }
+            Ok(None)
+        }
+    }
+}
+
+async fn query_activity(
+    client: &Client,
+    derived: &DerivedAddress,
+) -> Result<(u64, bool), anyhow::Error> {
+    let sequence_number = client.kanari.get_sequence_number(derived.address).await?;
+    let utxos = client
+        .kanari
+        .query_utxos(
+            UTXOFilterView::owner(derived.bitcoin_address.clone()),
+            None,
+            Some(1),
+            None,
+        )
+        .await?;
+    Ok((sequence_number, !utxos.data.is_empty()))
+}
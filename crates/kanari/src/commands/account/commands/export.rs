@@ -5,7 +5,8 @@ use crate::cli_types::{CommandAction, WalletContextOptions};
 use async_trait::async_trait;
 use clap::Parser;
 use kanari_key::keystore::account_keystore::AccountKeystore;
-use kanari_rpc_api::jsonrpc_types::export_view::ExportInfoView;
+use kanari_rpc_api::jsonrpc_types::export_view::{DerivationPathView, ExportInfoView};
+use kanari_rpc_client::wallet_context::WalletContext;
 use kanari_types::{
     address::{KanariAddress, ParsedAddress},
     error::{KanariError, KanariResult},
@@ -16,10 +17,17 @@ use kanari_types::{
 ///
 /// Default to export all addresses with a mnemonic phrase but can be specified with -a or
 /// --address to export only one address with a private key.
+/// With --path-info, the derivation path metadata of the address is exported instead of the
+/// private key, so that the mnemonic can be restored in a Bitcoin wallet.
 #[derive(Debug, Parser)]
 pub struct ExportCommand {
     #[clap(short = 'a', long = "address", value_parser=ParsedAddress::parse, default_value = "")]
     address: ParsedAddress,
+
+    /// Export the derivation path, account xpub and output descriptor of the address
+    #[clap(long)]
+    path_info: bool,
+
     #[clap(flatten)]
     pub context_options: WalletContextOptions,
 
@@ -33,6 +41,9 @@ impl CommandAction<Option<ExportInfoView>> for ExportCommand {
     async fn execute(self) -> KanariResult<Option<ExportInfoView>> {
         let mut context = self.context_options.build_require_password()?;
         let password = context.get_password();
+        if self.path_info {
+            return self.export_path_info(&context, password);
+        }
         let result = if self.address == ParsedAddress::Named("".to_owned()) {
             context.keystore.export_mnemonic_phrase(password)?
         } else {
@@ -70,3 +81,50 @@ impl CommandAction<Option<ExportInfoView>> for ExportCommand {
         }
     }
 }
+
+impl ExportCommand {
+    fn export_path_info(
+        self,
+        context: &WalletContext,
+        password: Option<String>,
+    ) -> KanariResult<Option<ExportInfoView>> {
+        if self.address == ParsedAddress::Named("".to_owned()) {
+            return Err(KanariError::CommandArgumentError(
+                "--path-info requires an address specified with -a or --address".to_owned(),
+            ));
+        }
+        let mapping = context.address_mapping();
+        let kanari_address: KanariAddress =
+            self.address.into_kanari_address(&mapping).map_err(|e| {
+                KanariError::CommandArgumentError(format!("Invalid Kanari address String: {}", e))
+            })?;
+        let info = context
+            .keystore
+            .export_derivation_path_info(&kanari_address, password.clone())?;
+        let scheme = info.scheme()?;
+        let public_key = context
+            .keystore
+            .get_key_pair(&kanari_address, password)?
+            .public();
+        let view = DerivationPathView {
+            derivation_path: info.derivation_path.clone(),
+            scheme: scheme.to_string(),
+            bitcoin_address: scheme.bitcoin_address(&public_key)?.into(),
+            master_fingerprint: info.master_fingerprint.clone(),
+            account_xpub: info.account_xpub.clone(),
+            descriptor: info.descriptor()?,
+        };
+
+        if self.json {
+            Ok(Some(ExportInfoView::new_derivation_path(view)))
+        } else {
+            println!("Derivation path    : {}", view.derivation_path);
+            println!("Scheme             : {}", view.scheme);
+            println!("Bitcoin address    : {}", view.bitcoin_address);
+            println!("Master fingerprint : {}", view.master_fingerprint);
+            println!("Account xpub       : {}", view.account_xpub);
+            println!("Descriptor         : {}", view.descriptor);
+            Ok(None)
+        }
+    }
+}
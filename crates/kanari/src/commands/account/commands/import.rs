@@ -3,24 +3,45 @@
 
 use crate::cli_types::{CommandAction, WalletContextOptions};
 use async_trait::async_trait;
+use bip32::DerivationPath;
 use clap::Parser;
 use fastcrypto::{secp256k1::Secp256k1KeyPair, traits::ToFromBytes};
+use kanari_key::key_derive::{
+    DerivationScheme, generate_derivation_path_with_scheme, parse_derivation_path,
+};
 use kanari_key::keystore::account_keystore::AccountKeystore;
 use kanari_types::{
-    crypto::KanariKeyPair,
+    crypto::{KanariKeyPair, PublicKey},
     error::{KanariError, KanariResult},
     kanari_key::ParsedSecretKey,
 };
+use std::str::FromStr;
 
 /// Import an external account from an encoded or a raw material private key into Kanari Key Store.
 /// The importing format shall be the same as the exported private keys.
 ///
 /// The command must be specified with -k or --secretkey to import an
-/// external account into Kanari Key Store.
+/// external account into Kanari Key Store, with --mnemonic to import the key of
+/// a derivation path of an external mnemonic, or with --public-key to import a watch-only account.
 #[derive(Debug, Parser)]
 pub struct ImportCommand {
-    #[clap(short = 'k', long = "secretkey", value_parser=ParsedSecretKey::parse, required = true)]
-    secretkey: ParsedSecretKey,
+    #[clap(short = 'k', long = "secretkey", value_parser=ParsedSecretKey::parse, required_unless_present_any = ["mnemonic", "public_key"], conflicts_with_all = ["mnemonic", "public_key"])]
+    secretkey: Option<ParsedSecretKey>,
+
+    /// The mnemonic phrase to derive the key from
+    #[clap(long, conflicts_with = "public_key")]
+    mnemonic: Option<String>,
+
+    /// The hex encoded public key of a watch-only account, a Bitcoin public key or a Kanari public key.
+    /// The transactions of a watch-only account can be built with this keystore and are signed offline
+    /// by the machine with the private key.
+    #[clap(long, value_parser = parse_public_key)]
+    public_key: Option<PublicKey>,
+
+    /// The derivation path of the key of the mnemonic, default to m/86'/0'/0'/0/0
+    #[clap(long, requires = "mnemonic", value_parser = parse_derivation_path)]
+    path: Option<DerivationPath>,
+
     #[clap(flatten)]
     pub context_options: WalletContextOptions,
 
@@ -34,25 +55,65 @@ impl CommandAction<Option<String>> for ImportCommand {
     async fn execute(self) -> KanariResult<Option<String>> {
         let mut context = self.context_options.build_require_password()?;
         let password = context.get_password();
-        let kp = KanariKeyPair::Secp256k1(
-            Secp256k1KeyPair::from_bytes(&self.secretkey.into_inner().secret_bytes()).map_err(
-                |e| KanariError::CommandArgumentError(format!("Invalid Kanari secret key: {}", e)),
-            )?,
-        );
-        let kanari_address = kp.public().kanari_address()?;
-        context
-            .keystore
-            .import_external_account(kanari_address, kp, password)?;
+        let kanari_address = match (self.secretkey, self.mnemonic, self.public_key) {
+            (Some(secretkey), _, _) => {
+                let kp = KanariKeyPair::Secp256k1(
+                    Secp256k1KeyPair::from_bytes(&secretkey.into_inner().secret_bytes()).map_err(
+                        |e| {
+                            KanariError::CommandArgumentError(format!(
+                                "Invalid Kanari secret key: {}",
+                                e
+                            ))
+                        },
+                    )?,
+                );
+                let kanari_address = kp.public().kanari_address()?;
+                context
+                    .keystore
+                    .import_external_account(kanari_address, kp, password)?;
+                kanari_address
+            }
+            (None, Some(mnemonic), _) => {
+                let derivation_path = match self.path {
+                    Some(path) => path,
+                    None => generate_derivation_path_with_scheme(DerivationScheme::Bip86, 0, 0, 0)?,
+                };
+                context
+                    .keystore
+                    .import_mnemonic_account(mnemonic, derivation_path, password)?
+            }
+            (None, None, Some(public_key)) => {
+                let kanari_address = public_key.kanari_address()?;
+                context
+                    .keystore
+                    .add_watch_only_account(kanari_address, public_key)?;
+                kanari_address
+            }
+            (None, None, None) => {
+                return Err(KanariError::CommandArgumentError(
+                    "One of --secretkey, --mnemonic or --public-key is required".to_owned(),
+                ));
+            }
+        };
 
         if self.json {
             Ok(Some(kanari_address.to_string()))
         } else {
-            println!(
-                "Import succeeded with address [{}] and the secret key",
-                kanari_address
-            );
+            println!("Import succeeded with address [{}]", kanari_address);
+            if context.keystore.is_watch_only(&kanari_address) {
+                println!(
+                    "The account is watch-only, sign its transactions offline with `kanari tx sign --offline`"
+                );
+            }
 
             Ok(None)
         }
     }
 }
+
+fn parse_public_key(s: &str) -> Result<PublicKey, anyhow::Error> {
+    match bitcoin::PublicKey::from_str(s.strip_prefix("0x").unwrap_or(s)) {
+        Ok(pk) => PublicKey::from_bitcoin_pubkey(&pk),
+        Err(_) => PublicKey::from_str(s),
+    }
+}
@@ -37,6 +37,7 @@ pub struct LocalAccountView {
     pub nostr_public_key: String,
     pub public_key: String,
     pub has_session_key: bool,
+    pub is_watch_only: bool,
 }
 
 impl LocalAccountView {
@@ -51,6 +52,7 @@ impl LocalAccountView {
             nostr_public_key: account.nostr_bech32_public_key,
             public_key: account.public_key.to_string(),
             has_session_key: account.has_session_key,
+            is_watch_only: account.is_watch_only,
         }
     }
 }
@@ -124,13 +126,20 @@ impl CommandAction<Option<AccountsView>> for ListCommand {
                     "Bitcoin Address",
                     "Public Key",
                     "Nostr Public Key",
+                    "Watch Only",
                 ];
+                let watch_only = if account.local_account.is_watch_only {
+                    "True".to_owned()
+                } else {
+                    "False".to_owned()
+                };
                 let values = [
                     &account.local_account.address,
                     &account.local_account.hex_address,
                     &account.local_account.bitcoin_address,
                     &account.local_account.public_key,
                     &account.local_account.nostr_public_key,
+                    &watch_only,
                 ];
 
                 let active = if account.active { "True" } else { "False" };
@@ -2,8 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod balance;
+pub mod change_password;
 pub mod create;
 pub mod create_multisign;
+pub mod discover;
 pub mod export;
 pub mod import;
 pub mod list;
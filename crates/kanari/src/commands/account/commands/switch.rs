@@ -37,7 +37,9 @@ impl CommandAction<Option<KanariAddressView>> for SwitchCommand {
                 KanariError::CommandArgumentError(format!("Invalid Kanari address String: {}", e))
             })?;
 
-        if !context.keystore.addresses().contains(&kanari_address) {
+        if !context.keystore.addresses().contains(&kanari_address)
+            && !context.keystore.is_watch_only(&kanari_address)
+        {
             return Err(KanariError::SwitchAccountError(format!(
                 "Address `{}` does not in the Kanari keystore",
                 kanari_address
@@ -63,12 +63,24 @@ impl CommandAction<ExecuteTransactionResponseView> for TransferCommand {
                 let tx = KanariTransaction::new(tx_data, authenticator.into());
                 context.execute(tx).await
             }
+            (_, Some(auth_key)) if self.tx_options.webauthn => {
+                let tx = context
+                    .sign_transaction_via_webauthn(&sender, tx_data, &auth_key)
+                    .map_err(|e| KanariError::SignMessageError(e.to_string()))?;
+                context.execute(tx).await
+            }
             (_, Some(auth_key)) => {
                 let tx = context
                     .sign_transaction_via_session_key(&sender, tx_data, &auth_key)
                     .map_err(|e| KanariError::SignMessageError(e.to_string()))?;
                 context.execute(tx).await
             }
+            (None, None) if self.tx_options.taproot => {
+                let tx = context
+                    .sign_transaction_via_taproot(sender, tx_data)
+                    .map_err(|e| KanariError::SignMessageError(e.to_string()))?;
+                context.execute(tx).await
+            }
             (None, None) => context.sign_and_execute(sender, tx_data).await,
         }
     }
@@ -131,6 +131,9 @@ impl CommandAction<Option<TransactionResultView>> for TxCommand {
                             MoveActionTypeView::ModuleBundle => {
                                 formatted_transaction_with_info.push("modulebundle".to_owned());
                             }
+                            MoveActionTypeView::Batch => {
+                                formatted_transaction_with_info.push("batch".to_owned());
+                            }
                         };
                         let move_action = MoveAction::from(tx.action);
                         let encoded_move_action_bytes = move_action.encode()?;
@@ -4,10 +4,11 @@
 use crate::cli_types::CommandAction;
 use async_trait::async_trait;
 use commands::{
-    balance::BalanceCommand, create::CreateCommand, create_multisign::CreateMultisignCommand,
-    export::ExportCommand, import::ImportCommand, list::ListCommand, nullify::NullifyCommand,
-    object::ObjectCommand, show::ShowCommand, sign::SignCommand, switch::SwitchCommand,
-    transfer::TransferCommand, tx::TxCommand, verify::VerifyCommand,
+    balance::BalanceCommand, change_password::ChangePasswordCommand, create::CreateCommand,
+    create_multisign::CreateMultisignCommand, discover::DiscoverCommand, export::ExportCommand,
+    import::ImportCommand, list::ListCommand, nullify::NullifyCommand, object::ObjectCommand,
+    show::ShowCommand, sign::SignCommand, switch::SwitchCommand, transfer::TransferCommand,
+    tx::TxCommand, verify::VerifyCommand,
 };
 use kanari_rpc_api::jsonrpc_types::json_to_table_display::json_to_table;
 use kanari_types::error::KanariResult;
@@ -51,6 +52,10 @@ impl CommandAction<String> for Account {
             }
             AccountCommand::Export(export) => export.execute_serialized().await,
             AccountCommand::Import(import) => import.execute_serialized().await,
+            AccountCommand::Discover(discover) => discover.execute_serialized().await,
+            AccountCommand::ChangePassword(change_password) => {
+                change_password.execute_serialized().await
+            }
             AccountCommand::Sign(sign) => sign.execute_serialized().await,
             AccountCommand::Verify(verify) => verify.execute_serialized().await,
             AccountCommand::Tx(tx) => tx.execute_serialized().await,
@@ -72,6 +77,8 @@ pub enum AccountCommand {
     Transfer(TransferCommand),
     Export(ExportCommand),
     Import(ImportCommand),
+    Discover(DiscoverCommand),
+    ChangePassword(ChangePasswordCommand),
     Sign(SignCommand),
     Verify(VerifyCommand),
     Tx(TxCommand),
@@ -3,6 +3,7 @@
 
 use super::FileOutput;
 use super::transaction_builder::TransactionBuilder;
+use super::utxo_selector::UTXOSelectStrategy;
 use crate::cli_types::{CommandAction, WalletContextOptions};
 use crate::commands::bitcoin::FileOutputData;
 use async_trait::async_trait;
@@ -103,10 +104,15 @@ pub struct BuildTx {
     change_address: Option<ParsedAddress>,
 
     /// Skip check seal of the UTXOs, default is false
-    /// If set to true, some UTXO which carries other asserts, such as Inscription, maybe unexpected spent.
+    /// If set to true, the specified inputs which carry other asserts, such as Inscription, maybe unexpected spent.
+    /// The UTXOs which carry Inscriptions are never selected automatically.
     #[clap(long)]
     skip_check_seal: bool,
 
+    /// The strategy to select the UTXOs of the sender
+    #[clap(long, value_enum, default_value_t = UTXOSelectStrategy::OldestFirst)]
+    utxo_strategy: UTXOSelectStrategy,
+
     /// The output file path for the psbt
     /// If not specified, the output will write to temp directory.
     #[clap(long)]
@@ -143,6 +149,7 @@ impl CommandAction<FileOutput> for BuildTx {
         if let Some(fee_rate) = self.fee_rate {
             tx_builder = tx_builder.with_fee_rate(fee_rate);
         }
+        tx_builder = tx_builder.with_utxo_select_strategy(self.utxo_strategy);
         if let Some(lock_time) = self.lock_time {
             tx_builder = tx_builder.with_lock_time(lock_time);
         }
@@ -9,6 +9,7 @@ use broadcast_tx::BroadcastTx;
 use build_tx::BuildTx;
 use clap::{Parser, Subcommand};
 use kanari_types::error::KanariResult;
+use proposal::Proposal;
 use serde::{Deserialize, Serialize};
 use sign_tx::SignTx;
 use std::{env, fs::File, io::Write, path::PathBuf};
@@ -16,6 +17,7 @@ use transfer::Transfer;
 
 pub mod broadcast_tx;
 pub mod build_tx;
+pub mod proposal;
 pub mod sign_tx;
 pub mod transaction_builder;
 pub mod transfer;
@@ -34,6 +36,7 @@ pub enum BitcoinCommands {
     SignTx(SignTx),
     BroadcastTx(BroadcastTx),
     Transfer(Transfer),
+    Proposal(Proposal),
 }
 
 #[async_trait]
@@ -44,6 +47,7 @@ impl CommandAction<String> for Bitcoin {
             BitcoinCommands::SignTx(sign_tx) => sign_tx.execute_serialized().await,
             BitcoinCommands::BroadcastTx(broadcast_tx) => broadcast_tx.execute_serialized().await,
             BitcoinCommands::Transfer(transfer) => transfer.execute_serialized().await,
+            BitcoinCommands::Proposal(proposal) => proposal.execute().await,
         }
     }
 }
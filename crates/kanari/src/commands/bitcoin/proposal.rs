@@ -0,0 +1,419 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, FileOrHexInput, TransactionOptions, WalletContextOptions};
+use crate::commands::bitcoin::sign_tx::{SignOutput, print_transaction_details, sign_psbt};
+use crate::utils::prompt_yes_no;
+use anyhow::{Result, bail, ensure};
+use async_trait::async_trait;
+use bitcoin::{
+    Psbt, Txid,
+    hashes::{Hash, sha256},
+    key::Secp256k1,
+    secp256k1::Message,
+};
+use clap::{Parser, Subcommand};
+use kanari_rpc_client::{Client, wallet_context::WalletContext};
+use kanari_types::{
+    address::{BitcoinAddress, KanariAddress, ParsedAddress},
+    bitcoin::multisign_account::MultisignAccountModule,
+    error::{KanariError, KanariResult},
+    into_address::{FromAddress, IntoAddress},
+    nursery::multisign_wallet::{
+        self, BitcoinProposalInfo, MultisignWalletModule, PROPOSAL_STATUS_APPROVED,
+        PROPOSAL_STATUS_PENDING,
+    },
+};
+use move_core_types::account_address::AccountAddress;
+use moveos_types::{module_binding::MoveFunctionCaller, transaction::MoveAction};
+use serde::{Deserialize, Serialize};
+
+/// Coordinate the multisign account Bitcoin transaction via the on-chain proposal
+#[derive(Debug, Parser)]
+pub struct Proposal {
+    #[clap(subcommand)]
+    cmd: ProposalCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ProposalCommand {
+    Create(CreateProposal),
+    List(ListProposal),
+    Sign(SignProposal),
+    Finalize(FinalizeProposal),
+}
+
+#[async_trait]
+impl CommandAction<String> for Proposal {
+    async fn execute(self) -> KanariResult<String> {
+        match self.cmd {
+            ProposalCommand::Create(create) => create.execute_serialized().await,
+            ProposalCommand::List(list) => list.execute_serialized().await,
+            ProposalCommand::Sign(sign) => sign.execute_serialized().await,
+            ProposalCommand::Finalize(finalize) => finalize.execute_serialized().await,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProposalOutput {
+    pub multisign_address: KanariAddress,
+    pub proposal_id: u64,
+    pub txid: String,
+    pub status: String,
+    /// The participants who have signed the proposal
+    pub participants: Vec<KanariAddress>,
+    /// The hex of the proposal PSBT
+    pub psbt: String,
+    /// The Bitcoin txid if the transaction has been broadcast by this command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub broadcast_txid: Option<String>,
+}
+
+impl From<BitcoinProposalInfo> for ProposalOutput {
+    fn from(info: BitcoinProposalInfo) -> Self {
+        let status = match info.status {
+            PROPOSAL_STATUS_PENDING => "pending",
+            PROPOSAL_STATUS_APPROVED => "approved",
+            _ => "rejected",
+        };
+        ProposalOutput {
+            multisign_address: info.multisign_address.into(),
+            proposal_id: info.proposal_id,
+            txid: Txid::from_address(info.tx_id).to_string(),
+            status: status.to_string(),
+            participants: info.participants.into_iter().map(Into::into).collect(),
+            psbt: hex::encode(&info.tx_data),
+            broadcast_txid: None,
+        }
+    }
+}
+
+/// Submit a PSBT spending the multisign account UTXOs as a proposal on-chain
+#[derive(Debug, Parser)]
+pub struct CreateProposal {
+    /// The input psbt file path or hex string
+    input: FileOrHexInput,
+
+    /// The multisign account address of the proposal
+    /// If not specified, we will find the multisign account from the PSBT inputs
+    #[clap(long, value_parser=ParsedAddress::parse)]
+    multisign_address: Option<ParsedAddress>,
+
+    #[clap(flatten)]
+    tx_options: TransactionOptions,
+
+    #[clap(flatten)]
+    context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<ProposalOutput> for CreateProposal {
+    async fn execute(self) -> KanariResult<ProposalOutput> {
+        let context = self.context_options.build_require_password()?;
+        let client = context.get_client().await?;
+        let sender: KanariAddress = context.resolve_address(self.tx_options.sender)?.into();
+
+        let psbt = Psbt::deserialize(&self.input.data)?;
+        let multisign_address = match self.multisign_address {
+            Some(address) => context.resolve_address(address)?,
+            None => find_multisign_address(&psbt, &client)?,
+        };
+        let txid = psbt.unsigned_tx.compute_txid();
+
+        let action = MultisignWalletModule::submit_bitcoin_proposal_action(
+            multisign_address,
+            txid.into_address(),
+            psbt.serialize(),
+        );
+        execute_action(&context, sender, action, self.tx_options.max_gas_amount).await?;
+
+        let wallet_module = client.as_module_binding::<MultisignWalletModule>();
+        let count = wallet_module.bitcoin_proposal_count(multisign_address)?;
+        // The proposals are appended, so search from the latest one
+        for proposal_id in (0..count).rev() {
+            let info = wallet_module.bitcoin_proposal_info(multisign_address, proposal_id)?;
+            if info.tx_id == txid.into_address() {
+                return Ok(info.into());
+            }
+        }
+        Err(KanariError::CommandArgumentError(format!(
+            "Cannot find the proposal of tx {} after submit",
+            txid
+        )))
+    }
+}
+
+/// List the Bitcoin proposals of the multisign account
+#[derive(Debug, Parser)]
+pub struct ListProposal {
+    /// The multisign account address
+    #[clap(long, value_parser=ParsedAddress::parse)]
+    multisign_address: ParsedAddress,
+
+    /// Include the approved and rejected proposals, by default only the pending proposals are listed
+    #[clap(long)]
+    all: bool,
+
+    #[clap(flatten)]
+    context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<Vec<ProposalOutput>> for ListProposal {
+    async fn execute(self) -> KanariResult<Vec<ProposalOutput>> {
+        let context = self.context_options.build()?;
+        let client = context.get_client().await?;
+        let multisign_address = context.resolve_address(self.multisign_address)?;
+
+        let wallet_module = client.as_module_binding::<MultisignWalletModule>();
+        let proposals = if self.all {
+            let count = wallet_module.bitcoin_proposal_count(multisign_address)?;
+            (0..count)
+                .map(|proposal_id| {
+                    wallet_module.bitcoin_proposal_info(multisign_address, proposal_id)
+                })
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            wallet_module.pending_bitcoin_proposals(multisign_address)?
+        };
+        Ok(proposals.into_iter().map(Into::into).collect())
+    }
+}
+
+/// Sign the proposal PSBT with the local keystore and submit the approval on-chain
+#[derive(Debug, Parser)]
+pub struct SignProposal {
+    /// The multisign account address
+    #[clap(long, value_parser=ParsedAddress::parse)]
+    multisign_address: ParsedAddress,
+
+    /// The proposal id
+    #[clap(long)]
+    proposal_id: u64,
+
+    /// Automatically answer 'yes' to all prompts
+    #[clap(long = "yes", short = 'y')]
+    answer_yes: bool,
+
+    #[clap(flatten)]
+    tx_options: TransactionOptions,
+
+    #[clap(flatten)]
+    context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<Option<ProposalOutput>> for SignProposal {
+    async fn execute(self) -> KanariResult<Option<ProposalOutput>> {
+        let context = self.context_options.build_require_password()?;
+        let client = context.get_client().await?;
+        let sender: KanariAddress = context.resolve_address(self.tx_options.sender)?.into();
+        let multisign_address = context.resolve_address(self.multisign_address)?;
+
+        let wallet_module = client.as_module_binding::<MultisignWalletModule>();
+        let info = wallet_module.bitcoin_proposal_info(multisign_address, self.proposal_id)?;
+        if !info.is_pending() {
+            return Err(KanariError::CommandArgumentError(format!(
+                "The proposal {} is not pending",
+                self.proposal_id
+            )));
+        }
+        if info.participants.contains(&sender.into()) {
+            return Err(KanariError::CommandArgumentError(format!(
+                "The proposal {} is already signed by {}",
+                self.proposal_id, sender
+            )));
+        }
+
+        let psbt = proposal_psbt(&info)?;
+        let network = context.get_bitcoin_network().await?;
+        print_transaction_details(&psbt, Some(network));
+        if !self.answer_yes && !prompt_yes_no("Do you want to sign this proposal?") {
+            return Ok(None);
+        }
+
+        let signer = ParsedAddress::Numerical(sender);
+        let output = sign_psbt(psbt, Some(signer), &context, Some(&client)).await?;
+        let (signed_psbt, finalized_tx) = match output {
+            SignOutput::Psbt(psbt) => (psbt.serialize(), None),
+            SignOutput::Tx(tx) => (vec![], Some(tx)),
+        };
+
+        let signature = sign_proposal_message(&context, &sender, info.tx_id)?;
+        let action = MultisignWalletModule::sign_bitcoin_proposal_with_psbt_action(
+            multisign_address,
+            self.proposal_id,
+            signature,
+            signed_psbt,
+        );
+        execute_action(&context, sender, action, self.tx_options.max_gas_amount).await?;
+
+        let info = wallet_module.bitcoin_proposal_info(multisign_address, self.proposal_id)?;
+        let mut output = ProposalOutput::from(info);
+        // The signature of this participant is enough to finalize the tx, broadcast it directly
+        if let Some(tx) = finalized_tx {
+            output.broadcast_txid =
+                Some(client.kanari.broadcast_bitcoin_tx(&tx, None, None).await?);
+        }
+        Ok(Some(output))
+    }
+}
+
+/// Combine the participants' PSBTs of an approved proposal, finalize and broadcast the tx.
+/// The malformed PSBTs or the ones for another tx are skipped with a warning.
+#[derive(Debug, Parser)]
+pub struct FinalizeProposal {
+    /// The multisign account address
+    #[clap(long, value_parser=ParsedAddress::parse)]
+    multisign_address: ParsedAddress,
+
+    /// The proposal id
+    #[clap(long)]
+    proposal_id: u64,
+
+    #[clap(flatten)]
+    context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<ProposalOutput> for FinalizeProposal {
+    async fn execute(self) -> KanariResult<ProposalOutput> {
+        let context = self.context_options.build_require_password()?;
+        let client = context.get_client().await?;
+        let multisign_address = context.resolve_address(self.multisign_address)?;
+
+        let wallet_module = client.as_module_binding::<MultisignWalletModule>();
+        let info = wallet_module.bitcoin_proposal_info(multisign_address, self.proposal_id)?;
+        if !info.is_approved() {
+            return Err(KanariError::CommandArgumentError(format!(
+                "The proposal {} is not approved, signatures: {}",
+                self.proposal_id,
+                info.participants.len()
+            )));
+        }
+
+        let threshold = client
+            .as_module_binding::<MultisignAccountModule>()
+            .threshold(multisign_address)? as usize;
+        let mut psbt = proposal_psbt(&info)?;
+        let mut combined = 0;
+        // A bad PSBT of one participant should not block the finalization, skip it and use the others
+        for (participant, signed_psbt) in info
+            .participants
+            .iter()
+            .zip(info.signed_psbts.iter())
+            .filter(|(_, psbt)| !psbt.is_empty())
+        {
+            match combine_signed_psbt(&psbt, signed_psbt) {
+                Ok(combined_psbt) => {
+                    psbt = combined_psbt;
+                    combined += 1;
+                }
+                Err(e) => eprintln!(
+                    "Skip the signed PSBT of the participant {}: {}",
+                    participant.to_hex_literal(),
+                    e
+                ),
+            }
+        }
+        if combined < threshold {
+            return Err(KanariError::CommandArgumentError(format!(
+                "The valid signed PSBTs of the participants are below the threshold, combined: {}, threshold: {}",
+                combined, threshold
+            )));
+        }
+
+        let tx = match sign_psbt(psbt, None, &context, Some(&client)).await? {
+            SignOutput::Tx(tx) => tx,
+            SignOutput::Psbt(_) => {
+                return Err(KanariError::CommandArgumentError(
+                    "The signatures of the participants are not enough to finalize the tx"
+                        .to_string(),
+                ));
+            }
+        };
+        let broadcast_txid = client.kanari.broadcast_bitcoin_tx(&tx, None, None).await?;
+
+        let mut output = ProposalOutput::from(info);
+        output.broadcast_txid = Some(broadcast_txid);
+        Ok(output)
+    }
+}
+
+/// Find the only multisign account which the PSBT inputs spend from
+fn find_multisign_address(psbt: &Psbt, client: &Client) -> Result<AccountAddress> {
+    let multisign_account_module = client.as_module_binding::<MultisignAccountModule>();
+    let mut multisign_addresses = vec![];
+    for input in &psbt.inputs {
+        if let Some(utxo) = input.witness_utxo.as_ref() {
+            let addr: AccountAddress = BitcoinAddress::from(&utxo.script_pubkey)
+                .to_kanari_address()
+                .into();
+            if !multisign_addresses.contains(&addr)
+                && multisign_account_module.is_multisign_account(addr)?
+            {
+                multisign_addresses.push(addr);
+            }
+        }
+    }
+    match multisign_addresses.as_slice() {
+        [address] => Ok(*address),
+        [] => bail!("No multisign account input found in the PSBT"),
+        _ => bail!("The PSBT spends from multiple multisign accounts, please specify one"),
+    }
+}
+
+/// Combine the signed PSBT of a participant into a copy of the proposal PSBT, it fails if the
+/// signed PSBT is malformed or is for another tx
+fn combine_signed_psbt(psbt: &Psbt, signed_psbt: &[u8]) -> Result<Psbt> {
+    let signed_psbt = Psbt::deserialize(signed_psbt)?;
+    ensure!(
+        signed_psbt.unsigned_tx.compute_txid() == psbt.unsigned_tx.compute_txid(),
+        "The signed PSBT is not for the proposal tx"
+    );
+    let mut combined = psbt.clone();
+    combined.combine(signed_psbt)?;
+    Ok(combined)
+}
+
+fn proposal_psbt(info: &BitcoinProposalInfo) -> Result<Psbt> {
+    let psbt = Psbt::deserialize(&info.tx_data)?;
+    ensure!(
+        psbt.unsigned_tx.compute_txid().into_address() == info.tx_id,
+        "The proposal PSBT does not match the proposal txid"
+    );
+    Ok(psbt)
+}
+
+/// Sign the approval of the proposal, the on-chain module verifies it via `ecdsa_k1` with sha256
+fn sign_proposal_message(
+    context: &WalletContext,
+    sender: &KanariAddress,
+    tx_id: AccountAddress,
+) -> Result<Vec<u8>> {
+    let kp = context.get_key_pair(sender)?;
+    let sk = kp
+        .secp256k1_secret_key()
+        .ok_or_else(|| anyhow::anyhow!("The key of {} is not a secp256k1 key", sender))?;
+    let message = multisign_wallet::bitcoin_proposal_sign_message(tx_id);
+    let digest = sha256::Hash::hash(&message);
+    let secp = Secp256k1::signing_only();
+    let signature = secp.sign_ecdsa(&Message::from_digest(digest.to_byte_array()), &sk);
+    Ok(signature.serialize_compact().to_vec())
+}
+
+async fn execute_action(
+    context: &WalletContext,
+    sender: KanariAddress,
+    action: MoveAction,
+    max_gas_amount: Option<u64>,
+) -> KanariResult<()> {
+    let tx_data = context
+        .build_tx_data(sender, action, max_gas_amount)
+        .await?;
+    let signed_tx = context.sign_transaction(sender, tx_data)?;
+    let result = context.execute(signed_tx).await?;
+    context.assert_execute_success(result)?;
+    Ok(())
+}
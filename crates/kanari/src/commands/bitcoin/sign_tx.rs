@@ -10,7 +10,7 @@ use anyhow::Result;
 use anyhow::bail;
 use async_trait::async_trait;
 use bitcoin::{
-    Address, Network, Psbt, TapLeafHash, TapSighashType, Witness,
+    Address, Network, Psbt, TapLeafHash, TapSighashType, Witness, XOnlyPublicKey,
     key::{Keypair, Secp256k1, TapTweak},
     sighash::{Prevouts, SighashCache},
 };
@@ -19,7 +19,7 @@ use kanari_key::keystore::account_keystore::AccountKeystore;
 use kanari_rpc_client::{Client, wallet_context::WalletContext};
 use kanari_types::{
     address::{BitcoinAddress, KanariAddress, ParsedAddress},
-    bitcoin::multisign_account::MultisignAccountModule,
+    bitcoin::multisign_account::{MultisignAccountModule, parse_multisig_script},
     error::{KanariError, KanariResult},
 };
 use moveos_types::module_binding::MoveFunctionCaller;
@@ -40,6 +40,11 @@ pub struct SignTx {
     #[clap(long)]
     output_file: Option<String>,
 
+    /// Sign without connecting to the network, on an air-gapped machine for example.
+    /// The threshold and the participants of a multisign input are parsed from its tap script.
+    #[clap(long)]
+    offline: bool,
+
     /// Automatically answer 'yes' to all prompts
     #[clap(long = "yes", short = 'y')]
     answer_yes: bool,
@@ -62,7 +67,11 @@ pub enum SignOutput {
 impl CommandAction<Option<FileOutput>> for SignTx {
     async fn execute(self) -> KanariResult<Option<FileOutput>> {
         let context = self.context_options.build_require_password()?;
-        let client = context.get_client().await?;
+        let client = if self.offline {
+            None
+        } else {
+            Some(context.get_client().await?)
+        };
 
         let psbt = Psbt::deserialize(&self.input.data)?;
         print_transaction_details(&psbt, self.network);
@@ -70,7 +79,7 @@ impl CommandAction<Option<FileOutput>> for SignTx {
             return Ok(None);
         }
         debug!("psbt before sign: {:?}", psbt);
-        let output = sign_psbt(psbt, self.signer, &context, &client).await?;
+        let output = sign_psbt(psbt, self.signer, &context, client.as_ref()).await?;
         debug!("sign output: {:?}", output);
 
         let file_output_data = match output {
@@ -82,21 +91,75 @@ impl CommandAction<Option<FileOutput>> for SignTx {
     }
 }
 
+/// The threshold and the participants of a multisign account input
+struct MultisignInput {
+    threshold: usize,
+    participants: Vec<(KanariAddress, XOnlyPublicKey)>,
+}
+
+async fn get_multisign_input(
+    input: &bitcoin::psbt::Input,
+    kanari_addr: KanariAddress,
+    client: Option<&Client>,
+) -> Result<Option<MultisignInput>> {
+    match client {
+        Some(client) => {
+            let multisign_account_module = client.as_module_binding::<MultisignAccountModule>();
+            if !multisign_account_module.is_multisign_account(kanari_addr.into())? {
+                return Ok(None);
+            }
+            let account_info = client
+                .kanari
+                .get_multisign_account_info(kanari_addr)
+                .await?;
+            debug!("Account info: {:?}", account_info);
+            let participants = account_info
+                .participants
+                .values()
+                .iter()
+                .map(|p| Ok((p.participant_address.into(), p.x_only_public_key()?)))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Some(MultisignInput {
+                threshold: account_info.threshold as usize,
+                participants,
+            }))
+        }
+        None => {
+            let Some((_, (multisig_script, _))) = input.tap_scripts.iter().next() else {
+                return Ok(None);
+            };
+            let (threshold, public_keys) = parse_multisig_script(multisig_script)?;
+            let secp = Secp256k1::verification_only();
+            let participants = public_keys
+                .into_iter()
+                .map(|pk| {
+                    let address = Address::p2tr(&secp, pk, None, Network::Bitcoin);
+                    (BitcoinAddress::from(address).to_kanari_address(), pk)
+                })
+                .collect();
+            Ok(Some(MultisignInput {
+                threshold,
+                participants,
+            }))
+        }
+    }
+}
+
+/// Sign the psbt with the keys in the keystore, the client is used to query the multisign
+/// account info, the psbt is signed offline if the client is None
 pub(crate) async fn sign_psbt(
     mut psbt: Psbt,
     signer: Option<ParsedAddress>,
     context: &WalletContext,
-    client: &Client,
+    client: Option<&Client>,
 ) -> Result<SignOutput, anyhow::Error> {
     let secp = Secp256k1::new();
 
     let signer = match signer {
-        Some(signer) => Some(context.resolve_bitcoin_address(signer).await?),
+        Some(signer) => Some(context.resolve_kanari_address(signer)?),
         None => None,
     };
 
-    let multisign_account_module = client.as_module_binding::<MultisignAccountModule>();
-
     let spend_utxos = (0..psbt.inputs.len())
         .map(|i| psbt.spend_utxo(i).ok().cloned())
         .collect::<Vec<_>>();
@@ -114,12 +177,7 @@ pub(crate) async fn sign_psbt(
         if let Some(utxo) = input.witness_utxo.as_ref() {
             let addr = BitcoinAddress::from(&utxo.script_pubkey);
             let kanari_addr = addr.to_kanari_address();
-            if multisign_account_module.is_multisign_account(kanari_addr.into())? {
-                let account_info = client
-                    .kanari
-                    .get_multisign_account_info(kanari_addr)
-                    .await?;
-                debug!("Account info: {:?}", account_info);
+            if let Some(multisign) = get_multisign_input(input, kanari_addr, client).await? {
                 let (control_block, (multisig_script, leaf_version)) = input
                     .tap_scripts
                     .iter()
@@ -138,16 +196,13 @@ pub(crate) async fn sign_psbt(
                     hash_ty,
                 )?;
                 debug!("Calculated sighash: {:?}", sighash);
-                for participant in account_info.participants.values() {
-                    if let Some(signer) = &signer {
-                        if signer != &participant.participant_bitcoin_address {
-                            continue;
-                        }
+                for (participant_addr, _) in &multisign.participants {
+                    if signer.is_some_and(|signer| signer != *participant_addr) {
+                        continue;
                     }
-                    let participant_addr: KanariAddress = participant.participant_address.into();
-                    if context.keystore.contains_address(&participant_addr) {
+                    if context.keystore.contains_address(participant_addr) {
                         debug!("Signing for participant: {}", participant_addr);
-                        let kp = context.get_key_pair(&participant_addr)?;
+                        let kp = context.get_key_pair(participant_addr)?;
                         let our_pubkey = kp.public().xonly_public_key()?;
 
                         let sk = kp.secp256k1_secret_key().expect("should have secret key");
@@ -166,17 +221,16 @@ pub(crate) async fn sign_psbt(
                 }
 
                 //Try to finalize the psbt
-                if input.tap_script_sigs.len() >= account_info.threshold as usize {
+                if input.tap_script_sigs.len() >= multisign.threshold {
                     //TODO handle multiple tap_leaf case
 
                     //make sure the signature order same as the public key order
                     let mut ordered_signatures = vec![];
-                    let mut x_only_public_keys = account_info
+                    let mut x_only_public_keys = multisign
                         .participants
-                        .values()
                         .iter()
-                        .map(|p| p.x_only_public_key())
-                        .collect::<Result<Vec<_>>>()?;
+                        .map(|(_, pk)| *pk)
+                        .collect::<Vec<_>>();
                     x_only_public_keys.sort();
                     //Becase the stack is LIFO, we need to reverse the order
                     x_only_public_keys.reverse();
@@ -254,7 +308,7 @@ fn is_psbt_finalized(psbt: &Psbt) -> bool {
         .all(|input| input.final_script_sig.is_some() || input.final_script_witness.is_some())
 }
 
-fn print_transaction_details(psbt: &Psbt, network: Option<Network>) {
+pub(crate) fn print_transaction_details(psbt: &Psbt, network: Option<Network>) {
     println!("Transaction details before signing:");
     println!("  Version: {}", psbt.unsigned_tx.version);
     println!("  Lock time: {}", psbt.unsigned_tx.lock_time);
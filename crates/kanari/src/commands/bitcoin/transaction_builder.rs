@@ -1,7 +1,7 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
-use super::utxo_selector::UTXOSelector;
+use super::utxo_selector::{UTXOSelectStrategy, UTXOSelector};
 use anyhow::{Result, anyhow, bail};
 use bitcoin::{
     Address, Amount, FeeRate, OutPoint, Psbt, ScriptBuf, Sequence, Transaction, TxIn, TxOut,
@@ -62,6 +62,11 @@ impl<'a> TransactionBuilder<'a> {
         self
     }
 
+    pub fn with_utxo_select_strategy(mut self, strategy: UTXOSelectStrategy) -> Self {
+        self.utxo_selector = self.utxo_selector.with_strategy(strategy);
+        self
+    }
+
     fn estimate_vbytes_with(inputs: usize, outputs: Vec<Address>) -> usize {
         Transaction {
             version: Version::TWO,
@@ -89,36 +94,53 @@ impl<'a> TransactionBuilder<'a> {
         self.build(vec![(receipient, amount)]).await
     }
 
+    fn estimate_fee(&self, inputs: usize, outputs: Vec<Address>) -> Result<Amount> {
+        self.fee_rate
+            .fee_vb(Self::estimate_vbytes_with(inputs, outputs) as u64)
+            .ok_or_else(|| anyhow!("Failed to estimate fee: {}", self.fee_rate))
+    }
+
     pub async fn build(mut self, outputs: Vec<(Address, Amount)>) -> Result<Psbt> {
         let total_output = outputs.iter().map(|(_, amount)| *amount).sum::<Amount>();
         let output_address = outputs
             .iter()
             .map(|(address, _)| address.clone())
             .collect::<Vec<_>>();
-        let estimate_inputs = if self.utxo_selector.specific_utxos().is_empty() {
-            1
-        } else {
-            self.utxo_selector.specific_utxos().len()
-        };
-        let estimate_fee = self
+        let mut output_address_with_change = output_address.clone();
+        output_address_with_change.push(self.change_address.clone());
+
+        // The fee of the inputs is covered by the effective value of the selected UTXOs
+        let base_fee = self.estimate_fee(0, output_address_with_change.clone())?;
+        // The cost to create the change output and to spend it later
+        let cost_of_change = self
             .fee_rate
-            .fee_vb(
-                (Self::estimate_vbytes_with(estimate_inputs, output_address)
-                    + Self::ADDITIONAL_INPUT_VBYTES
-                    + Self::ADDITIONAL_OUTPUT_VBYTES) as u64,
-            )
+            .fee_vb((Self::ADDITIONAL_OUTPUT_VBYTES + Self::ADDITIONAL_INPUT_VBYTES) as u64)
             .ok_or_else(|| anyhow!("Failed to estimate fee: {}", self.fee_rate))?;
         let mut utxos = self
             .utxo_selector
-            .select_utxos(total_output + estimate_fee)
+            .select_utxos(total_output + base_fee, self.fee_rate, cost_of_change)
             .await?;
-        let mut tx_inputs = vec![];
-        let mut total_input = Amount::from_sat(0);
-        for utxo in utxos.iter() {
-            tx_inputs.push(Self::utxo_to_txin(utxo));
-            total_input += utxo.amount();
+        let mut total_input = utxos.iter().map(|utxo| utxo.amount()).sum::<Amount>();
+
+        let fee = self.estimate_fee(utxos.len(), output_address.clone())?;
+        if total_input < total_output + fee {
+            //we need to add more inputs
+            let additional_utxos = self
+                .utxo_selector
+                .select_utxos(
+                    total_output + fee - total_input,
+                    self.fee_rate,
+                    cost_of_change,
+                )
+                .await?;
+            total_input += additional_utxos
+                .iter()
+                .map(|utxo| utxo.amount())
+                .sum::<Amount>();
+            utxos.extend(additional_utxos);
         }
 
+        let tx_inputs = utxos.iter().map(Self::utxo_to_txin).collect::<Vec<_>>();
         let tx_outputs = outputs
             .into_iter()
             .map(|(address, amount)| TxOut {
@@ -133,31 +155,23 @@ impl<'a> TransactionBuilder<'a> {
             input: tx_inputs,
             output: tx_outputs,
         };
-        let fee = self
-            .fee_rate
-            .fee_vb(tx.vsize() as u64)
-            .ok_or_else(|| anyhow!("Failed to estimate fee: {}", self.fee_rate))?;
-        if fee > estimate_fee && total_input < total_output + fee {
-            //we need to add more inputs
-            let additional_utxos = self
-                .utxo_selector
-                .select_utxos(total_output + fee - total_input)
-                .await?;
-            tx.input
-                .extend(additional_utxos.iter().map(Self::utxo_to_txin));
-            total_input += additional_utxos
-                .iter()
-                .map(|utxo| utxo.amount())
-                .sum::<Amount>();
-            utxos.extend(additional_utxos);
-        }
 
-        let change = total_input - total_output - fee;
-        if change > Amount::from_sat(0) {
-            tx.output.push(TxOut {
-                value: change,
-                script_pubkey: self.change_address.script_pubkey(),
-            });
+        let fee_with_change = self.estimate_fee(utxos.len(), output_address_with_change)?;
+        let change_script_pubkey = self.change_address.script_pubkey();
+        let min_change = self
+            .utxo_selector
+            .min_change(change_script_pubkey.minimal_non_dust(), cost_of_change);
+        // Avoid creating dust change, the small change is added to the fee
+        match total_input.checked_sub(total_output + fee_with_change) {
+            Some(change) if change > min_change => {
+                tx.output.push(TxOut {
+                    value: change,
+                    script_pubkey: change_script_pubkey,
+                });
+            }
+            _ => {
+                debug!("No change output, fee: {}", total_input - total_output);
+            }
         }
         let mut psbt = Psbt::from_unsigned_tx(tx)?;
 
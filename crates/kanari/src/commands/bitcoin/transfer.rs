@@ -3,6 +3,7 @@
 
 use super::sign_tx::SignOutput;
 use super::transaction_builder::TransactionBuilder;
+use super::utxo_selector::UTXOSelectStrategy;
 use crate::cli_types::{CommandAction, WalletContextOptions};
 use crate::commands::bitcoin::sign_tx::sign_psbt;
 use async_trait::async_trait;
@@ -31,10 +32,15 @@ pub struct Transfer {
     fee_rate: Option<FeeRate>,
 
     /// Skip check seal of the UTXOs, default is false
-    /// If set to true, some UTXO which carries other asserts, such as Inscription, maybe unexpected spent.
+    /// If set to true, the specified inputs which carry other asserts, such as Inscription, maybe unexpected spent.
+    /// The UTXOs which carry Inscriptions are never selected automatically.
     #[clap(long)]
     skip_check_seal: bool,
 
+    /// The strategy to select the UTXOs of the sender
+    #[clap(long, value_enum, default_value_t = UTXOSelectStrategy::OldestFirst)]
+    utxo_strategy: UTXOSelectStrategy,
+
     #[clap(flatten)]
     pub(crate) context_options: WalletContextOptions,
 }
@@ -63,12 +69,13 @@ impl CommandAction<String> for Transfer {
         if let Some(fee_rate) = self.fee_rate {
             tx_builder = tx_builder.with_fee_rate(fee_rate);
         }
+        tx_builder = tx_builder.with_utxo_select_strategy(self.utxo_strategy);
 
         let psbt = tx_builder
             .build_transfer(to.to_bitcoin_address(bitcoin_network)?, amount)
             .await?;
         debug!("PSBT: {}", serde_json::to_string_pretty(&psbt).unwrap());
-        let sign_out = sign_psbt(psbt, None, &context, &client).await?;
+        let sign_out = sign_psbt(psbt, None, &context, Some(&client)).await?;
         match sign_out {
             SignOutput::Psbt(_psbt) => {
                 return Err(KanariError::CommandArgumentError(
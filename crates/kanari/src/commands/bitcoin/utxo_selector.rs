@@ -3,28 +3,54 @@
 
 use std::collections::VecDeque;
 
-use anyhow::{Result, bail};
-use bitcoin::{Address, Amount};
+use anyhow::{Result, anyhow, bail};
+use bitcoin::{Address, Amount, FeeRate};
 use kanari_rpc_api::jsonrpc_types::{
     IndexerStateIDView,
     btc::utxo::{UTXOFilterView, UTXOObjectView, UTXOStateView},
 };
 use kanari_rpc_client::Client;
+use kanari_types::bitcoin::bbn::BBNStakeSeal;
+use kanari_types::bitcoin::ord::Inscription;
 use kanari_types::bitcoin::{types::OutPoint, utxo::derive_utxo_id};
 use moveos_types::moveos_std::object::{GENESIS_STATE_ROOT, ObjectID};
+use moveos_types::state::MoveStructType;
 use tracing::debug;
 
+/// The max tries of the branch and bound search, same as Bitcoin Core
+const BNB_TOTAL_TRIES: usize = 100_000;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum UTXOSelectStrategy {
+    /// Select the oldest UTXOs first, avoid spending immature coinbase outputs
+    #[default]
+    OldestFirst,
+    /// Select the largest UTXOs first, minimize the number of inputs
+    LargestFirst,
+    /// Search an input set which covers the amount without a change output,
+    /// fallback to largest-first if there is no such input set
+    BranchAndBound,
+    /// Only spend the UTXOs of the sender address and prefer a single UTXO,
+    /// avoid linking the UTXOs together
+    SingleAddress,
+}
+
 #[derive(Debug)]
 pub struct UTXOSelector {
     client: Client,
     sender: Address,
     specific_utxos: Vec<ObjectID>,
+    required_utxos: Vec<UTXOObjectView>,
     loaded_page: Option<(Option<IndexerStateIDView>, bool)>,
     candidate_utxos: VecDeque<UTXOObjectView>,
     skip_seal_check: bool,
+    strategy: UTXOSelectStrategy,
 }
 
 impl UTXOSelector {
+    /// The estimated vbytes of a P2TR key path input
+    pub const INPUT_VBYTES: u64 = 58;
+
     pub async fn new(
         client: Client,
         sender: Address,
@@ -35,14 +61,25 @@ impl UTXOSelector {
             client,
             sender,
             specific_utxos,
+            required_utxos: vec![],
             loaded_page: None,
             candidate_utxos: VecDeque::new(),
             skip_seal_check,
+            strategy: UTXOSelectStrategy::default(),
         };
         selector.load_specific_utxos().await?;
         Ok(selector)
     }
 
+    pub fn with_strategy(mut self, strategy: UTXOSelectStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    pub fn strategy(&self) -> UTXOSelectStrategy {
+        self.strategy
+    }
+
     async fn load_specific_utxos(&mut self) -> Result<()> {
         if self.specific_utxos.is_empty() {
             return Ok(());
@@ -76,7 +113,7 @@ impl UTXOSelector {
                     utxo_state_view.metadata
                 );
             }
-            self.candidate_utxos.push_front(utxo_state_view.into());
+            self.required_utxos.push(utxo_state_view.into());
         }
         Ok(())
     }
@@ -100,6 +137,15 @@ impl UTXOSelector {
         let minimal_non_dust = self.sender.script_pubkey().minimal_non_dust();
         for utxo_view in utxo_page.data {
             let utxo = &utxo_view.value;
+            // The specific UTXOs are always selected, do not select them twice
+            if self.specific_utxos.contains(&utxo_view.metadata.id) {
+                continue;
+            }
+            // The UTXOs which carry Inscriptions or other assets are never selected automatically,
+            // even if the seal check is skipped, they can only be spent via the specific inputs
+            if carries_asset(&utxo_view) {
+                continue;
+            }
             if !self.skip_seal_check && skip_utxo(&utxo_view, minimal_non_dust) {
                 continue;
             }
@@ -119,6 +165,16 @@ impl UTXOSelector {
         Ok(())
     }
 
+    async fn load_all_utxos(&mut self) -> Result<()> {
+        while self
+            .loaded_page
+            .is_none_or(|(_, has_next_page)| has_next_page)
+        {
+            self.load_utxos().await?;
+        }
+        Ok(())
+    }
+
     /// Get the next utxo from the candidate utxos
     pub async fn next_utxo(&mut self) -> Result<Option<UTXOObjectView>> {
         if self.candidate_utxos.is_empty() {
@@ -127,21 +183,136 @@ impl UTXOSelector {
         Ok(self.candidate_utxos.pop_back())
     }
 
-    pub async fn select_utxos(&mut self, expected_amount: Amount) -> Result<Vec<UTXOObjectView>> {
+    /// Select UTXOs whose effective value covers the `target` amount.
+    /// The effective value of a UTXO is its amount minus the fee to spend it at `fee_rate`,
+    /// so the `target` should not include the fee of the inputs.
+    /// The specific UTXOs are always selected first.
+    pub async fn select_utxos(
+        &mut self,
+        target: Amount,
+        fee_rate: FeeRate,
+        cost_of_change: Amount,
+    ) -> Result<Vec<UTXOObjectView>> {
+        let input_fee = Self::input_fee(fee_rate)?;
+        let mut utxos = std::mem::take(&mut self.required_utxos);
+        if self.strategy == UTXOSelectStrategy::SingleAddress {
+            let sender = self.sender.to_string();
+            if let Some(utxo) = utxos
+                .iter()
+                .find(|utxo| utxo.metadata.owner_bitcoin_address.as_ref() != Some(&sender))
+            {
+                bail!(
+                    "UTXO {} is not owned by the sender {}, can not use single-address strategy",
+                    utxo.outpoint(),
+                    sender
+                );
+            }
+        }
+        let required_value = utxos
+            .iter()
+            .map(|utxo| effective_value(utxo, input_fee))
+            .sum::<Amount>();
+        if required_value >= target {
+            return Ok(utxos);
+        }
+        let target = target - required_value;
+        let selected = match self.strategy {
+            UTXOSelectStrategy::OldestFirst => self.select_oldest_first(target, input_fee).await?,
+            UTXOSelectStrategy::LargestFirst => {
+                self.load_all_utxos().await?;
+                self.take_candidates(input_fee, |values| {
+                    select_largest_first(values, target.to_sat())
+                })?
+            }
+            UTXOSelectStrategy::BranchAndBound => {
+                self.load_all_utxos().await?;
+                self.take_candidates(input_fee, |values| {
+                    select_branch_and_bound(values, target.to_sat(), cost_of_change.to_sat())
+                        .or_else(|| select_largest_first(values, target.to_sat()))
+                })?
+            }
+            UTXOSelectStrategy::SingleAddress => {
+                self.load_all_utxos().await?;
+                self.take_candidates(input_fee, |values| {
+                    select_smallest_sufficient(values, target.to_sat())
+                        .map(|idx| vec![idx])
+                        .or_else(|| select_largest_first(values, target.to_sat()))
+                })?
+            }
+        };
+        utxos.extend(selected);
+        Ok(utxos)
+    }
+
+    /// The minimal change amount, the change below it is added to the fee
+    pub fn min_change(&self, dust: Amount, cost_of_change: Amount) -> Amount {
+        match self.strategy {
+            // The branch and bound selection expects no change if the excess is less than the cost of change
+            UTXOSelectStrategy::BranchAndBound => dust.max(cost_of_change),
+            _ => dust,
+        }
+    }
+
+    async fn select_oldest_first(
+        &mut self,
+        target: Amount,
+        input_fee: Amount,
+    ) -> Result<Vec<UTXOObjectView>> {
         let mut utxos = vec![];
-        let mut total_input = Amount::from_sat(0);
-        while total_input < expected_amount {
-            let utxo = self.next_utxo().await?;
-            if utxo.is_none() {
-                bail!("not enough BTC funds");
-            }
-            let utxo = utxo.unwrap();
-            total_input += utxo.amount();
+        let mut total_value = Amount::from_sat(0);
+        while total_value < target {
+            let utxo = self
+                .next_utxo()
+                .await?
+                .ok_or_else(|| anyhow!("not enough BTC funds"))?;
+            let value = effective_value(&utxo, input_fee);
+            if value == Amount::ZERO {
+                debug!(
+                    "UTXO {} can not pay the fee to spend itself: {}, skip.",
+                    utxo.outpoint(),
+                    input_fee
+                );
+                continue;
+            }
+            total_value += value;
             utxos.push(utxo);
         }
         Ok(utxos)
     }
 
+    /// Select from all the loaded candidates with the given `select` function,
+    /// the unselected candidates are kept for the following selection
+    fn take_candidates<F>(&mut self, input_fee: Amount, select: F) -> Result<Vec<UTXOObjectView>>
+    where
+        F: FnOnce(&[u64]) -> Option<Vec<usize>>,
+    {
+        let (economical, uneconomical): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.candidate_utxos)
+                .into_iter()
+                .partition(|utxo| utxo.amount() > input_fee);
+        let values = economical
+            .iter()
+            .map(|utxo| effective_value(utxo, input_fee).to_sat())
+            .collect::<Vec<_>>();
+        let selected = select(&values).ok_or_else(|| anyhow!("not enough BTC funds"))?;
+        let mut utxos = vec![];
+        for (idx, utxo) in economical.into_iter().enumerate() {
+            if selected.contains(&idx) {
+                utxos.push(utxo);
+            } else {
+                self.candidate_utxos.push_back(utxo);
+            }
+        }
+        self.candidate_utxos.extend(uneconomical);
+        Ok(utxos)
+    }
+
+    fn input_fee(fee_rate: FeeRate) -> Result<Amount> {
+        fee_rate
+            .fee_vb(Self::INPUT_VBYTES)
+            .ok_or_else(|| anyhow!("Failed to estimate fee: {}", fee_rate))
+    }
+
     pub fn specific_utxos(&self) -> &[ObjectID] {
         &self.specific_utxos
     }
@@ -156,6 +327,122 @@ impl UTXOSelector {
     }
 }
 
+/// The amount of the UTXO minus the fee to spend it
+fn effective_value(utxo: &UTXOObjectView, input_fee: Amount) -> Amount {
+    utxo.amount()
+        .checked_sub(input_fee)
+        .unwrap_or(Amount::from_sat(0))
+}
+
+/// Select the largest values first until the `target` is covered
+fn select_largest_first(values: &[u64], target: u64) -> Option<Vec<usize>> {
+    let mut pool = (0..values.len()).collect::<Vec<_>>();
+    pool.sort_by(|a, b| values[*b].cmp(&values[*a]));
+    let mut selected = vec![];
+    let mut total = 0u64;
+    for idx in pool {
+        if total >= target {
+            break;
+        }
+        selected.push(idx);
+        total += values[idx];
+    }
+    (total >= target).then_some(selected)
+}
+
+/// Select the smallest single value which covers the `target`
+fn select_smallest_sufficient(values: &[u64], target: u64) -> Option<usize> {
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, value)| **value >= target)
+        .min_by_key(|(_, value)| **value)
+        .map(|(idx, _)| idx)
+}
+
+/// Depth first search for the values whose sum is in `[target, target + cost_of_change]`,
+/// port of the branch and bound coin selection of Bitcoin Core.
+/// Return the selection with the least excess, or None if not found.
+fn select_branch_and_bound(values: &[u64], target: u64, cost_of_change: u64) -> Option<Vec<usize>> {
+    let mut pool = (0..values.len()).collect::<Vec<_>>();
+    pool.sort_by(|a, b| values[*b].cmp(&values[*a]));
+    let mut curr_available = values.iter().sum::<u64>();
+    if curr_available < target {
+        return None;
+    }
+    let upper_bound = target.saturating_add(cost_of_change);
+    let mut curr_value = 0u64;
+    // The positions in the pool of the included values
+    let mut curr_selection: Vec<usize> = vec![];
+    let mut best_selection: Option<Vec<usize>> = None;
+    let mut best_excess = u64::MAX;
+    let mut pos = 0usize;
+    for _ in 0..BNB_TOTAL_TRIES {
+        let mut backtrack = false;
+        if curr_value + curr_available < target || curr_value > upper_bound {
+            backtrack = true;
+        } else if curr_value >= target {
+            let excess = curr_value - target;
+            if excess < best_excess {
+                best_excess = excess;
+                best_selection = Some(curr_selection.clone());
+            }
+            if excess == 0 {
+                break;
+            }
+            backtrack = true;
+        }
+
+        if backtrack {
+            let Some(last) = curr_selection.pop() else {
+                // All the branches have been searched
+                break;
+            };
+            // Add the omitted values back before traversing the omission branch of the last included value
+            for omitted in (last + 1)..pos {
+                curr_available += values[pool[omitted]];
+            }
+            curr_value -= values[pool[last]];
+            pos = last + 1;
+        } else {
+            let value = values[pool[pos]];
+            curr_available -= value;
+            // Skip the inclusion branch if the previous equal value was omitted,
+            // the branch is the same as the one already searched
+            if curr_selection.is_empty()
+                || curr_selection.last() == Some(&(pos - 1))
+                || value != values[pool[pos - 1]]
+            {
+                curr_selection.push(pos);
+                curr_value += value;
+            }
+            pos += 1;
+        }
+    }
+    best_selection.map(|selection| selection.into_iter().map(|pos| pool[pos]).collect())
+}
+
+/// Check whether the UTXO carries the Inscriptions or other assets, such as the Babylon staking seal.
+/// Kanari does not index Runes, a rune etching is sealed as an Inscription.
+fn carries_asset(utxo_state_view: &UTXOStateView) -> bool {
+    let utxo = &utxo_state_view.value;
+    let protected_protocols = [
+        Inscription::type_tag().to_canonical_string(),
+        BBNStakeSeal::type_tag().to_canonical_string(),
+    ];
+    for protocol in protected_protocols {
+        if utxo.seals.contains_key(&protocol) {
+            debug!(
+                "UTXO {} carries asset sealed by {}, skip.",
+                utxo.outpoint(),
+                protocol
+            );
+            return true;
+        }
+    }
+    false
+}
+
 fn skip_utxo(utxo_state_view: &UTXOStateView, minimal_non_dust: Amount) -> bool {
     let utxo = &utxo_state_view.value;
     if !utxo.seals.is_empty() {
@@ -182,3 +469,48 @@ fn skip_utxo(utxo_state_view: &UTXOStateView, minimal_non_dust: Amount) -> bool
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_select_largest_first() {
+        let values = vec![100, 500, 300];
+        assert_eq!(select_largest_first(&values, 600), Some(vec![1, 2]));
+        assert_eq!(select_largest_first(&values, 500), Some(vec![1]));
+        assert_eq!(select_largest_first(&values, 901), None);
+    }
+
+    #[test]
+    fn test_select_smallest_sufficient() {
+        let values = vec![100, 500, 300];
+        assert_eq!(select_smallest_sufficient(&values, 200), Some(2));
+        assert_eq!(select_smallest_sufficient(&values, 501), None);
+    }
+
+    #[test]
+    fn test_select_branch_and_bound() {
+        let values = vec![1000, 700, 300, 250, 50];
+        // exact match
+        let mut selected = select_branch_and_bound(&values, 550, 0).unwrap();
+        selected.sort();
+        assert_eq!(selected, vec![2, 3]);
+        // match within the cost of change
+        let mut selected = select_branch_and_bound(&values, 990, 20).unwrap();
+        selected.sort();
+        assert_eq!(selected, vec![0]);
+        // no match without change
+        assert_eq!(select_branch_and_bound(&values, 1290, 0), None);
+        // not enough value
+        assert_eq!(select_branch_and_bound(&values, 3000, 100), None);
+    }
+
+    #[test]
+    fn test_select_branch_and_bound_duplicate_values() {
+        let values = vec![100; 20];
+        let selected = select_branch_and_bound(&values, 500, 0).unwrap();
+        assert_eq!(selected.len(), 5);
+        assert_eq!(select_branch_and_bound(&values, 550, 10), None);
+    }
+}
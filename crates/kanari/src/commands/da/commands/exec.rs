@@ -26,6 +26,7 @@ use kanari_notify::subscription_handler::SubscriptionHandler;
 use kanari_pipeline_processor::actor::processor::is_vm_panic_error;
 use kanari_store::META_SEQUENCER_INFO_COLUMN_FAMILY_NAME;
 use kanari_store::meta_store::SEQUENCER_INFO_KEY;
+use kanari_types::address::KanariAddress;
 use kanari_types::bitcoin::types::Block as BitcoinBlock;
 use kanari_types::error::KanariResult;
 use kanari_types::kanari_network::{BuiltinChainID, KanariChainID};
@@ -43,6 +44,7 @@ use moveos_types::transaction::{TransactionExecutionInfo, VerifiedMoveOSTransact
 use raw_store::rocks::batch::WriteBatch;
 use raw_store::traits::DBStore;
 use std::cmp::{PartialEq, max, min};
+use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
@@ -99,6 +101,11 @@ pub struct ExecCommand {
     pub max_block_number: Option<u128>,
     #[clap(long = "bypass-verify", help = "bypass verification of state root")]
     pub bypass_verify: bool,
+    #[clap(
+        long = "parallel-batch-size",
+        help = "Experimental: execute the consecutive L2 txs of different senders in parallel batches of this size. Disabled by default. The L2 txs conflict on the gas fee pool, see the executor_parallel_* metrics"
+    )]
+    pub parallel_batch_size: Option<usize>,
 
     #[clap(long = "btc-rpc-url")]
     pub btc_rpc_url: String,
@@ -315,6 +322,7 @@ impl ExecCommand {
             mode: self.mode,
             bypass_verify: self.bypass_verify,
             force_align: self.force_align,
+            parallel_batch_size: self.parallel_batch_size.unwrap_or_default(),
             ledger_tx_getter: ledger_tx_loader,
             tx_meta_store,
             sequenced_tx_store,
@@ -336,6 +344,7 @@ struct ExecInner {
     mode: ExecMode,
     bypass_verify: bool,
     force_align: bool,
+    parallel_batch_size: usize,
 
     ledger_tx_getter: LedgerTxGetter,
     tx_meta_store: TxMetaStore,
@@ -364,6 +373,14 @@ struct ExecMsg {
     l1_block_with_body: Option<L1BlockWithBody>,
 }
 
+/// The validated L2 txs waiting for parallel execution.
+/// A batch only contains the txs of different senders, so every tx can be validated against the state before the batch.
+#[derive(Default)]
+struct ExecBatch {
+    txs: Vec<(u64, VerifiedMoveOSTransaction)>,
+    senders: HashSet<KanariAddress>,
+}
+
 impl ExecInner {
     fn start_logging_task(&self, shutdown_signal: watch::Receiver<()>) {
         let done_cloned = self.done.clone();
@@ -688,6 +705,7 @@ impl ExecInner {
 
         // for auto b-search first mismatched tx_order as start point
         let mut last_eq_tx_order = None;
+        let mut batch = ExecBatch::default();
 
         loop {
             let exec_msg_opt = rx.recv().await;
@@ -704,7 +722,7 @@ impl ExecInner {
             };
 
             let elapsed = std::time::Instant::now();
-            self.execute(exec_msg, &mut last_eq_tx_order)
+            self.execute(exec_msg, &mut last_eq_tx_order, &mut batch)
                 .await
                 .with_context(|| {
                     format!(
@@ -712,6 +730,11 @@ impl ExecInner {
                         tx_order, executed_tx_order
                     )
                 })?;
+            // Do not wait for a full batch when the producer is slower
+            if rx.is_empty() {
+                self.execute_batch(&mut batch, &mut last_eq_tx_order)
+                    .await?;
+            }
             let tx_cost = elapsed.elapsed().as_micros() as u64;
             interval_cost += tx_cost;
 
@@ -746,6 +769,8 @@ impl ExecInner {
                 Self::print_tx_cost_stats(&hist_l2tx, "L2Tx", false);
             }
         }
+        self.execute_batch(&mut batch, &mut last_eq_tx_order)
+            .await?;
         if let Some(verify_targets) = self.mode.get_verify_targets_str(self.bypass_verify) {
             info!(
                 "All transactions {} are strictly equal to KanariNetwork: [0, {}]",
@@ -763,6 +788,7 @@ impl ExecInner {
         &self,
         msg: ExecMsg,
         last_eq_tx_order: &mut Option<u64>,
+        batch: &mut ExecBatch,
     ) -> anyhow::Result<()> {
         let ExecMsg {
             tx_order,
@@ -786,6 +812,26 @@ impl ExecInner {
         }
 
         if self.mode.need_exec() && !bypass_execution {
+            if let LedgerTxData::L2Tx(l2_tx) = &ledger_tx.data
+                && self.parallel_batch_size > 1
+            {
+                let sender = l2_tx.sender();
+                if batch.senders.contains(&sender) {
+                    self.execute_batch(batch, last_eq_tx_order).await?;
+                }
+                let moveos_tx = self
+                    .validate_ledger_transaction(ledger_tx, l1_block_with_body)
+                    .await?;
+                batch.txs.push((tx_order, moveos_tx));
+                batch.senders.insert(sender);
+                if batch.txs.len() >= self.parallel_batch_size {
+                    self.execute_batch(batch, last_eq_tx_order).await?;
+                }
+                return Ok(());
+            }
+            // The L1 txs are validated against the state after the batch
+            self.execute_batch(batch, last_eq_tx_order).await?;
+
             let moveos_tx = self
                 .validate_ledger_transaction(ledger_tx, l1_block_with_body)
                 .await?;
@@ -800,6 +846,31 @@ impl ExecInner {
         Ok(())
     }
 
+    async fn execute_batch(
+        &self,
+        batch: &mut ExecBatch,
+        last_eq_tx_order: &mut Option<u64>,
+    ) -> anyhow::Result<()> {
+        let Some(last_tx_order) = batch.txs.last().map(|(tx_order, _)| *tx_order) else {
+            return Ok(());
+        };
+        batch.senders.clear();
+        let (tx_orders, txs): (Vec<_>, Vec<_>) = std::mem::take(&mut batch.txs).into_iter().unzip();
+        let result = async {
+            let results = self.executor.execute_batch_transactions(txs).await?;
+            for (tx_order, (_output, execution_info)) in tx_orders.into_iter().zip(results) {
+                self.verify_state_root(tx_order, execution_info, last_eq_tx_order)
+                    .await?;
+            }
+            Ok::<_, anyhow::Error>(())
+        }
+        .await;
+        if let Err(err) = result {
+            self.handle_execution_error(err, true, last_tx_order)?;
+        }
+        Ok(())
+    }
+
     fn handle_execution_error(
         &self,
         error: anyhow::Error,
@@ -882,7 +953,16 @@ impl ExecInner {
         let executor = self.executor.clone();
 
         let (_output, execution_info) = executor.execute_transaction(moveos_tx.clone()).await?;
+        self.verify_state_root(tx_order, execution_info, last_eq_tx_order)
+            .await
+    }
 
+    async fn verify_state_root(
+        &self,
+        tx_order: u64,
+        execution_info: TransactionExecutionInfo,
+        last_eq_tx_order: &mut Option<u64>,
+    ) -> anyhow::Result<()> {
         let exp_state_root = if self.bypass_verify {
             None
         } else {
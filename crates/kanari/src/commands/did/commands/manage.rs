@@ -86,6 +86,11 @@ pub struct AddVerificationMethodCommand {
     )]
     pub relationships: Option<String>,
 
+    /// Generate a P-256 passkey of the software WebAuthn authenticator as the verification method.
+    /// The passkey is always added to the authentication relationship, so it can sign via `--webauthn`
+    #[clap(long, conflicts_with = "public_key")]
+    pub passkey: bool,
+
     #[clap(flatten)]
     pub tx_options: TransactionOptions,
 
@@ -294,7 +299,7 @@ impl CommandAction<ManageOutput> for AddVerificationMethodCommand {
         }
 
         // Parse verification relationships
-        let relationships = if let Some(rel_str) = &self.relationships {
+        let mut relationships = if let Some(rel_str) = &self.relationships {
             parse_verification_relationships(rel_str)?
         } else {
             vec![] // No relationships by default
@@ -305,6 +310,22 @@ impl CommandAction<ManageOutput> for AddVerificationMethodCommand {
         let mut method_type = MoveString::from_str(&self.method_type)?;
         let public_key = if let Some(public_key) = &self.public_key {
             MoveString::from_str(public_key)?
+        } else if self.passkey {
+            // The passkey is registered as a session key via the authentication relationship
+            let authentication = VerificationRelationship::Authentication as u8;
+            if !relationships.contains(&authentication) {
+                relationships.push(authentication);
+            }
+            let auth_key = context.generate_passkey(&did_address)?;
+            let kp = context
+                .get_session_key(&did_address, &auth_key)?
+                .ok_or_else(|| {
+                    kanari_types::error::KanariError::CommandArgumentError(
+                        "Failed to get passkey".to_string(),
+                    )
+                })?;
+            method_type = MoveString::from_str(&kp.public().did_verification_method_type())?;
+            MoveString::from_str(&kp.public().raw_to_multibase())?
         } else {
             let auth_key = context.generate_session_key(&did_address)?;
             let kp = context
@@ -4,12 +4,15 @@
 use clap::Parser;
 use kanari_config::{KanariOpt, R_OPT_NET_HELP};
 use kanari_db::KanariDB;
-use kanari_genesis::KanariGenesisV2;
+use kanari_genesis::{GenesisSpec, KanariGenesisV2};
+use kanari_store::meta_store::MetaStore;
 use kanari_types::{
     error::{KanariError, KanariResult},
+    framework::chain_id::ChainID,
     kanari_network::KanariChainID,
 };
 use metrics::RegistryService;
+use moveos_types::h256::H256;
 use std::path::PathBuf;
 
 /// Init genesis statedb
@@ -21,32 +24,97 @@ pub struct InitCommand {
 
     /// If local chainid, start the service with a temporary data store.
     /// All data will be deleted when the service is stopped.
-    #[clap(long, short = 'n', help = R_OPT_NET_HELP)]
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP, conflicts_with = "from")]
     pub chain_id: Option<KanariChainID>,
 
-    #[clap(long)]
+    #[clap(long, conflicts_with = "from")]
     /// The genesis config file path for custom chain network.
     /// If the file path equals to builtin chain network name(local/dev/test/main), will use builtin genesis config.
     pub genesis_config: Option<String>,
+
+    /// Build the genesis of a custom chain network from the declarative genesis spec yaml file,
+    /// which declares initial balances, packages, objects, gas schedule overrides and service status.
+    #[clap(long, conflicts_with = "genesis_file")]
+    pub from: Option<PathBuf>,
+
+    /// The output file of the genesis binary built by `--from`, default is `genesis.bin` in the data dir.
+    #[clap(long, requires = "from")]
+    pub output: Option<PathBuf>,
+
+    /// Init the genesis from the genesis binary file built by `--from`.
+    #[clap(long)]
+    pub genesis_file: Option<PathBuf>,
+
+    /// The expected genesis hash, the command fails if the genesis hash does not match.
+    #[clap(long)]
+    pub genesis_hash: Option<H256>,
 }
 
 impl InitCommand {
     pub async fn execute(self) -> KanariResult<()> {
-        let opt =
-            KanariOpt::new_with_default(self.base_data_dir, self.chain_id, self.genesis_config)?;
+        let spec = self.from.as_ref().map(GenesisSpec::load).transpose()?;
+        let (chain_id, genesis_config) = match &spec {
+            Some(spec) => (
+                Some(KanariChainID::from(ChainID::from(spec.chain_id))),
+                Some(spec.genesis_config.clone()),
+            ),
+            None => (self.chain_id, self.genesis_config),
+        };
+        let mut opt = KanariOpt::new_with_default(self.base_data_dir, chain_id, genesis_config)?;
+        opt.genesis_file = self.genesis_file;
         let store_config = opt.store_config();
         let registry_service = RegistryService::default();
         let kanari_db = KanariDB::init(store_config, &registry_service.default_registry())?;
         let network = opt.network();
-        let _genesis = KanariGenesisV2::load_or_init(network, &kanari_db)?;
+
+        let output = self
+            .output
+            .unwrap_or_else(|| opt.base().data_dir().join("genesis.bin"));
+        let genesis = match (&spec, &opt.genesis_file) {
+            (Some(spec), _) => {
+                let genesis = KanariGenesisV2::build_from_spec(spec)?;
+                genesis.save_to(&output)?;
+                genesis
+            }
+            (None, Some(genesis_file)) => {
+                KanariGenesisV2::load_for_network(genesis_file, &network)?
+            }
+            (None, None) => KanariGenesisV2::load_or_build(network)?,
+        };
+        let genesis_hash = genesis.genesis_hash();
+        if let Some(expected_hash) = self.genesis_hash {
+            if expected_hash != genesis_hash {
+                return Err(KanariError::from(anyhow::anyhow!(
+                    "Genesis hash mismatch, expected: {:?}, actual: {:?}",
+                    expected_hash,
+                    genesis_hash
+                )));
+            }
+        }
+        let _genesis = genesis.check_or_init(&kanari_db)?;
         let root = kanari_db
             .latest_root()?
             .ok_or_else(|| KanariError::from(anyhow::anyhow!("Load latest root failed")))?;
         println!(
-            "Genesis statedb initialized at {:?} successfully, state_root: {:?}",
+            "Genesis statedb initialized at {:?} successfully, state_root: {:?}, genesis_hash: {:?}",
             opt.base().data_dir(),
-            root.state_root()
+            root.state_root(),
+            genesis_hash
         );
+        if let Some(spec) = spec {
+            // The service status is not a part of the genesis state, save it to the meta store,
+            // the node uses it on start if `--service-status` is not set.
+            kanari_db
+                .kanari_store
+                .save_service_status(spec.service_status)?;
+            println!(
+                "Start the node with: --chain-id {} --genesis-config {} --genesis-file {}, service status: {:?}",
+                spec.chain_id,
+                spec.genesis_config,
+                output.display(),
+                spec.service_status
+            );
+        }
         Ok(())
     }
 }
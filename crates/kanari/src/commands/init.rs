@@ -5,7 +5,6 @@ use crate::cli_types::{CommandAction, WalletContextOptions};
 use crate::utils::read_line;
 use async_trait::async_trait;
 use clap::Parser;
-use fastcrypto::encoding::{Base64, Encoding};
 use kanari_config::config::Config;
 use kanari_config::{KANARI_CLIENT_CONFIG, KANARI_KEYSTORE_FILENAME, kanari_config_dir};
 use kanari_key::key_derive::hash_password;
@@ -141,11 +140,7 @@ impl CommandAction<()> for Init {
                 let dev_env = Env::new_dev_env();
                 let active_env_alias = dev_env.alias.clone();
 
-                let password_hash = hash_password(
-                    &Base64::decode(&result.key_pair_data.private_key_encryption.nonce)
-                        .map_err(|e| KanariError::KeyConversionError(e.to_string()))?,
-                    password,
-                )?;
+                let password_hash = hash_password(password)?;
                 keystore.set_password_hash_with_indicator(password_hash, is_password_empty)?;
 
                 let client_config = ClientConfig {
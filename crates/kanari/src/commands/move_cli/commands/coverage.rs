@@ -6,14 +6,35 @@
 
 use crate::cli_types::CommandAction;
 use crate::commands::move_cli::serialized_success;
+use crate::tx_runner::convert_to_verified_tx;
+use crate::utils::open_kanari_db;
+use anyhow::format_err;
 use async_trait::async_trait;
-use clap::Parser;
+use clap::{Args, Parser};
+use kanari_config::R_OPT_NET_HELP;
+use kanari_genesis::FrameworksGasParameters;
+use kanari_store::KanariStore;
+use kanari_store::meta_store::MetaStore;
 use kanari_types::error::KanariResult;
+use kanari_types::framework::{system_post_execute_functions, system_pre_execute_functions};
+use kanari_types::kanari_network::KanariChainID;
+use kanari_types::transaction::LedgerTxData;
 use move_cli::{Move, base::coverage::Coverage};
+use move_command_line_common::files::MOVE_COVERAGE_MAP_EXTENSION;
+use move_coverage::coverage_map::{CoverageMap, output_map_to_file};
+use moveos::moveos::{MoveOS, MoveOSConfig};
+use moveos_store::MoveOSStore;
+use moveos_store::transaction_store::TransactionStore;
+use moveos_types::moveos_std::object::ObjectMeta;
+use moveos_types::state_resolver::RootObjectResolver;
 use serde_json::Value;
+use std::path::{Path, PathBuf};
 
-/// Inspect test coverage for this package. A previous test run with the `--coverage` flag must
-/// have previously been run.
+/// The env var read by the Move VM, the VM appends every executed instruction to the file.
+const MOVE_VM_TRACE_ENV_VAR_NAME: &str = "MOVE_VM_TRACE";
+
+/// Inspect test coverage for this package. A previous `kanari move test` or
+/// `kanari move integration-test` run with the `--coverage` flag must have previously been run.
 #[derive(Parser)]
 #[clap(name = "coverage")]
 pub struct CoverageCommand {
@@ -38,3 +59,277 @@ impl CommandAction<Option<Value>> for CoverageCommand {
         serialized_success(self.json)
     }
 }
+
+/// Collects the coverage of the Move VM executions in this process into the coverage map of a package.
+/// The Move VM reads the trace env var only once, so only one collector can be started in a process.
+pub(crate) struct CoverageCollector {
+    trace_path: PathBuf,
+    coverage_map_path: PathBuf,
+}
+
+impl CoverageCollector {
+    pub fn start(package_path: &Path) -> anyhow::Result<Self> {
+        let trace_path = package_path.join(".trace");
+        let coverage_map_path = package_path
+            .join(".coverage_map")
+            .with_extension(MOVE_COVERAGE_MAP_EXTENSION);
+        if trace_path.exists() {
+            std::fs::remove_file(&trace_path)?;
+        }
+        unsafe {
+            std::env::set_var(MOVE_VM_TRACE_ENV_VAR_NAME, &trace_path);
+        }
+        Ok(Self {
+            trace_path,
+            coverage_map_path,
+        })
+    }
+
+    /// Merge the executions traced so far into the coverage map of the package,
+    /// so the coverage of a previous `kanari move test --coverage` run is kept.
+    pub fn finish(self) -> anyhow::Result<()> {
+        if !self.trace_path.exists() {
+            eprintln!("No Move function is executed, the coverage map is not generated.");
+            return Ok(());
+        }
+        let coverage_map = if self.coverage_map_path.exists() {
+            CoverageMap::from_binary_file(&self.coverage_map_path)?
+                .update_coverage_from_trace_file(&self.trace_path)
+        } else {
+            CoverageMap::from_trace_file(&self.trace_path)
+        };
+        output_map_to_file(&self.coverage_map_path, &coverage_map)?;
+        std::fs::remove_file(&self.trace_path)?;
+        Ok(())
+    }
+}
+
+/// Replay the L2 transactions of a local node to collect the coverage of the real traffic.
+#[derive(Debug, Args)]
+pub struct ReplayOpts {
+    /// Path to the data dir of the node whose transactions are replayed,
+    /// this dir is base dir, the final data_dir is base_dir/chain_network_name.
+    /// The transactions are replayed only if it is set.
+    #[clap(long = "replay-data-dir", requires = "compute_coverage")]
+    pub replay_base_data_dir: Option<PathBuf>,
+
+    #[clap(long = "replay-chain-id", help = R_OPT_NET_HELP)]
+    pub replay_chain_id: Option<KanariChainID>,
+
+    /// The tx_order of the first replayed transaction, the genesis transaction is never replayed.
+    #[clap(long, default_value = "1")]
+    pub replay_from_order: u64,
+
+    /// The tx_order of the last replayed transaction (inclusive), default to the last transaction of the node.
+    #[clap(long)]
+    pub replay_to_order: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct ReplaySummary {
+    pub replayed: u64,
+    /// The L1 blocks and L1 transactions are not replayed
+    pub skipped: u64,
+}
+
+impl ReplayOpts {
+    /// Execute every L2 transaction in the range on the state root before it, the node store is not changed.
+    /// The transactions are not authenticated again, so the auth validator functions are not covered.
+    pub(crate) fn replay(&self) -> anyhow::Result<Option<ReplaySummary>> {
+        let Some(base_data_dir) = self.replay_base_data_dir.clone() else {
+            return Ok(None);
+        };
+        let (_root, kanari_db, _start_time) =
+            open_kanari_db(Some(base_data_dir), self.replay_chain_id.clone());
+        let kanari_store = kanari_db.kanari_store;
+        let moveos_store = kanari_db.moveos_store;
+        let last_order = kanari_store
+            .get_sequencer_info()?
+            .map(|info| info.last_order)
+            .unwrap_or_default();
+        let to_order = self
+            .replay_to_order
+            .map_or(last_order, |order| order.min(last_order));
+
+        let mut summary = ReplaySummary::default();
+        let mut moveos = None;
+        for tx_order in self.replay_from_order.max(1)..=to_order {
+            let ledger_tx = kanari_store
+                .get_transaction_store()
+                .get_tx_by_order(tx_order)?
+                .ok_or_else(|| format_err!("Transaction of order {} not found", tx_order))?;
+            let tx = match ledger_tx.data {
+                LedgerTxData::L2Tx(tx) => tx,
+                _ => {
+                    summary.skipped += 1;
+                    continue;
+                }
+            };
+            let root = pre_execution_root(&kanari_store, &moveos_store, tx_order)?;
+            if moveos.is_none() {
+                moveos = Some(new_moveos(&moveos_store, &root)?);
+            }
+            let executor = moveos.as_ref().expect("MoveOS should be created");
+            let verified_tx = convert_to_verified_tx(root, tx.data)?;
+            let (raw_output, _vm_error_info) = executor.execute_only(verified_tx)?;
+            summary.replayed += 1;
+            if raw_output.is_gas_upgrade {
+                // The native functions are created with the gas parameters, so recreate the MoveOS
+                moveos = None;
+            } else {
+                executor.flush_module_cache(raw_output.is_upgrade)?;
+            }
+        }
+        Ok(Some(summary))
+    }
+}
+
+/// The state root after the transaction before `tx_order`.
+fn pre_execution_root(
+    kanari_store: &KanariStore,
+    moveos_store: &MoveOSStore,
+    tx_order: u64,
+) -> anyhow::Result<ObjectMeta> {
+    let pre_tx_hash = kanari_store
+        .get_transaction_store()
+        .get_tx_hashes(vec![tx_order - 1])?
+        .pop()
+        .flatten()
+        .ok_or_else(|| format_err!("Transaction of order {} not found", tx_order - 1))?;
+    let pre_execution_info = moveos_store
+        .get_transaction_store()
+        .get_tx_execution_info(pre_tx_hash)?
+        .ok_or_else(|| format_err!("Execution info of {:?} not found", pre_tx_hash))?;
+    Ok(ObjectMeta::root_metadata(
+        pre_execution_info.state_root,
+        pre_execution_info.size,
+    ))
+}
+
+fn new_moveos(moveos_store: &MoveOSStore, root: &ObjectMeta) -> anyhow::Result<MoveOS> {
+    let resolver = RootObjectResolver::new(root.clone(), moveos_store);
+    let gas_parameters = FrameworksGasParameters::load_from_chain(&resolver)?;
+    MoveOS::new(
+        moveos_store.clone(),
+        gas_parameters.all_natives(),
+        MoveOSConfig::default(),
+        system_pre_execute_functions(),
+        system_post_execute_functions(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use kanari_config::KanariOpt;
+    use kanari_db::KanariDB;
+    use kanari_executor::actor::executor::ExecutorActor;
+    use kanari_genesis::KanariGenesisV2;
+    use kanari_types::crypto::KanariKeyPair;
+    use kanari_types::framework::empty::Empty;
+    use kanari_types::kanari_network::{BuiltinChainID, KanariNetwork};
+    use kanari_types::sequencer::SequencerInfo;
+    use kanari_types::transaction::{KanariTransactionData, LedgerTransaction};
+    use metrics::RegistryService;
+    use move_core_types::account_address::AccountAddress;
+    use move_core_types::identifier::Identifier;
+    use move_core_types::vm_status::KeptVMStatus;
+    use moveos_types::moveos_std::gas_schedule::GasScheduleConfig;
+    use moveos_types::transaction::MoveAction;
+
+    /// Sequence one `empty::empty` call after the genesis in a local store of the `base_data_dir`.
+    fn init_local_store(base_data_dir: &Path) -> anyhow::Result<()> {
+        let kp = KanariKeyPair::generate_secp256k1();
+        let mut network: KanariNetwork = BuiltinChainID::Local.into();
+        network.mock_genesis_account(&kp)?;
+        let genesis = KanariGenesisV2::build(network.clone())?;
+
+        let opt = KanariOpt::new_with_default(
+            Some(base_data_dir.to_path_buf()),
+            Some(BuiltinChainID::Local.into()),
+            None,
+        )?;
+        let registry_service = RegistryService::default();
+        let registry = registry_service.default_registry();
+        let kanari_db = KanariDB::init(opt.store_config(), &registry)?;
+        let root = genesis.init_genesis(&kanari_db)?;
+        let mut executor = ExecutorActor::new(
+            root,
+            kanari_db.moveos_store.clone(),
+            kanari_db.kanari_store.clone(),
+            &registry,
+            None,
+        )?;
+
+        let sender = kp.public().bitcoin_address()?.to_kanari_address();
+        let mut tx = KanariTransactionData::new(
+            sender,
+            0,
+            network.chain_id.id,
+            GasScheduleConfig::CLI_DEFAULT_MAX_GAS_AMOUNT,
+            MoveAction::Function(Empty::empty_function_call()),
+        )
+        .sign(&kp);
+        let tx_hash = tx.tx_hash();
+        let verified_tx = executor.validate_l2_tx(tx.clone())?;
+        let result = executor.execute(verified_tx)?;
+        assert_eq!(result.transaction_info.status, KeptVMStatus::Executed);
+
+        let accumulator_info = kanari_db
+            .kanari_store
+            .get_sequencer_info()?
+            .expect("Genesis should be sequenced")
+            .last_accumulator_info;
+        let ledger_tx = LedgerTransaction::build_ledger_transaction(
+            LedgerTxData::L2Tx(tx),
+            0,
+            1,
+            vec![],
+            accumulator_info.clone(),
+        );
+        kanari_db.kanari_store.save_sequenced_tx(
+            tx_hash,
+            ledger_tx,
+            SequencerInfo::new(1, accumulator_info),
+            None,
+        )?;
+        Ok(())
+    }
+
+    // The Move VM reads the trace env var only once per process, so the collector
+    // is started before the genesis is executed.
+    #[tokio::test]
+    async fn test_replay_coverage() {
+        let base_data_dir = tempfile::tempdir().unwrap();
+        let package_dir = tempfile::tempdir().unwrap();
+        let collector = CoverageCollector::start(package_dir.path()).unwrap();
+        init_local_store(base_data_dir.path()).unwrap();
+
+        let replay_opts = ReplayOpts {
+            replay_base_data_dir: Some(base_data_dir.path().to_path_buf()),
+            replay_chain_id: Some(BuiltinChainID::Local.into()),
+            replay_from_order: 1,
+            replay_to_order: None,
+        };
+        let summary = replay_opts.replay().unwrap().unwrap();
+        assert_eq!(summary.replayed, 1);
+        assert_eq!(summary.skipped, 0);
+
+        let coverage_map_path = collector.coverage_map_path.clone();
+        collector.finish().unwrap();
+        let coverage_map = CoverageMap::from_binary_file(&coverage_map_path).unwrap();
+        let exec_map = coverage_map.to_unified_exec_map();
+        let empty_module = exec_map
+            .module_maps
+            .get(&(
+                AccountAddress::from_hex_literal("0x3").unwrap(),
+                Identifier::new("empty").unwrap(),
+            ))
+            .expect("The empty module should be covered");
+        assert!(
+            empty_module
+                .get_function_coverage(&Identifier::new("empty").unwrap())
+                .is_some()
+        );
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli_types::{CommandAction, WalletContextOptions};
+use crate::commands::move_cli::commands::coverage::{CoverageCollector, ReplayOpts};
 use crate::commands::move_cli::serialized_success;
 use async_trait::async_trait;
 use clap::{Args, Parser};
@@ -25,6 +26,7 @@ use serde_json::Value;
 use std::collections::BTreeMap;
 use std::fmt::Display;
 use std::num::NonZeroUsize;
+use std::path::Path;
 use std::str::FromStr;
 use std::sync::Mutex;
 
@@ -133,6 +135,14 @@ pub struct IntegrationTestCommand {
     #[clap(long, value_parser = crate::utils::parse_map::<String, String>, default_value = "")]
     pub(crate) named_addresses: BTreeMap<String, String>,
 
+    /// Collect coverage information for later use with the various `kanari move coverage` subcommands.
+    /// The coverage of the integration tests and the replayed transactions is merged into one coverage map.
+    #[clap(long = "coverage")]
+    compute_coverage: bool,
+
+    #[clap(flatten)]
+    replay_opts: ReplayOpts,
+
     #[clap(flatten)]
     move_args: Move,
 
@@ -282,71 +292,100 @@ impl CommandAction<Option<Value>> for IntegrationTestCommand {
 
         let tests_dir = rerooted_path.join(INTEGRATION_TESTS_DIR);
 
-        if !tests_dir.exists() || !tests_dir.is_dir() {
+        let coverage_collector = if self.compute_coverage {
+            Some(CoverageCollector::start(&rerooted_path)?)
+        } else {
+            None
+        };
+
+        if tests_dir.exists() && tests_dir.is_dir() {
+            let mut named_address_string_map = BTreeMap::new();
+            let _ = named_addresses_maps
+                .iter()
+                .map(|(key, value)| {
+                    named_address_string_map.insert(key.to_string(), value.to_string());
+                })
+                .collect::<Vec<_>>();
+            run_integration_tests(
+                &tests_dir,
+                named_address_string_map,
+                self.test_opts,
+                self.update_baseline,
+            )?;
+        } else {
             eprintln!("No integration tests file in the dir `integration-tests`.");
-            return serialized_success(self.json);
         }
 
-        let mut named_address_string_map = BTreeMap::new();
-        let _ = named_addresses_maps
-            .iter()
-            .map(|(key, value)| {
-                named_address_string_map.insert(key.to_string(), value.to_string());
-            })
-            .collect::<Vec<_>>();
-
-        let requirements = datatest_stable::Requirements::new(
-            move |path, data| {
-                kanari_integration_test_runner::run_integration_test_with_extended_check(
-                    path,
-                    G_PRE_COMPILED_LIB.lock().unwrap().as_ref(),
-                    data,
-                )
-            },
-            "integration-test333".to_owned(),
-            tests_dir.display().to_string(),
-            r".*\.move".to_owned(),
-            named_address_string_map,
-        );
-        if self.update_baseline {
-            unsafe {
-                std::env::set_var(UPDATE_BASELINE, "true");
-            }
+        if let Some(summary) = self.replay_opts.replay()? {
+            eprintln!(
+                "Replayed {} L2 transactions, skipped {} L1 transactions.",
+                summary.replayed, summary.skipped
+            );
         }
-        let mut test_args = vec![
-            "test_runner".to_owned(),
-            "--format".to_owned(),
-            self.test_opts.format.to_string(),
-            "--test-threads".to_owned(),
-            self.test_opts.test_threads.to_string(),
-        ];
-        if self.test_opts.list {
-            test_args.push("--list".to_owned());
-        }
-        if self.test_opts.quiet {
-            test_args.push("--quiet".to_owned());
-        }
-        if self.test_opts.filter_exact {
-            test_args.push("--exact".to_owned());
+        if let Some(coverage_collector) = coverage_collector {
+            coverage_collector.finish()?;
         }
 
-        if let Some(filter) = self.test_opts.filter {
-            test_args.push("--".to_owned());
-            test_args.push(filter);
-        }
+        serialized_success(self.json)
+    }
+}
 
-        let test_opts = datatest_stable::TestOpts::try_parse_from(test_args.as_slice());
-        match test_opts {
-            Ok(test_opts) => {
-                datatest_stable::runner_with_opts(&[requirements], test_opts);
-            }
-            Err(_) => {
-                return Err(KanariError::CommandArgumentError(
-                    "Failed to parse test arguments.".to_string(),
-                ));
-            }
+fn run_integration_tests(
+    tests_dir: &Path,
+    named_address_string_map: BTreeMap<String, String>,
+    test_opts: TestOpts,
+    update_baseline: bool,
+) -> KanariResult<()> {
+    let requirements = datatest_stable::Requirements::new(
+        move |path, data| {
+            kanari_integration_test_runner::run_integration_test_with_extended_check(
+                path,
+                G_PRE_COMPILED_LIB.lock().unwrap().as_ref(),
+                data,
+            )
+        },
+        "integration-test333".to_owned(),
+        tests_dir.display().to_string(),
+        r".*\.move".to_owned(),
+        named_address_string_map,
+    );
+    if update_baseline {
+        unsafe {
+            std::env::set_var(UPDATE_BASELINE, "true");
         }
+    }
+    let mut test_args = vec![
+        "test_runner".to_owned(),
+        "--format".to_owned(),
+        test_opts.format.to_string(),
+        "--test-threads".to_owned(),
+        test_opts.test_threads.to_string(),
+    ];
+    if test_opts.list {
+        test_args.push("--list".to_owned());
+    }
+    if test_opts.quiet {
+        test_args.push("--quiet".to_owned());
+    }
+    if test_opts.filter_exact {
+        test_args.push("--exact".to_owned());
+    }
 
-        serialized_success(self.json)
+    if let Some(filter) = test_opts.filter {
+        test_args.push("--".to_owned());
+        test_args.push(filter);
+    }
+
+    let test_opts = datatest_stable::TestOpts::try_parse_from(test_args.as_slice());
+    match test_opts {
+        Ok(test_opts) => {
+            datatest_stable::runner_with_opts(&[requirements], test_opts);
+        }
+        Err(_) => {
+            return Err(KanariError::CommandArgumentError(
+                "Failed to parse test arguments.".to_string(),
+            ));
+        }
     }
+    Ok(())
 }
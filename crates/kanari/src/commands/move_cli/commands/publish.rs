@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli_types::{CommandAction, TransactionOptions, WalletContextOptions};
+use crate::commands::move_cli::package_hooks::{UPGRADE_POLICY_FIELD, get_package_info};
 use crate::tx_runner::dry_run_tx_locally;
 use async_trait::async_trait;
 use clap::Parser;
@@ -13,15 +14,20 @@ use kanari_types::address::KanariAddress;
 use kanari_types::error::{KanariError, KanariResult};
 use kanari_types::transaction::kanari::KanariTransaction;
 use move_binary_format::CompiledModule;
+use move_binary_format::access::ModuleAccess;
 use move_cli::Move;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::effects::Op;
 use move_core_types::resolver::ModuleResolver;
 use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+use move_package::source_package::manifest_parser::parse_move_manifest_from_file;
+use move_package::source_package::parsed_manifest::PackageName;
 use moveos_compiler::dependency_order::sort_by_dependency_order;
 use moveos_types::access_path::AccessPath;
 use moveos_types::move_std::string::MoveString;
-use moveos_types::moveos_std::module_store::{ModuleStore, PackageData};
+use moveos_types::moveos_std::module_store::{
+    ModuleStore, PackageData, PackageDependency, PackageMetadata, UpgradePolicy,
+};
 use moveos_types::moveos_std::move_module::MoveModule;
 use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::{
@@ -29,8 +35,10 @@ use moveos_types::{
     transaction::MoveAction,
 };
 use moveos_verifier::build::run_verifier;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::io::stderr;
+use std::path::Path;
+use std::str::FromStr;
 use tokio::runtime::Handle;
 
 struct MemoryModuleResolver {
@@ -155,7 +163,8 @@ pub struct Publish {
 
     /// Whether publish modules by `MoveAction::ModuleBundle`?
     /// If not set, publish moduels through Move entry function
-    /// `moveos_std::module_store::publish_package_entry`.
+    /// `moveos_std::module_store::publish_package_with_metadata_entry`,
+    /// or `publish_package_entry` if the chain does not support the package metadata.
     /// **Deprecated**! Publish modules by `MoveAction::ModuleBundle` is no longer used anymore.
     /// So you should never add this option.
     /// For now, the option is kept for test only.
@@ -190,7 +199,7 @@ impl CommandAction<ExecuteTransactionResponseView> for Publish {
             let pkg_data: PackageData = bcs::from_reader(file)?;
             eprintln!("Publish modules to address: {:?}", pkg_data.package_id);
             return self
-                .publish_package(&context, sender, pkg_data, max_gas_amount)
+                .publish_package(&context, sender, pkg_data, None, max_gas_amount)
                 .await;
         }
 
@@ -210,7 +219,7 @@ impl CommandAction<ExecuteTransactionResponseView> for Publish {
 
         // Compile the package and run the verifier
         let mut package = config.compile_package_no_exit(&package_path, &mut stderr())?;
-        run_verifier(package_path, config_cloned, &mut package)?;
+        run_verifier(package_path.clone(), config_cloned, &mut package)?;
 
         // Get the modules from the package
         let modules = package.root_modules_map();
@@ -266,12 +275,29 @@ impl CommandAction<ExecuteTransactionResponseView> for Publish {
 
         // Prepare and execute the transaction based on the action type
         let tx_result = if !self.by_move_action {
-            let pkg_data = PackageData::new(
-                MoveString::from(package.compiled_package_info.package_name.as_str()),
-                pkg_address,
-                bundles,
+            let package_name = package.compiled_package_info.package_name.as_str();
+            let dependency_addresses = package
+                .all_modules_map()
+                .get_map()
+                .keys()
+                .map(|mid| *mid.address())
+                .filter(|address| *address != pkg_address)
+                .collect::<BTreeSet<_>>();
+            let metadata = Self::build_package_metadata(
+                &context.get_client().await?,
+                &package_path,
+                package_name,
+                dependency_addresses,
+            )
+            .await?;
+            eprintln!(
+                "Package {} version {}, upgrade policy {}",
+                package_name,
+                metadata.version,
+                metadata.upgrade_policy()?
             );
-            self.publish_package(&context, sender, pkg_data, max_gas_amount)
+            let pkg_data = PackageData::new(MoveString::from(package_name), pkg_address, bundles);
+            self.publish_package(&context, sender, pkg_data, Some(metadata), max_gas_amount)
                 .await?
         } else {
             // Handle MoveAction.ModuleBundle case
@@ -399,25 +425,107 @@ impl Publish {
         Ok(output)
     }
 
+    /// Build the package metadata from Move.toml, the dependencies are pinned to the digests of
+    /// the packages published on chain.
+    async fn build_package_metadata(
+        client: &Client,
+        package_path: &Path,
+        package_name: &str,
+        dependency_addresses: BTreeSet<AccountAddress>,
+    ) -> KanariResult<PackageMetadata> {
+        let manifest = parse_move_manifest_from_file(package_path)?;
+        let upgrade_policy = match manifest
+            .package
+            .custom_properties
+            .get(&PackageName::from(UPGRADE_POLICY_FIELD))
+        {
+            Some(policy) => UpgradePolicy::from_str(policy)?,
+            None => UpgradePolicy::default(),
+        };
+        let mut dependencies = vec![];
+        for package_id in dependency_addresses {
+            let digest = get_package_info(client, package_id)
+                .await?
+                .map(|info| info.digest)
+                .unwrap_or_default();
+            dependencies.push(PackageDependency { package_id, digest });
+        }
+        Ok(PackageMetadata::new(
+            MoveString::from(package_name),
+            manifest.package.version.into(),
+            upgrade_policy,
+            dependencies,
+        ))
+    }
+
+    /// Whether the `moveos_std::module_store` on chain has `publish_package_with_metadata_entry`,
+    /// the chains upgraded from a framework before the package metadata only have `publish_package_entry`.
+    async fn is_metadata_supported(client: &Client) -> Result<bool, anyhow::Error> {
+        let module_id = ModuleId::new(
+            MOVEOS_STD_ADDRESS,
+            Identifier::new("module_store".to_owned()).unwrap(),
+        );
+        let state = client
+            .kanari
+            .get_states(AccessPath::module(&module_id), None)
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| anyhow::anyhow!("Module {} not found", module_id))?;
+        let module = ObjectState::from(state).value_as_df::<MoveString, MoveModule>()?;
+        let compiled_module = CompiledModule::deserialize(&module.value.byte_codes)?;
+        Ok(compiled_module.function_defs().iter().any(|def| {
+            let handle = compiled_module.function_handle_at(def.function);
+            compiled_module.identifier_at(handle.name).as_str()
+                == "publish_package_with_metadata_entry"
+        }))
+    }
+
     async fn publish_package(
         &self,
         context: &WalletContext,
         sender: KanariAddress,
         pkg_data: PackageData,
+        metadata: Option<PackageMetadata>,
         max_gas_amount: Option<u64>,
     ) -> KanariResult<ExecuteTransactionResponseView> {
         let pkg_bytes = bcs::to_bytes(&pkg_data).unwrap();
-        let args = bcs::to_bytes(&pkg_bytes).unwrap();
+        let metadata = if metadata.is_some()
+            && !Self::is_metadata_supported(&context.get_client().await?).await?
+        {
+            eprintln!(
+                "The moveos_std on chain does not support package metadata, publish the package without it."
+            );
+            None
+        } else {
+            metadata
+        };
+        let (function_name, args) = match metadata {
+            Some(metadata) => {
+                let metadata_bytes = bcs::to_bytes(&metadata).unwrap();
+                (
+                    "publish_package_with_metadata_entry",
+                    vec![
+                        bcs::to_bytes(&pkg_bytes).unwrap(),
+                        bcs::to_bytes(&metadata_bytes).unwrap(),
+                    ],
+                )
+            }
+            None => (
+                "publish_package_entry",
+                vec![bcs::to_bytes(&pkg_bytes).unwrap()],
+            ),
+        };
         let action = MoveAction::new_function_call(
             FunctionId::new(
                 ModuleId::new(
                     MOVEOS_STD_ADDRESS,
                     Identifier::new("module_store".to_owned()).unwrap(),
                 ),
-                Identifier::new("publish_package_entry".to_owned()).unwrap(),
+                Identifier::new(function_name.to_owned()).unwrap(),
             ),
             vec![],
-            vec![args],
+            args,
         );
 
         if self.dry_run {
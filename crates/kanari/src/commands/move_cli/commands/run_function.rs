@@ -3,7 +3,7 @@
 
 use crate::cli_types::{CommandAction, FunctionArg, TransactionOptions, WalletContextOptions};
 use crate::tx_runner::{dry_run_tx_locally, execute_tx_locally_with_gas_profile};
-use anyhow::Result;
+use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use clap::Parser;
 use kanari_rpc_api::jsonrpc_types::{ExecuteTransactionResponseView, HumanReadableDisplay};
@@ -15,16 +15,80 @@ use kanari_types::{
     transaction::kanari::KanariTransaction,
 };
 use move_command_line_common::types::ParsedStructType;
+use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::TypeTag;
-use moveos_types::transaction::MoveAction;
+use moveos_types::transaction::{CallResultRef, FunctionCall, MoveAction};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// A function call in the batch file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchCallArg {
+    /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
+    pub function: String,
+    #[serde(default)]
+    pub type_args: Vec<String>,
+    /// The same format as `--args`, and `result:<CALL_INDEX>:<RESULT_INDEX>` refers to
+    /// the object returned by a previous call of the batch.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+impl BatchCallArg {
+    fn into_function_call(
+        self,
+        address_mapping: &impl Fn(&str) -> Option<AccountAddress>,
+    ) -> Result<FunctionCall> {
+        let function_id =
+            ParsedFunctionId::from_str(&self.function)?.into_function_id(address_mapping)?;
+        let type_args = self
+            .type_args
+            .iter()
+            .map(|tag| {
+                Ok(TypeTag::Struct(Box::new(
+                    ParsedStructType::parse(tag)?.into_struct_tag(address_mapping)?,
+                )))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let args = self
+            .args
+            .iter()
+            .map(|arg| match arg.strip_prefix("result:") {
+                Some(result_ref) => parse_call_result_ref(result_ref),
+                None => parse_function_arg(arg)?.into_bytes(address_mapping),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(FunctionCall::new(function_id, type_args, args))
+    }
+}
+
+fn parse_call_result_ref(s: &str) -> Result<Vec<u8>> {
+    let (call_index, result_index) = s.split_once(':').ok_or_else(|| {
+        anyhow!(
+            "Invalid result reference `{}`, expect `result:<CALL_INDEX>:<RESULT_INDEX>`",
+            s
+        )
+    })?;
+    Ok(CallResultRef::new(u16::from_str(call_index)?, u16::from_str(result_index)?).to_arg())
+}
 
 /// Run a Move function
 #[derive(Parser)]
 pub struct RunFunction {
     /// Function name as `<ADDRESS>::<MODULE_ID>::<FUNCTION_NAME>`
     /// Example: `0x42::message::set_message`, `kanari_framework::empty::empty`
-    #[clap(long)]
-    pub function: ParsedFunctionId,
+    #[clap(long, required_unless_present = "batch")]
+    pub function: Option<ParsedFunctionId>,
+
+    /// Run the function calls in the JSON file atomically in one transaction, conflicts with `--function`.
+    ///
+    /// Example: `[{"function": "0x42::nft::mint", "args": ["string:kanari"]}, {"function": "0x42::nft::deposit", "args": ["result:0:0"]}]`
+    ///
+    /// A public function in the batch can return `Object<T>` values, every returned object must be
+    /// consumed by a later call via the `result:<CALL_INDEX>:<RESULT_INDEX>` argument.
+    #[clap(long, conflicts_with_all = ["function", "type_args", "args"])]
+    pub batch: Option<PathBuf>,
 
     /// TypeTag arguments separated by spaces.
     ///
@@ -74,22 +138,45 @@ impl CommandAction<ExecuteTransactionResponseView> for RunFunction {
         let sender: KanariAddress = context.resolve_address(self.tx_options.sender)?.into();
         let max_gas_amount: Option<u64> = self.tx_options.max_gas_amount;
         let sequence_number: Option<u64> = self.tx_options.sequence_number;
-        let function_id = self.function.into_function_id(&address_mapping)?;
-        let args = self
-            .args
-            .into_iter()
-            .map(|arg| arg.into_bytes(&address_mapping))
-            .collect::<Result<Vec<_>>>()?;
-        let type_args = self
-            .type_args
-            .into_iter()
-            .map(|tag| {
-                Ok(TypeTag::Struct(Box::new(
-                    tag.into_struct_tag(&address_mapping)?,
-                )))
-            })
-            .collect::<Result<Vec<_>>>()?;
-        let action = MoveAction::new_function_call(function_id, type_args, args);
+        let action = match (self.function, self.batch) {
+            (_, Some(batch)) => {
+                let batch_calls: Vec<BatchCallArg> =
+                    serde_json::from_slice(&std::fs::read(&batch)?).map_err(|e| {
+                        KanariError::CommandArgumentError(format!(
+                            "Invalid batch file {:?}: {}",
+                            batch, e
+                        ))
+                    })?;
+                let calls = batch_calls
+                    .into_iter()
+                    .map(|call| call.into_function_call(&address_mapping))
+                    .collect::<Result<Vec<_>>>()?;
+                MoveAction::new_batch(calls)
+            }
+            (Some(function), None) => {
+                let function_id = function.into_function_id(&address_mapping)?;
+                let args = self
+                    .args
+                    .into_iter()
+                    .map(|arg| arg.into_bytes(&address_mapping))
+                    .collect::<Result<Vec<_>>>()?;
+                let type_args = self
+                    .type_args
+                    .into_iter()
+                    .map(|tag| {
+                        Ok(TypeTag::Struct(Box::new(
+                            tag.into_struct_tag(&address_mapping)?,
+                        )))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                MoveAction::new_function_call(function_id, type_args, args)
+            }
+            (None, None) => {
+                return Err(KanariError::CommandArgumentError(
+                    "One of `--function` or `--batch` is required".to_owned(),
+                ));
+            }
+        };
 
         if self.dry_run {
             let kanari_tx_data = context
@@ -121,6 +208,20 @@ impl CommandAction<ExecuteTransactionResponseView> for RunFunction {
                 let tx = KanariTransaction::new(tx_data, authenticator.into());
                 context.execute(tx).await?
             }
+            (_, Some(session_key)) if self.tx_options.webauthn => {
+                let tx_data = context
+                    .build_tx_data_with_sequence_number(
+                        sender,
+                        action,
+                        max_gas_amount,
+                        sequence_number,
+                    )
+                    .await?;
+                let tx = context
+                    .sign_transaction_via_webauthn(&sender, tx_data, &session_key)
+                    .map_err(|e| KanariError::SignMessageError(e.to_string()))?;
+                context.execute(tx).await?
+            }
             (_, Some(session_key)) => {
                 let tx_data = context
                     .build_tx_data_with_sequence_number(
@@ -135,6 +236,20 @@ impl CommandAction<ExecuteTransactionResponseView> for RunFunction {
                     .map_err(|e| KanariError::SignMessageError(e.to_string()))?;
                 context.execute(tx).await?
             }
+            (None, None) if self.tx_options.taproot => {
+                let tx_data = context
+                    .build_tx_data_with_sequence_number(
+                        sender,
+                        action,
+                        max_gas_amount,
+                        sequence_number,
+                    )
+                    .await?;
+                let tx = context
+                    .sign_transaction_via_taproot(sender, tx_data)
+                    .map_err(|e| KanariError::SignMessageError(e.to_string()))?;
+                context.execute(tx).await?
+            }
             (None, None) => {
                 let tx_data = context
                     .build_tx_data_with_sequence_number(
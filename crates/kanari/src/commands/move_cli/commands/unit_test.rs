@@ -24,6 +24,7 @@ use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::{moveos_std::tx_context::TxContext, state_resolver::RootObjectResolver};
 use moveos_verifier::build::build_model_with_test_attr;
 use moveos_verifier::metadata::run_extended_checks;
+use moveos_wasm::context::NativeWASMContext;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
 use serde_json::Value;
@@ -151,4 +152,5 @@ fn new_moveos_natives_runtime(ext: &mut NativeContextExtensions) {
     ext.add(table_ext);
     ext.add(module_ext);
     ext.add(event_ext);
+    ext.add(NativeWASMContext::default());
 }
@@ -0,0 +1,186 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use crate::commands::move_cli::package_hooks::list_package_modules;
+use async_trait::async_trait;
+use clap::Parser;
+use kanari_rpc_api::jsonrpc_types::package_source_view::{
+    PackageSourceView, SubmitPackageSourceView,
+};
+use kanari_rpc_api::jsonrpc_types::{H256View, KanariAddressView};
+use kanari_types::address::{KanariAddress, ParsedAddress};
+use kanari_types::error::{KanariError, KanariResult};
+use move_core_types::account_address::AccountAddress;
+use move_package::BuildConfig;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::module_store::package_digest;
+use moveos_verifier::build::run_verifier;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::stderr;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Verify that the modules published at an address are built from the package sources.
+/// The package is rebuilt locally and its bytecode is compared with the modules on chain.
+#[derive(Parser)]
+pub struct VerifySource {
+    /// The address the package is published at
+    #[clap(long, value_parser=ParsedAddress::parse)]
+    pub address: ParsedAddress,
+
+    /// Path to the package sources, default is the current directory
+    #[clap(long = "package", value_name = "PACKAGE_DIR")]
+    pub package: Option<PathBuf>,
+
+    /// Package build options, they must be the same as the options used to publish the package
+    #[clap(flatten)]
+    pub build_config: BuildConfig,
+
+    /// Named addresses for the move binary
+    ///
+    /// Example: alice=0x1234, bob=default, alice2=alice
+    #[clap(long, value_parser=crate::utils::parse_map::<String, String>, default_value = "")]
+    pub(crate) named_addresses: BTreeMap<String, String>,
+
+    /// Submit the source digest to the indexer registry of the RPC node, the node only accepts it
+    /// if it is started with `--accept-package-sources`
+    #[clap(long, default_value = "false")]
+    pub submit: bool,
+
+    #[clap(flatten)]
+    pub(crate) context_options: WalletContextOptions,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerifySourceOutput {
+    pub package_address: KanariAddressView,
+    pub package_name: String,
+    pub package_digest: H256View,
+    pub source_digest: H256View,
+    /// The package source recorded by the indexer registry if `--submit` is set
+    pub package_source: Option<PackageSourceView>,
+}
+
+#[async_trait]
+impl CommandAction<VerifySourceOutput> for VerifySource {
+    async fn execute(self) -> KanariResult<VerifySourceOutput> {
+        let context = self.context_options.build()?;
+        let package_address: AccountAddress = context.resolve_address(self.address)?;
+        let package_path = self
+            .package
+            .unwrap_or_else(|| std::env::current_dir().unwrap());
+
+        let mut config = self.build_config;
+        config.additional_named_addresses =
+            context.parse_and_resolve_addresses(self.named_addresses)?;
+
+        // Rebuild the package the same way as it is published, the verifier appends the metadata
+        let mut package = config
+            .clone()
+            .compile_package_no_exit(&package_path, &mut stderr())?;
+        run_verifier(package_path, config, &mut package)?;
+
+        let package_name = package.compiled_package_info.package_name.to_string();
+        let source_digest = package
+            .compiled_package_info
+            .source_digest
+            .ok_or_else(|| {
+                KanariError::MoveCompilationError(format!(
+                    "The source digest of package {} is not computed",
+                    package_name
+                ))
+            })
+            .and_then(|digest| {
+                H256::from_str(digest.as_str())
+                    .map_err(|e| KanariError::UnableToParse("source digest", e.to_string()))
+            })?;
+
+        let mut local_modules = BTreeMap::new();
+        for module in package.root_modules_map().iter_modules() {
+            if *module.self_id().address() != package_address {
+                return Err(KanariError::CommandArgumentError(format!(
+                    "Module {} is not at the package address {}",
+                    module.self_id(),
+                    package_address
+                )));
+            }
+            let mut binary = vec![];
+            module.serialize(&mut binary)?;
+            local_modules.insert(module.self_id().name().to_string(), binary);
+        }
+
+        let client = context.get_client().await?;
+        let onchain_modules = list_package_modules(&client, package_address).await?;
+        if onchain_modules.is_empty() {
+            return Err(KanariError::CommandArgumentError(format!(
+                "No module is published at {}",
+                package_address
+            )));
+        }
+        check_modules(&local_modules, &onchain_modules)?;
+
+        let package_digest = H256::from_slice(&package_digest(
+            &local_modules.into_values().collect::<Vec<_>>(),
+        ));
+        eprintln!(
+            "Package {} at {} matches the modules on chain, package digest {:?}, source digest {:?}",
+            package_name, package_address, package_digest, source_digest
+        );
+
+        let package_source = if self.submit {
+            Some(
+                client
+                    .kanari
+                    .submit_package_source(SubmitPackageSourceView {
+                        package_address: KanariAddress::from(package_address).into(),
+                        package_name: package_name.clone(),
+                        package_digest: package_digest.into(),
+                        source_digest: source_digest.into(),
+                    })
+                    .await?,
+            )
+        } else {
+            None
+        };
+
+        Ok(VerifySourceOutput {
+            package_address: KanariAddress::from(package_address).into(),
+            package_name,
+            package_digest: package_digest.into(),
+            source_digest: source_digest.into(),
+            package_source,
+        })
+    }
+}
+
+/// Compare the rebuilt modules with the modules on chain, and report all the differences.
+fn check_modules(
+    local_modules: &BTreeMap<String, Vec<u8>>,
+    onchain_modules: &BTreeMap<String, Vec<u8>>,
+) -> KanariResult<()> {
+    let names = local_modules
+        .keys()
+        .chain(onchain_modules.keys())
+        .collect::<BTreeSet<_>>();
+    let mut errors = vec![];
+    for name in names {
+        match (local_modules.get(name), onchain_modules.get(name)) {
+            (Some(_), None) => errors.push(format!("module {} is not published on chain", name)),
+            (None, Some(_)) => errors.push(format!("module {} is not built from sources", name)),
+            (Some(local), Some(onchain)) if local != onchain => {
+                errors.push(format!("module {} bytecode mismatch", name))
+            }
+            _ => {}
+        }
+    }
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(KanariError::CommandArgumentError(format!(
+            "The sources do not match the modules on chain: {}",
+            errors.join(", ")
+        )))
+    }
+}
@@ -8,15 +8,17 @@ use commands::{
     docgen::DocgenCommand, errmap::ErrmapCommand, info::InfoCommand,
     integration_test::IntegrationTestCommand, new::NewCommand, prove::ProveCommand,
     publish::Publish, run_function::RunFunction, run_view_function::RunViewFunction,
-    unit_test::TestCommand,
+    unit_test::TestCommand, verify_source::VerifySource,
 };
 use kanari_types::error::KanariResult;
 use serde_json::{Value, json};
 
 use crate::CommandAction;
 use crate::commands::move_cli::commands::explain::ExplainCommand;
+use crate::commands::move_cli::package_hooks::KanariPackageHooks;
 
 pub mod commands;
+pub mod package_hooks;
 
 #[derive(Parser)]
 pub struct MoveCli {
@@ -41,11 +43,13 @@ pub enum MoveCommand {
     View(RunViewFunction),
     IntegrationTest(IntegrationTestCommand),
     Explain(ExplainCommand),
+    VerifySource(VerifySource),
 }
 
 #[async_trait]
 impl CommandAction<String> for MoveCli {
     async fn execute(self) -> KanariResult<String> {
+        KanariPackageHooks::register();
         match self.cmd {
             MoveCommand::Build(c) => c.execute_serialized().await,
             MoveCommand::Coverage(c) => c.execute_serialized().await,
@@ -61,6 +65,7 @@ impl CommandAction<String> for MoveCli {
             MoveCommand::View(c) => c.execute_serialized().await,
             MoveCommand::IntegrationTest(c) => c.execute_serialized().await,
             MoveCommand::Explain(c) => c.execute_serialized().await,
+            MoveCommand::VerifySource(c) => c.execute_serialized().await,
         }
     }
 }
@@ -0,0 +1,160 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Result, bail};
+use kanari_rpc_client::{Client, ClientBuilder};
+use move_core_types::account_address::AccountAddress;
+use move_package::package_hooks::PackageHooks;
+use move_package::source_package::parsed_manifest::{CustomDepInfo, PackageName};
+use moveos_types::move_std::string::MoveString;
+use moveos_types::moveos_std::module_store::{
+    Package, PackageInfo, PackageMetadata, UpgradePolicy,
+};
+use moveos_types::moveos_std::move_module::MoveModule;
+use moveos_types::state::ObjectState;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The custom field in the `[package]` section of Move.toml, the value is `compatible` or `immutable`.
+pub const UPGRADE_POLICY_FIELD: &str = "upgrade_policy";
+/// The custom dependency key in Move.toml, a dependency published on chain is declared as
+/// `Dep = { kanari = "<node_url>", address = "<package_address>" }`.
+pub const KANARI_DEPENDENCY_KEY: &str = "kanari";
+
+const LIST_MODULES_PAGE_SIZE: u64 = 50;
+
+/// Resolve the Move package dependencies from the packages published on chain.
+pub struct KanariPackageHooks;
+
+impl KanariPackageHooks {
+    pub fn register() {
+        move_package::package_hooks::register_package_hooks(Box::new(KanariPackageHooks));
+    }
+}
+
+impl PackageHooks for KanariPackageHooks {
+    fn custom_package_info_fields(&self) -> Vec<String> {
+        vec![UPGRADE_POLICY_FIELD.to_string()]
+    }
+
+    fn custom_dependency_key(&self) -> Option<String> {
+        Some(KANARI_DEPENDENCY_KEY.to_string())
+    }
+
+    fn resolve_custom_dependency(&self, dep_name: PackageName, info: &CustomDepInfo) -> Result<()> {
+        let package_address = AccountAddress::from_str(info.package_address.as_str())?;
+        let node_url = info.node_url.as_str().to_owned();
+        // The hook is called by the synchronous package system, which may run in an async context,
+        // so the package is downloaded by a dedicated runtime in another thread.
+        let (modules, metadata) = std::thread::spawn(move || -> Result<_> {
+            tokio::runtime::Runtime::new()?.block_on(async {
+                let client = ClientBuilder::default().build(node_url).await?;
+                let modules = list_package_modules(&client, package_address).await?;
+                let metadata = get_package_info(&client, package_address)
+                    .await?
+                    .and_then(|info| Option::<PackageMetadata>::from(info.metadata));
+                Ok((modules, metadata))
+            })
+        })
+        .join()
+        .map_err(|_| anyhow::anyhow!("Download package {} panicked", dep_name))??;
+        if modules.is_empty() {
+            bail!(
+                "Package {} is not found at {} on {}",
+                dep_name,
+                info.package_address,
+                info.node_url
+            );
+        }
+        save_bytecode_package(
+            &info.download_to,
+            dep_name.as_str(),
+            metadata.as_ref(),
+            modules,
+        )
+    }
+}
+
+/// List the module bytecodes of the package published at `package_address`, keyed by module name.
+pub(crate) async fn list_package_modules(
+    client: &Client,
+    package_address: AccountAddress,
+) -> Result<BTreeMap<String, Vec<u8>>> {
+    let package_id = Package::package_id(&package_address);
+    let mut modules = BTreeMap::new();
+    let mut cursor = None;
+    loop {
+        let page = client
+            .kanari
+            .list_field_states(
+                package_id.clone().into(),
+                cursor,
+                Some(LIST_MODULES_PAGE_SIZE),
+                None,
+            )
+            .await?;
+        for kv in page.data {
+            let module = ObjectState::from(kv.state).value_as_df::<MoveString, MoveModule>()?;
+            modules.insert(module.name.to_string(), module.value.byte_codes);
+        }
+        if !page.has_next_page {
+            break;
+        }
+        cursor = page.next_cursor;
+    }
+    Ok(modules)
+}
+
+/// Get the `PackageInfo` of the package published at `package_address`, None if the package
+/// is not published or published before the package info is recorded.
+pub(crate) async fn get_package_info(
+    client: &Client,
+    package_address: AccountAddress,
+) -> Result<Option<PackageInfo>> {
+    let package_info_id = PackageInfo::package_info_id(package_address);
+    let state = client
+        .kanari
+        .get_object_states(vec![package_info_id], None)
+        .await?
+        .pop()
+        .flatten();
+    state
+        .map(|state| ObjectState::from(state).value_as::<PackageInfo>())
+        .transpose()
+}
+
+/// Save the modules as a bytecode only package, which the package system loads without sources.
+fn save_bytecode_package(
+    package_path: &Path,
+    package_name: &str,
+    metadata: Option<&PackageMetadata>,
+    modules: BTreeMap<String, Vec<u8>>,
+) -> Result<()> {
+    if package_path.exists() {
+        std::fs::remove_dir_all(package_path)?;
+    }
+    let bytecode_dir = package_path
+        .join("build")
+        .join(package_name)
+        .join("bytecode_modules");
+    std::fs::create_dir_all(&bytecode_dir)?;
+    std::fs::create_dir_all(package_path.join("sources"))?;
+    for (name, bytes) in modules {
+        std::fs::write(bytecode_dir.join(format!("{}.mv", name)), bytes)?;
+    }
+
+    let (version, upgrade_policy) = match metadata {
+        Some(metadata) => (
+            metadata.version.to_string(),
+            metadata.upgrade_policy().unwrap_or_default(),
+        ),
+        None => ("0.0.0".to_string(), UpgradePolicy::default()),
+    };
+    let manifest = format!(
+        "[package]\nname = \"{}\"\nversion = \"{}\"\n{} = \"{}\"\n",
+        package_name, version, UPGRADE_POLICY_FIELD, upgrade_policy
+    );
+    std::fs::write(package_path.join("Move.toml"), manifest)?;
+    Ok(())
+}
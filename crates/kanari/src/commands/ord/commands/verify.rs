@@ -0,0 +1,327 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use kanari_ord::ord_client::OrdClient;
+use kanari_ord::verify::{
+    InscriptionCountReport, InscriptionMismatch, VerifyReport, compare_inscription,
+    find_missing_in_ord,
+};
+use kanari_rpc_client::Client;
+use kanari_types::bitcoin::BitcoinModule;
+use kanari_types::bitcoin::ord::{Inscription, InscriptionID, InscriptionStore};
+use kanari_types::error::{KanariError, KanariResult};
+use moveos_types::module_binding::MoveFunctionCaller;
+use moveos_types::moveos_std::object::MAX_OBJECT_IDS_PER_QUERY;
+use moveos_types::state::{FieldKey, ObjectState};
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Verify the inscriptions in Kanari against the index of an ord server.
+/// The command walks the blocks from `start-block` to `end-block`, fetches the inscriptions of every block from ord,
+/// and compares the inscription id, number and location with the inscription objects in Kanari.
+/// The inscriptions created by Kanari in the blocks but not listed by ord are reported too.
+/// The blessed and cursed inscription counts are compared when both sides are on the same height.
+#[derive(Debug, Parser)]
+pub struct VerifyCommand {
+    /// The ord server http url, e.g. http://127.0.0.1:80
+    #[clap(long)]
+    pub ord_url: String,
+
+    /// The first block height to verify
+    #[clap(long)]
+    pub start_block: u64,
+
+    /// The last block height to verify, included.
+    /// If not specified, use the latest block processed by Kanari
+    #[clap(long, conflicts_with = "follow")]
+    pub end_block: Option<u64>,
+
+    /// Keep verifying the new blocks once both Kanari and ord have indexed them, until Ctrl-C
+    #[clap(long)]
+    pub follow: bool,
+
+    /// The interval in seconds to poll the new blocks in follow mode
+    #[clap(long, default_value = "10")]
+    pub interval: u64,
+
+    /// Write the json report to the file, the file is rewritten after every round in follow mode
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<VerifyReport> for VerifyCommand {
+    async fn execute(self) -> KanariResult<VerifyReport> {
+        let context = self.context_options.build()?;
+        let client = context.get_client().await?;
+        let verifier = Verifier {
+            client,
+            ord_client: OrdClient::new(self.ord_url.trim_end_matches('/').to_string()),
+        };
+
+        let kanari_height = verifier.kanari_height()?;
+        let end_block = match self.end_block {
+            Some(end_block) => end_block,
+            None => kanari_height.ok_or_else(|| {
+                KanariError::CommandArgumentError(
+                    "Kanari has not processed any Bitcoin block".to_string(),
+                )
+            })?,
+        };
+        if self.start_block > end_block && !self.follow {
+            return Err(KanariError::CommandArgumentError(format!(
+                "Invalid block range: {} > {}",
+                self.start_block, end_block
+            )));
+        }
+
+        let mut report = VerifyReport::new(self.start_block, end_block);
+        if self.start_block <= end_block {
+            verifier
+                .verify_blocks(self.start_block, end_block, &mut report)
+                .await?;
+        } else {
+            // Nothing is verified yet, the follow mode starts from `start_block`
+            report.end_block = self.start_block - 1;
+        }
+        verifier.compare_counts(kanari_height, &mut report).await?;
+        write_report(&report, self.output.as_ref())?;
+
+        if self.follow {
+            let interval = Duration::from_secs(self.interval);
+            loop {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {
+                        info!("Stop following new blocks");
+                        break;
+                    }
+                    _ = tokio::time::sleep(interval) => {}
+                }
+                let kanari_height = verifier.kanari_height()?;
+                let ord_height = verifier.ord_client.get_status().await?.height;
+                // Only verify the blocks both sides have indexed
+                let Some(end_block) = kanari_height
+                    .zip(ord_height)
+                    .map(|(kanari_height, ord_height)| kanari_height.min(ord_height as u64))
+                else {
+                    continue;
+                };
+                if end_block <= report.end_block {
+                    continue;
+                }
+                verifier
+                    .verify_blocks(report.end_block + 1, end_block, &mut report)
+                    .await?;
+                report.end_block = end_block;
+                verifier.compare_counts(kanari_height, &mut report).await?;
+                write_report(&report, self.output.as_ref())?;
+            }
+        }
+        Ok(report)
+    }
+}
+
+fn write_report(report: &VerifyReport, output: Option<&PathBuf>) -> KanariResult<()> {
+    info!(
+        "Verified {} inscriptions in blocks [{}, {}], mismatches: {}",
+        report.checked_inscriptions,
+        report.start_block,
+        report.end_block,
+        report.mismatches.len()
+    );
+    if let Some(output) = output {
+        let file = File::create(output)?;
+        serde_json::to_writer_pretty(file, report)?;
+    }
+    Ok(())
+}
+
+struct Verifier {
+    client: Client,
+    ord_client: OrdClient,
+}
+
+impl Verifier {
+    fn kanari_height(&self) -> KanariResult<Option<u64>> {
+        let bitcoin_module = self.client.as_module_binding::<BitcoinModule>();
+        Ok(bitcoin_module
+            .get_latest_block()?
+            .map(|block| block.block_height))
+    }
+
+    async fn get_inscription_store(&self) -> KanariResult<InscriptionStore> {
+        let inscription_store = self
+            .client
+            .kanari
+            .get_object_states(vec![InscriptionStore::object_id()], None)
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| {
+                KanariError::CommandArgumentError("InscriptionStore not found".to_string())
+            })?;
+        Ok(ObjectState::from(inscription_store).value_as::<InscriptionStore>()?)
+    }
+
+    async fn compare_counts(
+        &self,
+        kanari_height: Option<u64>,
+        report: &mut VerifyReport,
+    ) -> KanariResult<()> {
+        let ord_status = self.ord_client.get_status().await?;
+        let inscription_store = self.get_inscription_store().await?;
+        let counts = InscriptionCountReport::new(&ord_status, kanari_height, &inscription_store);
+        if !counts.compared {
+            warn!(
+                "Kanari height {:?} is different from ord height {:?}, the locations and counts may be not comparable",
+                counts.kanari_height, counts.ord_height
+            );
+        }
+        report.counts = Some(counts);
+        Ok(())
+    }
+
+    async fn verify_blocks(
+        &self,
+        start_block: u64,
+        end_block: u64,
+        report: &mut VerifyReport,
+    ) -> KanariResult<()> {
+        let mut kanari_inscriptions = self.get_kanari_inscriptions(start_block, end_block).await?;
+        for block_height in start_block..=end_block {
+            let ord_inscription_ids = self
+                .ord_client
+                .get_inscriptions_by_block(block_height)
+                .await?;
+            let mut mismatches = self.verify_block(&ord_inscription_ids, report).await?;
+            mismatches.extend(find_missing_in_ord(
+                &ord_inscription_ids,
+                &kanari_inscriptions
+                    .remove(&block_height)
+                    .unwrap_or_default(),
+            ));
+            if !mismatches.is_empty() {
+                warn!(
+                    "Block {} has {} inscription mismatches",
+                    block_height,
+                    mismatches.len()
+                );
+            }
+            report.add_mismatches(block_height, mismatches);
+        }
+        Ok(())
+    }
+
+    /// Check the inscriptions listed by ord in a block against the inscriptions in Kanari
+    async fn verify_block(
+        &self,
+        inscription_ids: &[InscriptionID],
+        report: &mut VerifyReport,
+    ) -> KanariResult<Vec<InscriptionMismatch>> {
+        let mut mismatches = vec![];
+        for ids in inscription_ids.chunks(MAX_OBJECT_IDS_PER_QUERY) {
+            let object_ids = ids.iter().map(|id| id.object_id()).collect();
+            let states = self
+                .client
+                .kanari
+                .get_object_states(object_ids, None)
+                .await?;
+            for (inscription_id, state) in ids.iter().zip(states) {
+                let Some(state) = state else {
+                    mismatches.push(InscriptionMismatch::MissingInKanari {
+                        inscription_id: *inscription_id,
+                    });
+                    continue;
+                };
+                let inscription = ObjectState::from(state).value_as::<Inscription>()?;
+                match self.ord_client.get_inscription(inscription_id).await? {
+                    Some(ord_inscription) => {
+                        mismatches.extend(compare_inscription(&ord_inscription, &inscription))
+                    }
+                    None => mismatches.push(InscriptionMismatch::MissingInOrd {
+                        inscription_id: *inscription_id,
+                    }),
+                }
+            }
+            report.checked_inscriptions += ids.len() as u64;
+        }
+        Ok(mismatches)
+    }
+
+    /// Get the inscriptions created by Kanari in the block range, grouped by block height.
+    /// The inscriptions are numbered by sequence number in block order, so binary search the first one in the range.
+    async fn get_kanari_inscriptions(
+        &self,
+        start_block: u64,
+        end_block: u64,
+    ) -> KanariResult<BTreeMap<u64, Vec<InscriptionID>>> {
+        let next_sequence_number = self.get_inscription_store().await?.next_sequence_number;
+        let (mut low, mut high) = (0, next_sequence_number);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let (_, block_height) = self.get_inscription_by_sequence_number(mid).await?;
+            if block_height < start_block {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+
+        let mut inscriptions = BTreeMap::<u64, Vec<InscriptionID>>::new();
+        for sequence_number in low..next_sequence_number {
+            let (inscription_id, block_height) = self
+                .get_inscription_by_sequence_number(sequence_number)
+                .await?;
+            if block_height > end_block {
+                break;
+            }
+            inscriptions
+                .entry(block_height)
+                .or_default()
+                .push(inscription_id);
+        }
+        Ok(inscriptions)
+    }
+
+    /// Get the inscription id and the block height of its reveal tx,
+    /// the inscriptions imported from genesis have no tx height, and are treated as height 0
+    async fn get_inscription_by_sequence_number(
+        &self,
+        sequence_number: u32,
+    ) -> KanariResult<(InscriptionID, u64)> {
+        let field = self
+            .client
+            .kanari
+            .get_field_states(
+                InscriptionStore::object_id().into(),
+                vec![FieldKey::derive(&sequence_number)?.into()],
+                None,
+            )
+            .await?
+            .pop()
+            .flatten()
+            .ok_or_else(|| {
+                KanariError::ViewFunctionError(format!(
+                    "Inscription of sequence number {} not found",
+                    sequence_number
+                ))
+            })?;
+        let inscription_id = ObjectState::from(field)
+            .value_as_df::<u32, InscriptionID>()?
+            .value;
+        let bitcoin_module = self.client.as_module_binding::<BitcoinModule>();
+        let block_height = bitcoin_module
+            .get_tx_height(inscription_id.txid)?
+            .unwrap_or_default();
+        Ok((inscription_id, block_height))
+    }
+}
@@ -3,3 +3,7 @@
 
 pub mod create;
 pub mod list;
+pub mod renew;
+pub mod revoke;
+pub mod rotate;
+pub mod show;
@@ -0,0 +1,88 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use super::show::SessionKeyInfoView;
+use crate::cli_types::{CommandAction, TransactionOptions, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use kanari_key::keystore::account_keystore::AccountKeystore;
+use kanari_types::{
+    address::KanariAddress,
+    authentication_key::AuthenticationKey,
+    error::{KanariError, KanariResult},
+    framework::session_key::SessionKeyModule,
+};
+
+/// Renew a session key, its last active time is reset to now.
+///
+/// The session key is removed and re-created with the same authentication key and scopes
+/// in one transaction, so an expired session key can be renewed too.
+#[derive(Debug, Parser)]
+pub struct RenewCommand {
+    /// The authentication key of the session key to renew
+    #[clap(long)]
+    authentication_key: AuthenticationKey,
+
+    /// The new max inactive interval of the session key, in seconds.
+    /// If absent, keep the current max inactive interval.
+    #[clap(long)]
+    max_inactive_interval: Option<u64>,
+
+    #[clap(flatten)]
+    pub tx_options: TransactionOptions,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<SessionKeyInfoView> for RenewCommand {
+    async fn execute(self) -> KanariResult<SessionKeyInfoView> {
+        let mut context = self.context_options.build_require_password()?;
+        let sender: KanariAddress = context.resolve_address(self.tx_options.sender)?.into();
+        let max_gas_amount = self.tx_options.max_gas_amount;
+        let client = context.get_client().await?;
+
+        let session_key = client
+            .kanari
+            .get_session_key(sender, &self.authentication_key)
+            .await?
+            .ok_or_else(|| {
+                KanariError::CommandArgumentError(format!(
+                    "Cannot find session key {} of address {}",
+                    self.authentication_key, sender
+                ))
+            })?;
+        let max_inactive_interval = self
+            .max_inactive_interval
+            .unwrap_or(session_key.max_inactive_interval);
+
+        let action = SessionKeyModule::renew_session_key_action(session_key, max_inactive_interval);
+        let tx_data = context
+            .build_tx_data(sender, action, max_gas_amount)
+            .await?;
+        let result = context.sign_and_execute(sender, tx_data).await?;
+        context.assert_execute_success(result)?;
+
+        let renewed = client
+            .kanari
+            .get_session_key(sender, &self.authentication_key)
+            .await?
+            .ok_or_else(|| {
+                KanariError::ViewFunctionError(format!(
+                    "Failed to get session key via {}",
+                    self.authentication_key
+                ))
+            })?;
+        if context
+            .keystore
+            .get_bound_session_key(&sender, &self.authentication_key)
+            .is_some()
+        {
+            context
+                .keystore
+                .binding_session_key(sender, renewed.clone())?;
+        }
+        Ok(SessionKeyInfoView::new(&context, &sender, renewed))
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use super::show::SessionKeyInfoView;
+use crate::cli_types::{CommandAction, TransactionOptions, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use kanari_key::keystore::account_keystore::AccountKeystore;
+use kanari_types::{
+    address::KanariAddress,
+    authentication_key::AuthenticationKey,
+    error::{KanariError, KanariResult},
+    framework::session_key::SessionKeyModule,
+};
+
+/// Revoke session keys on-chain in one transaction and remove them from the keystore
+#[derive(Debug, Parser)]
+pub struct RevokeCommand {
+    /// The authentication keys of the session keys to revoke
+    #[clap(long = "authentication-key", required_unless_present = "expired")]
+    authentication_keys: Vec<AuthenticationKey>,
+
+    /// Revoke all the expired session keys
+    #[clap(long)]
+    expired: bool,
+
+    #[clap(flatten)]
+    pub tx_options: TransactionOptions,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<Vec<SessionKeyInfoView>> for RevokeCommand {
+    async fn execute(self) -> KanariResult<Vec<SessionKeyInfoView>> {
+        let mut context = self.context_options.build_require_password()?;
+        let sender: KanariAddress = context.resolve_address(self.tx_options.sender)?.into();
+        let max_gas_amount = self.tx_options.max_gas_amount;
+        let client = context.get_client().await?;
+
+        let session_keys = client.kanari.get_session_keys(sender).await?;
+        for authentication_key in &self.authentication_keys {
+            if !session_keys
+                .iter()
+                .any(|session_key| &session_key.authentication_key() == authentication_key)
+            {
+                return Err(KanariError::CommandArgumentError(format!(
+                    "Cannot find session key {} of address {}",
+                    authentication_key, sender
+                )));
+            }
+        }
+        let revoked = session_keys
+            .into_iter()
+            .filter(|session_key| {
+                (self.expired && session_key.is_expired())
+                    || self
+                        .authentication_keys
+                        .contains(&session_key.authentication_key())
+            })
+            .collect::<Vec<_>>();
+        if revoked.is_empty() {
+            return Err(KanariError::CommandArgumentError(
+                "No session key to revoke".to_owned(),
+            ));
+        }
+
+        let action = SessionKeyModule::remove_session_keys_action(
+            revoked
+                .iter()
+                .map(|session_key| session_key.authentication_key.clone())
+                .collect(),
+        );
+        let tx_data = context
+            .build_tx_data(sender, action, max_gas_amount)
+            .await?;
+        let result = context.sign_and_execute(sender, tx_data).await?;
+        context.assert_execute_success(result)?;
+
+        for session_key in &revoked {
+            context
+                .keystore
+                .remove_session_key(&sender, &session_key.authentication_key())?;
+        }
+        Ok(revoked
+            .into_iter()
+            .map(|session_key| SessionKeyInfoView::new(&context, &sender, session_key))
+            .collect())
+    }
+}
@@ -0,0 +1,106 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use super::show::SessionKeyInfoView;
+use crate::cli_types::{CommandAction, TransactionOptions, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use kanari_key::keystore::account_keystore::AccountKeystore;
+use kanari_types::{
+    address::KanariAddress,
+    error::{KanariError, KanariResult},
+    framework::session_key::SessionKeyModule,
+};
+
+/// Rotate the session keys of an account in one transaction.
+///
+/// Each session key of the keystore is replaced by a newly generated key with the same app,
+/// scopes and max inactive interval. The old keys are removed on-chain and from the keystore.
+#[derive(Debug, Parser)]
+pub struct RotateCommand {
+    /// Also rotate the expired session keys, they are skipped by default
+    #[clap(long)]
+    include_expired: bool,
+
+    #[clap(flatten)]
+    pub tx_options: TransactionOptions,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+}
+
+#[async_trait]
+impl CommandAction<Vec<SessionKeyInfoView>> for RotateCommand {
+    async fn execute(self) -> KanariResult<Vec<SessionKeyInfoView>> {
+        let mut context = self.context_options.build_require_password()?;
+        let sender: KanariAddress = context.resolve_address(self.tx_options.sender)?.into();
+        let max_gas_amount = self.tx_options.max_gas_amount;
+        let client = context.get_client().await?;
+
+        // Only the session keys of the keystore are rotated, the others may be used by other wallets or apps
+        let session_keys = client
+            .kanari
+            .get_session_keys(sender)
+            .await?
+            .into_iter()
+            .filter(|session_key| {
+                context
+                    .keystore
+                    .get_bound_session_key(&sender, &session_key.authentication_key())
+                    .is_some()
+            })
+            .filter(|session_key| self.include_expired || !session_key.is_expired())
+            .collect::<Vec<_>>();
+        if session_keys.is_empty() {
+            return Err(KanariError::CommandArgumentError(format!(
+                "No session key of address {} to rotate in the keystore",
+                sender
+            )));
+        }
+
+        let mut rotations = Vec::with_capacity(session_keys.len());
+        for session_key in session_keys {
+            let new_authentication_key = context.generate_session_key(&sender)?;
+            rotations.push((session_key, new_authentication_key));
+        }
+        let action = SessionKeyModule::rotate_session_keys_action(rotations.clone());
+        let tx_data = context
+            .build_tx_data(sender, action, max_gas_amount)
+            .await?;
+        let result = context
+            .sign_and_execute(sender, tx_data)
+            .await
+            .and_then(|result| context.assert_execute_success(result));
+        if let Err(e) = result {
+            // The new session keys are not created on-chain, remove them from the keystore
+            for (_, new_authentication_key) in &rotations {
+                context
+                    .keystore
+                    .remove_session_key(&sender, new_authentication_key)?;
+            }
+            return Err(e);
+        }
+
+        let mut views = Vec::with_capacity(rotations.len());
+        for (old_session_key, new_authentication_key) in rotations {
+            context
+                .keystore
+                .remove_session_key(&sender, &old_session_key.authentication_key())?;
+            let session_key = client
+                .kanari
+                .get_session_key(sender, &new_authentication_key)
+                .await?
+                .ok_or_else(|| {
+                    KanariError::ViewFunctionError(format!(
+                        "Failed to get session key via {}",
+                        new_authentication_key
+                    ))
+                })?;
+            context
+                .keystore
+                .binding_session_key(sender, session_key.clone())?;
+            views.push(SessionKeyInfoView::new(&context, &sender, session_key));
+        }
+        Ok(views)
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::{CommandAction, WalletContextOptions};
+use async_trait::async_trait;
+use clap::Parser;
+use kanari_key::keystore::account_keystore::AccountKeystore;
+use kanari_rpc_client::wallet_context::WalletContext;
+use kanari_types::address::{KanariAddress, ParsedAddress};
+use kanari_types::authentication_key::AuthenticationKey;
+use kanari_types::error::{KanariError, KanariResult};
+use kanari_types::framework::session_key::SessionKey;
+use serde::{Deserialize, Serialize};
+use tabled::{builder::Builder, settings::Style};
+
+/// Show the session keys of an account with their scopes, last active time and expiry
+#[derive(Debug, Parser)]
+pub struct ShowCommand {
+    #[clap(short = 'a', long = "address", value_parser=ParsedAddress::parse, default_value = "default")]
+    /// The account's address to show session keys, if absent, show the default active account.
+    address: ParsedAddress,
+
+    /// Only show the session key of the authentication key
+    #[clap(long)]
+    authentication_key: Option<AuthenticationKey>,
+
+    #[clap(flatten)]
+    pub context_options: WalletContextOptions,
+
+    /// Return command outputs in json format
+    #[clap(long)]
+    pub json: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionKeyInfoView {
+    pub authentication_key: String,
+    pub app_name: String,
+    pub app_url: String,
+    pub scopes: Vec<String>,
+    pub create_time: u64,
+    pub last_active_time: u64,
+    pub max_inactive_interval: u64,
+    /// The session key expires if it is not active after this time, `None` if it never expires
+    pub expiration_time: Option<u64>,
+    pub is_expired: bool,
+    /// Whether the private key of the session key is in the keystore
+    pub in_keystore: bool,
+}
+
+impl SessionKeyInfoView {
+    pub fn new(context: &WalletContext, address: &KanariAddress, session_key: SessionKey) -> Self {
+        let authentication_key = session_key.authentication_key();
+        let in_keystore = context
+            .keystore
+            .get_bound_session_key(address, &authentication_key)
+            .is_some();
+        SessionKeyInfoView {
+            authentication_key: authentication_key.to_string(),
+            app_name: session_key.app_name.to_string(),
+            app_url: session_key.app_url.to_string(),
+            scopes: session_key.scopes.iter().map(ToString::to_string).collect(),
+            create_time: session_key.create_time,
+            last_active_time: session_key.last_active_time,
+            max_inactive_interval: session_key.max_inactive_interval,
+            expiration_time: session_key.expiration_time(),
+            is_expired: session_key.is_expired(),
+            in_keystore,
+        }
+    }
+}
+
+#[async_trait]
+impl CommandAction<Vec<SessionKeyInfoView>> for ShowCommand {
+    async fn execute(self) -> KanariResult<Vec<SessionKeyInfoView>> {
+        let context = self.context_options.build()?;
+        let address = context.resolve_kanari_address(self.address)?;
+        let client = context.get_client().await?;
+
+        let mut session_keys = client.kanari.get_session_keys(address).await?;
+        if let Some(authentication_key) = &self.authentication_key {
+            session_keys
+                .retain(|session_key| &session_key.authentication_key() == authentication_key);
+            if session_keys.is_empty() {
+                return Err(KanariError::CommandArgumentError(format!(
+                    "Cannot find session key {} of address {}",
+                    authentication_key, address
+                )));
+            }
+        }
+
+        Ok(session_keys
+            .into_iter()
+            .map(|session_key| SessionKeyInfoView::new(&context, &address, session_key))
+            .collect())
+    }
+}
+
+pub(crate) fn display_session_keys_table(session_keys: &[SessionKeyInfoView]) {
+    if session_keys.is_empty() {
+        println!("No session key");
+        return;
+    }
+    let mut builder = Builder::default();
+    builder.push_record([
+        "Authentication Key",
+        "App",
+        "Scopes",
+        "Last Active Time",
+        "Expiration Time",
+        "Status",
+    ]);
+    for session_key in session_keys {
+        let expiration_time = session_key
+            .expiration_time
+            .map_or("Never".to_owned(), |time| time.to_string());
+        let status = match (session_key.is_expired, session_key.in_keystore) {
+            (true, _) => "Expired",
+            (false, true) => "Active",
+            (false, false) => "Active (not in keystore)",
+        };
+        builder.push_record([
+            session_key.authentication_key.as_str(),
+            &format!("{}\n{}", session_key.app_name, session_key.app_url),
+            &session_key.scopes.join("\n"),
+            &session_key.last_active_time.to_string(),
+            &expiration_time,
+            status,
+        ]);
+    }
+    println!("{}", builder.build().with(Style::rounded()));
+}
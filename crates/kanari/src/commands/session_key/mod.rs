@@ -6,6 +6,10 @@ use async_trait::async_trait;
 use clap::Parser;
 use commands::create::CreateCommand;
 use commands::list::ListCommand;
+use commands::renew::RenewCommand;
+use commands::revoke::RevokeCommand;
+use commands::rotate::RotateCommand;
+use commands::show::{ShowCommand, display_session_keys_table};
 use kanari_types::error::KanariResult;
 use serde_json::Value;
 use tabled::{builder::Builder, settings::Style};
@@ -38,6 +42,20 @@ impl CommandAction<String> for SessionKey {
                     Ok(String::new())
                 }
             }
+            SessionKeyCommand::Show(show) => {
+                let display_as_json = show.json;
+                let session_keys = show.execute().await?;
+                if display_as_json {
+                    Ok(serde_json::to_string_pretty(&session_keys)
+                        .expect("Failed to serialize response"))
+                } else {
+                    display_session_keys_table(&session_keys);
+                    Ok(String::new())
+                }
+            }
+            SessionKeyCommand::Revoke(revoke) => revoke.execute_serialized().await,
+            SessionKeyCommand::Renew(renew) => renew.execute_serialized().await,
+            SessionKeyCommand::Rotate(rotate) => rotate.execute_serialized().await,
         }
     }
 }
@@ -47,6 +65,10 @@ impl CommandAction<String> for SessionKey {
 pub enum SessionKeyCommand {
     Create(Box<CreateCommand>),
     List(ListCommand),
+    Show(ShowCommand),
+    Revoke(RevokeCommand),
+    Renew(RenewCommand),
+    Rotate(RotateCommand),
 }
 
 fn display_json_as_table(data: &Value) {
@@ -5,12 +5,14 @@ use crate::cli_types::{CommandAction, TransactionOptions, WalletContextOptions};
 use anyhow::Result;
 use async_trait::async_trait;
 use kanari_types::{
+    bitcoin::multisign_account::MultisignAccountModule,
     error::KanariResult,
     function_arg::{FunctionArg, ParsedFunctionId, parse_function_arg},
+    transaction::signing_request::{SigningRequest, SigningRequestMultisign},
 };
 use move_command_line_common::types::ParsedStructType;
 use move_core_types::language_storage::TypeTag;
-use moveos_types::transaction::MoveAction;
+use moveos_types::{module_binding::MoveFunctionCaller, transaction::MoveAction};
 
 use super::{FileOutput, FileOutputData};
 
@@ -51,6 +53,12 @@ pub struct BuildCommand {
     #[clap(long, short = 'o')]
     output: Option<String>,
 
+    /// Write a signing request instead of the raw transaction data.
+    /// The signing request carries the multisign info of the sender, so it can be signed
+    /// on an offline machine with `kanari tx sign --offline`.
+    #[clap(long)]
+    signing_request: bool,
+
     /// Return command outputs in json format
     #[clap(long, default_value = "false")]
     json: bool,
@@ -86,12 +94,39 @@ impl CommandAction<Option<FileOutput>> for BuildCommand {
             .build_tx_data_with_sequence_number(sender, action, max_gas_amount, sequenc_number)
             .await?;
 
-        let output =
-            FileOutput::write_to_file(FileOutputData::KanariTransactionData(tx_data), self.output)?;
+        let output_data = if self.signing_request {
+            let client = context.get_client().await?;
+            let multisign_account_module = client.as_module_binding::<MultisignAccountModule>();
+            let multisign = if multisign_account_module.is_multisign_account(sender.into())? {
+                let threshold = multisign_account_module.threshold(sender.into())?;
+                let participants = multisign_account_module
+                    .participants(sender.into())?
+                    .into_iter()
+                    .map(|participant| participant.participant_address.into())
+                    .collect();
+                Some(SigningRequestMultisign {
+                    threshold,
+                    participants,
+                })
+            } else {
+                None
+            };
+            FileOutputData::SigningRequest(SigningRequest::new(tx_data, multisign))
+        } else {
+            FileOutputData::KanariTransactionData(tx_data)
+        };
+
+        let output = FileOutput::write_to_file(output_data, self.output)?;
         if self.json {
             Ok(Some(output))
         } else {
             println!("Build transaction succeeded write to file: {}", output.path);
+            if self.signing_request {
+                println!(
+                    "You can sign the signing request offline with `kanari tx sign --offline {}`",
+                    output.path
+                );
+            }
             Ok(None)
         }
     }
@@ -4,6 +4,7 @@
 use anyhow::Result;
 use kanari_types::transaction::{
     KanariTransaction, KanariTransactionData, kanari::PartiallySignedKanariTransaction,
+    signing_request::SigningRequest,
 };
 use moveos_types::h256::H256;
 use serde::{Deserialize, Serialize};
@@ -21,6 +22,7 @@ pub(crate) enum FileOutputData {
     KanariTransactionData(KanariTransactionData),
     SignedKanariTransaction(KanariTransaction),
     PartiallySignedKanariTransaction(PartiallySignedKanariTransaction),
+    SigningRequest(SigningRequest),
 }
 
 impl FileOutputData {
@@ -29,6 +31,7 @@ impl FileOutputData {
             FileOutputData::KanariTransactionData(data) => data.tx_hash(),
             FileOutputData::SignedKanariTransaction(data) => data.data.tx_hash(),
             FileOutputData::PartiallySignedKanariTransaction(data) => data.data.tx_hash(),
+            FileOutputData::SigningRequest(data) => data.tx_hash(),
         }
     }
 
@@ -39,6 +42,7 @@ impl FileOutputData {
             FileOutputData::PartiallySignedKanariTransaction(data) => {
                 data.signatories().to_string()
             }
+            FileOutputData::SigningRequest(data) => data.sender().to_bech32(),
         }
     }
 
@@ -47,6 +51,7 @@ impl FileOutputData {
             FileOutputData::KanariTransactionData(_) => "ktd",
             FileOutputData::SignedKanariTransaction(_) => "skt",
             FileOutputData::PartiallySignedKanariTransaction(_) => "pskt",
+            FileOutputData::SigningRequest(_) => "ksr",
         }
     }
 
@@ -55,6 +60,7 @@ impl FileOutputData {
             FileOutputData::KanariTransactionData(data) => data.encode(),
             FileOutputData::SignedKanariTransaction(data) => data.encode(),
             FileOutputData::PartiallySignedKanariTransaction(data) => data.encode(),
+            FileOutputData::SigningRequest(data) => data.encode(),
         }
     }
 
@@ -6,13 +6,14 @@ use crate::cli_types::{CommandAction, FileOrHexInput, WalletContextOptions};
 use crate::utils::prompt_yes_no;
 use async_trait::async_trait;
 use kanari_key::keystore::account_keystore::AccountKeystore;
+use kanari_rpc_client::wallet_context::WalletContext;
 use kanari_types::{
     address::{KanariAddress, ParsedAddress},
     bitcoin::multisign_account::MultisignAccountModule,
     error::KanariResult,
     transaction::{
         KanariTransaction, KanariTransactionData, authenticator::BitcoinAuthenticator,
-        kanari::PartiallySignedKanariTransaction,
+        kanari::PartiallySignedKanariTransaction, signing_request::SigningRequest,
     },
 };
 use moveos_types::module_binding::MoveFunctionCaller;
@@ -21,12 +22,18 @@ use moveos_types::module_binding::MoveFunctionCaller;
 pub enum SignInput {
     KanariTransactionData(KanariTransactionData),
     PartiallySignedKanariTransaction(PartiallySignedKanariTransaction),
+    SigningRequest(SigningRequest),
 }
 
 impl TryFrom<FileOrHexInput> for SignInput {
     type Error = anyhow::Error;
 
     fn try_from(value: FileOrHexInput) -> Result<Self, Self::Error> {
+        if SigningRequest::is_signing_request(&value.data) {
+            return Ok(SignInput::SigningRequest(SigningRequest::decode(
+                &value.data,
+            )?));
+        }
         let input = match bcs::from_bytes::<KanariTransactionData>(&value.data) {
             Ok(tx_data) => SignInput::KanariTransactionData(tx_data),
             Err(_) => {
@@ -48,9 +55,17 @@ impl SignInput {
         match self {
             SignInput::KanariTransactionData(tx_data) => tx_data.sender,
             SignInput::PartiallySignedKanariTransaction(psrt) => psrt.sender(),
+            SignInput::SigningRequest(request) => request.sender(),
         }
     }
 }
+
+/// The multisign info of the sender, the participants are unknown when a partially signed
+/// transaction is signed offline.
+struct MultisignInfo {
+    threshold: u64,
+    participants: Option<Vec<KanariAddress>>,
+}
 pub enum SignOutput {
     SignedKanariTransaction(KanariTransaction),
     PartiallySignedKanariTransaction(PartiallySignedKanariTransaction),
@@ -77,8 +92,8 @@ impl From<SignOutput> for FileOutputData {
 #[derive(Debug, clap::Parser)]
 pub struct SignCommand {
     /// Input data to be used for signing
-    /// Input can be a transaction data hex or a partially signed transaction data hex
-    /// or a file path which contains transaction data or partially signed transaction data
+    /// Input can be a transaction data hex, a partially signed transaction data hex or a signing request hex
+    /// or a file path which contains one of them
     input: FileOrHexInput,
 
     /// The address of the signer when the transaction is a multisign account transaction
@@ -91,6 +106,13 @@ pub struct SignCommand {
     #[clap(long, short = 'o')]
     output: Option<String>,
 
+    /// Sign without connecting to the network, on an air-gapped machine for example.
+    /// The multisign info is taken from the signing request built by `kanari tx build --signing-request`,
+    /// a raw transaction data is signed as a single signer transaction,
+    /// and a partially signed transaction requires --signer.
+    #[clap(long)]
+    offline: bool,
+
     /// Automatically answer 'yes' to all prompts
     #[clap(long = "yes", short = 'y')]
     answer_yes: bool,
@@ -104,31 +126,70 @@ pub struct SignCommand {
 }
 
 impl SignCommand {
-    async fn sign(self) -> anyhow::Result<SignOutput> {
-        let context = self.context.build_require_password()?;
+    async fn multisign_info(
+        &self,
+        context: &WalletContext,
+        sign_input: &SignInput,
+    ) -> anyhow::Result<Option<MultisignInfo>> {
+        if let SignInput::SigningRequest(request) = sign_input {
+            return Ok(request.multisign.as_ref().map(|multisign| MultisignInfo {
+                threshold: multisign.threshold,
+                participants: Some(multisign.participants.clone()),
+            }));
+        }
+        if self.offline {
+            return Ok(match sign_input {
+                SignInput::PartiallySignedKanariTransaction(psrt) => Some(MultisignInfo {
+                    threshold: psrt.threshold,
+                    participants: None,
+                }),
+                _ => None,
+            });
+        }
+        let sender = sign_input.sender();
         let client = context.get_client().await?;
         let multisign_account_module = client.as_module_binding::<MultisignAccountModule>();
-        let sign_input = SignInput::try_from(self.input)?;
-        let sender = sign_input.sender();
-        let output = if multisign_account_module.is_multisign_account(sender.into())? {
-            let threshold = multisign_account_module.threshold(sender.into())?;
+        if !multisign_account_module.is_multisign_account(sender.into())? {
+            return Ok(None);
+        }
+        let threshold = multisign_account_module.threshold(sender.into())?;
+        let participants = multisign_account_module
+            .participants(sender.into())?
+            .into_iter()
+            .map(|participant| participant.participant_address.into())
+            .collect();
+        Ok(Some(MultisignInfo {
+            threshold,
+            participants: Some(participants),
+        }))
+    }
 
+    async fn sign(self) -> anyhow::Result<SignOutput> {
+        let context = self.context.build_require_password()?;
+        let sign_input = SignInput::try_from(self.input.clone())?;
+        let sender = sign_input.sender();
+        let output = if let Some(multisign) = self.multisign_info(&context, &sign_input).await? {
             let mut psrt = match sign_input {
                 SignInput::KanariTransactionData(tx_data) => {
-                    PartiallySignedKanariTransaction::new(tx_data, threshold)
+                    PartiallySignedKanariTransaction::new(tx_data, multisign.threshold)
+                }
+                SignInput::SigningRequest(request) => {
+                    PartiallySignedKanariTransaction::new(request.data, multisign.threshold)
                 }
                 SignInput::PartiallySignedKanariTransaction(psrt) => psrt,
             };
             match self.signer {
                 Some(signer) => {
-                    let signer = context.resolve_address(signer)?;
-                    if !multisign_account_module.is_participant(sender.into(), signer)? {
+                    let signer = context.resolve_kanari_address(signer)?;
+                    if let Some(participants) = &multisign.participants
+                        && !participants.contains(&signer)
+                    {
                         return Err(anyhow::anyhow!(
                             "The signer address {} is not a participant in the multisign account",
                             signer
                         ));
                     }
-                    let kp = context.get_key_pair(&signer.into())?;
+                    let kp = context.get_key_pair(&signer)?;
                     let authenticator = BitcoinAuthenticator::sign(&kp, &psrt.data);
                     if psrt.contains_authenticator(&authenticator) {
                         return Err(anyhow::anyhow!(
@@ -138,16 +199,16 @@ impl SignCommand {
                     psrt.add_authenticator(authenticator)?;
                 }
                 None => {
-                    let participants = multisign_account_module.participants(sender.into())?;
+                    let participants = multisign.participants.ok_or_else(|| {
+                        anyhow::anyhow!(
+                            "The participants of the multisign account are unknown offline, please specify the signer with --signer"
+                        )
+                    })?;
                     let mut has_participant = false;
                     for participant in participants.iter() {
-                        if context
-                            .keystore
-                            .contains_address(&participant.participant_address.into())
-                        {
+                        if context.keystore.contains_address(participant) {
                             has_participant = true;
-                            let kp =
-                                context.get_key_pair(&participant.participant_address.into())?;
+                            let kp = context.get_key_pair(participant)?;
                             let authenticator = BitcoinAuthenticator::sign(&kp, &psrt.data);
                             if psrt.contains_authenticator(&authenticator) {
                                 continue;
@@ -172,6 +233,7 @@ impl SignCommand {
         } else {
             let tx_data = match sign_input {
                 SignInput::KanariTransactionData(tx_data) => tx_data,
+                SignInput::SigningRequest(request) => request.data,
                 SignInput::PartiallySignedKanariTransaction(_) => {
                     return Err(anyhow::anyhow!(
                         "Cannot sign a partially signed transaction with a single signer"
@@ -200,6 +262,16 @@ impl SignCommand {
             SignInput::KanariTransactionData(tx) => {
                 println!("Transaction data:\n{}", tx_data(tx));
             }
+            SignInput::SigningRequest(request) => {
+                println!("Signing request:\n{}", tx_data(&request.data));
+                if let Some(multisign) = &request.multisign {
+                    println!(
+                        " Multisign threshold: {}/{}",
+                        multisign.threshold,
+                        multisign.participants.len()
+                    );
+                }
+            }
             SignInput::PartiallySignedKanariTransaction(pstx) => {
                 println!(
                     "Partially signed transaction data:\n{}",
@@ -0,0 +1,686 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::cli_types::CommandAction;
+use crate::tx_runner::convert_to_verified_tx;
+use async_trait::async_trait;
+use clap::Parser;
+use framework_builder::releaser::check_compiled_module_compat;
+use framework_builder::stdlib_version::StdlibVersion;
+use framework_types::addresses::KANARI_FRAMEWORK_ADDRESS;
+use kanari_config::{KanariOpt, R_OPT_NET_HELP};
+use kanari_db::KanariDB;
+use kanari_genesis::FrameworksGasParameters;
+use kanari_rpc_client::{ClientBuilder, ClientNodeReader};
+use kanari_types::error::{KanariError, KanariResult};
+use kanari_types::framework::chain_id::ChainID;
+use kanari_types::framework::onchain_config::ConfigUpdateCap;
+use kanari_types::framework::session_key::{SessionKey, SessionKeys};
+use kanari_types::framework::{system_post_execute_functions, system_pre_execute_functions};
+use kanari_types::kanari_network::KanariChainID;
+use kanari_types::transaction::KanariTransactionData;
+use metrics::RegistryService;
+use move_binary_format::CompiledModule;
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::normalized;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use move_core_types::resolver::MoveResolver;
+use move_core_types::vm_status::KeptVMStatus;
+use moveos::moveos::{MoveOS, MoveOSConfig};
+use moveos_store::MoveOSStore;
+use moveos_types::addresses::MOVEOS_STD_ADDRESS;
+use moveos_types::h256::H256;
+use moveos_types::move_std::string::MoveString;
+use moveos_types::move_types::FunctionId;
+use moveos_types::moveos_std::gas_schedule::{GasSchedule, GasScheduleConfig};
+use moveos_types::moveos_std::module_store::{self, Package, UpgradeCap};
+use moveos_types::moveos_std::move_module::MoveModule;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::state::{FieldKey, MoveState, MoveStructType, ObjectState};
+use moveos_types::state_resolver::{RootObjectResolver, StateReaderExt, StateResolver};
+use moveos_types::transaction::{FunctionCall, MoveAction};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+const LIST_FIELDS_PAGE_SIZE: usize = 1000;
+
+/// Dry-run the framework upgrade against the state of a node, and report the compatibility issues.
+/// The upgrade is executed in a sandbox created from a checkpoint of the node store,
+/// so the node store is not changed, but the node should be stopped before running this command.
+/// With `--fork-url`, the sandbox reads the state from a running node through RPC instead.
+#[derive(Debug, Parser)]
+pub struct CheckCommand {
+    #[clap(long = "data-dir", short = 'd', conflicts_with = "fork_url")]
+    /// Path to data dir, this dir is base dir, the final data_dir is base_dir/chain_network_name
+    pub base_data_dir: Option<PathBuf>,
+
+    #[clap(long, short = 'n', help = R_OPT_NET_HELP, conflicts_with = "fork_url")]
+    pub chain_id: Option<KanariChainID>,
+
+    /// The RPC URL of the node to check against, the state is read from the latest state root of the node.
+    #[clap(long)]
+    pub fork_url: Option<String>,
+
+    /// The accounts to check the session key scopes for.
+    /// There is no index of the session keys, so the scopes are only checked for the given accounts.
+    #[clap(long = "session-key-account")]
+    pub session_key_accounts: Vec<AccountAddress>,
+
+    /// The candidate stdlib version to upgrade to, a released version number or `latest`.
+    #[clap(long, default_value = "latest")]
+    pub stdlib_version: StdlibVersion,
+
+    /// The max gas amount of every upgrade transaction executed in the sandbox.
+    #[clap(long, default_value_t = GasScheduleConfig::CLI_DEFAULT_MAX_GAS_AMOUNT)]
+    pub max_gas_amount: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UpgradeCheckReport {
+    pub state_root: H256,
+    pub stdlib_version: String,
+    /// The modules which are incompatible, removed or have layout changes
+    pub modules: Vec<ModuleCheckResult>,
+    /// The session key scopes of the `--session-key-account` accounts which refer to the removed entry functions
+    pub session_key_scopes: Vec<SessionScopeCheckResult>,
+    /// The gas schedule diff, `None` if the gas parameters of the stdlib version are unknown
+    pub gas_schedule: Option<GasScheduleDiff>,
+    /// The upgrade transactions executed in the sandbox in order
+    pub sandbox_txs: Vec<SandboxTxResult>,
+    /// Whether all the modules are compatible and all the upgrade transactions are executed
+    pub compatible: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ModuleCheckResult {
+    pub module_id: String,
+    pub removed: bool,
+    pub compat_error: Option<String>,
+    pub layout_changes: Vec<String>,
+    pub removed_entry_functions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SessionScopeCheckResult {
+    pub account: AccountAddress,
+    pub app_name: String,
+    pub scope: String,
+    pub removed_entry_functions: Vec<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct GasScheduleDiff {
+    pub onchain_schedule_version: u64,
+    pub added: BTreeMap<String, u64>,
+    /// The modified entries, with the onchain value and the new value
+    pub modified: BTreeMap<String, (u64, u64)>,
+    /// The onchain entries which are not in the new gas schedule
+    pub removed: Vec<String>,
+}
+
+impl GasScheduleDiff {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SandboxTxResult {
+    pub name: String,
+    pub sender: AccountAddress,
+    pub status: String,
+    pub gas_used: u64,
+    pub error: Option<String>,
+}
+
+impl SandboxTxResult {
+    fn is_executed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+#[async_trait]
+impl CommandAction<UpgradeCheckReport> for CheckCommand {
+    async fn execute(self) -> KanariResult<UpgradeCheckReport> {
+        let registry_service = RegistryService::default();
+        let registry = registry_service.default_registry();
+        // The sandbox dir should be held until the check is finished.
+        let (moveos_store, root, _sandbox_dir) = match &self.fork_url {
+            Some(fork_url) => {
+                let client = ClientBuilder::default().build(fork_url).await?;
+                let root_state = client.kanari.status().await?.kanari_status.root_state;
+                let root = ObjectMeta::root_metadata(root_state.state_root.0, root_state.size.0);
+                let sandbox_dir = moveos_config::temp_dir();
+                let remote = Arc::new(ClientNodeReader::new(client));
+                let moveos_store = MoveOSStore::new_fork(sandbox_dir.path(), &registry, remote)?;
+                (moveos_store, root, sandbox_dir)
+            }
+            None => {
+                let opt = KanariOpt::new_with_default(self.base_data_dir, self.chain_id, None)?;
+                // Create the sandbox under the data dir, so the sst files can be hard linked
+                let sandbox_dir = moveos_config::temp_dir_in(opt.base().data_dir().to_path_buf());
+                let moveos_store = KanariDB::open_sandbox_store(
+                    opt.store_config(),
+                    &sandbox_dir.path().join("store"),
+                    &registry,
+                )?;
+                let root = moveos_store
+                    .config_store
+                    .get_startup_info()?
+                    .map(|startup_info| startup_info.into_root_metadata())
+                    .ok_or_else(|| KanariError::from(anyhow::anyhow!("Load latest root failed")))?;
+                (moveos_store, root, sandbox_dir)
+            }
+        };
+
+        let stdlib = framework_release::load_stdlib(self.stdlib_version)?;
+        let mut sandbox = Sandbox::new(moveos_store, root.clone(), self.max_gas_amount)?;
+
+        let resolver = sandbox.resolver();
+        let mut modules = vec![];
+        let mut removed_entry_functions = BTreeSet::new();
+        for package in stdlib.packages().iter() {
+            let onchain_modules = list_package_modules(&resolver, package.genesis_account)?;
+            let new_modules = package
+                .modules()?
+                .into_iter()
+                .map(|module| (module.self_id(), module))
+                .collect::<BTreeMap<_, _>>();
+            for (module_id, old_module) in onchain_modules.iter() {
+                let result = check_module(old_module, new_modules.get(module_id));
+                removed_entry_functions.extend(result.removed_entry_functions.iter().map(
+                    |function_name| FunctionId::new(module_id.clone(), function_name.clone()),
+                ));
+                if result.removed
+                    || result.compat_error.is_some()
+                    || !result.layout_changes.is_empty()
+                    || !result.removed_entry_functions.is_empty()
+                {
+                    modules.push(result.into_check_result(module_id));
+                }
+            }
+        }
+        let session_key_scopes = check_session_key_scopes(
+            &resolver,
+            &self.session_key_accounts,
+            &removed_entry_functions,
+        )?;
+        let chain_id = sandbox.chain_id()?;
+        let new_gas_config = gas_parameters_of_stdlib(self.stdlib_version)
+            .map(|gas_parameters| gas_parameters.to_gas_schedule_config(chain_id));
+        let gas_schedule = new_gas_config
+            .as_ref()
+            .map(|new_gas_config| diff_gas_schedule(&resolver, new_gas_config))
+            .transpose()?;
+        drop(resolver);
+
+        // The framework upgrade path: upgrade the gas schedule by the admin,
+        // then upgrade the packages in dependency order by the owner of the `UpgradeCap`.
+        let mut sandbox_txs = vec![];
+        if let (Some(new_gas_config), Some(gas_schedule)) = (&new_gas_config, &gas_schedule) {
+            if !gas_schedule.is_empty() {
+                let admin = sandbox
+                    .object_owner(&ConfigUpdateCap::config_update_cap_object_id())?
+                    .ok_or_else(|| {
+                        KanariError::from(anyhow::anyhow!("ConfigUpdateCap not found"))
+                    })?;
+                let gas_schedule_bytes = new_gas_config
+                    .to_move_value()
+                    .simple_serialize()
+                    .ok_or_else(|| {
+                        KanariError::from(anyhow::anyhow!("Serialize the gas schedule failed"))
+                    })?;
+                let action = MoveAction::new_function_call(
+                    FunctionId::new(
+                        ModuleId::new(KANARI_FRAMEWORK_ADDRESS, Identifier::new("upgrade")?),
+                        Identifier::new("upgrade_gas_schedule")?,
+                    ),
+                    vec![],
+                    vec![bcs::to_bytes(&gas_schedule_bytes)?],
+                );
+                sandbox_txs.push(sandbox.execute("gas_schedule".to_owned(), admin, action)?);
+            }
+        }
+        for package in stdlib.packages().iter() {
+            let name = package.package_name.clone();
+            let upgrade_cap_id = UpgradeCap::upgrade_cap_id(package.genesis_account);
+            let result = match sandbox.object_owner(&upgrade_cap_id)? {
+                Some(owner) => {
+                    let package_bytes = bcs::to_bytes(&package.as_package_data())?;
+                    let action = MoveAction::new_function_call(
+                        FunctionId::new(
+                            ModuleId::new(MOVEOS_STD_ADDRESS, module_store::MODULE_NAME.to_owned()),
+                            Identifier::new("publish_package_entry")?,
+                        ),
+                        vec![],
+                        vec![bcs::to_bytes(&package_bytes)?],
+                    );
+                    sandbox.execute(name, owner, action)?
+                }
+                None => SandboxTxResult {
+                    name,
+                    sender: package.genesis_account,
+                    status: "Skipped".to_owned(),
+                    gas_used: 0,
+                    error: Some(format!(
+                        "The UpgradeCap of package {} is not issued",
+                        package.genesis_account
+                    )),
+                },
+            };
+            sandbox_txs.push(result);
+        }
+
+        let compatible = modules
+            .iter()
+            .all(|module| !module.removed && module.compat_error.is_none())
+            && sandbox_txs.iter().all(SandboxTxResult::is_executed);
+        Ok(UpgradeCheckReport {
+            state_root: root.state_root(),
+            stdlib_version: self.stdlib_version.to_string(),
+            modules,
+            session_key_scopes,
+            gas_schedule,
+            sandbox_txs,
+            compatible,
+        })
+    }
+}
+
+/// Execute the upgrade transactions on the sandbox store, without authentication.
+struct Sandbox {
+    moveos_store: MoveOSStore,
+    moveos: MoveOS,
+    root: ObjectMeta,
+    max_gas_amount: u64,
+}
+
+impl Sandbox {
+    fn new(
+        moveos_store: MoveOSStore,
+        root: ObjectMeta,
+        max_gas_amount: u64,
+    ) -> anyhow::Result<Self> {
+        let moveos = Self::new_moveos(&moveos_store, &root)?;
+        Ok(Self {
+            moveos_store,
+            moveos,
+            root,
+            max_gas_amount,
+        })
+    }
+
+    fn new_moveos(moveos_store: &MoveOSStore, root: &ObjectMeta) -> anyhow::Result<MoveOS> {
+        let resolver = RootObjectResolver::new(root.clone(), moveos_store);
+        let gas_parameters = FrameworksGasParameters::load_from_chain(&resolver)?;
+        MoveOS::new(
+            moveos_store.clone(),
+            gas_parameters.all_natives(),
+            MoveOSConfig::default(),
+            system_pre_execute_functions(),
+            system_post_execute_functions(),
+        )
+    }
+
+    fn resolver(&self) -> RootObjectResolver<MoveOSStore> {
+        RootObjectResolver::new(self.root.clone(), &self.moveos_store)
+    }
+
+    fn chain_id(&self) -> anyhow::Result<ChainID> {
+        let chain_id = self
+            .resolver()
+            .get_object(&ChainID::chain_id_object_id())?
+            .ok_or_else(|| anyhow::anyhow!("ChainID object not found"))?
+            .into_object::<ChainID>()?;
+        Ok(chain_id.value)
+    }
+
+    fn object_owner(&self, object_id: &ObjectID) -> anyhow::Result<Option<AccountAddress>> {
+        Ok(self
+            .resolver()
+            .get_object(object_id)?
+            .map(|state| state.owner()))
+    }
+
+    fn execute(
+        &mut self,
+        name: String,
+        sender: AccountAddress,
+        action: MoveAction,
+    ) -> anyhow::Result<SandboxTxResult> {
+        let sequence_number = self
+            .resolver()
+            .get_account(sender)?
+            .map(|account| account.value.sequence_number)
+            .unwrap_or_default();
+        let tx_data = KanariTransactionData::new(
+            sender.into(),
+            sequence_number,
+            self.chain_id()?.id(),
+            self.max_gas_amount,
+            action,
+        );
+        let tx_hash = tx_data.tx_hash();
+        let verified_tx = convert_to_verified_tx(self.root.clone(), tx_data)?;
+        let (raw_output, vm_error_info) = self.moveos.execute_only(verified_tx)?;
+        let is_upgrade = raw_output.is_upgrade;
+        let is_gas_upgrade = raw_output.is_gas_upgrade;
+        let status = raw_output.status.clone();
+        let gas_used = raw_output.gas_used;
+        let (output, _execution_info) = self.moveos_store.handle_tx_output(tx_hash, raw_output)?;
+        self.root = output.changeset.root_metadata();
+        if is_gas_upgrade {
+            // The native functions are created with the gas parameters, so recreate the MoveOS
+            self.moveos = Self::new_moveos(&self.moveos_store, &self.root)?;
+        } else {
+            self.moveos.flush_module_cache(is_upgrade)?;
+        }
+
+        let error = match status {
+            KeptVMStatus::Executed => None,
+            _ => Some(
+                vm_error_info
+                    .map(|info| info.error_message)
+                    .unwrap_or_else(|| format!("{:?}", status)),
+            ),
+        };
+        Ok(SandboxTxResult {
+            name,
+            sender,
+            status: format!("{:?}", status),
+            gas_used,
+            error,
+        })
+    }
+}
+
+struct ModuleDiff {
+    removed: bool,
+    compat_error: Option<String>,
+    layout_changes: Vec<String>,
+    removed_entry_functions: Vec<Identifier>,
+}
+
+impl ModuleDiff {
+    fn into_check_result(self, module_id: &ModuleId) -> ModuleCheckResult {
+        ModuleCheckResult {
+            module_id: module_id.short_str_lossless(),
+            removed: self.removed,
+            compat_error: self.compat_error,
+            layout_changes: self.layout_changes,
+            removed_entry_functions: self
+                .removed_entry_functions
+                .iter()
+                .map(|function_name| function_name.to_string())
+                .collect(),
+        }
+    }
+}
+
+fn check_module(old_module: &CompiledModule, new_module: Option<&CompiledModule>) -> ModuleDiff {
+    let old = normalized::Module::new(old_module);
+    let old_entry_functions = old
+        .exposed_functions
+        .iter()
+        .filter(|(_, function)| function.is_entry)
+        .map(|(name, _)| name.clone());
+    let Some(new_module) = new_module else {
+        return ModuleDiff {
+            removed: true,
+            compat_error: None,
+            layout_changes: vec![],
+            removed_entry_functions: old_entry_functions.collect(),
+        };
+    };
+
+    let new = normalized::Module::new(new_module);
+    let mut layout_changes = vec![];
+    for (name, old_struct) in old.structs.iter() {
+        match new.structs.get(name) {
+            None => layout_changes.push(format!("struct {} is removed", name)),
+            Some(new_struct) => {
+                if old_struct.abilities != new_struct.abilities {
+                    layout_changes.push(format!(
+                        "the abilities of struct {} are changed from {:?} to {:?}",
+                        name, old_struct.abilities, new_struct.abilities
+                    ));
+                }
+                if old_struct.type_parameters != new_struct.type_parameters {
+                    layout_changes.push(format!(
+                        "the type parameters of struct {} are changed",
+                        name
+                    ));
+                }
+                if old_struct.fields != new_struct.fields {
+                    layout_changes.push(format!(
+                        "the fields of struct {} are changed from {{{}}} to {{{}}}",
+                        name,
+                        format_fields(&old_struct.fields),
+                        format_fields(&new_struct.fields)
+                    ));
+                }
+            }
+        }
+    }
+    let removed_entry_functions = old_entry_functions
+        .filter(|name| {
+            !new.exposed_functions
+                .get(name)
+                .map(|function| function.is_entry)
+                .unwrap_or(false)
+        })
+        .collect();
+    ModuleDiff {
+        removed: false,
+        compat_error: check_compiled_module_compat(old_module, new_module)
+            .err()
+            .map(|err| format!("{:?}", err)),
+        layout_changes,
+        removed_entry_functions,
+    }
+}
+
+fn format_fields(fields: &[normalized::Field]) -> String {
+    fields
+        .iter()
+        .map(|field| format!("{}: {}", field.name, field.type_))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn list_all_fields(
+    resolver: &RootObjectResolver<MoveOSStore>,
+    object_id: &ObjectID,
+) -> anyhow::Result<Vec<ObjectState>> {
+    let mut states = vec![];
+    let mut cursor: Option<FieldKey> = None;
+    loop {
+        let page = resolver.list_fields(object_id, cursor, LIST_FIELDS_PAGE_SIZE)?;
+        let page_size = page.len();
+        cursor = page.last().map(|(key, _)| *key);
+        states.extend(page.into_iter().map(|(_, state)| state));
+        if page_size < LIST_FIELDS_PAGE_SIZE {
+            return Ok(states);
+        }
+    }
+}
+
+fn list_package_modules(
+    resolver: &RootObjectResolver<MoveOSStore>,
+    package_id: AccountAddress,
+) -> anyhow::Result<BTreeMap<ModuleId, CompiledModule>> {
+    list_all_fields(resolver, &Package::package_id(&package_id))?
+        .into_iter()
+        .map(|state| {
+            let module = state.value_as_df::<MoveString, MoveModule>()?.value;
+            let module = CompiledModule::deserialize(&module.byte_codes)?;
+            Ok((module.self_id(), module))
+        })
+        .collect()
+}
+
+/// The gas parameters which are released with the stdlib version.
+/// Only the latest gas parameters are in the code, the gas schedule is not a part of the released stdlib,
+/// so the gas schedule is kept unchanged when checking a released stdlib version.
+fn gas_parameters_of_stdlib(stdlib_version: StdlibVersion) -> Option<FrameworksGasParameters> {
+    match stdlib_version {
+        StdlibVersion::Latest => Some(FrameworksGasParameters::latest()),
+        StdlibVersion::Version(_) => None,
+    }
+}
+
+/// Find the session key scopes of the accounts which refer to the removed entry functions.
+fn check_session_key_scopes(
+    resolver: &RootObjectResolver<MoveOSStore>,
+    accounts: &[AccountAddress],
+    removed_entry_functions: &BTreeSet<FunctionId>,
+) -> anyhow::Result<Vec<SessionScopeCheckResult>> {
+    let mut results = vec![];
+    if removed_entry_functions.is_empty() {
+        return Ok(results);
+    }
+    let removed_calls = removed_entry_functions
+        .iter()
+        .map(|function_id| FunctionCall::new(function_id.clone(), vec![], vec![]))
+        .collect::<Vec<_>>();
+    for account in accounts.iter().copied() {
+        let Some(session_keys) = resolver.get_resource(&account, &SessionKeys::struct_tag())?
+        else {
+            continue;
+        };
+        let session_keys: SessionKeys = bcs::from_bytes(&session_keys)?;
+        for field in list_all_fields(resolver, &session_keys.keys)? {
+            let session_key = field.value_as_df::<Vec<u8>, SessionKey>()?.value;
+            for scope in session_key.scopes.iter() {
+                let removed = removed_calls
+                    .iter()
+                    .filter(|call| scope.check_scope_match(call))
+                    .map(|call| call.function_id.to_string())
+                    .collect::<Vec<_>>();
+                if !removed.is_empty() {
+                    results.push(SessionScopeCheckResult {
+                        account,
+                        app_name: session_key.app_name.to_string(),
+                        scope: scope.to_string(),
+                        removed_entry_functions: removed,
+                    });
+                }
+            }
+        }
+    }
+    Ok(results)
+}
+
+fn diff_gas_schedule(
+    resolver: &RootObjectResolver<MoveOSStore>,
+    new_gas_config: &GasScheduleConfig,
+) -> anyhow::Result<GasScheduleDiff> {
+    let onchain_gas_schedule = resolver
+        .get_object(&GasSchedule::gas_schedule_object_id())?
+        .ok_or_else(|| anyhow::anyhow!("Gas schedule object not found"))?
+        .into_object::<GasSchedule>()?
+        .value;
+    let onchain_entries = onchain_gas_schedule
+        .entries
+        .iter()
+        .map(|entry| (entry.key.to_string(), entry.val))
+        .collect::<BTreeMap<_, _>>();
+    let new_entries = new_gas_config
+        .entries
+        .iter()
+        .map(|entry| (entry.key.to_string(), entry.val))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut diff = GasScheduleDiff {
+        onchain_schedule_version: onchain_gas_schedule.schedule_version,
+        ..Default::default()
+    };
+    for (key, value) in new_entries.iter() {
+        match onchain_entries.get(key) {
+            None => {
+                diff.added.insert(key.clone(), *value);
+            }
+            Some(onchain_value) if onchain_value != value => {
+                diff.modified.insert(key.clone(), (*onchain_value, *value));
+            }
+            _ => {}
+        }
+    }
+    diff.removed = onchain_entries
+        .keys()
+        .filter(|key| !new_entries.contains_key(*key))
+        .cloned()
+        .collect();
+    Ok(diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::{
+        SignatureToken, StructFieldInformation, Visibility, basic_test_module,
+    };
+
+    fn entry_test_module() -> CompiledModule {
+        let mut module = basic_test_module();
+        module.function_defs[0].visibility = Visibility::Public;
+        module.function_defs[0].is_entry = true;
+        module
+    }
+
+    #[test]
+    fn test_check_module_unchanged() {
+        let module = entry_test_module();
+        let diff = check_module(&module, Some(&module));
+        assert!(!diff.removed);
+        assert!(diff.compat_error.is_none());
+        assert!(diff.layout_changes.is_empty());
+        assert!(diff.removed_entry_functions.is_empty());
+    }
+
+    #[test]
+    fn test_check_module_removed() {
+        let diff = check_module(&entry_test_module(), None);
+        assert!(diff.removed);
+        assert_eq!(
+            diff.removed_entry_functions,
+            vec![Identifier::new("foo").unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_check_module_removed_entry_function() {
+        let mut new_module = entry_test_module();
+        new_module.function_defs[0].is_entry = false;
+        let diff = check_module(&entry_test_module(), Some(&new_module));
+        assert!(!diff.removed);
+        assert_eq!(
+            diff.removed_entry_functions,
+            vec![Identifier::new("foo").unwrap()]
+        );
+    }
+
+    #[test]
+    fn test_check_module_layout_change() {
+        let mut new_module = entry_test_module();
+        let StructFieldInformation::Declared(fields) =
+            &mut new_module.struct_defs[0].field_information
+        else {
+            panic!("The struct Bar should have declared fields");
+        };
+        fields[0].signature.0 = SignatureToken::U8;
+        let diff = check_module(&entry_test_module(), Some(&new_module));
+        assert_eq!(diff.layout_changes.len(), 1);
+        assert!(diff.compat_error.is_some());
+    }
+
+    #[test]
+    fn test_gas_parameters_of_stdlib() {
+        assert!(gas_parameters_of_stdlib(StdlibVersion::Latest).is_some());
+        assert!(gas_parameters_of_stdlib(StdlibVersion::Version(1)).is_none());
+    }
+}
@@ -1,4 +1,5 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod check;
 pub mod upgrade_gas_config;
@@ -5,10 +5,9 @@ use crate::cli_types::{CommandAction, TransactionOptions, WalletContextOptions};
 use crate::commands::transaction::commands::{FileOutput, FileOutputData};
 use async_trait::async_trait;
 use framework_types::addresses::KANARI_FRAMEWORK_ADDRESS;
-use kanari_framework::natives::gas_parameter::gas_member::ToOnChainGasSchedule;
 use kanari_genesis::FrameworksGasParameters;
 use kanari_types::error::{KanariError, KanariResult};
-use kanari_types::kanari_network::BuiltinChainID;
+use kanari_types::framework::chain_id::ChainID;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::ModuleId;
 use moveos_types::module_binding::MoveFunctionCaller;
@@ -61,27 +60,16 @@ impl CommandAction<Option<FileOutput>> for UpgradeGasConfigCommand {
             _ => None,
         };
 
-        let local_latest_gas_parameters = FrameworksGasParameters::latest();
+        // The WASM cost table of the nursery is only in the gas schedule of the dev and local chains
+        let chain_id = ChainID::new(client.kanari.get_chain_id().await?);
+        let latest_gas_schedule =
+            FrameworksGasParameters::latest().to_gas_schedule_config(chain_id);
 
         match onchain_gas_schedule {
             None => {
                 return Err(KanariError::OnchainGasScheduleIsEmpty);
             }
             Some((onchain_gas_schedule_version, onchain_gas_schedule_map)) => {
-                let mut local_gas_entries = local_latest_gas_parameters
-                    .vm_gas_params
-                    .to_on_chain_gas_schedule();
-                local_gas_entries.extend(
-                    local_latest_gas_parameters
-                        .kanari_framework_gas_params
-                        .to_on_chain_gas_schedule(),
-                );
-                local_gas_entries.extend(
-                    local_latest_gas_parameters
-                        .bitcoin_move_gas_params
-                        .to_on_chain_gas_schedule(),
-                );
-
                 // The last gas schedule version on the testnet allows to be inconsistent with onchain gas schedule version
                 // if LATEST_GAS_SCHEDULE_VERSION < onchain_gas_schedule_version {
                 //     return Err(KanariError::InvalidLocalGasVersion(
@@ -90,8 +78,11 @@ impl CommandAction<Option<FileOutput>> for UpgradeGasConfigCommand {
                 //     ));
                 // }
 
-                let local_gas_schedule_map: BTreeMap<String, u64> =
-                    local_gas_entries.into_iter().collect();
+                let local_gas_schedule_map: BTreeMap<String, u64> = latest_gas_schedule
+                    .entries
+                    .iter()
+                    .map(|gas_entry| (gas_entry.key.to_string(), gas_entry.val))
+                    .collect();
 
                 if local_gas_schedule_map.len() < onchain_gas_schedule_map.len() {
                     println!(
@@ -166,8 +157,6 @@ impl CommandAction<Option<FileOutput>> for UpgradeGasConfigCommand {
             }
         };
 
-        let latest_gas_schedule =
-            local_latest_gas_parameters.to_gas_schedule_config(BuiltinChainID::Test.chain_id());
         let gas_schedule_bytes = latest_gas_schedule
             .to_move_value()
             .simple_serialize()
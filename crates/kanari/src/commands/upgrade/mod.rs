@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cli_types::CommandAction;
+use crate::commands::upgrade::commands::check::CheckCommand;
 use crate::commands::upgrade::commands::upgrade_gas_config::UpgradeGasConfigCommand;
 
 use async_trait::async_trait;
@@ -22,6 +23,7 @@ impl CommandAction<String> for Upgrade {
     async fn execute(self) -> KanariResult<String> {
         match self.cmd {
             UpgradeCommand::UpgradeGasConfig(cmd) => cmd.execute_serialized().await,
+            UpgradeCommand::Check(cmd) => cmd.execute_serialized().await,
         }
     }
 }
@@ -29,4 +31,5 @@ impl CommandAction<String> for Upgrade {
 #[derive(Subcommand)]
 pub enum UpgradeCommand {
     UpgradeGasConfig(UpgradeGasConfigCommand),
+    Check(CheckCommand),
 }
@@ -14,8 +14,8 @@ use cli_types::CommandAction;
 use commands::{
     abi::ABI, account::Account, bitcoin::Bitcoin, bitseed::Bitseed, did::DID,
     dynamic_field::DynamicField, env::Env, faucet::Faucet, genesis::Genesis, init::Init,
-    move_cli::MoveCli, object::ObjectCommand, oracle::Oracle, resource::ResourceCommand, rpc::Rpc,
-    server::Server, session_key::SessionKey, state::StateCommand, task::Task,
+    move_cli::MoveCli, object::ObjectCommand, oracle::Oracle, ord::Ord, resource::ResourceCommand,
+    rpc::Rpc, server::Server, session_key::SessionKey, state::StateCommand, task::Task,
     transaction::Transaction, upgrade::Upgrade, util::Util, version::Version,
 };
 use kanari_types::error::KanariResult;
@@ -75,6 +75,7 @@ pub enum Command {
     Util(Util),
     Faucet(Faucet),
     Oracle(Oracle),
+    Ord(Ord),
     DA(DA),
     #[clap(name = "did")]
     DID(DID),
@@ -108,6 +109,7 @@ pub async fn run_cli(opt: KanariCli) -> KanariResult<String> {
         Command::Util(util) => util.execute().await,
         Command::Faucet(faucet) => faucet.execute().await,
         Command::Oracle(oracle) => oracle.execute().await,
+        Command::Ord(ord) => ord.execute().await,
         Command::DA(da) => da.execute().await,
         Command::DID(did) => did.execute().await,
     }
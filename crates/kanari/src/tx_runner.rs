@@ -125,7 +125,7 @@ pub fn execute_tx_locally_with_gas_profile(
     );
     gas_meter.charge_io_write(tx.tx_size()).unwrap();
 
-    let mut gas_profiler = new_gas_profiler(tx.clone().action, gas_meter);
+    let mut gas_profiler = new_gas_profiler(tx.clone().action, gas_meter)?;
 
     let mut moveos_session = MoveOSSession::new(
         move_mv.inner(),
@@ -226,7 +226,7 @@ pub fn prepare_execute_env(
     (vm, object_runtime, client_resolver, action, cost_table)
 }
 
-fn convert_to_verified_tx(
+pub fn convert_to_verified_tx(
     root: ObjectMeta,
     tx_data: KanariTransactionData,
 ) -> anyhow::Result<VerifiedMoveOSTransaction> {
@@ -267,6 +267,10 @@ fn convert_to_verified_tx(
             module_bundle,
             init_function_modules: vec![],
         },
+        MoveAction::Batch(calls) => {
+            anyhow::ensure!(!calls.is_empty(), "Batch payload should not be empty");
+            VerifiedMoveAction::Batch { calls }
+        }
     };
 
     Ok(VerifiedMoveOSTransaction::new(
@@ -296,6 +300,7 @@ pub async fn dry_run_tx_locally(
         Some(vm_error_info) => Ok(DryRunTransactionResponseView {
             raw_output: raw_output_view,
             vm_error_info,
+            trace: None,
         }),
         None => {
             let empty_error_info = VMErrorInfo {
@@ -305,6 +310,7 @@ pub async fn dry_run_tx_locally(
             Ok(DryRunTransactionResponseView {
                 raw_output: raw_output_view,
                 vm_error_info: empty_error_info,
+                trace: None,
             })
         }
     }
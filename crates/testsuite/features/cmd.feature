@@ -31,6 +31,26 @@ Feature: Kanari CLI integration tests
       Then assert: "{{$.rpc[-1].name}} == 'display'"
       Then stop the server
 
+    @serial
+    Scenario: fork
+      Given a server for fork
+      Then cmd: "move run --function kanari_framework::gas_coin::faucet_entry --args u256:10000000000 --json"
+      Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+      Then cmd: "rpc request --method kanari_getBalance --params '["{{$.address_mapping.default}}", "0x3::gas_coin::RGas"]' --json"
+      Then assert: "'{{$.rpc[-1].balance}}' != '0'"
+      # the fork server listens on the next port, and reads the state from the server
+      Given a fork server for fork
+      Then cmd: "env add --alias fork --rpc http://127.0.0.1:6768"
+      Then cmd: "env switch --alias fork"
+      Then cmd: "rpc request --method kanari_getBalance --params '["{{$.address_mapping.default}}", "0x3::gas_coin::RGas"]' --json"
+      Then assert: "'{{$.rpc[-1].balance}}' == '{{$.rpc[-2].balance}}'"
+      # the txs are executed on top of the forked state
+      Then cmd: "move run --function kanari_framework::empty::empty --json"
+      Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+      Then cmd: "env switch --alias local"
+      Then stop the fork server
+      Then stop the server
+
     @serial
     Scenario: account
       Given a server for account
@@ -77,6 +97,14 @@ Feature: Kanari CLI integration tests
       Then assert: "'{{$.session-key[-1]}}' not_contains error"
       Then cmd: "move run --function 0x3::empty::empty  --session-key {{$.session-key[-1][0].name}} --json"
       Then assert: "{{$.move[-1].execution_info.status.type}} == executed"
+      Then cmd: "session-key show --json"
+      Then assert: "{{$.session-key[-1][0].in_keystore}} == true"
+      Then cmd: "session-key renew --authentication-key {{$.session-key[-1][0].authentication_key}} --max-inactive-interval 7200"
+      Then assert: "{{$.session-key[-1].max_inactive_interval}} == 7200"
+      Then cmd: "session-key rotate"
+      Then assert: "{{$.session-key[-1][0].max_inactive_interval}} == 7200"
+      Then cmd: "session-key revoke --authentication-key {{$.session-key[-1][0].authentication_key}}"
+      Then assert: "{{$.session-key[-1][0].in_keystore}} == false"
 
       # transaction
       Then cmd: "transaction get-transactions-by-order --cursor 0 --limit 1 --descending-order false"
@@ -51,5 +51,29 @@ Feature: Kanari CLI multisign integration tests
       Then assert: "{{$.tx[-1].execution_info.status.type}} == executed"
 
 
+      # l1 transaction via the multisign wallet proposal
+      Then cmd: "account transfer --to {{$.account[-3].participants[0].participant_address}} --amount 10000000000 --coin-type kanari_framework::gas_coin::RGas --json"
+      Then assert: "{{$.account[-1].execution_info.status.type}} == executed"
+      Then cmd: "account transfer --to {{$.account[-4].participants[1].participant_address}} --amount 10000000000 --coin-type kanari_framework::gas_coin::RGas --json"
+      Then assert: "{{$.account[-1].execution_info.status.type}} == executed"
+      Then cmd: "bitcoin build-tx --sender {{$.account[-5].multisign_bitcoin_address}} -o {{$.account[-6].account1.bitcoin_address}}:100000000"
+      Then assert: "'{{$.bitcoin[-1]}}' not_contains error"
+      Then cmd: "bitcoin proposal create {{$.bitcoin[-1].path}} --sender {{$.account[-5].participants[0].participant_address}}"
+      Then assert: "{{$.bitcoin[-1].status}} == pending"
+      Then cmd: "bitcoin proposal list --multisign-address {{$.account[-5].multisign_address}}"
+      Then assert: "'{{$.bitcoin[-1]}}' not_contains error"
+      Then cmd: "bitcoin proposal sign --multisign-address {{$.account[-5].multisign_address}} --proposal-id {{$.bitcoin[-2].proposal_id}} --sender {{$.account[-5].participants[0].participant_address}} -y"
+      Then assert: "{{$.bitcoin[-1].status}} == pending"
+      Then cmd: "bitcoin proposal sign --multisign-address {{$.account[-5].multisign_address}} --proposal-id {{$.bitcoin[-1].proposal_id}} --sender {{$.account[-5].participants[1].participant_address}} -y"
+      Then assert: "{{$.bitcoin[-1].status}} == approved"
+      Then cmd: "bitcoin proposal finalize --multisign-address {{$.account[-5].multisign_address}} --proposal-id {{$.bitcoin[-1].proposal_id}}"
+      Then assert: "'{{$.bitcoin[-1]}}' not_contains error"
+
+      Then cmd bitcoin-cli: "generatetoaddress 1 {{$.account[-5].multisign_bitcoin_address}}"
+      Then sleep: "10" # wait for the transaction to be confirmed
+
+      Then cmd: "account balance -a {{$.account[-6].account1.address}} --json"
+      Then assert: "{{$.account[-1].BTC.balance}} == 100000000"
+
       Then stop the server
       Then stop the bitcoind server 
@@ -37,6 +37,7 @@ struct World {
     docker: Cli,
     container_network: String,
     service: Option<Service>,
+    fork_service: Option<Service>,
     bitcoind: Option<Container<BitcoinD>>,
     ord: Option<Container<Ord>>,
     tpl_ctx: Option<TemplateContext>,
@@ -51,6 +52,7 @@ impl Default for World {
             docker: Cli::default(),
             container_network: format!("test_network_{}", network_uuid),
             service: None,
+            fork_service: None,
             bitcoind: None,
             ord: None,
             tpl_ctx: None,
@@ -110,6 +112,32 @@ async fn stop_server(w: &mut World) {
     }
 }
 
+#[given(expr = "a fork server for {word}")] // Cucumber Expression
+async fn start_fork_server(w: &mut World, _scenario: String) {
+    // The fork server listens on the next port of the server, and forks the latest state of the server.
+    let mut fork_opt = KanariOpt::new_with_temp_store().expect("new kanari opt should be ok");
+    fork_opt.port = Some(w.opt.port() + 1);
+    fork_opt.fork_url = Some(format!("http://127.0.0.1:{}", w.opt.port()));
+    wait_port_available(fork_opt.port()).await;
+
+    let mut server_opt = ServerOpt::new();
+    let kp: KanariKeyPair = KanariKeyPair::generate_secp256k1();
+    server_opt.sequencer_keypair = Some(kp.copy());
+    server_opt.proposer_keypair = Some(kp.copy());
+
+    let mut service = Service::new();
+    service.start(fork_opt, server_opt).await.unwrap();
+    w.fork_service = Some(service);
+}
+
+#[then(expr = "stop the fork server")] // Cucumber Expression
+async fn stop_fork_server(w: &mut World) {
+    if let Some(service) = w.fork_service.take() {
+        service.stop().unwrap();
+        info!("Shutdown fork server");
+    }
+}
+
 #[given(expr = "a bitcoind server for {word}")] // Cucumber Expression
 async fn start_bitcoind_server(w: &mut World, _scenario: String) {
     tokio::time::sleep(Duration::from_secs(5)).await;
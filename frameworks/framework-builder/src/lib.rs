@@ -275,6 +275,11 @@ impl Stdlib {
         Ok(())
     }
 
+    /// The packages in dependency order
+    pub fn packages(&self) -> &[StdlibPackage] {
+        &self.packages
+    }
+
     pub fn all_modules(&self) -> Result<Vec<CompiledModule>> {
         let mut modules = vec![];
         for package in self.packages.iter() {
@@ -214,7 +214,7 @@ pub fn check_package_compat(
 }
 
 /// check module compatibility
-fn check_compiled_module_compat(
+pub fn check_compiled_module_compat(
     old_module: &CompiledModule,
     new_module: &CompiledModule,
 ) -> PartialVMResult<()> {
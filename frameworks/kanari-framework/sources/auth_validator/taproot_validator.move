@@ -0,0 +1,92 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+/// This module implements Taproot key path validator with the BIP-340 Schnorr signature over Secp256k1.
+/// The payload carries the x-only internal public key, the signature is signed by the tweaked output key.
+module kanari_framework::taproot_validator {
+
+    use std::option;
+    use moveos_std::tx_context;
+    use moveos_std::hash;
+    use kanari_framework::schnorr;
+    use kanari_framework::auth_payload;
+    use kanari_framework::auth_validator;
+    use kanari_framework::auth_payload::AuthPayload;
+    use kanari_framework::bitcoin_address::{Self, BitcoinAddress};
+
+    friend kanari_framework::transaction_validator;
+
+    /// there defines auth validator id for each auth validator
+    const TAPROOT_AUTH_VALIDATOR_ID: u64 = 4;
+
+    struct TaprootValidator has store, drop {}
+
+    public fun auth_validator_id(): u64 {
+        TAPROOT_AUTH_VALIDATOR_ID
+    }
+
+    /// Only validate the authenticator's signature.
+    fun validate_signature(payload: &AuthPayload, tx_hash: vector<u8>) {
+
+        let message = auth_payload::encode_full_message(payload, tx_hash);
+
+        // Sign the same digest as the Bitcoin validator, the sha2_256 twice of the message
+        let message_hash = hash::sha2_256(hash::sha2_256(message));
+        let output_key = schnorr::taproot_tweak_pubkey(&auth_payload::public_key(payload), option::none());
+        assert!(
+            schnorr::verify(
+                &auth_payload::signature(payload),
+                &output_key,
+                &message_hash,
+            ),
+            auth_validator::error_validate_invalid_authenticator()
+        );
+    }
+
+    public(friend) fun validate(authenticator_payload: vector<u8>) :BitcoinAddress{
+
+        let sender = tx_context::sender();
+        let tx_hash = tx_context::tx_hash();
+        let payload = auth_payload::from_bytes(authenticator_payload);
+
+        validate_signature(&payload, tx_hash);
+
+        let from_address_in_payload = auth_payload::from_address(&payload);
+        let bitcoin_addr = bitcoin_address::from_string(&from_address_in_payload);
+
+        // Check if the address is the P2TR address of the internal public key
+        let taproot_addr = bitcoin_address::p2tr(&auth_payload::public_key(&payload), option::none());
+        assert!(
+            taproot_addr == bitcoin_addr,
+            auth_validator::error_validate_invalid_authenticator()
+        );
+
+        let kanari_addr = bitcoin_address::to_kanari_address(&bitcoin_addr);
+
+        // Check if the sender is related to the Kanari address
+        assert!(
+            sender == kanari_addr,
+            auth_validator::error_validate_invalid_authenticator()
+        );
+        bitcoin_addr
+    }
+
+    #[test]
+    fun test_validate_signature_success() {
+        let tx_hash = x"5415b18de0b880bb2af5dfe1ee27fd19ae8a0c99b5328e8b4b44f4c86cc7176a";
+        let auth_payload_bytes = x"40d7c4dffef65b776330898dc06aa6dfdd49c6bc0991fb925de688def6cf287aea24723a57512000f0afb96f3df345b7c0394358f2360c512a73b77aaf0e37c56818426974636f696e205369676e6564204d6573736167653a0a144b616e617269205472616e73616374696f6e3a0a202297674107399a6ea7a72e4c3f506e30b422c8d37e6f662fcdce67badf82d9ab3e6263317077386a7337356e726e366d3975613077353474356774396878746d6130666e716a383771377a63786b7171676b63763032366a73766d387a776c";
+        let payload = auth_payload::from_bytes(auth_payload_bytes);
+
+        validate_signature(&payload, tx_hash);
+    }
+
+    #[test]
+    #[expected_failure(location=Self, abort_code = 1010)]
+    fun test_validate_signature_fail() {
+        let tx_hash = x"5515b18de0b880bb2af5dfe1ee27fd19ae8a0c99b5328e8b4b44f4c86cc7176a";
+        let auth_payload_bytes = x"40d7c4dffef65b776330898dc06aa6dfdd49c6bc0991fb925de688def6cf287aea24723a57512000f0afb96f3df345b7c0394358f2360c512a73b77aaf0e37c56818426974636f696e205369676e6564204d6573736167653a0a144b616e617269205472616e73616374696f6e3a0a202297674107399a6ea7a72e4c3f506e30b422c8d37e6f662fcdce67badf82d9ab3e6263317077386a7337356e726e366d3975613077353474356774396878746d6130666e716a383771377a63786b7171676b63763032366a73766d387a776c";
+        let payload = auth_payload::from_bytes(auth_payload_bytes);
+
+        validate_signature(&payload, tx_hash);
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+/// BIP-340 Schnorr signatures over Secp256k1 and the BIP-341 Taproot key tweak.
+module kanari_framework::schnorr {
+    use std::vector;
+    use std::option::Option;
+
+    /// X-only public key length
+    const SCHNORR_PUBKEY_LENGTH: u64 = 32;
+    /// Signature length (r, s)
+    const SCHNORR_SIGNATURE_LENGTH: u64 = 64;
+    /// The signed message is a 32 bytes digest
+    const SCHNORR_MESSAGE_LENGTH: u64 = 32;
+
+    // Error codes
+    const ErrorInvalidSignature: u64 = 1;
+    const ErrorInvalidPubKey: u64 = 2;
+    const ErrorInvalidMessage: u64 = 3;
+
+    /// Verifies a BIP-340 Schnorr signature.
+    /// @param signature: A 64-bytes signature in form (r, s).
+    /// @param public_key: A 32-bytes x-only public key.
+    /// @param msg: The 32-bytes digest that the signature is signed against.
+    public fun verify(
+        signature: &vector<u8>,
+        public_key: &vector<u8>,
+        msg: &vector<u8>
+    ): bool {
+        assert!(vector::length(signature) == SCHNORR_SIGNATURE_LENGTH, ErrorInvalidSignature);
+        assert!(vector::length(public_key) == SCHNORR_PUBKEY_LENGTH, ErrorInvalidPubKey);
+        assert!(vector::length(msg) == SCHNORR_MESSAGE_LENGTH, ErrorInvalidMessage);
+        native_verify(signature, public_key, msg)
+    }
+
+    /// Tweaks the x-only internal public key with the optional script tree merkle root.
+    /// Returns the 32-bytes x-only output key of the Taproot output, the key path spending is signed by it.
+    public fun taproot_tweak_pubkey(internal_pubkey: &vector<u8>, merkle_root: Option<address>): vector<u8> {
+        assert!(vector::length(internal_pubkey) == SCHNORR_PUBKEY_LENGTH, ErrorInvalidPubKey);
+        native_taproot_tweak_pubkey(internal_pubkey, merkle_root)
+    }
+
+    native fun native_verify(
+        signature: &vector<u8>,
+        public_key: &vector<u8>,
+        msg: &vector<u8>
+    ): bool;
+
+    native fun native_taproot_tweak_pubkey(internal_pubkey: &vector<u8>, merkle_root: Option<address>): vector<u8>;
+
+    public fun public_key_length(): u64 {
+        SCHNORR_PUBKEY_LENGTH
+    }
+
+    public fun signature_length(): u64 {
+        SCHNORR_SIGNATURE_LENGTH
+    }
+
+    #[test]
+    fun test_verify_success() {
+        let msg = x"f08285dc969c9cdfa65a5a29dc592371acb80534ae301965f38b0583817ea33f";
+        let pubkey = x"cddcc4a1d4a94d627e7808f904d0477cf16ae9d4fafa1eb883ab7a498bdda777";
+        let sig = x"6c2565ceabff153609aa9ccdeb13421a1181a54d0ca4fe10cd074b0c2da44c641c98992701c9a4d3e24391db3e358eff190510be46e73d0e517d5e5b13bb06fd";
+        assert!(verify(&sig, &pubkey, &msg), 0);
+    }
+
+    #[test]
+    fun test_verify_fails_wrong_msg() {
+        let msg = x"f18285dc969c9cdfa65a5a29dc592371acb80534ae301965f38b0583817ea33f";
+        let pubkey = x"cddcc4a1d4a94d627e7808f904d0477cf16ae9d4fafa1eb883ab7a498bdda777";
+        let sig = x"6c2565ceabff153609aa9ccdeb13421a1181a54d0ca4fe10cd074b0c2da44c641c98992701c9a4d3e24391db3e358eff190510be46e73d0e517d5e5b13bb06fd";
+        assert!(!verify(&sig, &pubkey, &msg), 0);
+    }
+
+    #[test]
+    #[expected_failure(location=Self, abort_code = ErrorInvalidSignature)]
+    fun test_verify_fails_invalid_sig_length() {
+        let msg = x"f08285dc969c9cdfa65a5a29dc592371acb80534ae301965f38b0583817ea33f";
+        let pubkey = x"cddcc4a1d4a94d627e7808f904d0477cf16ae9d4fafa1eb883ab7a498bdda777";
+        let sig = x"6c2565ceabff153609aa9ccdeb13421a";
+        verify(&sig, &pubkey, &msg);
+    }
+
+    #[test]
+    #[expected_failure(location=Self, abort_code = ErrorInvalidMessage)]
+    fun test_verify_fails_invalid_msg_length() {
+        let msg = x"00010203";
+        let pubkey = x"cddcc4a1d4a94d627e7808f904d0477cf16ae9d4fafa1eb883ab7a498bdda777";
+        let sig = x"6c2565ceabff153609aa9ccdeb13421a1181a54d0ca4fe10cd074b0c2da44c641c98992701c9a4d3e24391db3e358eff190510be46e73d0e517d5e5b13bb06fd";
+        verify(&sig, &pubkey, &msg);
+    }
+
+    #[test]
+    fun test_taproot_tweak_pubkey() {
+        // The BIP-341 key path test vector without script tree
+        let internal_pubkey = x"d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d";
+        let output_key = taproot_tweak_pubkey(&internal_pubkey, std::option::none());
+        assert!(output_key == x"53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343", 0);
+    }
+}
@@ -28,6 +28,7 @@ module kanari_framework::transaction_validator {
     use kanari_framework::coin;
     use kanari_framework::bitcoin_address;
     use kanari_framework::webauthn_validator;
+    use kanari_framework::taproot_validator;
 
     const MAX_U64: u128 = 18446744073709551615;
 
@@ -99,6 +100,9 @@ module kanari_framework::transaction_validator {
         }else if (auth_validator_id == bitcoin_validator::auth_validator_id()){
             let bitcoin_address = bitcoin_validator::validate(authenticator_payload);
             (option::some(bitcoin_address), option::none(), option::none())
+        }else if (auth_validator_id == taproot_validator::auth_validator_id()){
+            let bitcoin_address = taproot_validator::validate(authenticator_payload);
+            (option::some(bitcoin_address), option::none(), option::none())
         }else if (auth_validator_id == webauthn_validator::auth_validator_id()){
             let session_key = webauthn_validator::validate(authenticator_payload);
             let bitcoin_address = address_mapping::resolve_bitcoin(sender);
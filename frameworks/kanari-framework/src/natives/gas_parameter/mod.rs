@@ -24,8 +24,10 @@ pub mod native;
 pub mod nursery;
 mod object;
 mod object_list_field_keys;
+mod plonk;
 mod rlp;
 mod rs256;
+mod schnorr;
 mod signer;
 mod test_helper;
 mod tx_context;
@@ -0,0 +1,13 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use moveos_stdlib::natives::moveos_stdlib::plonk::GasParameters;
+
+crate::natives::gas_parameter::native::define_gas_parameters_for_natives!(GasParameters, "plonk", [
+    [.prepare_verifying_key_internal.base, optional "prepare_verifying_key_internal.base", 0],
+    [.prepare_verifying_key_internal.per_byte, optional "prepare_verifying_key_internal.per_byte", 0],
+    [.verify_plonk_proof_internal.base, optional "verify_plonk_proof_internal.base", 0],
+    [.verify_plonk_proof_internal.per_byte, optional "verify_plonk_proof_internal.per_byte", 0],
+    [.verify_kzg_opening_internal.base, optional "verify_kzg_opening_internal.base", 0],
+    [.verify_kzg_opening_internal.per_byte, optional "verify_kzg_opening_internal.per_byte", 0],
+]);
@@ -0,0 +1,11 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::kanari_framework::crypto::schnorr::GasParameters;
+
+crate::natives::gas_parameter::native::define_gas_parameters_for_natives!(GasParameters, "schnorr", [
+    [.verify.base, optional "verify.base", 0],
+    [.verify.per_byte, optional "verify.per_byte", 0],
+    [.taproot_tweak_pubkey.base, optional "taproot_tweak_pubkey.base", 0],
+    [.taproot_tweak_pubkey.per_byte, optional "taproot_tweak_pubkey.per_byte", 0],
+]);
@@ -5,3 +5,4 @@ pub mod ecdsa_k1;
 pub mod ecdsa_r1;
 pub mod ed25519;
 pub mod rs256;
+pub mod schnorr;
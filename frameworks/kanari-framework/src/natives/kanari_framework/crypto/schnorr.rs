@@ -0,0 +1,188 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::natives::helpers::{make_module_natives, make_native};
+use bitcoin::{
+    TapNodeHash, XOnlyPublicKey,
+    hashes::Hash,
+    key::TapTweak,
+    secp256k1::{Message, Secp256k1, schnorr::Signature},
+};
+use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_core_types::{
+    account_address::AccountAddress,
+    gas_algebra::{InternalGas, InternalGasPerByte, NumBytes},
+    vm_status::StatusCode,
+};
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{Value, VectorRef},
+};
+use moveos_types::{move_std::option::MoveOption, state::MoveState};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+// Error codes
+const E_INVALID_SIGNATURE: u64 = 1;
+const E_INVALID_PUBKEY: u64 = 2;
+const E_INVALID_MESSAGE: u64 = 3;
+
+/// Verify a BIP-340 Schnorr signature, the message is the 32 bytes digest which is signed.
+pub fn native_verify(
+    gas_params: &SchnorrGasParameters,
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 3);
+
+    let msg = pop_arg!(args, VectorRef);
+    let public_key = pop_arg!(args, VectorRef);
+    let signature = pop_arg!(args, VectorRef);
+
+    let msg_ref = msg.as_bytes_ref();
+    let public_key_ref = public_key.as_bytes_ref();
+    let signature_ref = signature.as_bytes_ref();
+
+    let base = gas_params.base.expect("gas parameter should initialize");
+    let per_byte = gas_params
+        .per_byte
+        .expect("gas parameter should initialize");
+
+    let cost = base
+        + per_byte * NumBytes::new(msg_ref.len() as u64)
+        + per_byte * NumBytes::new(public_key_ref.len() as u64)
+        + per_byte * NumBytes::new(signature_ref.len() as u64);
+
+    let Ok(sig) = Signature::from_slice(&signature_ref) else {
+        return Ok(NativeResult::err(cost, E_INVALID_SIGNATURE));
+    };
+    let Ok(pubkey) = XOnlyPublicKey::from_slice(&public_key_ref) else {
+        return Ok(NativeResult::err(cost, E_INVALID_PUBKEY));
+    };
+    let Ok(message) = Message::from_digest_slice(&msg_ref) else {
+        return Ok(NativeResult::err(cost, E_INVALID_MESSAGE));
+    };
+
+    let secp = Secp256k1::verification_only();
+    let result = secp.verify_schnorr(&sig, &message, &pubkey).is_ok();
+
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(result)]))
+}
+
+/// Tweak the x-only internal public key with the optional script tree merkle root as BIP-341,
+/// return the x-only output public key which signs the Taproot key path spending.
+pub fn native_taproot_tweak_pubkey(
+    gas_params: &SchnorrGasParameters,
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let merkle_root_arg = args.pop_back().expect("merkle root is missing");
+    let internal_pubkey = pop_arg!(args, VectorRef);
+    let merkle_root: Option<AccountAddress> = MoveOption::from_runtime_value(merkle_root_arg)
+        .map_err(|e| {
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message(format!("Failed to parse merkle root: {}", e))
+        })?
+        .into();
+
+    let internal_pubkey_ref = internal_pubkey.as_bytes_ref();
+
+    let base = gas_params.base.expect("gas parameter should initialize");
+    let per_byte = gas_params
+        .per_byte
+        .expect("gas parameter should initialize");
+
+    let merkle_root_bytes_len = match &merkle_root {
+        Some(_addr) => AccountAddress::LENGTH,
+        None => 1,
+    };
+    let cost = base
+        + per_byte * NumBytes::new(internal_pubkey_ref.len() as u64)
+        + per_byte * NumBytes::new(merkle_root_bytes_len as u64);
+
+    let Ok(internal_key) = XOnlyPublicKey::from_slice(&internal_pubkey_ref) else {
+        return Ok(NativeResult::err(cost, E_INVALID_PUBKEY));
+    };
+    let merkle_root = merkle_root.map(|addr| {
+        TapNodeHash::from_slice(addr.as_slice()).expect("address to merkle root should success")
+    });
+
+    let secp = Secp256k1::verification_only();
+    let (output_key, _parity) = internal_key.tap_tweak(&secp, merkle_root);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(output_key.to_inner().serialize().to_vec())],
+    ))
+}
+
+#[derive(Debug, Clone)]
+pub struct SchnorrGasParameters {
+    pub base: Option<InternalGas>,
+    pub per_byte: Option<InternalGasPerByte>,
+}
+
+impl SchnorrGasParameters {
+    pub fn zeros() -> Self {
+        Self {
+            base: None,
+            per_byte: None,
+        }
+    }
+
+    pub fn init(base: InternalGas, per_byte: InternalGasPerByte) -> Self {
+        Self {
+            base: Some(base),
+            per_byte: Some(per_byte),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.base.is_none() || self.per_byte.is_none()
+    }
+}
+
+/***************************************************************************************************
+ * module
+ **************************************************************************************************/
+
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub verify: SchnorrGasParameters,
+    pub taproot_tweak_pubkey: SchnorrGasParameters,
+}
+
+impl GasParameters {
+    pub fn zeros() -> Self {
+        Self {
+            verify: SchnorrGasParameters::zeros(),
+            taproot_tweak_pubkey: SchnorrGasParameters::zeros(),
+        }
+    }
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let mut natives = vec![];
+    if !gas_params.verify.is_empty() {
+        natives.push((
+            "native_verify",
+            make_native(gas_params.verify, native_verify),
+        ));
+    }
+    if !gas_params.taproot_tweak_pubkey.is_empty() {
+        natives.push((
+            "native_taproot_tweak_pubkey",
+            make_native(gas_params.taproot_tweak_pubkey, native_taproot_tweak_pubkey),
+        ));
+    }
+    make_module_natives(natives)
+}
@@ -31,6 +31,7 @@ pub struct NativeGasParameters {
     pub ecdsa_k1: kanari_framework::crypto::ecdsa_k1::GasParameters,
     pub ecdsa_r1: kanari_framework::crypto::ecdsa_r1::GasParameters,
     pub rs256: kanari_framework::crypto::rs256::GasParameters,
+    pub schnorr: kanari_framework::crypto::schnorr::GasParameters,
     pub bitcoin_address: kanari_framework::bitcoin_address::GasParameters,
 }
 
@@ -43,6 +44,7 @@ impl FromOnChainGasSchedule for NativeGasParameters {
             ecdsa_k1: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule).unwrap(),
             ecdsa_r1: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule).unwrap(),
             rs256: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule).unwrap(),
+            schnorr: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule).unwrap(),
             bitcoin_address: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule)
                 .unwrap(),
         })
@@ -56,6 +58,7 @@ impl ToOnChainGasSchedule for NativeGasParameters {
         entires.extend(self.ecdsa_k1.to_on_chain_gas_schedule());
         entires.extend(self.ecdsa_r1.to_on_chain_gas_schedule());
         entires.extend(self.rs256.to_on_chain_gas_schedule());
+        entires.extend(self.schnorr.to_on_chain_gas_schedule());
         entires.extend(self.bitcoin_address.to_on_chain_gas_schedule());
         entires
     }
@@ -69,6 +72,7 @@ impl InitialGasSchedule for NativeGasParameters {
             ecdsa_k1: InitialGasSchedule::initial(),
             ecdsa_r1: InitialGasSchedule::initial(),
             rs256: InitialGasSchedule::initial(),
+            schnorr: InitialGasSchedule::initial(),
             bitcoin_address: InitialGasSchedule::initial(),
         }
     }
@@ -103,6 +107,7 @@ impl FromOnChainGasSchedule for MoveOSStdlibGasParameters {
             )
             .unwrap(),
             ability: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule).unwrap(),
+            plonk: FromOnChainGasSchedule::from_on_chain_gas_schedule(gas_schedule).unwrap(),
         })
     }
 }
@@ -132,6 +137,7 @@ impl ToOnChainGasSchedule for MoveOSStdlibGasParameters {
         entires.extend(self.groth16.to_on_chain_gas_schedule());
         entires.extend(self.object_list_field_keys.to_on_chain_gas_schedule());
         entires.extend(self.ability.to_on_chain_gas_schedule());
+        entires.extend(self.plonk.to_on_chain_gas_schedule());
         entires
     }
 }
@@ -162,6 +168,7 @@ impl InitialGasSchedule for MoveOSStdlibGasParameters {
             groth16: InitialGasSchedule::initial(),
             object_list_field_keys: InitialGasSchedule::initial(),
             ability: InitialGasSchedule::initial(),
+            plonk: InitialGasSchedule::initial(),
         }
     }
 }
@@ -174,6 +181,7 @@ impl NativeGasParameters {
             ecdsa_k1: kanari_framework::crypto::ecdsa_k1::GasParameters::zeros(),
             ecdsa_r1: kanari_framework::crypto::ecdsa_r1::GasParameters::zeros(),
             rs256: kanari_framework::crypto::rs256::GasParameters::zeros(),
+            schnorr: kanari_framework::crypto::schnorr::GasParameters::zeros(),
             bitcoin_address: kanari_framework::bitcoin_address::GasParameters::zeros(),
         }
     }
@@ -209,6 +217,10 @@ pub fn all_natives(gas_params: NativeGasParameters) -> NativeFunctionTable {
         "rs256",
         kanari_framework::crypto::rs256::make_all(gas_params.rs256)
     );
+    add_natives!(
+        "schnorr",
+        kanari_framework::crypto::schnorr::make_all(gas_params.schnorr)
+    );
     add_natives!(
         "bitcoin_address",
         kanari_framework::bitcoin_address::make_all(gas_params.bitcoin_address)
@@ -54,6 +54,26 @@ module kanari_nursery::multisign_wallet{
         signatures: vector<vector<u8>>,
     }
 
+    /// The key of the participant's partially signed PSBT of a Bitcoin proposal,
+    /// the PSBT is stored as a field of the MultisignWallet object
+    struct BitcoinProposalPsbtKey has copy, drop, store {
+        proposal_id: u64,
+        participant: address,
+    }
+
+    /// The Bitcoin proposal info for query
+    struct BitcoinProposalInfo has copy, drop, store {
+        multisign_address: address,
+        proposal_id: u64,
+        tx_id: address,
+        tx_data: vector<u8>,
+        status: u8,
+        participants: vector<address>,
+        /// The partially signed PSBTs of the participants, in the same order as `participants`
+        /// The PSBT is empty if the participant signs without PSBT
+        signed_psbts: vector<vector<u8>>,
+    }
+
     struct KanariProposal has store {
         /// The multisign account address
         multisign_address: address,
@@ -91,7 +111,7 @@ module kanari_nursery::multisign_wallet{
         object::borrow_mut(wallet_obj)
     }
 
-    public fun submit_bitcoin_proposal(
+    public entry fun submit_bitcoin_proposal(
         sender: &signer,
         multisign_address: address,
         tx_id: address,
@@ -118,7 +138,7 @@ module kanari_nursery::multisign_wallet{
         table_vec::push_back(&mut wallet.bitcoin_proposals, proposal);
     }
 
-    public fun sign_bitcoin_proposal(
+    public entry fun sign_bitcoin_proposal(
         sender: &signer,
         multisign_address: address,
         proposal_id: u64,
@@ -141,12 +161,77 @@ module kanari_nursery::multisign_wallet{
         let participant_public_key = multisign_account::participant_public_key(&participant);
         verify_bitcoin_signature(proposal.tx_id, &signature, participant_public_key);
         let threshold = multisign_account::threshold(multisign_address);
+        vector::push_back(&mut proposal.participants, sender_addr);
         vector::push_back(&mut proposal.signatures, signature);
         if(vector::length(&proposal.signatures) >= threshold){
             proposal.status = PROPOSAL_STATUS_APPROVED;
         }
     }
 
+    /// Sign the Bitcoin proposal and attach the participant's partially signed PSBT,
+    /// so the last signer can combine the PSBTs and finalize the Bitcoin transaction
+    public entry fun sign_bitcoin_proposal_with_psbt(
+        sender: &signer,
+        multisign_address: address,
+        proposal_id: u64,
+        signature: vector<u8>,
+        signed_psbt: vector<u8>,
+    ){
+        sign_bitcoin_proposal(sender, multisign_address, proposal_id, signature);
+        let wallet_id = object::account_named_object_id<MultisignWallet>(multisign_address);
+        let wallet_obj = object::borrow_mut_object_extend<MultisignWallet>(wallet_id);
+        let key = BitcoinProposalPsbtKey {
+            proposal_id,
+            participant: signer::address_of(sender),
+        };
+        object::add_field(wallet_obj, key, signed_psbt);
+    }
+
+    /// Get the count of Bitcoin proposals of the multisign account, the proposal id is the index
+    public fun bitcoin_proposal_count(multisign_address: address): u64 {
+        let wallet_id = object::account_named_object_id<MultisignWallet>(multisign_address);
+        if (!object::exists_object(wallet_id)){
+            return 0
+        };
+        let wallet_obj = object::borrow_object<MultisignWallet>(wallet_id);
+        table_vec::length(&object::borrow(wallet_obj).bitcoin_proposals)
+    }
+
+    public fun bitcoin_proposal_info(multisign_address: address, proposal_id: u64): BitcoinProposalInfo {
+        let wallet_id = object::account_named_object_id<MultisignWallet>(multisign_address);
+        assert!(object::exists_object(wallet_id), ErrorInvalidProposal);
+        let wallet_obj = object::borrow_object<MultisignWallet>(wallet_id);
+        let wallet = object::borrow(wallet_obj);
+        assert!(table_vec::contains(&wallet.bitcoin_proposals, proposal_id), ErrorInvalidProposal);
+        let proposal = table_vec::borrow(&wallet.bitcoin_proposals, proposal_id);
+
+        let signed_psbts = vector::empty();
+        let idx = 0;
+        let len = vector::length(&proposal.participants);
+        while(idx < len){
+            let key = BitcoinProposalPsbtKey {
+                proposal_id,
+                participant: *vector::borrow(&proposal.participants, idx),
+            };
+            if (object::contains_field(wallet_obj, key)){
+                vector::push_back(&mut signed_psbts, *object::borrow_field(wallet_obj, key));
+            }else{
+                vector::push_back(&mut signed_psbts, vector::empty());
+            };
+            idx = idx + 1;
+        };
+
+        BitcoinProposalInfo {
+            multisign_address: proposal.multisign_address,
+            proposal_id: proposal.proposal_id,
+            tx_id: proposal.tx_id,
+            tx_data: proposal.tx_data,
+            status: proposal.status,
+            participants: proposal.participants,
+            signed_psbts,
+        }
+    }
+
 
     fun verify_bitcoin_signature(tx_id: address, signature: &vector<u8>, public_key: &vector<u8>) {
         assert!(
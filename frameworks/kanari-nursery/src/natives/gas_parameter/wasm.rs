@@ -7,6 +7,24 @@ kanari_framework::natives::gas_parameter::native::define_gas_parameters_for_nati
     [.create_instance_gas_parameter.base_create_instance, "create_instance_gas_parameter.base_create_instance", 10000],
     [.create_instance_gas_parameter.per_byte_instance, "create_instance_gas_parameter.per_byte_instance", 100],
 
+    [.create_instance_gas_parameter.cost_table.default_cost, optional "create_instance_gas_parameter.cost_table.default_cost", 0],
+    [.create_instance_gas_parameter.cost_table.call, optional "create_instance_gas_parameter.cost_table.call", 0],
+    [.create_instance_gas_parameter.cost_table.branch, optional "create_instance_gas_parameter.cost_table.branch", 0],
+    [.create_instance_gas_parameter.cost_table.load, optional "create_instance_gas_parameter.cost_table.load", 0],
+    [.create_instance_gas_parameter.cost_table.store, optional "create_instance_gas_parameter.cost_table.store", 0],
+    [.create_instance_gas_parameter.cost_table.global, optional "create_instance_gas_parameter.cost_table.global", 0],
+    [.create_instance_gas_parameter.cost_table.int32_arith, optional "create_instance_gas_parameter.cost_table.int32_arith", 0],
+    [.create_instance_gas_parameter.cost_table.int32_div, optional "create_instance_gas_parameter.cost_table.int32_div", 0],
+    [.create_instance_gas_parameter.cost_table.int64_arith, optional "create_instance_gas_parameter.cost_table.int64_arith", 0],
+    [.create_instance_gas_parameter.cost_table.int64_div, optional "create_instance_gas_parameter.cost_table.int64_div", 0],
+    [.create_instance_gas_parameter.cost_table.float32_arith, optional "create_instance_gas_parameter.cost_table.float32_arith", 0],
+    [.create_instance_gas_parameter.cost_table.float64_arith, optional "create_instance_gas_parameter.cost_table.float64_arith", 0],
+    [.create_instance_gas_parameter.cost_table.memory, optional "create_instance_gas_parameter.cost_table.memory", 0],
+    [.create_instance_gas_parameter.cost_table.bulk_memory, optional "create_instance_gas_parameter.cost_table.bulk_memory", 0],
+    [.create_instance_gas_parameter.cost_table.gas_limit, optional "create_instance_gas_parameter.cost_table.gas_limit", 0],
+    [.create_instance_gas_parameter.cost_table.max_memory_pages, optional "create_instance_gas_parameter.cost_table.max_memory_pages", 0],
+    [.create_instance_gas_parameter.cost_table.max_call_depth, optional "create_instance_gas_parameter.cost_table.max_call_depth", 0],
+
     [.create_cbor_value_gas_parameter.base, "create_cbor_value_gas_parameter.base", 100],
     [.create_cbor_value_gas_parameter.per_byte, "create_cbor_value_gas_parameter.per_byte", 10000],
 
@@ -15,8 +15,8 @@ pub mod wasm;
 
 #[derive(Debug, Clone)]
 pub struct GasParameters {
-    wasm: crate::natives::wasm::GasParameters,
-    cosmwasm_vm: crate::natives::cosmwasm_vm::GasParameters, // Add this field
+    pub wasm: crate::natives::wasm::GasParameters,
+    pub cosmwasm_vm: crate::natives::cosmwasm_vm::GasParameters, // Add this field
 }
 
 impl GasParameters {
@@ -17,9 +17,9 @@ use std::ops::Deref;
 use std::vec;
 use tracing::{debug, warn};
 
-use moveos_wasm::wasm::{
-    create_wasm_instance, get_instance_pool, insert_wasm_instance, put_data_on_stack,
-};
+use moveos_wasm::context::NativeWASMContext;
+use moveos_wasm::gas_config::WASMGasConfig;
+use moveos_wasm::wasm::{create_wasm_instance, put_data_on_stack};
 
 use moveos_stdlib::natives::helpers::{make_module_natives, make_native};
 
@@ -45,10 +45,112 @@ pub const E_WASM_REMOVE_INSTANCE_FAILED: u64 = 19;
 pub const E_WASM_PUT_DATA_ON_STACK_FAILED: u64 = 20;
 pub const E_VM_ERROR: u64 = 99;
 
+/// The WASM operator cost table and limits in the gas schedule.
+/// The missing or zero entries use the values of `WASMGasConfig::default()`.
+#[derive(Debug, Clone)]
+pub struct WASMCostTableGasParameters {
+    pub default_cost: Option<u64>,
+    pub call: Option<u64>,
+    pub branch: Option<u64>,
+    pub load: Option<u64>,
+    pub store: Option<u64>,
+    pub global: Option<u64>,
+    pub int32_arith: Option<u64>,
+    pub int32_div: Option<u64>,
+    pub int64_arith: Option<u64>,
+    pub int64_div: Option<u64>,
+    pub float32_arith: Option<u64>,
+    pub float64_arith: Option<u64>,
+    pub memory: Option<u64>,
+    pub bulk_memory: Option<u64>,
+    pub gas_limit: Option<u64>,
+    pub max_memory_pages: Option<u64>,
+    pub max_call_depth: Option<u64>,
+}
+
+impl WASMCostTableGasParameters {
+    pub fn zeros() -> Self {
+        Self {
+            default_cost: None,
+            call: None,
+            branch: None,
+            load: None,
+            store: None,
+            global: None,
+            int32_arith: None,
+            int32_div: None,
+            int64_arith: None,
+            int64_div: None,
+            float32_arith: None,
+            float64_arith: None,
+            memory: None,
+            bulk_memory: None,
+            gas_limit: None,
+            max_memory_pages: None,
+            max_call_depth: None,
+        }
+    }
+
+    pub fn init(config: WASMGasConfig) -> Self {
+        Self {
+            default_cost: Some(config.default_cost),
+            call: Some(config.call),
+            branch: Some(config.branch),
+            load: Some(config.load),
+            store: Some(config.store),
+            global: Some(config.global),
+            int32_arith: Some(config.int32_arith),
+            int32_div: Some(config.int32_div),
+            int64_arith: Some(config.int64_arith),
+            int64_div: Some(config.int64_div),
+            float32_arith: Some(config.float32_arith),
+            float64_arith: Some(config.float64_arith),
+            memory: Some(config.memory),
+            bulk_memory: Some(config.bulk_memory),
+            gas_limit: Some(config.gas_limit),
+            max_memory_pages: Some(config.max_memory_pages as u64),
+            max_call_depth: Some(config.max_call_depth as u64),
+        }
+    }
+
+    pub fn to_gas_config(&self) -> WASMGasConfig {
+        fn value(param: Option<u64>, default: u64) -> u64 {
+            param.filter(|v| *v > 0).unwrap_or(default)
+        }
+        fn limit(param: Option<u64>, default: u32) -> u32 {
+            param
+                .filter(|v| *v > 0)
+                .map_or(default, |v| u32::try_from(v).unwrap_or(u32::MAX))
+        }
+
+        let default = WASMGasConfig::default();
+        WASMGasConfig {
+            default_cost: value(self.default_cost, default.default_cost),
+            call: value(self.call, default.call),
+            branch: value(self.branch, default.branch),
+            load: value(self.load, default.load),
+            store: value(self.store, default.store),
+            global: value(self.global, default.global),
+            int32_arith: value(self.int32_arith, default.int32_arith),
+            int32_div: value(self.int32_div, default.int32_div),
+            int64_arith: value(self.int64_arith, default.int64_arith),
+            int64_div: value(self.int64_div, default.int64_div),
+            float32_arith: value(self.float32_arith, default.float32_arith),
+            float64_arith: value(self.float64_arith, default.float64_arith),
+            memory: value(self.memory, default.memory),
+            bulk_memory: value(self.bulk_memory, default.bulk_memory),
+            gas_limit: value(self.gas_limit, default.gas_limit),
+            max_memory_pages: limit(self.max_memory_pages, default.max_memory_pages),
+            max_call_depth: limit(self.max_call_depth, default.max_call_depth),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct WASMCreateInstanceGasParameters {
     pub base_create_instance: InternalGas,
     pub per_byte_instance: InternalGasPerByte,
+    pub cost_table: WASMCostTableGasParameters,
 }
 
 impl WASMCreateInstanceGasParameters {
@@ -56,6 +158,7 @@ impl WASMCreateInstanceGasParameters {
         Self {
             base_create_instance: 0.into(),
             per_byte_instance: 0.into(),
+            cost_table: WASMCostTableGasParameters::zeros(),
         }
     }
 }
@@ -64,7 +167,7 @@ impl WASMCreateInstanceGasParameters {
 #[inline]
 fn native_create_wasm_instance(
     gas_params: &WASMCreateInstanceGasParameters,
-    _context: &mut NativeContext,
+    context: &mut NativeContext,
     _ty_args: Vec<Type>,
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
@@ -77,9 +180,11 @@ fn native_create_wasm_instance(
 
     let wasm_bytes = pop_arg!(args, Vec<u8>);
 
-    let (instance_id, error_code) = match create_wasm_instance(&wasm_bytes) {
+    let config = gas_params.cost_table.to_gas_config();
+    let (instance_id, error_code) = match create_wasm_instance(&wasm_bytes, config) {
         Ok(instance) => {
-            match insert_wasm_instance(instance) {
+            let wasm_context = context.extensions_mut().get_mut::<NativeWASMContext>();
+            match wasm_context.insert_instance(instance) {
                 Ok(id) => (id, 0), // No error
                 Err(e) => {
                     warn!("insert_wasm_instance_error: {:?}", &e);
@@ -262,7 +367,7 @@ impl WASMCreateArgsGasParameters {
 #[inline]
 fn native_create_wasm_args_in_memory(
     gas_params: &WASMCreateArgsGasParameters,
-    _context: &mut NativeContext,
+    context: &mut NativeContext,
     _ty_args: Vec<Type>,
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
@@ -294,17 +399,8 @@ fn native_create_wasm_args_in_memory(
 
     let mut data_ptr_list = Vec::new();
 
-    let instance_pool = get_instance_pool();
-    let mut pool_object = match instance_pool.lock() {
-        Ok(v) => v,
-        Err(_) => {
-            return Ok(NativeResult::err(
-                gas_params.base_create_args,
-                E_GET_INSTANCE_POOL_FAILED,
-            ));
-        }
-    };
-    match pool_object.get_mut(&instance_id) {
+    let wasm_context = context.extensions_mut().get_mut::<NativeWASMContext>();
+    match wasm_context.get_instance_mut(instance_id) {
         None => {
             return Ok(NativeResult::err(
                 gas_params.base_create_args,
@@ -371,11 +467,11 @@ fn build_err(cost: InternalGas, abort_code: u64) -> PartialVMResult<NativeResult
 #[inline]
 fn native_execute_wasm_function(
     gas_params: &WASMExecuteGasParameters,
-    _context: &mut NativeContext,
+    context: &mut NativeContext,
     _ty_args: Vec<Type>,
     args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
-    let vm_result = execute_wasm_function_inner(gas_params, _context, _ty_args, args);
+    let vm_result = execute_wasm_function_inner(gas_params, context, _ty_args, args);
     match vm_result {
         PartialVMResult::Ok(native_result) => match native_result {
             NativeResult::Success { cost, ret_vals } => {
@@ -406,7 +502,7 @@ fn native_execute_wasm_function(
 
 fn execute_wasm_function_inner(
     gas_params: &WASMExecuteGasParameters,
-    _context: &mut NativeContext,
+    context: &mut NativeContext,
     _ty_args: Vec<Type>,
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
@@ -421,28 +517,21 @@ fn execute_wasm_function_inner(
     let func_name = pop_arg!(args, Vec<u8>);
     let instance_id = pop_arg!(args, u64);
 
-    let instance_pool = get_instance_pool();
-    let mut pool_object = match instance_pool.lock() {
-        Ok(v) => v,
-        Err(e) => {
-            warn!(
-                "execute_wasm_function_inner->instance_pool_lock_error: {:?}",
-                &e
-            );
-
-            return Ok(NativeResult::err(
-                gas_params.base_create_execution,
-                E_GET_INSTANCE_POOL_FAILED,
-            ));
-        }
-    };
-
-    let ret = match pool_object.get_mut(&instance_id) {
+    let wasm_context = context.extensions_mut().get_mut::<NativeWASMContext>();
+    let ret = match wasm_context.get_instance_mut(instance_id) {
         None => Ok(NativeResult::err(
             gas_params.base_create_execution,
             E_INSTANCE_NO_EXISTS,
         )),
         Some(instance) => {
+            // The call depth is left over if the last call of the instance trapped
+            if let Err(e) = instance.reset_call_depth() {
+                warn!(
+                    "execute_wasm_function_inner->reset_call_depth_error:{:?}",
+                    &e
+                );
+                return build_err(gas_params.base_create_execution, E_WASM_EXECUTION_FAILED);
+            }
             match instance.instance.exports.get_function(
                 String::from_utf8_lossy(func_name.as_slice())
                     .to_string()
@@ -559,7 +648,7 @@ impl WASMReadAddLength {
 #[inline]
 fn native_read_data_length(
     gas_params: &WASMReadAddLength,
-    _context: &mut NativeContext,
+    context: &mut NativeContext,
     _ty_args: Vec<Type>,
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
@@ -573,17 +662,8 @@ fn native_read_data_length(
     let data_ptr = pop_arg!(args, u64);
     let instance_id = pop_arg!(args, u64);
 
-    let instance_pool = get_instance_pool();
-    let mut pool_object = match instance_pool.lock() {
-        Ok(v) => v,
-        Err(_) => {
-            return Ok(NativeResult::err(
-                gas_params.base,
-                E_GET_INSTANCE_POOL_FAILED,
-            ));
-        }
-    };
-    let ret = match pool_object.get_mut(&instance_id) {
+    let wasm_context = context.extensions_mut().get_mut::<NativeWASMContext>();
+    let ret = match wasm_context.get_instance_mut(instance_id) {
         None => Ok(NativeResult::err(gas_params.base, E_INSTANCE_NO_EXISTS)),
         Some(instance) => {
             let memory = match instance.instance.exports.get_memory("memory") {
@@ -631,7 +711,7 @@ impl WASMReadHeapData {
 #[inline]
 fn native_read_data_from_heap(
     gas_params: &WASMReadHeapData,
-    _context: &mut NativeContext,
+    context: &mut NativeContext,
     _ty_args: Vec<Type>,
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
@@ -646,17 +726,8 @@ fn native_read_data_from_heap(
     let data_ptr = pop_arg!(args, u32);
     let instance_id = pop_arg!(args, u64);
 
-    let instance_pool = get_instance_pool();
-    let mut pool_object = match instance_pool.lock() {
-        Ok(v) => v,
-        Err(_) => {
-            return Ok(NativeResult::err(
-                gas_params.base,
-                E_GET_INSTANCE_POOL_FAILED,
-            ));
-        }
-    };
-    let ret = match pool_object.get_mut(&instance_id) {
+    let wasm_context = context.extensions_mut().get_mut::<NativeWASMContext>();
+    let ret = match wasm_context.get_instance_mut(instance_id) {
         None => Ok(NativeResult::err(gas_params.base, E_INSTANCE_NO_EXISTS)),
         Some(instance) => {
             let memory = match instance.instance.exports.get_memory("memory") {
@@ -729,7 +800,7 @@ impl WASMReleaseInstance {
 #[inline]
 fn native_release_wasm_instance(
     gas_params: &WASMReleaseInstance,
-    _context: &mut NativeContext,
+    context: &mut NativeContext,
     _ty_args: Vec<Type>,
     mut args: VecDeque<Value>,
 ) -> PartialVMResult<NativeResult> {
@@ -751,32 +822,11 @@ fn native_release_wasm_instance(
 
     let instance_id = val.value_as::<u64>()?;
 
-    {
-        let instance_pool = get_instance_pool();
-        let mut pool_object = match instance_pool.lock() {
-            Ok(v) => v,
-            Err(_) => {
-                return Ok(NativeResult::err(
-                    gas_params.base,
-                    E_GET_INSTANCE_POOL_FAILED,
-                ));
-            }
-        };
-        if pool_object.get_mut(&instance_id).is_none() {
-            return Ok(NativeResult::err(gas_params.base, E_INSTANCE_NO_EXISTS));
-        };
+    let wasm_context = context.extensions_mut().get_mut::<NativeWASMContext>();
+    if wasm_context.remove_instance(instance_id).is_none() {
+        return Ok(NativeResult::err(gas_params.base, E_INSTANCE_NO_EXISTS));
     }
 
-    match moveos_wasm::wasm::remove_instance(instance_id) {
-        Ok(_) => {}
-        Err(_) => {
-            return Ok(NativeResult::err(
-                gas_params.base,
-                E_GET_INSTANCE_POOL_FAILED,
-            ));
-        }
-    };
-
     Ok(NativeResult::Success {
         cost: gas_params.base,
         ret_vals: smallvec![Value::bool(true)],
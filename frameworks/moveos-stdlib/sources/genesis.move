@@ -18,6 +18,12 @@ module moveos_std::genesis {
         timestamp: u64,
     }
 
+    /// GenesisCalls is the BCS encoded function calls in the TxContext,
+    /// they are executed after the genesis modules are published, to set up the initial state of custom networks.
+    struct GenesisCalls has copy,store,drop{
+        calls: vector<vector<u8>>,
+    }
+
     fun init(){
         
         let genesis_context_option = tx_context::get_attribute<GenesisContext>();
@@ -5,6 +5,7 @@
 /// `module_store` provide object to manage packages and modules.
 module moveos_std::module_store {
     use std::vector;
+    use std::option::{Self, Option};
     use std::string::String;
     use moveos_std::core_addresses;
     use moveos_std::object::{Self, ObjectID, Object};
@@ -14,6 +15,8 @@ module moveos_std::module_store {
     use moveos_std::features;
     use moveos_std::bcs;
     use moveos_std::event;
+    use moveos_std::hash;
+    use moveos_std::sort;
 
     friend moveos_std::genesis;
     
@@ -23,6 +26,17 @@ module moveos_std::module_store {
     const ErrorNoUpgradePermission: u64 = 2;
     /// Upgrade cap issued already
     const ErrorUpgradeCapIssued: u64 = 3;
+    /// The package is published with metadata, so the upgrade must provide the metadata
+    const ErrorPackageMetadataRequired: u64 = 4;
+    /// The dependency package does not exist
+    const ErrorDependencyNotFound: u64 = 5;
+    /// The digest of the dependency package does not match the pinned digest
+    const ErrorDependencyDigestMismatch: u64 = 6;
+
+    /// The package can be upgraded with compatible modules
+    const UPGRADE_POLICY_COMPATIBLE: u8 = 0;
+    /// The package can not be upgraded anymore
+    const UPGRADE_POLICY_IMMUTABLE: u8 = 1;
 
     /// Allowlist for module function invocation
     struct Allowlist has key, store {
@@ -61,6 +75,40 @@ module moveos_std::module_store {
         package_id: address,
     }
 
+    #[data_struct]
+    /// Semantic version of a package
+    struct PackageVersion has store, copy, drop {
+        major: u64,
+        minor: u64,
+        patch: u64,
+    }
+
+    #[data_struct]
+    /// A dependency of a package, pinned to the digest of the dependency package.
+    struct PackageDependency has store, copy, drop {
+        package_id: address,
+        /// The digest of the dependency package, empty if the dependency package has no digest.
+        digest: vector<u8>,
+    }
+
+    #[data_struct]
+    /// The package metadata, which is the same with the Rust definition.
+    struct PackageMetadata has store, copy, drop {
+        package_name: String,
+        version: PackageVersion,
+        upgrade_policy: u8,
+        dependencies: vector<PackageDependency>,
+    }
+
+    /// The digest and the metadata of a published package.
+    /// It is a named object under the package account, and owned by the package account.
+    struct PackageInfo has key {
+        /// The digest of the modules of the package, see `compute_package_digest`.
+        digest: vector<u8>,
+        /// None if the package is published without metadata.
+        metadata: Option<PackageMetadata>,
+    }
+
     /// Event for package upgrades. New published modules will also trigger this event.
     struct UpgradeEvent has drop, store, copy {
         package_id: address,
@@ -141,8 +189,24 @@ module moveos_std::module_store {
     /// Entry function to publish package
     /// The order of modules must be sorted by dependency order.
     public entry fun publish_package_entry(account: &signer, package_bytes: vector<u8>) {
-        let sender_address = signer::address_of(account);
         let package_data = bcs::from_bytes<PackageData>(package_bytes);
+        publish_package(account, package_data, option::none());
+    }
+
+    /// Entry function to publish package with the package metadata
+    /// The metadata is verified, and the dependencies must be published with the pinned digests,
+    /// except the system packages, whose digests are not recorded.
+    /// If the package is published with metadata before, the upgrade must follow the published metadata.
+    public entry fun publish_package_with_metadata_entry(
+        account: &signer, package_bytes: vector<u8>, metadata_bytes: vector<u8>
+    ) {
+        let package_data = bcs::from_bytes<PackageData>(package_bytes);
+        let metadata = bcs::from_bytes<PackageMetadata>(metadata_bytes);
+        publish_package(account, package_data, option::some(metadata));
+    }
+
+    fun publish_package(account: &signer, package_data: PackageData, metadata: Option<PackageMetadata>) {
+        let sender_address = signer::address_of(account);
 
         // check if the package id is in allowlist
         if (features::module_publishing_allowlist_enabled()) {
@@ -176,14 +240,45 @@ module moveos_std::module_store {
             i = i + 1;
         };
 
+        let package_id = package_data.package_id;
+        let old_metadata = package_metadata(package_id);
+        if (option::is_some(&metadata)) {
+            let new_metadata = option::borrow(&metadata);
+            move_module::verify_package_metadata(
+                &module_vec, package_id, bcs::to_bytes(new_metadata), bcs::to_bytes(&old_metadata)
+            );
+            check_dependencies(&new_metadata.dependencies);
+        } else {
+            assert!(option::is_none(&old_metadata), ErrorPackageMetadataRequired);
+        };
 
         let module_store = borrow_mut_module_store(); 
-        let upgrade_flag = publish_modules_internal(module_store, package_data.package_id, module_vec);
+        let upgrade_flag = publish_modules_internal(module_store, package_id, module_vec);
+        if (option::is_some(&metadata)) {
+            let package_info = object::borrow_mut_object_extend<PackageInfo>(package_info_id(package_id));
+            object::borrow_mut(package_info).metadata = metadata;
+        };
         // Store ModuleUpgradeFlag in tx_context which will be fetched in VM in Rust, 
         // and then announce to the VM that the code loading cache should be considered outdated. 
         tx_context::set_module_upgrade_flag(upgrade_flag);
     }
 
+    /// Every dependency must be published, and its digest must be the same as the pinned one.
+    /// The system packages are published by the genesis and the framework upgrades, which do not
+    /// record the package digests, so the system dependencies are not checked.
+    fun check_dependencies(dependencies: &vector<PackageDependency>) {
+        let i = 0;
+        let len = vector::length(dependencies);
+        while (i < len) {
+            let dependency = vector::borrow(dependencies, i);
+            if (!core_addresses::is_system_reserved_address(dependency.package_id)) {
+                assert!(exists_package(dependency.package_id), ErrorDependencyNotFound);
+                assert!(package_digest(dependency.package_id) == dependency.digest, ErrorDependencyDigestMismatch);
+            };
+            i = i + 1;
+        };
+    }
+
     public fun package_version(package_id: address): u64 {
         let package_obj_id = package_obj_id(package_id);
         let package = borrow_package(package_obj_id);
@@ -233,6 +328,8 @@ module moveos_std::module_store {
 
         // version increment
         let version = version_increase(package);
+        let digest = compute_package_digest(move_module::into_byte_codes_batch(modules));
+        save_package_digest(package_id, digest);
 
         event::emit<UpgradeEvent>(UpgradeEvent { package_id, version });
 
@@ -268,6 +365,119 @@ module moveos_std::module_store {
         version
     }
 
+    // ==== Package info functions ====
+
+    public fun package_info_id(package_id: address): ObjectID {
+        object::account_named_object_id<PackageInfo>(package_id)
+    }
+
+    /// Return the digest of the package, empty if the package has no digest.
+    public fun package_digest(package_id: address): vector<u8> {
+        let id = package_info_id(package_id);
+        if (!object::exists_object(id)) {
+            return vector::empty()
+        };
+        object::borrow(object::borrow_object<PackageInfo>(id)).digest
+    }
+
+    /// Return the metadata of the package, none if the package is published without metadata.
+    public fun package_metadata(package_id: address): Option<PackageMetadata> {
+        let id = package_info_id(package_id);
+        if (!object::exists_object(id)) {
+            return option::none()
+        };
+        object::borrow(object::borrow_object<PackageInfo>(id)).metadata
+    }
+
+    /// The digest of a package does not depend on the order of the modules.
+    /// It is the sha3_256 of the BCS encoded sorted sha3_256 digests of the module bytecodes.
+    public fun compute_package_digest(byte_codes_batch: vector<vector<u8>>): vector<u8> {
+        let module_digests = vector::empty<vector<u8>>();
+        let i = 0;
+        let len = vector::length(&byte_codes_batch);
+        while (i < len) {
+            vector::push_back(&mut module_digests, hash::sha3_256(*vector::borrow(&byte_codes_batch, i)));
+            i = i + 1;
+        };
+        sort::sort(&mut module_digests);
+        hash::sha3_256(bcs::to_bytes(&module_digests))
+    }
+
+    fun save_package_digest(package_id: address, digest: vector<u8>) {
+        let id = package_info_id(package_id);
+        if (object::exists_object(id)) {
+            let package_info = object::borrow_mut_object_extend<PackageInfo>(id);
+            object::borrow_mut(package_info).digest = digest;
+        } else {
+            let package_info = object::new_account_named_object(package_id, PackageInfo {
+                digest,
+                metadata: option::none(),
+            });
+            object::transfer_extend(package_info, package_id);
+        }
+    }
+
+    public fun upgrade_policy_compatible(): u8 {
+        UPGRADE_POLICY_COMPATIBLE
+    }
+
+    public fun upgrade_policy_immutable(): u8 {
+        UPGRADE_POLICY_IMMUTABLE
+    }
+
+    public fun new_package_version(major: u64, minor: u64, patch: u64): PackageVersion {
+        PackageVersion { major, minor, patch }
+    }
+
+    public fun new_package_dependency(package_id: address, digest: vector<u8>): PackageDependency {
+        PackageDependency { package_id, digest }
+    }
+
+    public fun new_package_metadata(
+        package_name: String,
+        version: PackageVersion,
+        upgrade_policy: u8,
+        dependencies: vector<PackageDependency>,
+    ): PackageMetadata {
+        PackageMetadata { package_name, version, upgrade_policy, dependencies }
+    }
+
+    public fun metadata_package_name(self: &PackageMetadata): String {
+        self.package_name
+    }
+
+    public fun metadata_version(self: &PackageMetadata): PackageVersion {
+        self.version
+    }
+
+    public fun metadata_upgrade_policy(self: &PackageMetadata): u8 {
+        self.upgrade_policy
+    }
+
+    public fun metadata_dependencies(self: &PackageMetadata): vector<PackageDependency> {
+        self.dependencies
+    }
+
+    public fun version_major(self: &PackageVersion): u64 {
+        self.major
+    }
+
+    public fun version_minor(self: &PackageVersion): u64 {
+        self.minor
+    }
+
+    public fun version_patch(self: &PackageVersion): u64 {
+        self.patch
+    }
+
+    public fun dependency_package_id(self: &PackageDependency): address {
+        self.package_id
+    }
+
+    public fun dependency_digest(self: &PackageDependency): vector<u8> {
+        self.digest
+    }
+
     fun borrow_allowlist(): &Allowlist {
         let allowlist_id = object::named_object_id<Allowlist>();
         let allowlist_obj = object::borrow_object(allowlist_id);
@@ -415,6 +625,134 @@ module moveos_std::module_store {
         assert!(!is_in_allowlist(@0x42), 3);
     }
 
+    #[test_only]
+    fun counter_package_data(): PackageData {
+        PackageData {
+            package_name: std::string::utf8(b"counter"),
+            package_id: @0x42,
+            modules: vector::singleton(COUNTER_MV_BYTES),
+        }
+    }
+
+    #[test_only]
+    fun counter_package_metadata(major: u64, upgrade_policy: u8): PackageMetadata {
+        new_package_metadata(
+            std::string::utf8(b"counter"),
+            new_package_version(major, 0, 0),
+            upgrade_policy,
+            vector::singleton(new_package_dependency(@moveos_std, vector::empty())),
+        )
+    }
+
+    #[test(account=@0x42)]
+    fun test_publish_package_with_metadata(account: &signer) {
+        init_module_store();
+        features::init_feature_store_for_test();
+
+        let metadata = counter_package_metadata(1, UPGRADE_POLICY_COMPATIBLE);
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+        assert!(package_metadata(@0x42) == option::some(metadata), 1);
+        assert!(package_digest(@0x42) == compute_package_digest(vector::singleton(COUNTER_MV_BYTES)), 2);
+
+        let metadata = counter_package_metadata(2, UPGRADE_POLICY_IMMUTABLE);
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+        assert!(package_metadata(@0x42) == option::some(metadata), 3);
+        assert!(package_version(@0x42) == 2, 4);
+    }
+
+    #[test(account=@0x42)]
+    fun test_publish_package_records_digest(account: &signer) {
+        init_module_store();
+        features::init_feature_store_for_test();
+
+        publish_package_entry(account, bcs::to_bytes(&counter_package_data()));
+        assert!(package_metadata(@0x42) == option::none(), 1);
+        assert!(package_digest(@0x42) == compute_package_digest(vector::singleton(COUNTER_MV_BYTES)), 2);
+    }
+
+    #[test(account=@0x42)]
+    #[expected_failure(abort_code = ErrorPackageMetadataRequired, location = Self)]
+    fun test_upgrade_package_without_metadata(account: &signer) {
+        init_module_store();
+        features::init_feature_store_for_test();
+
+        let metadata = counter_package_metadata(1, UPGRADE_POLICY_COMPATIBLE);
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+        publish_package_entry(account, bcs::to_bytes(&counter_package_data()));
+    }
+
+    #[test(account=@0x42)]
+    #[expected_failure(abort_code = 14006, location = moveos_std::move_module)]
+    fun test_upgrade_package_without_version_increase(account: &signer) {
+        init_module_store();
+        features::init_feature_store_for_test();
+
+        let metadata = counter_package_metadata(1, UPGRADE_POLICY_COMPATIBLE);
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+    }
+
+    #[test(account=@0x42)]
+    #[expected_failure(abort_code = 14003, location = moveos_std::move_module)]
+    fun test_upgrade_immutable_package(account: &signer) {
+        init_module_store();
+        features::init_feature_store_for_test();
+
+        let metadata = counter_package_metadata(1, UPGRADE_POLICY_IMMUTABLE);
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+        let metadata = counter_package_metadata(2, UPGRADE_POLICY_IMMUTABLE);
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+    }
+
+    #[test(account=@0x42)]
+    #[expected_failure(abort_code = 14001, location = moveos_std::move_module)]
+    fun test_publish_package_with_undeclared_dependency(account: &signer) {
+        init_module_store();
+        features::init_feature_store_for_test();
+
+        let metadata = new_package_metadata(
+            std::string::utf8(b"counter"),
+            new_package_version(1, 0, 0),
+            UPGRADE_POLICY_COMPATIBLE,
+            vector::empty(),
+        );
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+    }
+
+    #[test(account=@0x42)]
+    #[expected_failure(abort_code = ErrorDependencyDigestMismatch, location = Self)]
+    fun test_publish_package_with_dependency_digest_mismatch(account: &signer) {
+        init_module_store();
+        features::init_feature_store_for_test();
+        create_package(borrow_mut_module_store(), @0x43, @0x43);
+        save_package_digest(@0x43, x"02");
+
+        let dependencies = vector::singleton(new_package_dependency(@moveos_std, vector::empty()));
+        vector::push_back(&mut dependencies, new_package_dependency(@0x43, x"01"));
+        let metadata = new_package_metadata(
+            std::string::utf8(b"counter"),
+            new_package_version(1, 0, 0),
+            UPGRADE_POLICY_COMPATIBLE,
+            dependencies,
+        );
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+    }
+
+    #[test(account=@0x42)]
+    fun test_publish_package_with_system_dependency_digest(account: &signer) {
+        init_module_store();
+        features::init_feature_store_for_test();
+
+        let metadata = new_package_metadata(
+            std::string::utf8(b"counter"),
+            new_package_version(1, 0, 0),
+            UPGRADE_POLICY_COMPATIBLE,
+            vector::singleton(new_package_dependency(@moveos_std, x"01")),
+        );
+        publish_package_with_metadata_entry(account, bcs::to_bytes(&counter_package_data()), bcs::to_bytes(&metadata));
+        assert!(exists_package(@0x42), 1000);
+    }
+
     #[test(account=@0x42)]
     #[expected_failure(abort_code = 9, location = object)]
     fun test_frozen_package(account: &signer) {
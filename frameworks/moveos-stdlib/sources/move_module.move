@@ -88,6 +88,23 @@ module moveos_std::move_module {
         check_compatibililty_inner(new_module.byte_codes, old_module.byte_codes);
     }
 
+    /// Verify the package metadata of the modules, and check the upgrade against the old metadata.
+    /// metadata: BCS bytes of `module_store::PackageMetadata`
+    /// old_metadata: BCS bytes of `Option<module_store::PackageMetadata>`
+    /// Abort if the metadata is invalid or the upgrade is not allowed.
+    public(friend) fun verify_package_metadata(
+        modules: &vector<MoveModule>, package_id: address, metadata: vector<u8>, old_metadata: vector<u8>
+    ) {
+        let bytes_vec = vector::empty<vector<u8>>();
+        let i = 0u64;
+        let len = vector::length(modules);
+        while (i < len) {
+            vector::push_back(&mut bytes_vec, vector::borrow(modules, i).byte_codes);
+            i = i + 1;
+        };
+        verify_package_metadata_inner(bytes_vec, package_id, metadata, old_metadata);
+    }
+
     // TODO: add more tests
     /// Binding given module's address to the new address
     public fun binding_module_address(
@@ -250,6 +267,10 @@ module moveos_std::move_module {
 
     native fun check_compatibililty_inner(new_bytecodes: vector<u8>, old_bytecodes: vector<u8>);
 
+    native fun verify_package_metadata_inner(
+        modules: vector<vector<u8>>, package_id: address, metadata: vector<u8>, old_metadata: vector<u8>
+    );
+
     /// Native function to replace addresses identifier in module binary where the length of
     /// `old_addresses` must equal to that of `new_addresses`.  
     native public(friend) fun replace_address_identifiers(
@@ -0,0 +1,199 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+/// PLONK proof and KZG opening verification over BN254.
+/// The verifying key, the proof and the transcript follow the snarkjs PLONK verifier, so the proofs generated
+/// by snarkjs (or any prover with the same Keccak256 transcript) can be verified on chain.
+/// All the field elements are 32 bytes big endian, a G1 point is `x || y` and a G2 point is `x.c1 || x.c0 || y.c1 || y.c0`,
+/// the same as the EVM precompiles.
+module moveos_std::plonk {
+
+    #[test_only]
+    use std::vector;
+
+    #[allow(unused_const)]
+    // Error if the given curve is not supported
+    const E_INVALID_CURVE: u64 = 0;
+
+    #[allow(unused_const)]
+    // Error if the verifying key is malformed or the domain is invalid.
+    const E_INVALID_VERIFYING_KEY: u64 = 1;
+
+    #[allow(unused_const)]
+    // Error if the number of public inputs given exceeds the max.
+    const E_TOO_MANY_PUBLIC_INPUTS: u64 = 2;
+
+    /// Represents an elliptic curve construction to be used in the verifier. Currently we only support BN254.
+    /// This should be given as the first parameter to `prepare_verifying_key` or `verify_plonk_proof`.
+    struct Curve has store, copy, drop {
+        id: u8,
+    }
+
+    /// Return the `Curve` value indicating that the BN254 construction should be used in a given function.
+    public fun bn254(): Curve { Curve { id: 1 } }
+
+    /// A `PreparedVerifyingKey` consisting of three components in serialized form:
+    /// the domain (n, nPublic, k1, k2, w), the selector and permutation commitments, and the `[x]_2` of the KZG setup.
+    struct PreparedVerifyingKey has store, copy, drop {
+        domain_bytes: vector<u8>,
+        commitments_bytes: vector<u8>,
+        x_2_bytes: vector<u8>,
+    }
+
+    /// Creates a `PreparedVerifyingKey` from bytes.
+    public fun pvk_from_bytes(domain_bytes: vector<u8>, commitments_bytes: vector<u8>, x_2_bytes: vector<u8>): PreparedVerifyingKey {
+        PreparedVerifyingKey {
+            domain_bytes,
+            commitments_bytes,
+            x_2_bytes,
+        }
+    }
+
+    /// Returns bytes of the three components of the `PreparedVerifyingKey`.
+    public fun pvk_to_bytes(pvk: PreparedVerifyingKey): vector<vector<u8>> {
+        vector[
+            pvk.domain_bytes,
+            pvk.commitments_bytes,
+            pvk.x_2_bytes,
+        ]
+    }
+
+    /// A `PublicProofInputs` wrapper around its serialized bytes, 32 bytes for each input.
+    struct PublicProofInputs has store, copy, drop {
+        bytes: vector<u8>,
+    }
+
+    /// Creates a `PublicProofInputs` wrapper from bytes.
+    public fun public_proof_inputs_from_bytes(bytes: vector<u8>): PublicProofInputs {
+        PublicProofInputs { bytes }
+    }
+
+    /// A `Proof` wrapper around the serialized form of the nine commitments and six evaluations.
+    struct Proof has store, copy, drop {
+        bytes: vector<u8>
+    }
+
+    /// Creates a PLONK `Proof` from bytes.
+    public fun proof_from_bytes(bytes: vector<u8>): Proof {
+        Proof { bytes }
+    }
+
+    /// @param curve: What elliptic curve construction to use. See `bn254`.
+    /// @param verifying_key: The 800 bytes verifying key, `n || nPublic || k1 || k2 || w` as 32 bytes integers,
+    /// followed by the G1 commitments `Qm || Ql || Qr || Qo || Qc || S1 || S2 || S3` and the G2 point `[x]_2`.
+    ///
+    /// Returns a prepared verifying key, the domain and all the points are validated.
+    /// This can be used as inputs for the `verify_plonk_proof` and `verify_kzg_opening` functions.
+    public fun prepare_verifying_key(curve: &Curve, verifying_key: &vector<u8>): PreparedVerifyingKey {
+        prepare_verifying_key_internal(curve.id, verifying_key)
+    }
+
+    /// Native functions that validates and splits the verifying key. May abort with `E_INVALID_CURVE` or `E_INVALID_VERIFYING_KEY`.
+    native fun prepare_verifying_key_internal(curve: u8, verifying_key: &vector<u8>): PreparedVerifyingKey;
+
+    /// @param curve: What elliptic curve construction to use. See `bn254`.
+    /// @param prepared_verifying_key: The prepared verifying key of the circuit.
+    /// @param public_proof_inputs: Represent inputs that are public.
+    /// @param proof: The 768 bytes proof, `A || B || C || Z || T1 || T2 || T3 || Wxi || Wxiw` followed by the evaluations
+    /// `a || b || c || s1 || s2 || zw`.
+    ///
+    /// Returns a boolean indicating whether the proof is valid.
+    public fun verify_plonk_proof(curve: &Curve, prepared_verifying_key: &PreparedVerifyingKey, public_proof_inputs: &PublicProofInputs, proof: &Proof): bool {
+        verify_plonk_proof_internal(
+            curve.id,
+            &prepared_verifying_key.domain_bytes,
+            &prepared_verifying_key.commitments_bytes,
+            &prepared_verifying_key.x_2_bytes,
+            &public_proof_inputs.bytes,
+            &proof.bytes
+        )
+    }
+
+    /// Native functions that flattens the inputs and passes to the Rust native function. May abort with `E_INVALID_CURVE`, `E_INVALID_VERIFYING_KEY` or `E_TOO_MANY_PUBLIC_INPUTS`.
+    native fun verify_plonk_proof_internal(curve: u8, domain_bytes: &vector<u8>, commitments_bytes: &vector<u8>, x_2_bytes: &vector<u8>, public_proof_inputs: &vector<u8>, proof_bytes: &vector<u8>): bool;
+
+    /// @param curve: What elliptic curve construction to use. See `bn254`.
+    /// @param prepared_verifying_key: The `[x]_2` of the prepared verifying key is used as the KZG setup.
+    /// @param commitment: The G1 commitment `[p(x)]_1` of the polynomial.
+    /// @param z: The 32 bytes evaluation point.
+    /// @param y: The 32 bytes claimed evaluation `p(z)`.
+    /// @param proof: The G1 opening proof `[(p(x) - y) / (x - z)]_1`.
+    ///
+    /// Returns a boolean indicating whether the opening is valid.
+    public fun verify_kzg_opening(curve: &Curve, prepared_verifying_key: &PreparedVerifyingKey, commitment: &vector<u8>, z: &vector<u8>, y: &vector<u8>, proof: &vector<u8>): bool {
+        verify_kzg_opening_internal(curve.id, &prepared_verifying_key.x_2_bytes, commitment, z, y, proof)
+    }
+
+    /// Native functions that verifies the KZG opening. May abort with `E_INVALID_CURVE`.
+    native fun verify_kzg_opening_internal(curve: u8, x_2_bytes: &vector<u8>, commitment: &vector<u8>, z: &vector<u8>, y: &vector<u8>, proof: &vector<u8>): bool;
+
+    // The circuit proves the knowledge of `a * b = c` with the public `c = 33` and `b = 7` is fixed
+    // in the constraints, the setup is a 8 rows domain with an insecure known toxic waste.
+    #[test_only]
+    const VERIFYING_KEY: vector<u8> = x"00000000000000000000000000000000000000000000000000000000000000080000000000000000000000000000000000000000000000000000000000000001000000000000000000000000000000000000000000000000000000000000000200000000000000000000000000000000000000000000000000000000000000032b337de1c8c14f22ec9b9e2f96afef3652627366f8170a0a948dad4ac1bd5e8029f4a7c8879af0d1b72fa0144f36b2949784251c688d35bc9342fd53259aa2283046860d405c27e39672c8d7c5a0aa110e5bbc74077aec46463f1c26d7139cd610b2e102830961e415be45e162909c23e6950f9f67a6ebded9a67012bd51c6801df49687dc281bd5181d00f5c925b70c37ddd79f5ed42c781203599f01fa770311ea68859c8d67f4e5367b69dedd68f3089fc5140aea19d9775fc787b2430aec0813ba018eff38dc83859eed94c0dcccfa188726a23765e1e23680033ab0134c29f4a7c8879af0d1b72fa0144f36b2949784251c688d35bc9342fd53259aa228001dc865a0d5784621dd7cdebbe0ae4c8925ae1d60f6de46f5e16ff0016960710c0a011b473b5df4fa68b23dbe7bcac173bc55068b488deedfb765bc2aae10101a06fe4f90824f339c2b8c541f2c4b5b4847d24258141ebb59b59e17099170f9239e0c02a03ddde80d0ee0b58ed627aafa15fe064b443ce6c0b70083c0b7354a2393a2a7a3301f2a4d8a36c663eb6aa3de3fa861a1af4c14b4646a57b25a992c2ba47db19bb1486e5ce301b7ea1fbe28ce9d88c30b5aa352c7563e97fc9932c101d6055af8d98d9ce58f93a5bcc7bf3344b374e19630be1f5946883dd349334b2a768d483bbdb03a8e0a9453ee82d3b864d8e1004784d3bee896517e03e97ab122af87c45e8e52d659df0e8b869355263922c77ead9579c826394713d60efaa72fa991f9d9654a6fe03a19c891e3f8298e59e7d579907c23766e8c071a99607d0923e1d4de1ad26d2258943a10f8cee94fc5516be3074fd75b494ae72e8bbb3905ddbacaa77db26735b8554c800cbf0f159a85a2e5ec71dffbaffbffaf4574f701f86545bd5d6b6cd70010f39e89725793cfb578a9aa37d88c64a039b40e4f1a";
+
+    #[test_only]
+    const PROOF: vector<u8> = x"25d53bdb103d2339c3c391e63517e6ba16e4167fb244ad135c6b4066989769720e6ee58a6d72a2717a8e2c26fa5bafcfedb450bbd450a99bd31f08c8ba556d410db02746106d98e69538ca75c608795234f96b9ddfc64430731bab2e44dedafc28172e8f651f2a32a1c39895d6c9baeb40c3c6376acbf216765c36575432bc7112e5a5908a26386164b38813813fc2409f3ea78055ad7ec7098981f3d493005b1f96b5e3675c4c7eb2f4dae889f81414158249d320368cf9b8963bc0e416aecf24bd79926a40401285866708f3ef2d92bd0acc92d93a37d2912302d027f153f90bb2ccc98a25809a660aac1d76baaf608d3284a1fe792aa29ef761459fee97d62dbde17ebabc391fcf28bdb5612cc4b7d53c93f7650fb659cce0335f1654af0e1440787040ddc4056c19e8d8cac791279842bd53838606f299abf4c0de0c267f24aea521db6e1fb8f3f235bc5cc6d4e94ac5359439e12979937efaf9c1a072ee2b545ba91b09d0b895975a02fbb663a499a4f8a3278a82c25b1bad94f26b4c920883de21346b3c43586282a8344b83b3397486f9ae74315bfab1256675da89892f526610b5ecb0e378cb3eb0a706b9b8ce49e5cce726dbf1f92726bb410bcc901e9fda092d6dc40d2fb479cb24f547b9dcae0cdafbbdce952d7457f7a45585c3168756f41dbadc8b22300040176df73959b15d881cb5a0bee949f796e90b4d49062cf7caaf2543c75ee5d3fc9c96435713c5c36941e3a90115d255c9d908a3241c395740f03b1610bc0d15eded8254c38292115c810acae04f95df5ef97c0997159610257ba8bc5677d56564f2632525d5106cb4e9a8e3d52d9545952dfe4fef29cf71bf610e870fbc7407dd7426e60fcbf306ed859ae6c69f5250ba338b816a1e16ae84553bd1389f64e67b537dcbc03e37aadeacc9d2d14aab94d147520e0a0d8cb78f2bd30dbc946a0f4b7257a5542df9ee1cd09ab15b4b7cb4fde5a553d92f028cb11170acc3b323e2db165dfa59409cd9cb2ae6f8478031d5f57fc0f3171c0ff405aa17388a14b2ad5f6d8975287dfb9c25621df1cecb9e5830242e9155";
+
+    #[test]
+    fun test_verify_plonk_proof() {
+        let curve = bn254();
+        let pvk = prepare_verifying_key(&curve, &VERIFYING_KEY);
+        let inputs = public_proof_inputs_from_bytes(x"0000000000000000000000000000000000000000000000000000000000000021");
+        let proof = proof_from_bytes(PROOF);
+        assert!(verify_plonk_proof(&curve, &pvk, &inputs, &proof), 1);
+    }
+
+    #[test]
+    fun test_verify_plonk_proof_with_wrong_input() {
+        let curve = bn254();
+        let pvk = prepare_verifying_key(&curve, &VERIFYING_KEY);
+        let inputs = public_proof_inputs_from_bytes(x"0000000000000000000000000000000000000000000000000000000000000022");
+        let proof = proof_from_bytes(PROOF);
+        assert!(!verify_plonk_proof(&curve, &pvk, &inputs, &proof), 1);
+
+        // The proof does not match the number of the public inputs
+        let inputs = public_proof_inputs_from_bytes(vector[]);
+        assert!(!verify_plonk_proof(&curve, &pvk, &inputs, &proof), 2);
+    }
+
+    #[test]
+    fun test_pvk_bytes() {
+        let curve = bn254();
+        let pvk = prepare_verifying_key(&curve, &VERIFYING_KEY);
+        let bytes = pvk_to_bytes(pvk);
+        let pvk2 = pvk_from_bytes(*vector::borrow(&bytes, 0), *vector::borrow(&bytes, 1), *vector::borrow(&bytes, 2));
+        assert!(pvk == pvk2, 1);
+        let inputs = public_proof_inputs_from_bytes(x"0000000000000000000000000000000000000000000000000000000000000021");
+        assert!(verify_plonk_proof(&curve, &pvk2, &inputs, &proof_from_bytes(PROOF)), 2);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = E_INVALID_VERIFYING_KEY, location = Self)]
+    fun test_prepare_invalid_verifying_key() {
+        let vk = VERIFYING_KEY;
+        // The domain size 6 is not a power of two
+        *vector::borrow_mut(&mut vk, 31) = 6;
+        prepare_verifying_key(&bn254(), &vk);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = E_INVALID_CURVE, location = Self)]
+    fun test_prepare_invalid_curve() {
+        prepare_verifying_key(&Curve { id: 0 }, &VERIFYING_KEY);
+    }
+
+    #[test]
+    fun test_verify_kzg_opening() {
+        let curve = bn254();
+        let pvk = prepare_verifying_key(&curve, &VERIFYING_KEY);
+        let commitment = x"18d52cace4479e0eebd71f809ee12ba7c7b7d69ba42acac396b7a84c76ffe0171147ee15dbb9b77526593ce4938add973a244df31e913b1c797c3cdd64cac073";
+        let z = x"00000000000000000000000000000000000000000000000000000000deadbeef";
+        let y = x"0000000aed1b0d4ebb83667dd3fd506cba4a5297cb3a4c2a80ab99a2bc8068b4";
+        let proof = x"1288a1581fdab3b58fb60a165ed847843d936f01060b8c60312976f07a087c1105f98b3e3cd6d73a24958d5d05c72643ba73075cef31bb032d71ea17f3f4e4eb";
+        assert!(verify_kzg_opening(&curve, &pvk, &commitment, &z, &y, &proof), 1);
+
+        let wrong_y = x"0000000aed1b0d4ebb83667dd3fd506cba4a5297cb3a4c2a80ab99a2bc8068b5";
+        assert!(!verify_kzg_opening(&curve, &pvk, &commitment, &z, &wrong_y, &proof), 2);
+    }
+}
@@ -67,6 +67,7 @@ pub struct GasParameters {
     pub groth16: moveos_stdlib::groth16::GasParameters,
     pub object_list_field_keys: moveos_stdlib::object::ListFieldsGasParameters,
     pub ability: moveos_stdlib::ability::GasParameters,
+    pub plonk: moveos_stdlib::plonk::GasParameters,
 }
 
 impl GasParameters {
@@ -95,6 +96,7 @@ impl GasParameters {
             groth16: moveos_stdlib::groth16::GasParameters::zeros(),
             object_list_field_keys: moveos_stdlib::object::ListFieldsGasParameters::zeros(),
             ability: moveos_stdlib::ability::GasParameters::zeros(),
+            plonk: moveos_stdlib::plonk::GasParameters::zeros(),
         }
     }
 }
@@ -177,6 +179,7 @@ pub fn all_natives(gas_params: GasParameters) -> NativeFunctionTable {
         "ability",
         moveos_stdlib::ability::make_all(gas_params.ability)
     );
+    add_natives!("plonk", moveos_stdlib::plonk::make_all(gas_params.plonk));
 
     let moveos_native_fun_table = make_table_from_iter(MOVEOS_STD_ADDRESS, natives);
     native_fun_table.extend(moveos_native_fun_table);
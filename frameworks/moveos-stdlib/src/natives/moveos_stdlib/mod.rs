@@ -16,6 +16,7 @@ pub mod hash;
 pub mod json;
 pub mod move_module;
 pub mod object;
+pub mod plonk;
 pub mod rlp;
 pub mod signer;
 pub mod test_helper;
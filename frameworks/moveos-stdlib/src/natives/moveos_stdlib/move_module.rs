@@ -28,9 +28,10 @@ use move_vm_types::{
 };
 use moveos_compiler::dependency_order::sort_by_dependency_order;
 use moveos_object_runtime::runtime::ObjectRuntimeContext;
+use moveos_types::moveos_std::module_store::PackageMetadata;
 use moveos_types::moveos_std::move_module::MoveModuleId;
 use moveos_types::moveos_std::onchain_features::COMPATIBILITY_CHECKER_V2;
-use moveos_verifier::verifier::check_metadata_compatibility;
+use moveos_verifier::verifier::{check_metadata_compatibility, verify_package_metadata};
 use smallvec::smallvec;
 use std::collections::{BTreeSet, HashMap, VecDeque};
 use std::hash::Hash;
@@ -334,6 +335,96 @@ fn check_compatibililty_inner(
     Ok(NativeResult::ok(cost, smallvec![]))
 }
 
+/***************************************************************************************************
+ * native fun verify_package_metadata_inner(
+ *     modules: vector<vector<u8>>,
+ *     package_id: address,
+ *     metadata: vector<u8>,
+ *     old_metadata: vector<u8>,
+ * );
+ * The metadata is the BCS bytes of `PackageMetadata`, and the old metadata is the BCS bytes of
+ * `Option<PackageMetadata>`.
+ * Abort if the metadata is invalid or the upgrade is not allowed by the old metadata.
+ **************************************************************************************************/
+
+#[derive(Debug, Clone)]
+pub struct VerifyPackageMetadataGasParameters {
+    pub base: Option<InternalGas>,
+    pub per_byte: Option<InternalGasPerByte>,
+}
+
+impl VerifyPackageMetadataGasParameters {
+    pub fn zeros() -> Self {
+        Self {
+            base: Some(InternalGas::zero()),
+            per_byte: Some(InternalGasPerByte::zero()),
+        }
+    }
+
+    pub fn init(base: InternalGas, per_byte: InternalGasPerByte) -> Self {
+        Self {
+            base: Some(base),
+            per_byte: Some(per_byte),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.base.is_none() || self.per_byte.is_none()
+    }
+}
+
+fn verify_package_metadata_inner(
+    gas_params: &VerifyPackageMetadataGasParameters,
+    _context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    let mut cost = gas_params.base.unwrap_or_else(InternalGas::zero);
+    let per_byte = gas_params.per_byte.unwrap_or_else(InternalGasPerByte::zero);
+
+    let old_metadata_bytes = pop_arg!(args, Vec<u8>);
+    let metadata_bytes = pop_arg!(args, Vec<u8>);
+    let package_id = pop_arg!(args, AccountAddress);
+    let mut bundle = vec![];
+    for module in pop_arg!(args, Vec<Value>) {
+        let byte_codes = module.value_as::<Vec<u8>>()?;
+        cost += per_byte * NumBytes::new(byte_codes.len() as u64);
+        bundle.push(byte_codes);
+    }
+    cost += per_byte * NumBytes::new((metadata_bytes.len() + old_metadata_bytes.len()) as u64);
+
+    let (metadata, old_metadata) = match (
+        bcs::from_bytes::<PackageMetadata>(&metadata_bytes),
+        bcs::from_bytes::<Option<PackageMetadata>>(&old_metadata_bytes),
+    ) {
+        (Ok(metadata), Ok(old_metadata)) => (metadata, old_metadata),
+        _ => return Ok(NativeResult::err(cost, E_MODULE_VERIFICATION_ERROR)),
+    };
+    let mut compiled_modules = vec![];
+    for module in bundle.iter() {
+        match CompiledModule::deserialize(module) {
+            Ok(compiled_module) => compiled_modules.push(compiled_module),
+            Err(_) => return Ok(NativeResult::err(cost, E_MODULE_VERIFICATION_ERROR)),
+        }
+    }
+
+    match verify_package_metadata(
+        &compiled_modules,
+        package_id,
+        &metadata,
+        old_metadata.as_ref(),
+    ) {
+        Ok(_) => Ok(NativeResult::ok(cost, smallvec![])),
+        Err(e) => {
+            tracing::info!("package metadata verification failed {:?}", e);
+            Ok(NativeResult::err(
+                cost,
+                e.sub_status().unwrap_or(E_MODULE_VERIFICATION_ERROR),
+            ))
+        }
+    }
+}
+
 ////////////////////// Native functions for replace identifiers //////////////////////
 
 #[derive(Debug, Clone)]
@@ -780,6 +871,7 @@ pub struct GasParameters {
     pub sort_and_verify_modules_inner: VerifyModulesGasParameters,
     pub request_init_functions: RequestInitFunctionsGasParameters,
     pub check_compatibililty_inner: CheckCompatibilityInnerGasParameters,
+    pub verify_package_metadata_inner: VerifyPackageMetadataGasParameters,
     pub replace_address_identifiers: ModuleModifyGasParameters,
     pub replace_addresses_constant: ModuleModifyGasParameters,
     pub replace_identifiers: ModuleModifyGasParameters,
@@ -813,6 +905,7 @@ impl GasParameters {
                 base: 0.into(),
                 per_byte: 0.into(),
             },
+            verify_package_metadata_inner: VerifyPackageMetadataGasParameters::zeros(),
             replace_address_identifiers: ModuleModifyGasParameters {
                 base: 0.into(),
                 per_byte: 0.into(),
@@ -873,6 +966,13 @@ pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, Nati
                 check_compatibililty_inner,
             ),
         ),
+        (
+            "verify_package_metadata_inner",
+            make_native(
+                gas_params.verify_package_metadata_inner,
+                verify_package_metadata_inner,
+            ),
+        ),
         (
             "replace_address_identifiers",
             make_native(
@@ -0,0 +1,635 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{Result, bail, ensure};
+use ark_bn254::{Bn254, Fq, Fq2, Fr, G1Affine, G1Projective, G2Affine};
+use ark_ec::{AffineRepr, CurveGroup, pairing::Pairing};
+use ark_ff::{BigInt, Field, One, PrimeField, Zero};
+use fastcrypto::hash::{HashFunction, Keccak256};
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::gas_algebra::{InternalGas, InternalGasPerByte, NumBytes};
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction};
+use move_vm_types::{
+    loaded_data::runtime_types::Type,
+    natives::function::NativeResult,
+    pop_arg,
+    values::{self, Value, VectorRef},
+};
+use smallvec::smallvec;
+use std::collections::VecDeque;
+
+use crate::natives::helpers::{make_module_natives, make_native};
+
+pub const E_INVALID_CURVE: u64 = 0;
+pub const E_INVALID_VERIFYING_KEY: u64 = 1;
+pub const E_TOO_MANY_PUBLIC_INPUTS: u64 = 2;
+
+// These must match the corresponding values in moveos_std::plonk::Curve.
+pub const BN254: u8 = 1;
+
+// We need to set an upper bound on the number of public inputs to avoid a DoS attack
+pub const MAX_PUBLIC_INPUTS: usize = 16;
+
+pub const SCALAR_SIZE: usize = 32;
+pub const G1_SIZE: usize = 64;
+pub const G2_SIZE: usize = 128;
+
+// n, nPublic, k1, k2 and the n-th root of unity w
+const DOMAIN_SIZE: usize = 5 * SCALAR_SIZE;
+// Qm, Ql, Qr, Qo, Qc, S1, S2, S3
+const COMMITMENTS_SIZE: usize = 8 * G1_SIZE;
+pub const VERIFYING_KEY_SIZE: usize = DOMAIN_SIZE + COMMITMENTS_SIZE + G2_SIZE;
+// A, B, C, Z, T1, T2, T3, Wxi, Wxiw and the evaluations a, b, c, s1, s2, zw
+pub const PROOF_SIZE: usize = 9 * G1_SIZE + 6 * SCALAR_SIZE;
+
+// The two-adicity of the BN254 scalar field
+const MAX_DOMAIN_SIZE_LOG: u32 = 28;
+
+/***************************************************************************************************
+ * BN254 encoding, compatible with the snarkjs generated Solidity verifier and the EVM precompiles:
+ * scalars and base field elements are 32 bytes big endian, a G1 point is `x || y` and the point at
+ * infinity is all zeros, a G2 point is `x.c1 || x.c0 || y.c1 || y.c0`.
+ **************************************************************************************************/
+
+fn read_field<F: PrimeField<BigInt = BigInt<4>>>(bytes: &[u8]) -> Result<F> {
+    ensure!(bytes.len() == SCALAR_SIZE, "Invalid field element length");
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let start = SCALAR_SIZE - 8 * (i + 1);
+        *limb = u64::from_be_bytes(bytes[start..start + 8].try_into()?);
+    }
+    match F::from_bigint(BigInt::new(limbs)) {
+        Some(f) => Ok(f),
+        None => bail!("Field element is not canonical"),
+    }
+}
+
+fn write_field<F: PrimeField<BigInt = BigInt<4>>>(f: &F, out: &mut Vec<u8>) {
+    for limb in f.into_bigint().0.iter().rev() {
+        out.extend_from_slice(&limb.to_be_bytes());
+    }
+}
+
+fn read_u64(bytes: &[u8]) -> Result<u64> {
+    ensure!(
+        bytes[..SCALAR_SIZE - 8].iter().all(|b| *b == 0),
+        "Integer overflows u64"
+    );
+    Ok(u64::from_be_bytes(bytes[SCALAR_SIZE - 8..].try_into()?))
+}
+
+fn read_g1(bytes: &[u8]) -> Result<G1Affine> {
+    ensure!(bytes.len() == G1_SIZE, "Invalid G1 point length");
+    if bytes.iter().all(|b| *b == 0) {
+        return Ok(G1Affine::identity());
+    }
+    let x = read_field::<Fq>(&bytes[..32])?;
+    let y = read_field::<Fq>(&bytes[32..])?;
+    let point = G1Affine::new_unchecked(x, y);
+    ensure!(point.is_on_curve(), "G1 point is not on curve");
+    Ok(point)
+}
+
+fn write_g1(point: &G1Affine, out: &mut Vec<u8>) {
+    match point.xy() {
+        Some((x, y)) => {
+            write_field(x, out);
+            write_field(y, out);
+        }
+        None => out.extend_from_slice(&[0u8; G1_SIZE]),
+    }
+}
+
+fn read_g2(bytes: &[u8]) -> Result<G2Affine> {
+    ensure!(bytes.len() == G2_SIZE, "Invalid G2 point length");
+    let x = Fq2::new(
+        read_field::<Fq>(&bytes[32..64])?,
+        read_field::<Fq>(&bytes[..32])?,
+    );
+    let y = Fq2::new(
+        read_field::<Fq>(&bytes[96..])?,
+        read_field::<Fq>(&bytes[64..96])?,
+    );
+    let point = G2Affine::new_unchecked(x, y);
+    ensure!(
+        point.is_on_curve() && point.is_in_correct_subgroup_assuming_on_curve(),
+        "Invalid G2 point"
+    );
+    Ok(point)
+}
+
+/// The snarkjs PLONK verifying key with the `[x]_2` of the KZG setup.
+struct VerifyingKey {
+    n: u64,
+    n_public: usize,
+    k1: Fr,
+    k2: Fr,
+    w: Fr,
+    qm: G1Affine,
+    ql: G1Affine,
+    qr: G1Affine,
+    qo: G1Affine,
+    qc: G1Affine,
+    s1: G1Affine,
+    s2: G1Affine,
+    s3: G1Affine,
+    x_2: G2Affine,
+}
+
+impl VerifyingKey {
+    fn from_parts(domain: &[u8], commitments: &[u8], x_2: &[u8]) -> Result<Self> {
+        ensure!(domain.len() == DOMAIN_SIZE, "Invalid domain length");
+        ensure!(
+            commitments.len() == COMMITMENTS_SIZE,
+            "Invalid commitments length"
+        );
+        let mut scalars = domain.chunks(SCALAR_SIZE);
+        let mut next_scalar = || scalars.next().expect("domain length is checked");
+        let n = read_u64(next_scalar())?;
+        let n_public = read_u64(next_scalar())?;
+        let k1 = read_field::<Fr>(next_scalar())?;
+        let k2 = read_field::<Fr>(next_scalar())?;
+        let w = read_field::<Fr>(next_scalar())?;
+        ensure!(
+            n.is_power_of_two() && n.trailing_zeros() <= MAX_DOMAIN_SIZE_LOG,
+            "Invalid domain size"
+        );
+        ensure!(
+            n_public <= MAX_PUBLIC_INPUTS as u64 && n_public <= n,
+            "Too many public inputs"
+        );
+        ensure!(w.pow([n]).is_one(), "Invalid root of unity");
+        ensure!(
+            n == 1 || !w.pow([n / 2]).is_one(),
+            "Root of unity is not primitive"
+        );
+
+        let mut points = commitments.chunks(G1_SIZE);
+        let mut next_point = || read_g1(points.next().expect("commitments length is checked"));
+        Ok(Self {
+            n,
+            n_public: n_public as usize,
+            k1,
+            k2,
+            w,
+            qm: next_point()?,
+            ql: next_point()?,
+            qr: next_point()?,
+            qo: next_point()?,
+            qc: next_point()?,
+            s1: next_point()?,
+            s2: next_point()?,
+            s3: next_point()?,
+            x_2: read_g2(x_2)?,
+        })
+    }
+}
+
+/// Validate the verifying key and split it into the domain, the commitments and `[x]_2`.
+fn prepare_verifying_key(verifying_key: &[u8]) -> Result<[Vec<u8>; 3]> {
+    ensure!(
+        verifying_key.len() == VERIFYING_KEY_SIZE,
+        "Invalid verifying key length"
+    );
+    let (domain, rest) = verifying_key.split_at(DOMAIN_SIZE);
+    let (commitments, x_2) = rest.split_at(COMMITMENTS_SIZE);
+    VerifyingKey::from_parts(domain, commitments, x_2)?;
+    Ok([domain.to_vec(), commitments.to_vec(), x_2.to_vec()])
+}
+
+struct Proof {
+    a: G1Affine,
+    b: G1Affine,
+    c: G1Affine,
+    z: G1Affine,
+    t1: G1Affine,
+    t2: G1Affine,
+    t3: G1Affine,
+    wxi: G1Affine,
+    wxiw: G1Affine,
+    eval_a: Fr,
+    eval_b: Fr,
+    eval_c: Fr,
+    eval_s1: Fr,
+    eval_s2: Fr,
+    eval_zw: Fr,
+}
+
+impl Proof {
+    fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(bytes.len() == PROOF_SIZE, "Invalid proof length");
+        let (points, evals) = bytes.split_at(9 * G1_SIZE);
+        let mut points = points.chunks(G1_SIZE);
+        let mut next_point = || read_g1(points.next().expect("proof length is checked"));
+        let mut evals = evals.chunks(SCALAR_SIZE);
+        let mut next_eval = || read_field::<Fr>(evals.next().expect("proof length is checked"));
+        Ok(Self {
+            a: next_point()?,
+            b: next_point()?,
+            c: next_point()?,
+            z: next_point()?,
+            t1: next_point()?,
+            t2: next_point()?,
+            t3: next_point()?,
+            wxi: next_point()?,
+            wxiw: next_point()?,
+            eval_a: next_eval()?,
+            eval_b: next_eval()?,
+            eval_c: next_eval()?,
+            eval_s1: next_eval()?,
+            eval_s2: next_eval()?,
+            eval_zw: next_eval()?,
+        })
+    }
+}
+
+/// The Keccak256 Fiat-Shamir transcript of snarkjs, it is reset after every challenge.
+#[derive(Default)]
+struct Transcript {
+    data: Vec<u8>,
+}
+
+impl Transcript {
+    fn add_point(&mut self, point: &G1Affine) {
+        write_g1(point, &mut self.data);
+    }
+
+    fn add_scalar(&mut self, scalar: &Fr) {
+        write_field(scalar, &mut self.data);
+    }
+
+    fn challenge(&mut self) -> Fr {
+        let hash = Keccak256::digest(&self.data);
+        self.data.clear();
+        Fr::from_be_bytes_mod_order(&hash.digest)
+    }
+}
+
+/// Verify a BN254 PLONK proof of snarkjs, return `Ok(false)` if the proof does not verify.
+fn verify_plonk_proof(vk: &VerifyingKey, public_inputs: &[u8], proof: &[u8]) -> Result<bool> {
+    ensure!(
+        public_inputs.len() % SCALAR_SIZE == 0,
+        "Invalid public inputs length"
+    );
+    let public_inputs = public_inputs
+        .chunks(SCALAR_SIZE)
+        .map(read_field::<Fr>)
+        .collect::<Result<Vec<_>>>()?;
+    if public_inputs.len() != vk.n_public {
+        return Ok(false);
+    }
+    let proof = Proof::from_bytes(proof)?;
+
+    let mut transcript = Transcript::default();
+    for point in [
+        &vk.qm, &vk.ql, &vk.qr, &vk.qo, &vk.qc, &vk.s1, &vk.s2, &vk.s3,
+    ] {
+        transcript.add_point(point);
+    }
+    for input in &public_inputs {
+        transcript.add_scalar(input);
+    }
+    for point in [&proof.a, &proof.b, &proof.c] {
+        transcript.add_point(point);
+    }
+    let beta = transcript.challenge();
+
+    transcript.add_scalar(&beta);
+    let gamma = transcript.challenge();
+
+    transcript.add_scalar(&beta);
+    transcript.add_scalar(&gamma);
+    transcript.add_point(&proof.z);
+    let alpha = transcript.challenge();
+
+    transcript.add_scalar(&alpha);
+    for point in [&proof.t1, &proof.t2, &proof.t3] {
+        transcript.add_point(point);
+    }
+    let xi = transcript.challenge();
+
+    transcript.add_scalar(&xi);
+    for eval in [
+        &proof.eval_a,
+        &proof.eval_b,
+        &proof.eval_c,
+        &proof.eval_s1,
+        &proof.eval_s2,
+        &proof.eval_zw,
+    ] {
+        transcript.add_scalar(eval);
+    }
+    let v1 = transcript.challenge();
+    let v2 = v1 * v1;
+    let v3 = v2 * v1;
+    let v4 = v3 * v1;
+    let v5 = v4 * v1;
+
+    transcript.add_point(&proof.wxi);
+    transcript.add_point(&proof.wxiw);
+    let u = transcript.challenge();
+
+    // The vanishing polynomial and the Lagrange polynomials of the public input rows at xi
+    let xin = xi.pow([vk.n]);
+    let zh = xin - Fr::one();
+    let n = Fr::from(vk.n);
+    let mut lagrange = Vec::with_capacity(vk.n_public.max(1));
+    let mut w = Fr::one();
+    for _ in 0..vk.n_public.max(1) {
+        let Some(denominator) = (n * (xi - w)).inverse() else {
+            return Ok(false);
+        };
+        lagrange.push(w * zh * denominator);
+        w *= vk.w;
+    }
+    let pi = public_inputs
+        .iter()
+        .zip(lagrange.iter())
+        .fold(Fr::zero(), |acc, (input, l)| acc - *input * l);
+    let l1 = lagrange[0];
+
+    let alpha2 = alpha * alpha;
+    let perm_a = proof.eval_a + beta * proof.eval_s1 + gamma;
+    let perm_b = proof.eval_b + beta * proof.eval_s2 + gamma;
+    let r0 = pi - l1 * alpha2 - perm_a * perm_b * (proof.eval_c + gamma) * proof.eval_zw * alpha;
+
+    // The commitment of the linearisation polynomial
+    let beta_xi = beta * xi;
+    let d2a = (proof.eval_a + beta_xi + gamma)
+        * (proof.eval_b + beta_xi * vk.k1 + gamma)
+        * (proof.eval_c + beta_xi * vk.k2 + gamma)
+        * alpha;
+    let d3 = perm_a * perm_b * alpha * beta * proof.eval_zw;
+    let d: G1Projective = vk.qm * (proof.eval_a * proof.eval_b)
+        + vk.ql * proof.eval_a
+        + vk.qr * proof.eval_b
+        + vk.qo * proof.eval_c
+        + vk.qc
+        + proof.z * (d2a + l1 * alpha2 + u)
+        - vk.s3 * d3
+        - (G1Projective::from(proof.t1) + proof.t2 * xin + proof.t3 * (xin * xin)) * zh;
+
+    let f = d + proof.a * v1 + proof.b * v2 + proof.c * v3 + vk.s1 * v4 + vk.s2 * v5;
+    let e = G1Affine::generator()
+        * (-r0
+            + v1 * proof.eval_a
+            + v2 * proof.eval_b
+            + v3 * proof.eval_c
+            + v4 * proof.eval_s1
+            + v5 * proof.eval_s2
+            + u * proof.eval_zw);
+
+    let a1 = G1Projective::from(proof.wxi) + proof.wxiw * u;
+    let b1 = proof.wxi * xi + proof.wxiw * (u * xi * vk.w) + f - e;
+
+    // e(-A1, [x]_2) * e(B1, [1]_2) == 1
+    let result = Bn254::multi_pairing(
+        [(-a1).into_affine(), b1.into_affine()],
+        [vk.x_2, G2Affine::generator()],
+    );
+    Ok(result.is_zero())
+}
+
+/// Verify the KZG opening proof of `p(z) = y` for the commitment `[p(x)]_1`.
+fn verify_kzg_opening(
+    x_2: &[u8],
+    commitment: &[u8],
+    z: &[u8],
+    y: &[u8],
+    proof: &[u8],
+) -> Result<bool> {
+    let x_2 = read_g2(x_2)?;
+    let commitment = read_g1(commitment)?;
+    let z = read_field::<Fr>(z)?;
+    let y = read_field::<Fr>(y)?;
+    let proof = read_g1(proof)?;
+
+    // e(C - [y]_1 + z * pi, [1]_2) == e(pi, [x]_2)
+    let lhs = G1Projective::from(commitment) - G1Affine::generator() * y + proof * z;
+    let result = Bn254::multi_pairing(
+        [lhs.into_affine(), (-proof).into()],
+        [G2Affine::generator(), x_2],
+    );
+    Ok(result.is_zero())
+}
+
+/***************************************************************************************************
+ * native fun prepare_verifying_key_internal
+ * Implementation of the Move native function `prepare_verifying_key_internal(curve: u8, verifying_key: &vector<u8>): PreparedVerifyingKey`
+ *   gas cost: plonk_prepare_verifying_key_internal_cost_base            | covers various fixed costs in the oper
+ *              + plonk_prepare_verifying_key_internal_cost_per_byte
+ *                                                   * size_of(verifying_key) | covers the cost of validating the key
+ **************************************************************************************************/
+pub fn native_prepare_verifying_key_internal(
+    gas_params: &PlonkGasParameters,
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 2);
+
+    let verifying_key = pop_arg!(args, VectorRef);
+    let curve = pop_arg!(args, u8);
+
+    let verifying_key_bytes_ref = verifying_key.as_bytes_ref();
+
+    let cost = gas_params.base.unwrap_or_else(InternalGas::zero)
+        + gas_params.per_byte.unwrap_or_else(InternalGasPerByte::zero)
+            * NumBytes::new(verifying_key_bytes_ref.len() as u64);
+
+    if curve != BN254 {
+        return Ok(NativeResult::err(cost, E_INVALID_CURVE));
+    }
+
+    match prepare_verifying_key(&verifying_key_bytes_ref) {
+        Ok([domain, commitments, x_2]) => Ok(NativeResult::ok(
+            cost,
+            smallvec![Value::struct_(values::Struct::pack(vec![
+                Value::vector_u8(domain),
+                Value::vector_u8(commitments),
+                Value::vector_u8(x_2),
+            ]))],
+        )),
+        Err(_) => Ok(NativeResult::err(cost, E_INVALID_VERIFYING_KEY)),
+    }
+}
+
+/***************************************************************************************************
+ * native fun verify_plonk_proof_internal
+ * Implementation of the Move native function `verify_plonk_proof_internal(curve: u8, domain_bytes: &vector<u8>,
+ *                          commitments_bytes: &vector<u8>, x_2_bytes: &vector<u8>,
+ *                          public_proof_inputs: &vector<u8>, proof_bytes: &vector<u8>): bool`
+ *   gas cost: plonk_verify_plonk_proof_internal_cost_base                | covers various fixed costs in the oper
+ *              + plonk_verify_plonk_proof_internal_cost_per_byte
+ *                                                   * size_of(public_proof_inputs) | covers the cost of the public inputs
+ * Note: every other arg is fixed size, so their costs are included in the base cost.
+ **************************************************************************************************/
+pub fn native_verify_plonk_proof_internal(
+    gas_params: &PlonkGasParameters,
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 6);
+
+    let proof_bytes = pop_arg!(args, VectorRef);
+    let public_proof_inputs = pop_arg!(args, VectorRef);
+    let x_2 = pop_arg!(args, VectorRef);
+    let commitments = pop_arg!(args, VectorRef);
+    let domain = pop_arg!(args, VectorRef);
+    let curve = pop_arg!(args, u8);
+
+    let proof_bytes_ref = proof_bytes.as_bytes_ref();
+    let public_proof_inputs_bytes_ref = public_proof_inputs.as_bytes_ref();
+    let x_2_bytes_ref = x_2.as_bytes_ref();
+    let commitments_bytes_ref = commitments.as_bytes_ref();
+    let domain_bytes_ref = domain.as_bytes_ref();
+
+    let cost = gas_params.base.unwrap_or_else(InternalGas::zero)
+        + gas_params.per_byte.unwrap_or_else(InternalGasPerByte::zero)
+            * NumBytes::new(public_proof_inputs_bytes_ref.len() as u64);
+
+    if curve != BN254 {
+        return Ok(NativeResult::err(cost, E_INVALID_CURVE));
+    }
+    if public_proof_inputs_bytes_ref.len() > SCALAR_SIZE * MAX_PUBLIC_INPUTS {
+        return Ok(NativeResult::err(cost, E_TOO_MANY_PUBLIC_INPUTS));
+    }
+
+    let Ok(vk) =
+        VerifyingKey::from_parts(&domain_bytes_ref, &commitments_bytes_ref, &x_2_bytes_ref)
+    else {
+        return Ok(NativeResult::err(cost, E_INVALID_VERIFYING_KEY));
+    };
+
+    let result = verify_plonk_proof(&vk, &public_proof_inputs_bytes_ref, &proof_bytes_ref);
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(result.unwrap_or(false))],
+    ))
+}
+
+/***************************************************************************************************
+ * native fun verify_kzg_opening_internal
+ * Implementation of the Move native function `verify_kzg_opening_internal(curve: u8, x_2_bytes: &vector<u8>,
+ *                          commitment: &vector<u8>, z: &vector<u8>, y: &vector<u8>, proof: &vector<u8>): bool`
+ *   gas cost: plonk_verify_kzg_opening_internal_cost_base                | covers various fixed costs in the oper
+ * Note: every arg is fixed size, so their costs are included in the base cost.
+ **************************************************************************************************/
+pub fn native_verify_kzg_opening_internal(
+    gas_params: &PlonkGasParameters,
+    _context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(args.len() == 6);
+
+    let proof = pop_arg!(args, VectorRef);
+    let y = pop_arg!(args, VectorRef);
+    let z = pop_arg!(args, VectorRef);
+    let commitment = pop_arg!(args, VectorRef);
+    let x_2 = pop_arg!(args, VectorRef);
+    let curve = pop_arg!(args, u8);
+
+    let cost = gas_params.base.unwrap_or_else(InternalGas::zero);
+
+    if curve != BN254 {
+        return Ok(NativeResult::err(cost, E_INVALID_CURVE));
+    }
+
+    let result = verify_kzg_opening(
+        &x_2.as_bytes_ref(),
+        &commitment.as_bytes_ref(),
+        &z.as_bytes_ref(),
+        &y.as_bytes_ref(),
+        &proof.as_bytes_ref(),
+    );
+
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(result.unwrap_or(false))],
+    ))
+}
+
+#[derive(Debug, Clone)]
+pub struct PlonkGasParameters {
+    pub base: Option<InternalGas>,
+    pub per_byte: Option<InternalGasPerByte>,
+}
+
+impl PlonkGasParameters {
+    pub fn zeros() -> Self {
+        Self {
+            base: None,
+            per_byte: None,
+        }
+    }
+
+    pub fn init(base: InternalGas, per_byte: InternalGasPerByte) -> Self {
+        Self {
+            base: Some(base),
+            per_byte: Some(per_byte),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.base.is_none() || self.per_byte.is_none()
+    }
+}
+
+/***************************************************************************************************
+ * module
+ **************************************************************************************************/
+
+#[derive(Debug, Clone)]
+pub struct GasParameters {
+    pub prepare_verifying_key_internal: PlonkGasParameters,
+    pub verify_plonk_proof_internal: PlonkGasParameters,
+    pub verify_kzg_opening_internal: PlonkGasParameters,
+}
+
+impl GasParameters {
+    pub fn zeros() -> Self {
+        Self {
+            prepare_verifying_key_internal: PlonkGasParameters::zeros(),
+            verify_plonk_proof_internal: PlonkGasParameters::zeros(),
+            verify_kzg_opening_internal: PlonkGasParameters::zeros(),
+        }
+    }
+}
+
+pub fn make_all(gas_params: GasParameters) -> impl Iterator<Item = (String, NativeFunction)> {
+    let mut natives = vec![];
+    if !gas_params.prepare_verifying_key_internal.is_empty() {
+        natives.push((
+            "prepare_verifying_key_internal",
+            make_native(
+                gas_params.prepare_verifying_key_internal,
+                native_prepare_verifying_key_internal,
+            ),
+        ));
+    }
+    if !gas_params.verify_plonk_proof_internal.is_empty() {
+        natives.push((
+            "verify_plonk_proof_internal",
+            make_native(
+                gas_params.verify_plonk_proof_internal,
+                native_verify_plonk_proof_internal,
+            ),
+        ));
+    }
+    if !gas_params.verify_kzg_opening_internal.is_empty() {
+        natives.push((
+            "verify_kzg_opening_internal",
+            make_native(
+                gas_params.verify_kzg_opening_internal,
+                native_verify_kzg_opening_internal,
+            ),
+        ));
+    }
+    make_module_natives(natives)
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::log::{CallFrame, ExecutionAndIOCosts, ExecutionGasEvent, FrameName, TransactionGasLog};
+use anyhow::{Result, bail};
 use move_binary_format::file_format::CodeOffset;
 use move_binary_format::file_format_common::Opcodes;
 use move_core_types::account_address::AccountAddress;
@@ -514,15 +515,27 @@ impl<G: GasMeter> SwitchableGasMeter for GasProfiler<G> {
     }
 }
 
-pub fn new_gas_profiler<G>(action: MoveAction, base_gas_meter: G) -> GasProfiler<G> {
+pub fn new_gas_profiler<G>(action: MoveAction, base_gas_meter: G) -> Result<GasProfiler<G>> {
     match action {
-        MoveAction::Script(_) => unreachable!("Script payload is not supported yet"),
-        MoveAction::Function(call) => GasProfiler::new_function(
+        MoveAction::Script(_) => bail!("Script payload is not supported yet"),
+        MoveAction::Function(call) => Ok(GasProfiler::new_function(
             base_gas_meter,
             call.function_id.module_id,
             call.function_id.function_name,
             call.ty_args,
-        ),
-        MoveAction::ModuleBundle(_) => unreachable!("ModuleBundle payload is not supported yet"),
+        )),
+        MoveAction::ModuleBundle(_) => bail!("ModuleBundle payload is not supported yet"),
+        // The batch calls are executed in one session, name the root frame with the first call.
+        MoveAction::Batch(calls) => {
+            let Some(call) = calls.into_iter().next() else {
+                bail!("Batch payload should not be empty");
+            };
+            Ok(GasProfiler::new_function(
+                base_gas_meter,
+                call.function_id.module_id,
+                call.function_id.function_name,
+                call.ty_args,
+            ))
+        }
     }
 }
@@ -457,6 +457,23 @@ impl<'r> ObjectRuntime<'r> {
         Ok(())
     }
 
+    /// Put back the `Object<T>` pointer returned from Move to the host by a function call,
+    /// so the object can be passed to the next function call as an argument.
+    pub fn return_object_pointer(
+        &mut self,
+        layout_loader: &dyn TypeLayoutLoader,
+        object_id: &ObjectID,
+    ) -> PartialVMResult<()> {
+        let (rt_obj, _) = self.load_object(layout_loader, object_id)?;
+        if rt_obj.is_none() || rt_obj.pointer.value.exists()? {
+            return Err(partial_extension_error(format!(
+                "The returned object {} is not taken out",
+                object_id
+            )));
+        }
+        rt_obj.pointer.init(object_id.clone())
+    }
+
     /// We need to release the Object pointer reference after executing the user function
     /// Because the system post function maybe need to access the object
     pub fn release_arguments(&mut self) -> PartialVMResult<()> {
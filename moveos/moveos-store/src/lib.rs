@@ -7,7 +7,7 @@
 use crate::config_store::{ConfigDBStore, ConfigStore, STARTUP_INFO_KEY};
 use crate::event_store::{EventDBStore, EventStore};
 use crate::state_store::statedb::StateDBStore;
-use crate::state_store::{NodeDBStore, nodes_to_write_batch};
+use crate::state_store::{NodeDBStore, RemoteNodeReader, nodes_to_write_batch};
 use crate::transaction_store::{TransactionDBStore, TransactionStore};
 use accumulator::inmemory::InMemoryAccumulator;
 use anyhow::{Error, Result};
@@ -99,9 +99,42 @@ impl MoveOSStore {
         Self::new_with_instance(instance, registry)
     }
 
+    /// Create a store in the `db_path` which forks the state from the `remote`.
+    pub fn new_fork(db_path: &Path, registry: &Registry, remote: RemoteNodeReader) -> Result<Self> {
+        let db_metrics = DBMetrics::get_or_init(registry).clone();
+        let instance = StoreInstance::new_db_instance(
+            RocksDB::new(
+                db_path,
+                StoreMeta::get_column_family_names().to_vec(),
+                RocksdbConfig::default(),
+            )?,
+            db_metrics,
+        );
+        Self::new_fork_with_instance(instance, registry, remote)
+    }
+
     pub fn new_with_instance(instance: StoreInstance, registry: &Registry) -> Result<Self> {
-        let store_config = MoveOSStoreConfig::default();
         let node_store = NodeDBStore::new(instance.clone());
+        Self::new_with_node_store(node_store, instance, registry)
+    }
+
+    /// Create a store which reads the state nodes missing in the local store from the `remote`,
+    /// it is used to run a node on top of the state of another chain.
+    pub fn new_fork_with_instance(
+        instance: StoreInstance,
+        registry: &Registry,
+        remote: RemoteNodeReader,
+    ) -> Result<Self> {
+        let node_store = NodeDBStore::new_with_remote(instance.clone(), remote);
+        Self::new_with_node_store(node_store, instance, registry)
+    }
+
+    fn new_with_node_store(
+        node_store: NodeDBStore,
+        instance: StoreInstance,
+        registry: &Registry,
+    ) -> Result<Self> {
+        let store_config = MoveOSStoreConfig::default();
         let state_store =
             StateDBStore::new(node_store.clone(), registry, store_config.state_cache_size);
 
@@ -5,17 +5,65 @@ pub mod metrics;
 pub mod statedb;
 
 use crate::STATE_NODE_COLUMN_FAMILY_NAME;
-use anyhow::Result;
+use anyhow::{Result, ensure};
 use moveos_types::h256::H256;
-use raw_store::CodecKVStore;
 use raw_store::rocks::batch::WriteBatch;
-use raw_store::{WriteOp, derive_store};
+use raw_store::{
+    CodecKVStore, ColumnFamily, ColumnFamilyName, InnerStore, SchemaStore, StoreInstance, WriteOp,
+};
 use smt::{NodeReader, NodeWriter};
 use std::collections::BTreeMap;
+use std::sync::Arc;
 
-derive_store!(NodeDBStore, H256, Vec<u8>, STATE_NODE_COLUMN_FAMILY_NAME);
+/// The reader of the state nodes which are missing in the local store.
+/// In fork mode, it reads the nodes of the forked state tree from the remote node.
+pub type RemoteNodeReader = Arc<dyn NodeReader + Send + Sync>;
+
+#[derive(Clone)]
+pub struct NodeDBStore {
+    store: InnerStore<Self>,
+    remote: Option<RemoteNodeReader>,
+}
+
+impl ColumnFamily for NodeDBStore {
+    type Key = H256;
+    type Value = Vec<u8>;
+
+    fn name() -> ColumnFamilyName {
+        STATE_NODE_COLUMN_FAMILY_NAME
+    }
+}
+
+impl SchemaStore for NodeDBStore {
+    fn get_store(&self) -> &InnerStore<Self> {
+        &self.store
+    }
+}
 
 impl NodeDBStore {
+    pub fn new(instance: StoreInstance) -> Self {
+        Self {
+            store: InnerStore::new(instance),
+            remote: None,
+        }
+    }
+
+    /// Read the nodes missing in the local store from the `remote`, and cache them to the local store.
+    pub fn new_with_remote(instance: StoreInstance, remote: RemoteNodeReader) -> Self {
+        Self {
+            store: InnerStore::new(instance),
+            remote: Some(remote),
+        }
+    }
+
+    /// Get the nodes from the local store only, the missing nodes are not read from the remote.
+    pub fn multi_get_local(&self, hashes: &[H256]) -> Result<Vec<Option<Vec<u8>>>> {
+        hashes
+            .iter()
+            .map(|hash| self.get_raw(hash.as_bytes()))
+            .collect()
+    }
+
     pub fn put(&self, key: H256, node: Vec<u8>) -> Result<()> {
         self.put_raw(key.as_bytes().to_vec(), node)
     }
@@ -42,7 +90,26 @@ pub fn nodes_to_write_batch(nodes: BTreeMap<H256, Vec<u8>>) -> WriteBatch {
 
 impl NodeReader for NodeDBStore {
     fn get(&self, hash: &H256) -> Result<Option<Vec<u8>>> {
-        self.get_raw(hash.as_bytes())
+        let node = self.get_raw(hash.as_bytes())?;
+        match (node, &self.remote) {
+            (None, Some(remote)) => {
+                let node = remote.get(hash)?;
+                // The node is content addressed, so it is safe to cache it to the local store
+                // after its hash is checked.
+                if let Some(node) = &node {
+                    let node_hash = smt::decode_node_hash(node)?;
+                    ensure!(
+                        node_hash == *hash,
+                        "The hash {:?} of the remote node mismatches the requested hash {:?}",
+                        node_hash,
+                        hash
+                    );
+                    self.put(*hash, node.clone())?;
+                }
+                Ok(node)
+            }
+            (node, _) => Ok(node),
+        }
     }
 }
 
@@ -1,15 +1,18 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::state_store::RemoteNodeReader;
 use crate::{MoveOSStore, StoreMeta};
 use anyhow::Result;
 use moveos_config::store_config::RocksdbConfig;
 use moveos_types::h256::H256;
+use moveos_types::state_resolver::StatelessResolver;
 use moveos_types::test_utils::random_state_change_set;
 use raw_store::StoreInstance;
 use raw_store::metrics::DBMetrics;
 use raw_store::rocks::RocksDB;
 use smt::NodeReader;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn test_reopen() -> Result<()> {
@@ -67,6 +70,65 @@ async fn test_statedb_state_root() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_fork_state_store() -> Result<()> {
+    let (remote_store, _remote_dir) =
+        MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
+    let mut change_set = random_state_change_set();
+    remote_store
+        .get_state_store()
+        .apply_change_set(&mut change_set)?;
+    let fork_root = change_set.state_root;
+
+    let temp_dir = moveos_config::temp_dir();
+    let registry = prometheus::Registry::new();
+    let store_instance = StoreInstance::new_db_instance(
+        RocksDB::new(
+            temp_dir.path(),
+            StoreMeta::get_column_family_names().to_vec(),
+            RocksdbConfig::default(),
+        )?,
+        DBMetrics::get_or_init(&registry).clone(),
+    );
+    let remote: RemoteNodeReader = Arc::new(remote_store.get_state_node_store().clone());
+    let fork_store = MoveOSStore::new_fork_with_instance(store_instance, &registry, remote)?;
+
+    // The states of the fork root are read from the remote and cached locally
+    let remote_states =
+        remote_store
+            .get_state_store()
+            .list_fields_at(fork_root, None, usize::MAX)?;
+    let fork_states = fork_store
+        .get_state_store()
+        .list_fields_at(fork_root, None, usize::MAX)?;
+    assert_eq!(remote_states, fork_states);
+    assert!(
+        fork_store
+            .get_state_node_store()
+            .multi_get_local(&[fork_root])?[0]
+            .is_some()
+    );
+
+    // The local changes are applied on top of the fork root without touching the remote
+    let mut new_change_set = random_state_change_set();
+    new_change_set.state_root = fork_root;
+    let mut remote_change_set = new_change_set.clone();
+    fork_store
+        .get_state_store()
+        .apply_change_set(&mut new_change_set)?;
+    assert!(
+        remote_store
+            .get_state_node_store()
+            .multi_get_local(&[new_change_set.state_root])?[0]
+            .is_none()
+    );
+    remote_store
+        .get_state_store()
+        .apply_change_set(&mut remote_change_set)?;
+    assert_eq!(new_change_set.state_root, remote_change_set.state_root);
+    Ok(())
+}
+
 // #[tokio::test]
 // async fn test_child_state_db_dump_and_apply() -> Result<()> {
 //     let mut moveos_store = MoveOSStore::mock_moveos_store().expect("moveos store mock should succ");
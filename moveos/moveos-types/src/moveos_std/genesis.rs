@@ -3,6 +3,8 @@
 
 use crate::addresses::MOVEOS_STD_ADDRESS;
 use crate::state::{MoveStructState, MoveStructType};
+use crate::transaction::FunctionCall;
+use anyhow::Result;
 use move_core_types::{account_address::AccountAddress, ident_str, identifier::IdentStr};
 use serde::{Deserialize, Serialize};
 
@@ -33,3 +35,47 @@ impl GenesisContext {
         Self { timestamp }
     }
 }
+
+/// GenesisCalls is the function calls executed after the genesis action in the genesis session.
+/// The calls bypass the visibility check, so custom networks can set up the initial state,
+/// such as initial balances, via the friend functions of the frameworks.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GenesisCalls {
+    /// The BCS encoded `FunctionCall`s
+    pub calls: Vec<Vec<u8>>,
+}
+
+impl MoveStructType for GenesisCalls {
+    const ADDRESS: AccountAddress = MOVEOS_STD_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("GenesisCalls");
+}
+
+impl MoveStructState for GenesisCalls {
+    fn struct_layout() -> move_core_types::value::MoveStructLayout {
+        move_core_types::value::MoveStructLayout::new(vec![
+            move_core_types::value::MoveTypeLayout::Vector(Box::new(
+                move_core_types::value::MoveTypeLayout::Vector(Box::new(
+                    move_core_types::value::MoveTypeLayout::U8,
+                )),
+            )),
+        ])
+    }
+}
+
+impl GenesisCalls {
+    pub fn new(calls: &[FunctionCall]) -> Result<Self> {
+        let calls = calls
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { calls })
+    }
+
+    pub fn function_calls(&self) -> Result<Vec<FunctionCall>> {
+        self.calls
+            .iter()
+            .map(|call| bcs::from_bytes(call).map_err(Into::into))
+            .collect()
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::h256::sha3_256_of;
+use crate::move_std::option::MoveOption;
 use crate::move_std::string::MoveString;
 use crate::moveos_std::object;
 use crate::moveos_std::object::ObjectID;
@@ -9,7 +11,7 @@ use crate::{
     addresses::MOVEOS_STD_ADDRESS,
     state::{MoveStructState, MoveStructType},
 };
-use anyhow::Result;
+use anyhow::{Result, bail};
 use move_binary_format::CompiledModule;
 use move_core_types::language_storage::StructTag;
 use move_core_types::{
@@ -19,9 +21,11 @@ use move_core_types::{
     value::{MoveStructLayout, MoveTypeLayout},
 };
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
 use std::fs::File;
 use std::io::Write;
 use std::path::Path;
+use std::str::FromStr;
 
 pub const MODULE_NAME: &IdentStr = ident_str!("module_store");
 
@@ -99,6 +103,252 @@ impl MoveStructState for Package {
     }
 }
 
+///////////// UpgradeCap ////////////////
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct UpgradeCap {
+    pub package_id: AccountAddress,
+}
+
+impl UpgradeCap {
+    /// The `UpgradeCap` is a named object under the package account, the owner of it can upgrade the package.
+    pub fn upgrade_cap_id(package_id: AccountAddress) -> ObjectID {
+        object::account_named_object_id(package_id, &Self::struct_tag())
+    }
+}
+
+impl MoveStructType for UpgradeCap {
+    const ADDRESS: AccountAddress = MOVEOS_STD_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("UpgradeCap");
+}
+
+impl MoveStructState for UpgradeCap {
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout::new(vec![MoveTypeLayout::Address])
+    }
+}
+
+///////////// UpgradePolicy ////////////////
+/// The upgrade policy of a package, the policy can only be changed to a stricter one.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum UpgradePolicy {
+    /// The package can be upgraded with compatible modules.
+    #[default]
+    Compatible = 0,
+    /// The package can not be upgraded anymore.
+    Immutable = 1,
+}
+
+impl UpgradePolicy {
+    pub fn to_u8(self) -> u8 {
+        self as u8
+    }
+}
+
+impl TryFrom<u8> for UpgradePolicy {
+    type Error = anyhow::Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(Self::Compatible),
+            1 => Ok(Self::Immutable),
+            _ => bail!("Invalid upgrade policy: {}", value),
+        }
+    }
+}
+
+impl FromStr for UpgradePolicy {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "compatible" => Ok(Self::Compatible),
+            "immutable" => Ok(Self::Immutable),
+            _ => bail!(
+                "Invalid upgrade policy: {}, expected `compatible` or `immutable`",
+                s
+            ),
+        }
+    }
+}
+
+impl Display for UpgradePolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Compatible => write!(f, "compatible"),
+            Self::Immutable => write!(f, "immutable"),
+        }
+    }
+}
+
+///////////// PackageVersion ////////////////
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Default, Deserialize, Serialize, PartialOrd, Ord)]
+pub struct PackageVersion {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl PackageVersion {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl From<(u64, u64, u64)> for PackageVersion {
+    fn from((major, minor, patch): (u64, u64, u64)) -> Self {
+        Self::new(major, minor, patch)
+    }
+}
+
+impl Display for PackageVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl MoveStructType for PackageVersion {
+    const ADDRESS: AccountAddress = MOVEOS_STD_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("PackageVersion");
+}
+
+impl MoveStructState for PackageVersion {
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout::new(vec![
+            MoveTypeLayout::U64,
+            MoveTypeLayout::U64,
+            MoveTypeLayout::U64,
+        ])
+    }
+}
+
+///////////// PackageDependency ////////////////
+/// A dependency of a package, pinned to the digest of the dependency package when it is published.
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct PackageDependency {
+    pub package_id: AccountAddress,
+    /// The digest of the dependency package, empty if the dependency package has no digest.
+    pub digest: Vec<u8>,
+}
+
+impl MoveStructType for PackageDependency {
+    const ADDRESS: AccountAddress = MOVEOS_STD_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("PackageDependency");
+}
+
+impl MoveStructState for PackageDependency {
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout::new(vec![
+            MoveTypeLayout::Address,
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+        ])
+    }
+}
+
+///////////// PackageMetadata ////////////////
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct PackageMetadata {
+    pub package_name: MoveString,
+    pub version: PackageVersion,
+    /// The `UpgradePolicy` of the package
+    pub upgrade_policy: u8,
+    pub dependencies: Vec<PackageDependency>,
+}
+
+impl PackageMetadata {
+    pub fn new(
+        package_name: MoveString,
+        version: PackageVersion,
+        upgrade_policy: UpgradePolicy,
+        dependencies: Vec<PackageDependency>,
+    ) -> Self {
+        Self {
+            package_name,
+            version,
+            upgrade_policy: upgrade_policy.to_u8(),
+            dependencies,
+        }
+    }
+
+    pub fn upgrade_policy(&self) -> Result<UpgradePolicy> {
+        UpgradePolicy::try_from(self.upgrade_policy)
+    }
+}
+
+impl MoveStructType for PackageMetadata {
+    const ADDRESS: AccountAddress = MOVEOS_STD_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("PackageMetadata");
+}
+
+impl MoveStructState for PackageMetadata {
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout::new(vec![
+            MoveTypeLayout::Struct(MoveString::struct_layout()),
+            MoveTypeLayout::Struct(PackageVersion::struct_layout()),
+            MoveTypeLayout::U8,
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::Struct(
+                PackageDependency::struct_layout(),
+            ))),
+        ])
+    }
+}
+
+///////////// PackageInfo ////////////////
+/// The digest and the metadata of a published package.
+#[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize)]
+pub struct PackageInfo {
+    pub digest: Vec<u8>,
+    /// None if the package is published without metadata.
+    pub metadata: MoveOption<PackageMetadata>,
+}
+
+impl PackageInfo {
+    /// The `PackageInfo` is a named object under the package account.
+    pub fn package_info_id(package_id: AccountAddress) -> ObjectID {
+        object::account_named_object_id(package_id, &Self::struct_tag())
+    }
+
+    pub fn metadata(&self) -> Option<&PackageMetadata> {
+        self.metadata.as_ref()
+    }
+}
+
+impl MoveStructType for PackageInfo {
+    const ADDRESS: AccountAddress = MOVEOS_STD_ADDRESS;
+    const MODULE_NAME: &'static IdentStr = MODULE_NAME;
+    const STRUCT_NAME: &'static IdentStr = ident_str!("PackageInfo");
+}
+
+impl MoveStructState for PackageInfo {
+    fn struct_layout() -> MoveStructLayout {
+        MoveStructLayout::new(vec![
+            MoveTypeLayout::Vector(Box::new(MoveTypeLayout::U8)),
+            MoveTypeLayout::Struct(MoveOption::<PackageMetadata>::struct_layout()),
+        ])
+    }
+}
+
+/// The digest of a package, it does not depend on the order of the modules.
+/// It is the sha3_256 of the BCS encoded sorted sha3_256 digests of the module bytecodes,
+/// the same as `module_store::compute_package_digest` in Move.
+pub fn package_digest(modules: &[Vec<u8>]) -> Vec<u8> {
+    let mut module_digests = modules
+        .iter()
+        .map(|module| sha3_256_of(module).0.to_vec())
+        .collect::<Vec<_>>();
+    module_digests.sort();
+    let bytes = bcs::to_bytes(&module_digests).expect("Serialize module digests should succeed");
+    sha3_256_of(&bytes).0.to_vec()
+}
+
 ///////////// PackageData ////////////////
 #[derive(Eq, PartialEq, Debug, Clone, Deserialize, Serialize)]
 pub struct PackageData {
@@ -130,6 +380,10 @@ impl PackageData {
         Ok(())
     }
 
+    pub fn digest(&self) -> Vec<u8> {
+        package_digest(&self.modules)
+    }
+
     pub fn compiled_modules(&self) -> Result<Vec<CompiledModule>> {
         self.modules
             .iter()
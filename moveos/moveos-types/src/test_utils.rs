@@ -29,6 +29,8 @@ pub enum MoveActionType {
     Function,
     //Publish Move modules
     ModuleBundle,
+    //Execute Move functions in a batch
+    Batch,
 }
 
 impl MoveActionType {
@@ -37,6 +39,7 @@ impl MoveActionType {
             MoveActionType::Script => 0,
             MoveActionType::Function => 1,
             MoveActionType::ModuleBundle => 2,
+            MoveActionType::Batch => 3,
         }
     }
 }
@@ -89,6 +92,8 @@ pub fn random_move_action_with_action_type(action_type: u8) -> MoveAction {
         random_move_action_script()
     } else if MoveActionType::Function.action_type() == action_type {
         random_move_action_function()
+    } else if MoveActionType::Batch.action_type() == action_type {
+        random_move_action_batch()
     } else {
         random_move_action_module_bundle()
     }
@@ -102,6 +107,14 @@ pub fn random_move_action_function() -> MoveAction {
     MoveAction::Function(random_function_call())
 }
 
+pub fn random_move_action_batch() -> MoveAction {
+    let mut rng = thread_rng();
+    let calls = (0..rng.gen_range(1..=5))
+        .map(|_| random_function_call())
+        .collect();
+    MoveAction::Batch(calls)
+}
+
 pub fn random_move_action_module_bundle() -> MoveAction {
     let mut module_bundle = vec![];
     let mut rng = thread_rng();
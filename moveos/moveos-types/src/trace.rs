@@ -0,0 +1,126 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::move_types::FunctionId;
+use crate::moveos_std::event::TransactionEvent;
+use crate::moveos_std::object::ObjectID;
+use crate::state::{ObjectChange, StateChangeSet};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::effects::Op;
+use move_core_types::language_storage::TypeTag;
+use move_core_types::u256::U256;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+/// A Move value observed by the tracer.
+/// The VM does not expose the struct field names to the tracer, so the struct fields are positional.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TraceValue {
+    Bool(bool),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    U256(U256),
+    Address(AccountAddress),
+    /// A `vector<u8>`
+    Bytes(Vec<u8>),
+    Vector(Vec<TraceValue>),
+    Struct(Vec<TraceValue>),
+    /// A reference, `is_global` is true if it points to a global resource.
+    Reference {
+        is_global: bool,
+        value: Box<TraceValue>,
+    },
+    /// The BCS encoded argument of a transaction entry call, the tracer does not decode it.
+    Raw(Vec<u8>),
+}
+
+/// A function call in the call tree of a transaction.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CallTrace {
+    /// The called function, None if it is a script.
+    pub function: Option<FunctionId>,
+    pub ty_args: Vec<TypeTag>,
+    pub args: Vec<TraceValue>,
+    pub is_native: bool,
+    /// The return values, None if the call aborted or did not finish.
+    pub return_values: Option<Vec<TraceValue>>,
+    pub calls: Vec<CallTrace>,
+}
+
+impl CallTrace {
+    pub fn new(
+        function: Option<FunctionId>,
+        ty_args: Vec<TypeTag>,
+        args: Vec<TraceValue>,
+        is_native: bool,
+    ) -> Self {
+        Self {
+            function,
+            ty_args,
+            args,
+            is_native,
+            return_values: None,
+            calls: vec![],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ObjectWriteKind {
+    New,
+    Modify,
+    Delete,
+    /// Only the metadata or the dynamic fields of the object are changed.
+    Fields,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ObjectWriteTrace {
+    pub object_id: ObjectID,
+    pub object_type: TypeTag,
+    pub owner: AccountAddress,
+    pub kind: ObjectWriteKind,
+}
+
+/// The trace of a transaction execution, including the call tree of the transaction action,
+/// the objects read and written, and the emitted events.
+/// The calls of the system pre_execute and post_execute functions are not traced.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize, Default)]
+pub struct TxTrace {
+    pub calls: Vec<CallTrace>,
+    pub object_reads: BTreeSet<ObjectID>,
+    pub object_writes: Vec<ObjectWriteTrace>,
+    pub events: Vec<TransactionEvent>,
+}
+
+impl TxTrace {
+    /// Flatten the object changes of the change set, the parent object is before its fields.
+    pub fn object_writes_of(changeset: &StateChangeSet) -> Vec<ObjectWriteTrace> {
+        let mut writes = vec![];
+        for change in changeset.changes.values() {
+            Self::collect_object_writes(change, &mut writes);
+        }
+        writes
+    }
+
+    fn collect_object_writes(change: &ObjectChange, writes: &mut Vec<ObjectWriteTrace>) {
+        let kind = match &change.value {
+            Some(Op::New(_)) => ObjectWriteKind::New,
+            Some(Op::Modify(_)) => ObjectWriteKind::Modify,
+            Some(Op::Delete) => ObjectWriteKind::Delete,
+            None => ObjectWriteKind::Fields,
+        };
+        writes.push(ObjectWriteTrace {
+            object_id: change.metadata.id.clone(),
+            object_type: change.metadata.object_type.clone(),
+            owner: change.metadata.owner,
+            kind,
+        });
+        for field_change in change.fields.values() {
+            Self::collect_object_writes(field_change, writes);
+        }
+    }
+}
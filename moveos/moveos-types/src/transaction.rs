@@ -111,6 +111,51 @@ impl Arbitrary for FunctionCall {
     }
 }
 
+/// The argument of a batch call which refers to an `Object<T>` returned by a previous call of the same batch.
+/// It is encoded as `[TAG, call_index(u16 LE), result_index(u16 LE)]`, a 5 bytes argument can not be a valid
+/// BCS encoded `ObjectID` or any other allowed argument, so it is unambiguous.
+#[derive(Clone, Copy, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
+pub struct CallResultRef {
+    pub call_index: u16,
+    pub result_index: u16,
+}
+
+impl CallResultRef {
+    pub const TAG: u8 = 0xfe;
+    pub const ENCODED_LENGTH: usize = 5;
+
+    pub fn new(call_index: u16, result_index: u16) -> Self {
+        Self {
+            call_index,
+            result_index,
+        }
+    }
+
+    pub fn to_arg(&self) -> Vec<u8> {
+        let mut arg = Vec::with_capacity(Self::ENCODED_LENGTH);
+        arg.push(Self::TAG);
+        arg.extend_from_slice(&self.call_index.to_le_bytes());
+        arg.extend_from_slice(&self.result_index.to_le_bytes());
+        arg
+    }
+
+    pub fn from_arg(arg: &[u8]) -> Option<Self> {
+        if arg.len() != Self::ENCODED_LENGTH || arg[0] != Self::TAG {
+            return None;
+        }
+        Some(Self {
+            call_index: u16::from_le_bytes([arg[1], arg[2]]),
+            result_index: u16::from_le_bytes([arg[3], arg[4]]),
+        })
+    }
+}
+
+impl Display for CallResultRef {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "result({},{})", self.call_index, self.result_index)
+    }
+}
+
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub enum MoveAction {
@@ -120,6 +165,9 @@ pub enum MoveAction {
     Function(FunctionCall),
     //Publish Move modules
     ModuleBundle(Vec<Vec<u8>>),
+    //Execute Move functions in order atomically,
+    //the later calls can consume the objects returned by the earlier calls via `CallResultRef`
+    Batch(Vec<FunctionCall>),
 }
 
 impl MoveAction {
@@ -128,6 +176,7 @@ impl MoveAction {
             MoveAction::Script(_) => 0,
             MoveAction::Function(_) => 1,
             MoveAction::ModuleBundle(_) => 2,
+            MoveAction::Batch(_) => 3,
         }
     }
 
@@ -136,6 +185,7 @@ impl MoveAction {
             MoveAction::Script(_) => "Script".to_string(),
             MoveAction::Function(_) => "Function".to_string(),
             MoveAction::ModuleBundle(_) => "ModuleBundle".to_string(),
+            MoveAction::Batch(_) => "Batch".to_string(),
         }
     }
 
@@ -153,6 +203,9 @@ impl MoveAction {
             args,
         })
     }
+    pub fn new_batch(calls: Vec<FunctionCall>) -> Self {
+        Self::Batch(calls)
+    }
     pub fn new_script_call(code: Vec<u8>, ty_args: Vec<TypeTag>, args: Vec<Vec<u8>>) -> Self {
         Self::Script(ScriptCall {
             code,
@@ -180,6 +233,7 @@ impl From<VerifiedMoveAction> for MoveAction {
                 module_bundle,
                 init_function_modules: _init_function_modules,
             } => MoveAction::ModuleBundle(module_bundle),
+            VerifiedMoveAction::Batch { calls } => MoveAction::Batch(calls),
         }
     }
 }
@@ -229,6 +283,23 @@ impl Display for MoveAction {
                 }
                 write!(f, "MoveAction::ModuleBundle( {:?} )", module_list)
             }
+            MoveAction::Batch(calls) => {
+                let mut call_list = vec![];
+                for call in calls.iter() {
+                    let mut arg_list = vec![];
+                    for arg in call.args.iter() {
+                        match CallResultRef::from_arg(arg) {
+                            Some(result_ref) => arg_list.push(result_ref.to_string()),
+                            None => arg_list.push(format!("0x{:}", hex::encode(arg))),
+                        }
+                    }
+                    call_list.push(format!(
+                        "( function_id: {},  type_args: {:?}, args: {:?})",
+                        call.function_id, call.ty_args, arg_list
+                    ));
+                }
+                write!(f, "MoveAction::Batch( {:?} )", call_list)
+            }
         }
     }
 }
@@ -247,6 +318,9 @@ pub enum VerifiedMoveAction {
         module_bundle: Vec<Vec<u8>>,
         init_function_modules: Vec<ModuleId>,
     },
+    Batch {
+        calls: Vec<FunctionCall>,
+    },
 }
 
 impl Display for VerifiedMoveAction {
@@ -276,6 +350,9 @@ impl Display for VerifiedMoveAction {
                     init_function_modules.len()
                 )
             }
+            VerifiedMoveAction::Batch { calls } => {
+                write!(f, "Batch(calls: {})", calls.len())
+            }
         }
     }
 }
@@ -579,7 +656,8 @@ impl TransactionExecutionInfo {
 
 #[cfg(test)]
 mod tests {
-    use super::MoveAction;
+    use super::{CallResultRef, MoveAction};
+    use crate::moveos_std::object::ObjectID;
     use proptest::prelude::*;
 
     proptest! {
@@ -589,5 +667,13 @@ mod tests {
             let deserialized: MoveAction = bcs::from_bytes(&serialized).unwrap();
             assert_eq!(input, deserialized);
         }
+
+        #[test]
+        fn test_call_result_ref_is_not_object_id(call_index in any::<u16>(), result_index in any::<u16>()) {
+            let result_ref = CallResultRef::new(call_index, result_index);
+            let arg = result_ref.to_arg();
+            assert_eq!(CallResultRef::from_arg(&arg), Some(result_ref));
+            assert!(bcs::from_bytes::<ObjectID>(&arg).is_err());
+        }
     }
 }
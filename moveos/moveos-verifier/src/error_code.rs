@@ -87,6 +87,14 @@ pub enum ErrorCode {
     INVALID_DATA_STRUCT_FUNC_INCOMPATIBLE_WITH_EXISTS = 13008,
     INVALID_PRIVATE_GENERICS_INCOMPATIBLE_WITH_EXISTS = 13009,
 
+    INVALID_PACKAGE_METADATA = 14000,
+    UNDECLARED_PACKAGE_DEPENDENCY = 14001,
+    DUPLICATE_PACKAGE_DEPENDENCY = 14002,
+    PACKAGE_IMMUTABLE = 14003,
+    PACKAGE_UPGRADE_POLICY_RELAXED = 14004,
+    PACKAGE_NAME_CHANGED = 14005,
+    PACKAGE_VERSION_NOT_INCREASED = 14006,
+
     UNKNOWN_CODE = 18446744073709551615,
 }
 }
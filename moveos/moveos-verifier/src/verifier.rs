@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use std::cmp::max;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::ops::Deref;
 
 use move_binary_format::binary_views::BinaryIndexedView;
@@ -13,6 +13,7 @@ use move_binary_format::file_format::{
     StructFieldInformation, StructHandleIndex, Visibility,
 };
 use move_binary_format::{CompiledModule, access::ModuleAccess};
+use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::resolver::ModuleResolver;
@@ -20,6 +21,7 @@ use move_core_types::vm_status::StatusCode;
 use move_vm_runtime::data_cache::TransactionCache;
 use move_vm_runtime::session::{LoadedFunctionInstantiation, Session};
 use move_vm_types::loaded_data::runtime_types::Type;
+use moveos_types::moveos_std::module_store::{PackageMetadata, UpgradePolicy};
 use once_cell::sync::Lazy;
 
 use crate::error_code::ErrorCode;
@@ -2270,6 +2272,117 @@ pub fn check_metadata_compatibility(
     Ok(true)
 }
 
+/// Verify the metadata of a package to be published.
+/// Every address the modules depend on must be the package itself or a declared dependency.
+/// If the package is upgraded with metadata, the upgrade must follow the rules of the old metadata:
+/// an immutable package can not be upgraded, the upgrade policy can not be relaxed,
+/// the package name can not be changed and the version must be increased.
+pub fn verify_package_metadata(
+    modules: &[CompiledModule],
+    package_id: AccountAddress,
+    metadata: &PackageMetadata,
+    old_metadata: Option<&PackageMetadata>,
+) -> VMResult<bool> {
+    if metadata.package_name.as_str().is_empty() {
+        return generate_package_error(
+            ErrorCode::INVALID_PACKAGE_METADATA,
+            "package name is empty".to_string(),
+        );
+    }
+    let upgrade_policy = match metadata.upgrade_policy() {
+        Ok(policy) => policy,
+        Err(e) => {
+            return generate_package_error(ErrorCode::INVALID_PACKAGE_METADATA, e.to_string());
+        }
+    };
+
+    let mut dependencies = BTreeSet::new();
+    for dependency in metadata.dependencies.iter() {
+        if dependency.package_id == package_id {
+            return generate_package_error(
+                ErrorCode::INVALID_PACKAGE_METADATA,
+                "package can not depend on itself".to_string(),
+            );
+        }
+        if !dependencies.insert(dependency.package_id) {
+            return generate_package_error(
+                ErrorCode::DUPLICATE_PACKAGE_DEPENDENCY,
+                format!(
+                    "package dependency {} is duplicated",
+                    dependency.package_id.to_hex_literal()
+                ),
+            );
+        }
+    }
+
+    for module in modules.iter() {
+        for dependency in module.immediate_dependencies() {
+            let address = *dependency.address();
+            if address != package_id && !dependencies.contains(&address) {
+                return generate_vm_error(
+                    ErrorCode::UNDECLARED_PACKAGE_DEPENDENCY,
+                    format!(
+                        "module {} depends on the undeclared package {}",
+                        module.self_id().short_str_lossless(),
+                        address.to_hex_literal()
+                    ),
+                    None,
+                    module,
+                );
+            }
+        }
+    }
+
+    let Some(old_metadata) = old_metadata else {
+        return Ok(true);
+    };
+    // The old metadata is verified when it is published, fallback to the strictest policy anyway.
+    let old_upgrade_policy = old_metadata
+        .upgrade_policy()
+        .unwrap_or(UpgradePolicy::Immutable);
+    if old_upgrade_policy == UpgradePolicy::Immutable {
+        return generate_package_error(
+            ErrorCode::PACKAGE_IMMUTABLE,
+            format!("package {} is immutable", package_id.to_hex_literal()),
+        );
+    }
+    if upgrade_policy < old_upgrade_policy {
+        return generate_package_error(
+            ErrorCode::PACKAGE_UPGRADE_POLICY_RELAXED,
+            format!(
+                "upgrade policy can not be changed from {} to {}",
+                old_upgrade_policy, upgrade_policy
+            ),
+        );
+    }
+    if metadata.package_name != old_metadata.package_name {
+        return generate_package_error(
+            ErrorCode::PACKAGE_NAME_CHANGED,
+            format!(
+                "package name can not be changed from {} to {}",
+                old_metadata.package_name, metadata.package_name
+            ),
+        );
+    }
+    if metadata.version <= old_metadata.version {
+        return generate_package_error(
+            ErrorCode::PACKAGE_VERSION_NOT_INCREASED,
+            format!(
+                "package version {} must be greater than the published version {}",
+                metadata.version, old_metadata.version
+            ),
+        );
+    }
+    Ok(true)
+}
+
+fn generate_package_error(error_code: ErrorCode, error_msg: String) -> VMResult<bool> {
+    Err(PartialVMError::new(StatusCode::ABORTED)
+        .with_message(error_msg)
+        .with_sub_status(error_code as u64)
+        .finish(Location::Undefined))
+}
+
 #[allow(dead_code)]
 fn struct_in_module(module: &CompiledModule, other_struct_name: &str) -> bool {
     let module_name_address = module.self_id().short_str_lossless();
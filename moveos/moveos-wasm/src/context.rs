@@ -0,0 +1,105 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::wasm::WASMInstance;
+use better_any::{Tid, TidAble};
+use std::collections::BTreeMap;
+
+/// The max number of WASM instances alive in a session at the same time.
+pub const MAX_WASM_INSTANCES_PER_SESSION: usize = 64;
+
+/// The WASM instances created by a transaction.
+/// It is a native context extension of the session, so the instances are released when the
+/// session finishes or aborts, even if the transaction never releases them.
+/// The instance IDs are allocated sequentially from 1, they only depend on the transaction.
+#[derive(Tid)]
+pub struct NativeWASMContext {
+    instances: BTreeMap<u64, WASMInstance>,
+    next_instance_id: u64,
+}
+
+impl Default for NativeWASMContext {
+    fn default() -> Self {
+        Self {
+            instances: BTreeMap::new(),
+            next_instance_id: 1,
+        }
+    }
+}
+
+impl NativeWASMContext {
+    pub fn insert_instance(&mut self, instance: WASMInstance) -> anyhow::Result<u64> {
+        if self.instances.len() >= MAX_WASM_INSTANCES_PER_SESSION {
+            return Err(anyhow::Error::msg(format!(
+                "exceed the max number of wasm instances in a session: {}",
+                MAX_WASM_INSTANCES_PER_SESSION
+            )));
+        }
+        let instance_id = self.next_instance_id;
+        self.next_instance_id += 1;
+        self.instances.insert(instance_id, instance);
+        Ok(instance_id)
+    }
+
+    pub fn get_instance_mut(&mut self, instance_id: u64) -> Option<&mut WASMInstance> {
+        self.instances.get_mut(&instance_id)
+    }
+
+    pub fn remove_instance(&mut self, instance_id: u64) -> Option<WASMInstance> {
+        self.instances.remove(&instance_id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+
+    /// Release all the instances, return the number of the instances not released by the transaction.
+    pub fn release_all(&mut self) -> usize {
+        let count = self.instances.len();
+        self.instances.clear();
+        count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gas_config::WASMGasConfig;
+    use crate::wasm::create_wasm_instance;
+
+    const EMPTY_WAT: &str = r#"(module (memory (export "memory") 1))"#;
+
+    fn new_instance() -> WASMInstance {
+        create_wasm_instance(EMPTY_WAT.as_bytes(), WASMGasConfig::default()).unwrap()
+    }
+
+    #[test]
+    fn test_instance_lifecycle() {
+        let mut context = NativeWASMContext::default();
+        let first = context.insert_instance(new_instance()).unwrap();
+        let second = context.insert_instance(new_instance()).unwrap();
+        assert_eq!((first, second), (1, 2));
+
+        assert!(context.remove_instance(first).is_some());
+        assert!(context.get_instance_mut(first).is_none());
+        // The released instance ID is never reused in the session
+        let third = context.insert_instance(new_instance()).unwrap();
+        assert_eq!(third, 3);
+
+        assert_eq!(context.release_all(), 2);
+        assert!(context.is_empty());
+    }
+
+    #[test]
+    fn test_instance_limit() {
+        let mut context = NativeWASMContext::default();
+        for _ in 0..MAX_WASM_INSTANCES_PER_SESSION {
+            context.insert_instance(new_instance()).unwrap();
+        }
+        assert!(context.insert_instance(new_instance()).is_err());
+    }
+}
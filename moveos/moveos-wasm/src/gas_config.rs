@@ -0,0 +1,117 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use wasmer::wasmparser::Operator;
+
+/// The operator cost table and the limits of WASM execution.
+/// It is loaded from the on-chain gas schedule, the default values are used for the missing entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WASMGasConfig {
+    /// The cost of the operators which are not in the other categories
+    pub default_cost: u64,
+    pub call: u64,
+    pub branch: u64,
+    pub load: u64,
+    pub store: u64,
+    pub global: u64,
+    pub int32_arith: u64,
+    pub int32_div: u64,
+    pub int64_arith: u64,
+    pub int64_div: u64,
+    pub float32_arith: u64,
+    pub float64_arith: u64,
+    /// The cost of `memory.grow` and `memory.size`
+    pub memory: u64,
+    /// The cost of the bulk memory and table operators, such as `memory.copy` and `memory.fill`
+    pub bulk_memory: u64,
+    /// The max gas a function call of an instance can use
+    pub gas_limit: u64,
+    /// The max number of 64KiB pages of an instance memory
+    pub max_memory_pages: u32,
+    /// The max depth of the nested function calls in an instance
+    pub max_call_depth: u32,
+}
+
+impl Default for WASMGasConfig {
+    fn default() -> Self {
+        Self {
+            default_cost: 1,
+            call: 5,
+            branch: 2,
+            load: 2,
+            store: 2,
+            global: 2,
+            int32_arith: 2,
+            int32_div: 3,
+            int64_arith: 3,
+            int64_div: 4,
+            float32_arith: 3,
+            float64_arith: 4,
+            memory: 3,
+            bulk_memory: 1,
+            gas_limit: 500000,
+            max_memory_pages: 1024,
+            max_call_depth: 1024,
+        }
+    }
+}
+
+impl WASMGasConfig {
+    pub fn cost(&self, operator: &Operator) -> u64 {
+        match operator {
+            Operator::Call { .. }
+            | Operator::ReturnCall { .. }
+            | Operator::ReturnCallIndirect { .. } => self.call,
+            Operator::I64DivS | Operator::I64DivU | Operator::I64RemS | Operator::I64RemU => {
+                self.int64_div
+            }
+            Operator::F64Add | Operator::F64Sub | Operator::F64Mul | Operator::F64Div => {
+                self.float64_arith
+            }
+            Operator::I32DivS | Operator::I32DivU | Operator::I32RemS | Operator::I32RemU => {
+                self.int32_div
+            }
+            Operator::I64Add | Operator::I64Sub | Operator::I64Mul => self.int64_arith,
+            Operator::F32Add | Operator::F32Sub | Operator::F32Mul | Operator::F32Div => {
+                self.float32_arith
+            }
+            Operator::MemoryGrow { .. } | Operator::MemorySize { .. } => self.memory,
+            Operator::MemoryCopy { .. }
+            | Operator::MemoryFill { .. }
+            | Operator::MemoryInit { .. }
+            | Operator::DataDrop { .. }
+            | Operator::TableCopy { .. }
+            | Operator::TableFill { .. }
+            | Operator::TableGrow { .. }
+            | Operator::TableInit { .. }
+            | Operator::ElemDrop { .. } => self.bulk_memory,
+            Operator::I32Add | Operator::I32Sub | Operator::I32Mul => self.int32_arith,
+            Operator::Br { .. } | Operator::BrIf { .. } | Operator::BrTable { .. } => self.branch,
+            Operator::I32Load { .. }
+            | Operator::I64Load { .. }
+            | Operator::F32Load { .. }
+            | Operator::F64Load { .. }
+            | Operator::I32Load8S { .. }
+            | Operator::I32Load8U { .. }
+            | Operator::I32Load16S { .. }
+            | Operator::I32Load16U { .. }
+            | Operator::I64Load8S { .. }
+            | Operator::I64Load8U { .. }
+            | Operator::I64Load16S { .. }
+            | Operator::I64Load16U { .. }
+            | Operator::I64Load32S { .. }
+            | Operator::I64Load32U { .. } => self.load,
+            Operator::I32Store { .. }
+            | Operator::I64Store { .. }
+            | Operator::F32Store { .. }
+            | Operator::F64Store { .. }
+            | Operator::I32Store8 { .. }
+            | Operator::I32Store16 { .. }
+            | Operator::I64Store8 { .. }
+            | Operator::I64Store16 { .. }
+            | Operator::I64Store32 { .. } => self.store,
+            Operator::GlobalGet { .. } | Operator::GlobalSet { .. } => self.global,
+            _ => self.default_cost,
+        }
+    }
+}
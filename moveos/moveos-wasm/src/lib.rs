@@ -1,7 +1,11 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
-pub mod cost_function;
+pub mod context;
+pub mod gas_config;
 pub mod gas_meter;
+pub mod metrics;
 pub mod middlewares;
+pub mod module_cache;
+pub mod tunables;
 pub mod wasm;
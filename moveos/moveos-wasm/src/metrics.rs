@@ -0,0 +1,60 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use metrics::metrics_util::LATENCY_SEC_BUCKETS;
+use once_cell::sync::OnceCell;
+use prometheus::{
+    Histogram, IntCounter, IntGauge, Registry, register_histogram_with_registry,
+    register_int_counter_with_registry, register_int_gauge_with_registry,
+};
+use std::sync::Arc;
+
+static WASM_METRICS_ONCE: OnceCell<Arc<WasmMetrics>> = OnceCell::new();
+
+#[derive(Debug)]
+pub struct WasmMetrics {
+    pub wasm_module_cache_hits: IntCounter,
+    pub wasm_module_cache_misses: IntCounter,
+    pub wasm_module_cache_size: IntGauge,
+    pub wasm_module_compile_latency_seconds: Histogram,
+}
+
+impl WasmMetrics {
+    fn new(registry: &Registry) -> Self {
+        WasmMetrics {
+            wasm_module_cache_hits: register_int_counter_with_registry!(
+                "wasm_module_cache_hits",
+                "Number of compiled WASM modules loaded from the cache",
+                registry,
+            )
+            .unwrap(),
+            wasm_module_cache_misses: register_int_counter_with_registry!(
+                "wasm_module_cache_misses",
+                "Number of WASM modules compiled because they are not in the cache",
+                registry,
+            )
+            .unwrap(),
+            wasm_module_cache_size: register_int_gauge_with_registry!(
+                "wasm_module_cache_size",
+                "Number of compiled WASM modules in the cache",
+                registry,
+            )
+            .unwrap(),
+            wasm_module_compile_latency_seconds: register_histogram_with_registry!(
+                "wasm_module_compile_latency_seconds",
+                "WASM module compile latency in seconds",
+                LATENCY_SEC_BUCKETS.to_vec(),
+                registry,
+            )
+            .unwrap(),
+        }
+    }
+
+    pub fn get() -> Option<&'static Arc<WasmMetrics>> {
+        WASM_METRICS_ONCE.get()
+    }
+
+    pub fn get_or_init(registry: &Registry) -> &'static Arc<WasmMetrics> {
+        WASM_METRICS_ONCE.get_or_init(|| Arc::new(WasmMetrics::new(registry)))
+    }
+}
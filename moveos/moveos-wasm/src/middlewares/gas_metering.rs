@@ -4,41 +4,30 @@
 use std::fmt;
 use std::sync::{Arc, Mutex};
 
-use wasmer::wasmparser::Operator;
+use wasmer::wasmparser::{BlockType, Operator};
 use wasmer::{LocalFunctionIndex, MiddlewareError, MiddlewareReaderState, ModuleMiddleware, Type};
 use wasmer_types::{
-    ExportIndex, FunctionIndex, FunctionType, ImportIndex, ImportKey, entity::PrimaryMap,
+    ExportIndex, FunctionIndex, FunctionType, GlobalIndex, GlobalInit, GlobalType, ImportIndex,
+    ImportKey, Mutability, entity::PrimaryMap,
 };
 
-type CostFunction = dyn Fn(&Operator) -> u64 + Send + Sync;
-
-fn default_cost_function(operator: &Operator) -> u64 {
-    match operator {
-        Operator::LocalGet { .. } => 1,
-        Operator::LocalSet { .. } => 1,
-        Operator::I32Const { .. } => 1,
-        Operator::I32Add { .. } => 2,
-        Operator::I32Sub { .. } => 2,
-        Operator::I32Mul { .. } => 3,
-        Operator::Call { .. } => 5,
-        Operator::Loop { .. } => 1,
-        Operator::If { .. } => 1,
-        Operator::Else { .. } => 1,
-        Operator::End { .. } => 1,
-        _ => 1,
-    }
-}
+use crate::gas_config::WASMGasConfig;
+
+/// The exported global which records the depth of the nested function calls.
+pub const CALL_DEPTH_GLOBAL_NAME: &str = "kanari_call_depth";
 
 pub struct GasMiddleware {
     charge_function_index: Arc<Mutex<Option<FunctionIndex>>>,
-    cost_function: Arc<CostFunction>,
+    call_depth_global_index: Arc<Mutex<Option<GlobalIndex>>>,
+    config: WASMGasConfig,
 }
 
 impl GasMiddleware {
-    pub fn new(cost_function: Option<Arc<CostFunction>>) -> Self {
+    pub fn new(config: WASMGasConfig) -> Self {
         Self {
             charge_function_index: Arc::new(Mutex::new(None)),
-            cost_function: cost_function.unwrap_or_else(|| Arc::new(default_cost_function)),
+            call_depth_global_index: Arc::new(Mutex::new(None)),
+            config,
         }
     }
 }
@@ -47,6 +36,8 @@ impl fmt::Debug for GasMiddleware {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("GasMiddleware")
             .field("charge_function_index", &self.charge_function_index)
+            .field("call_depth_global_index", &self.call_depth_global_index)
+            .field("config", &self.config)
             .finish()
     }
 }
@@ -57,11 +48,13 @@ impl ModuleMiddleware for GasMiddleware {
         _index: LocalFunctionIndex,
     ) -> Box<dyn wasmer::FunctionMiddleware> {
         let charge_function_index = self.charge_function_index.lock().unwrap().unwrap();
+        let call_depth_global_index = self.call_depth_global_index.lock().unwrap().unwrap();
 
         Box::new(GasFunctionMiddleware {
             charge_function_index,
+            call_depth_global_index,
             accumulated_cost: 0,
-            cost_function: self.cost_function.clone(),
+            config: self.config,
         })
     }
 
@@ -140,13 +133,63 @@ impl ModuleMiddleware for GasMiddleware {
 
         let mut charge_function_index_lock = self.charge_function_index.lock().unwrap();
         *charge_function_index_lock = Some(charge_function_index);
+
+        // Append the global for the call depth, it is exported so the host can reset it
+        let call_depth_global_index = module_info
+            .globals
+            .push(GlobalType::new(Type::I32, Mutability::Var));
+        module_info
+            .global_initializers
+            .push(GlobalInit::I32Const(0));
+        module_info.exports.insert(
+            CALL_DEPTH_GLOBAL_NAME.to_string(),
+            ExportIndex::Global(call_depth_global_index),
+        );
+
+        let mut call_depth_global_index_lock = self.call_depth_global_index.lock().unwrap();
+        *call_depth_global_index_lock = Some(call_depth_global_index);
     }
 }
 
 struct GasFunctionMiddleware {
     charge_function_index: FunctionIndex,
+    call_depth_global_index: GlobalIndex,
     accumulated_cost: u64,
-    cost_function: Arc<CostFunction>,
+    config: WASMGasConfig,
+}
+
+impl GasFunctionMiddleware {
+    /// Increase the call depth before a call, trap if it exceeds the max call depth.
+    fn enter_call<'a>(&self) -> [Operator<'a>; 10] {
+        let global_index = self.call_depth_global_index.as_u32();
+        [
+            Operator::GlobalGet { global_index },
+            Operator::I32Const { value: 1 },
+            Operator::I32Add,
+            Operator::GlobalSet { global_index },
+            Operator::GlobalGet { global_index },
+            Operator::I32Const {
+                value: self.config.max_call_depth.min(i32::MAX as u32) as i32,
+            },
+            Operator::I32GtU,
+            Operator::If {
+                blockty: BlockType::Empty,
+            },
+            Operator::Unreachable,
+            Operator::End,
+        ]
+    }
+
+    /// Decrease the call depth after the call returns.
+    fn leave_call<'a>(&self) -> [Operator<'a>; 4] {
+        let global_index = self.call_depth_global_index.as_u32();
+        [
+            Operator::GlobalGet { global_index },
+            Operator::I32Const { value: 1 },
+            Operator::I32Sub,
+            Operator::GlobalSet { global_index },
+        ]
+    }
 }
 
 impl fmt::Debug for GasFunctionMiddleware {
@@ -164,8 +207,8 @@ impl wasmer::FunctionMiddleware for GasFunctionMiddleware {
         operator: Operator<'a>,
         state: &mut MiddlewareReaderState<'a>,
     ) -> Result<(), MiddlewareError> {
-        // Use cost_function to evaluate the cost of the instruction
-        self.accumulated_cost += (self.cost_function)(&operator);
+        // Use the cost table to evaluate the cost of the instruction
+        self.accumulated_cost += self.config.cost(&operator);
 
         // Perform batch charging before critical points
         match operator {
@@ -193,9 +236,10 @@ impl wasmer::FunctionMiddleware for GasFunctionMiddleware {
             _ => {}
         }
 
-        // Update function call indices if necessary
+        // Update function call indices if necessary, and track the call depth
         match operator {
             Operator::Call { function_index } => {
+                state.extend(&self.enter_call());
                 if function_index >= self.charge_function_index.as_u32() {
                     state.push_operator(Operator::Call {
                         function_index: function_index + 1,
@@ -203,6 +247,12 @@ impl wasmer::FunctionMiddleware for GasFunctionMiddleware {
                 } else {
                     state.push_operator(operator.clone());
                 }
+                state.extend(&self.leave_call());
+            }
+            Operator::CallIndirect { .. } => {
+                state.extend(&self.enter_call());
+                state.push_operator(operator.clone());
+                state.extend(&self.leave_call());
             }
             Operator::ReturnCall { function_index } => {
                 if function_index >= self.charge_function_index.as_u32() {
@@ -0,0 +1,250 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::gas_config::WASMGasConfig;
+use crate::metrics::WasmMetrics;
+use crate::middlewares::gas_metering::GasMiddleware;
+use crate::tunables::LimitingTunables;
+use quick_cache::sync::Cache;
+use sha3::{Digest, Sha3_256};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+use tracing::debug;
+use wasmer::{BaseTunables, Engine, Module, NativeEngineExt, Pages, Store, Target};
+use wasmer_compiler_singlepass::Singlepass;
+
+/// The max number of compiled modules kept in the global cache.
+pub const DEFAULT_MODULE_CACHE_SIZE: usize = 64;
+
+pub type CodeHash = [u8; 32];
+
+/// A gas instrumented WASM module, and the engine it is compiled by.
+/// The instances created from it share the compiled artifact, so they have the same metering
+/// behavior as the instances created from a freshly compiled module.
+#[derive(Clone)]
+pub struct CompiledWasmModule {
+    pub code_hash: CodeHash,
+    pub bytecode: Arc<Vec<u8>>,
+    /// The gas config the module is instrumented and limited with
+    pub config: WASMGasConfig,
+    engine: Engine,
+    module: Module,
+}
+
+impl CompiledWasmModule {
+    /// Compile the WASM text or binary code with the gas metering middleware and the limits of the config.
+    pub fn compile(code: &[u8], config: WASMGasConfig) -> anyhow::Result<Self> {
+        let code_hash = code_hash(code);
+        let bytecode = wasmer::wat2wasm(code)
+            .map_err(|e| anyhow::Error::msg(e.to_string()))?
+            .to_vec();
+
+        // The middleware records the charge function index of the module it transforms,
+        // so every module is compiled by a dedicated compiler.
+        let mut compiler = Singlepass::new();
+        let gas_middleware = GasMiddleware::new(config);
+        compiler.push_middleware(Arc::new(gas_middleware));
+        let mut engine = Engine::from(compiler);
+        engine.set_tunables(LimitingTunables::new(
+            BaseTunables::for_target(&Target::default()),
+            Pages(config.max_memory_pages),
+        ));
+
+        let module = Module::new(&engine, &bytecode).map_err(|e| {
+            debug!("compile_wasm_module->new_module_error:{:?}", &e);
+            anyhow::Error::msg(e.to_string())
+        })?;
+        Ok(Self {
+            code_hash,
+            bytecode: Arc::new(bytecode),
+            config,
+            engine,
+            module,
+        })
+    }
+
+    /// Create a new store for instantiating the module.
+    pub fn new_store(&self) -> Store {
+        Store::new(self.engine.clone())
+    }
+
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+}
+
+/// A bounded cache of the compiled modules keyed by the hash of the WASM code and the gas config,
+/// so the modules are recompiled after the gas config is upgraded.
+pub struct WasmModuleCache {
+    cache: Cache<(CodeHash, WASMGasConfig), CompiledWasmModule>,
+}
+
+impl WasmModuleCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            cache: Cache::new(capacity),
+        }
+    }
+
+    /// Get the compiled module of the code, compile and cache it if it is not in the cache.
+    pub fn get_or_compile(
+        &self,
+        code: &[u8],
+        config: WASMGasConfig,
+    ) -> anyhow::Result<CompiledWasmModule> {
+        let key = (code_hash(code), config);
+        let metrics = WasmMetrics::get();
+        if let Some(module) = self.cache.get(&key) {
+            if let Some(metrics) = metrics {
+                metrics.wasm_module_cache_hits.inc();
+            }
+            return Ok(module);
+        }
+
+        let start = Instant::now();
+        let module = CompiledWasmModule::compile(code, config)?;
+        self.cache.insert(key, module.clone());
+        if let Some(metrics) = metrics {
+            metrics.wasm_module_cache_misses.inc();
+            metrics
+                .wasm_module_compile_latency_seconds
+                .observe(start.elapsed().as_secs_f64());
+            metrics.wasm_module_cache_size.set(self.cache.len() as i64);
+        }
+        Ok(module)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+}
+
+static GLOBAL_MODULE_CACHE: OnceLock<WasmModuleCache> = OnceLock::new();
+
+/// The module cache shared by all the sessions in the process.
+pub fn global_module_cache() -> &'static WasmModuleCache {
+    GLOBAL_MODULE_CACHE.get_or_init(|| WasmModuleCache::new(DEFAULT_MODULE_CACHE_SIZE))
+}
+
+pub fn code_hash(code: &[u8]) -> CodeHash {
+    Sha3_256::digest(code).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wasm::create_wasm_instance_from_module;
+    use wasmer::Value;
+
+    const LIMITS_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func $depth (export "depth") (param i32) (result i32)
+            (if (result i32) (i32.eqz (local.get 0))
+              (then (i32.const 0))
+              (else (i32.add (i32.const 1) (call $depth (i32.sub (local.get 0) (i32.const 1)))))))
+          (func (export "grow") (param i32) (result i32)
+            (memory.grow (local.get 0))))
+    "#;
+
+    const SUM_WAT: &str = r#"
+        (module
+          (memory (export "memory") 1)
+          (func (export "sum") (param i32) (result i32)
+            (local i32)
+            (block
+              (loop
+                (br_if 1 (i32.eqz (local.get 0)))
+                (local.set 1 (i32.add (local.get 1) (local.get 0)))
+                (local.set 0 (i32.sub (local.get 0) (i32.const 1)))
+                (br 0)))
+            (local.get 1)))
+    "#;
+
+    fn call_sum(module: &CompiledWasmModule, n: i32) -> (i32, u64) {
+        let mut instance = create_wasm_instance_from_module(module).unwrap();
+        let sum = instance.instance.exports.get_function("sum").unwrap();
+        let result = sum.call(&mut instance.store, &[Value::I32(n)]).unwrap();
+        let gas_used = instance.gas_meter.lock().unwrap().used();
+        (result[0].unwrap_i32(), gas_used)
+    }
+
+    #[test]
+    fn test_cached_module_metering() {
+        let cache = WasmModuleCache::new(DEFAULT_MODULE_CACHE_SIZE);
+        let config = WASMGasConfig::default();
+        let compiled = cache.get_or_compile(SUM_WAT.as_bytes(), config).unwrap();
+        let cached = cache.get_or_compile(SUM_WAT.as_bytes(), config).unwrap();
+        assert_eq!(cache.len(), 1);
+        assert_eq!(compiled.code_hash, cached.code_hash);
+
+        let fresh = CompiledWasmModule::compile(SUM_WAT.as_bytes(), config).unwrap();
+        let (expected_result, expected_gas) = call_sum(&fresh, 100);
+        assert_eq!(expected_result, 5050);
+        assert!(expected_gas > 0);
+        // Every instance of the cached module has its own gas meter
+        for _ in 0..2 {
+            assert_eq!(call_sum(&cached, 100), (expected_result, expected_gas));
+        }
+    }
+
+    #[test]
+    fn test_gas_config_cost_table() {
+        let cache = WasmModuleCache::new(DEFAULT_MODULE_CACHE_SIZE);
+        let config = WASMGasConfig::default();
+        let expensive_config = WASMGasConfig {
+            int32_arith: config.int32_arith * 10,
+            ..config
+        };
+        let module = cache.get_or_compile(SUM_WAT.as_bytes(), config).unwrap();
+        let expensive_module = cache
+            .get_or_compile(SUM_WAT.as_bytes(), expensive_config)
+            .unwrap();
+        // The module is compiled again with the upgraded config
+        assert_eq!(cache.len(), 2);
+
+        let (result, gas_used) = call_sum(&module, 100);
+        let (expensive_result, expensive_gas_used) = call_sum(&expensive_module, 100);
+        assert_eq!(result, expensive_result);
+        assert!(expensive_gas_used > gas_used);
+    }
+
+    #[test]
+    fn test_gas_config_limits() {
+        let config = WASMGasConfig {
+            max_memory_pages: 2,
+            max_call_depth: 10,
+            ..WASMGasConfig::default()
+        };
+        let module = CompiledWasmModule::compile(LIMITS_WAT.as_bytes(), config).unwrap();
+        let mut instance = create_wasm_instance_from_module(&module).unwrap();
+
+        let depth = instance
+            .instance
+            .exports
+            .get_function("depth")
+            .unwrap()
+            .clone();
+        let result = depth.call(&mut instance.store, &[Value::I32(10)]).unwrap();
+        assert_eq!(result[0].unwrap_i32(), 10);
+        assert!(depth.call(&mut instance.store, &[Value::I32(11)]).is_err());
+        // The call depth is left over by the trapped call until it is reset
+        instance.reset_call_depth().unwrap();
+        let result = depth.call(&mut instance.store, &[Value::I32(10)]).unwrap();
+        assert_eq!(result[0].unwrap_i32(), 10);
+
+        let grow = instance.instance.exports.get_function("grow").unwrap();
+        let result = grow.call(&mut instance.store, &[Value::I32(1)]).unwrap();
+        assert_eq!(result[0].unwrap_i32(), 1);
+        let result = grow.call(&mut instance.store, &[Value::I32(1)]).unwrap();
+        assert_eq!(result[0].unwrap_i32(), -1);
+    }
+}
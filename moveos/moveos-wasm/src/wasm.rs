@@ -1,21 +1,16 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
-use rand;
-use std::collections::BTreeMap;
 use std::ops::Deref;
-use std::sync::{Arc, Mutex, OnceLock};
+use std::sync::{Arc, Mutex};
 use tracing::{debug, error, warn};
 use wasmer::Value::I32;
 use wasmer::*;
 
-use wasmer_compiler_singlepass::Singlepass;
-
-use crate::cost_function::cost_function;
+use crate::gas_config::WASMGasConfig;
 use crate::gas_meter::GasMeter;
-use crate::middlewares::gas_metering::GasMiddleware;
-
-const GAS_LIMIT: u64 = 500000;
+use crate::middlewares::gas_metering::CALL_DEPTH_GLOBAL_NAME;
+use crate::module_cache::{CompiledWasmModule, global_module_cache};
 
 pub struct WASMInstance {
     pub bytecode: Vec<u8>,
@@ -38,53 +33,20 @@ impl WASMInstance {
             gas_meter,
         }
     }
-}
-
-/*
-TODO:
-The WASMInstance must be protected by the locker which owned by the signer
-    while we enable the parallel tx execution.
-The way we're doing it now is by using this big global lock, but it's too broad.
- */
-static GLOBAL_INSTANCE_POOL: OnceLock<Arc<Mutex<BTreeMap<u64, WASMInstance>>>> = OnceLock::new();
 
-fn get_global_instance_pool() -> &'static Arc<Mutex<BTreeMap<u64, WASMInstance>>> {
-    GLOBAL_INSTANCE_POOL.get_or_init(|| Arc::new(Mutex::new(BTreeMap::new())))
-}
-
-pub fn insert_wasm_instance(instance: WASMInstance) -> anyhow::Result<u64> {
-    loop {
-        let instance_id: u64 = rand::random();
-        let instance_pool_arc = get_global_instance_pool();
-        let mut instance_pool = match instance_pool_arc.lock() {
-            Ok(pool_guard) => pool_guard,
-            Err(_) => {
-                return Err(anyhow::Error::msg("get global instance pool failed"));
-            }
-        };
-        if instance_pool.get(&instance_id).is_none() {
-            instance_pool.insert(instance_id, instance);
-            return Ok(instance_id);
-        }
+    /// Reset the call depth, it is left over if the last call trapped.
+    pub fn reset_call_depth(&mut self) -> anyhow::Result<()> {
+        let global = self
+            .instance
+            .exports
+            .get_global(CALL_DEPTH_GLOBAL_NAME)
+            .map_err(|e| anyhow::Error::msg(e.to_string()))?;
+        global
+            .set(&mut self.store, Value::I32(0))
+            .map_err(|e| anyhow::Error::msg(e.message()))
     }
 }
 
-pub fn get_instance_pool() -> Arc<Mutex<BTreeMap<u64, WASMInstance>>> {
-    get_global_instance_pool().clone()
-}
-
-pub fn remove_instance(instance_id: u64) -> anyhow::Result<()> {
-    let instance_pool_arc = get_global_instance_pool();
-    let mut instance_pool = match instance_pool_arc.lock() {
-        Ok(pool_guard) => pool_guard,
-        Err(_) => {
-            return Err(anyhow::Error::msg("get global instance pool failed"));
-        }
-    };
-    instance_pool.remove(&instance_id);
-    Ok(())
-}
-
 #[allow(dead_code)]
 #[derive(Clone)]
 struct Env {
@@ -261,34 +223,20 @@ fn charge(env: FunctionEnvMut<Env>, amount: i64) -> Result<(), wasmer::RuntimeEr
     gas_meter.charge(amount as u64)
 }
 
-pub fn create_wasm_instance(code: &[u8]) -> anyhow::Result<WASMInstance> {
-    // Create the GasMeter
-    let gas_meter = Arc::new(Mutex::new(GasMeter::new(GAS_LIMIT)));
-
-    // Create and configure the compiler
-    let mut compiler = Singlepass::new();
-
-    // Add gas meter middleware
-    let gas_middleware = GasMiddleware::new(Some(Arc::new(cost_function)));
-    compiler.push_middleware(Arc::new(gas_middleware));
-
-    // Create the store
-    let mut store = Store::new(compiler);
+/// Create a WASM instance of the code, the compiled module is loaded from the global module cache.
+pub fn create_wasm_instance(code: &[u8], config: WASMGasConfig) -> anyhow::Result<WASMInstance> {
+    let module = global_module_cache().get_or_compile(code, config)?;
+    create_wasm_instance_from_module(&module)
+}
 
-    let bytecode = match wasmer::wat2wasm(code) {
-        Ok(m) => m,
-        Err(e) => {
-            return Err(anyhow::Error::msg(e.to_string()));
-        }
-    };
+pub fn create_wasm_instance_from_module(
+    module: &CompiledWasmModule,
+) -> anyhow::Result<WASMInstance> {
+    // Create the GasMeter
+    let gas_meter = Arc::new(Mutex::new(GasMeter::new(module.config.gas_limit)));
 
-    let module = match Module::new(&store, bytecode.clone()) {
-        Ok(m) => m,
-        Err(e) => {
-            debug!("create_wasm_instance->new_module_error:{:?}", &e);
-            return Err(anyhow::Error::msg(e.to_string()));
-        }
-    };
+    // Create the store with the engine which compiled the module
+    let mut store = module.new_store();
 
     let env = FunctionEnv::new(
         &mut store,
@@ -311,7 +259,7 @@ pub fn create_wasm_instance(code: &[u8]) -> anyhow::Result<WASMInstance> {
         },
     };
 
-    let instance = match Instance::new(&mut store, &module, &import_object) {
+    let instance = match Instance::new(&mut store, module.module(), &import_object) {
         Ok(v) => v,
         Err(e) => {
             debug!("create_wasm_instance->new_instance_error:{:?}", &e);
@@ -324,7 +272,7 @@ pub fn create_wasm_instance(code: &[u8]) -> anyhow::Result<WASMInstance> {
     }
 
     Ok(WASMInstance::new(
-        bytecode.to_vec(),
+        module.bytecode.to_vec(),
         instance,
         store,
         gas_meter,
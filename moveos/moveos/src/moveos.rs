@@ -4,8 +4,10 @@
 use crate::gas::table::{
     CostTable, MoveOSGasMeter, get_gas_schedule_entries, initial_cost_schedule,
 };
+use crate::parallel_executor::ReadSetRecorder;
 use crate::vm::data_cache::MoveosDataCache;
 use crate::vm::moveos_vm::{MoveOSSession, MoveOSVM};
+use crate::vm::tracer::{TracingGasMeter, TxTracer};
 use anyhow::{Error, Result, bail, format_err};
 use move_binary_format::CompiledModule;
 use move_binary_format::binary_views::BinaryIndexedView;
@@ -22,7 +24,7 @@ use move_core_types::{
 use move_vm_runtime::config::VMConfig;
 use move_vm_runtime::data_cache::TransactionCache;
 use move_vm_runtime::native_functions::NativeFunction;
-use moveos_common::types::ClassifiedGasMeter;
+use moveos_common::types::{ClassifiedGasMeter, SwitchableGasMeter};
 use moveos_store::config_store::ConfigDBStore;
 use moveos_store::event_store::EventDBStore;
 use moveos_store::state_store::statedb::StateDBStore;
@@ -31,11 +33,15 @@ use moveos_store::{MoveOSStore, load_feature_store_object};
 use moveos_types::addresses::MOVEOS_STD_ADDRESS;
 use moveos_types::function_return_value::FunctionResult;
 use moveos_types::moveos_std::gas_schedule::{GasScheduleConfig, GasScheduleUpdated};
+use moveos_types::moveos_std::genesis::GenesisCalls;
 use moveos_types::moveos_std::object::ObjectMeta;
 use moveos_types::moveos_std::tx_context::TxContext;
 use moveos_types::moveos_std::tx_result::TxResult;
 use moveos_types::state::{MoveStructState, MoveStructType, ObjectState};
-use moveos_types::state_resolver::{GenesisResolver, RootObjectResolver};
+use moveos_types::state_resolver::{
+    GenesisResolver, MoveOSResolver, RootObjectResolver, StatelessResolver,
+};
+use moveos_types::trace::TxTrace;
 use moveos_types::transaction::{FunctionCall, VMErrorInfo};
 use moveos_types::transaction::{
     MoveOSTransaction, RawTransactionOutput, VerifiedMoveAction, VerifiedMoveOSTransaction,
@@ -155,6 +161,11 @@ impl MoveOS {
     ) -> Result<RawTransactionOutput> {
         let MoveOSTransaction { root, ctx, action } = tx;
         assert!(root.is_genesis());
+        let genesis_calls = ctx
+            .get::<GenesisCalls>()?
+            .map(|calls| calls.function_calls())
+            .transpose()?
+            .unwrap_or_default();
         let resolver = GenesisResolver::default();
         let mut session = self.vm.new_genesis_session(&resolver, ctx, genesis_objects);
 
@@ -163,8 +174,12 @@ impl MoveOS {
             e
         })?;
 
-        // execute main tx
-        let execute_result = session.execute_move_action(verified_action);
+        // execute main tx, then the genesis calls which set up the initial state of custom networks
+        let execute_result = session.execute_move_action(verified_action).and_then(|_| {
+            genesis_calls
+                .into_iter()
+                .try_for_each(|call| session.execute_function_bypass_visibility(call).map(|_| ()))
+        });
         if let Some(vm_error) = execute_result.clone().err() {
             tracing::error!("execute_genesis_tx vm_error:{:?}", vm_error,);
         }
@@ -263,6 +278,55 @@ impl MoveOS {
         &self,
         tx: VerifiedMoveOSTransaction,
     ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>)> {
+        self.execute_with_resolver(tx, &self.db)
+    }
+
+    /// Execute the transaction and read the state via the given resolver at the tx root.
+    /// The resolver can wrap the store to observe the reads of the transaction.
+    pub fn execute_with_resolver<R: StatelessResolver>(
+        &self,
+        tx: VerifiedMoveOSTransaction,
+        state_resolver: &R,
+    ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>)> {
+        self.execute_with_gas_meter(tx, state_resolver, |gas_meter| gas_meter, None)
+    }
+
+    /// Execute the transaction with the tracer, and return the trace of the transaction action.
+    /// The object reads include the reads of the system pre_execute and post_execute functions.
+    pub fn execute_with_trace(
+        &self,
+        tx: VerifiedMoveOSTransaction,
+    ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>, TxTrace)> {
+        let root = tx.root.clone();
+        let recorder = ReadSetRecorder::new(&self.db, &root);
+        let tracer = TxTracer::new();
+        let (output, vm_error_info) = self.execute_with_gas_meter(
+            tx,
+            &recorder,
+            |gas_meter| TracingGasMeter::new(gas_meter, tracer.clone()),
+            Some(&tracer),
+        )?;
+        let trace = TxTrace {
+            calls: tracer.into_calls(),
+            object_reads: recorder.into_read_objects(),
+            object_writes: TxTrace::object_writes_of(&output.changeset),
+            events: output.events.clone(),
+        };
+        Ok((output, vm_error_info, trace))
+    }
+
+    fn execute_with_gas_meter<R, G, F>(
+        &self,
+        tx: VerifiedMoveOSTransaction,
+        state_resolver: &R,
+        wrap_gas_meter: F,
+        tracer: Option<&TxTracer>,
+    ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>)>
+    where
+        R: StatelessResolver,
+        G: SwitchableGasMeter + ClassifiedGasMeter + Clone,
+        F: FnOnce(MoveOSGasMeter) -> G,
+    {
         let VerifiedMoveOSTransaction { root, ctx, action } = tx;
         let tx_hash = ctx.tx_hash();
         if tracing::enabled!(tracing::Level::DEBUG) {
@@ -280,7 +344,7 @@ impl MoveOS {
         // So we keep a backup here, and then insert to the TxContext kv store when session respawed.
         let system_env = ctx.map.clone();
 
-        let feature_resolver = RootObjectResolver::new(root.clone(), &self.db);
+        let feature_resolver = RootObjectResolver::new(root.clone(), state_resolver);
         let feature_store_opt = load_feature_store_object(&feature_resolver);
         let has_io_tired_write_feature = match feature_store_opt {
             None => false,
@@ -288,11 +352,14 @@ impl MoveOS {
         };
 
         let cost_table = self.load_cost_table(&root)?;
-        let gas_meter =
-            MoveOSGasMeter::new(cost_table, ctx.max_gas_amount, has_io_tired_write_feature);
+        let gas_meter = wrap_gas_meter(MoveOSGasMeter::new(
+            cost_table,
+            ctx.max_gas_amount,
+            has_io_tired_write_feature,
+        ));
         let tx_size = ctx.tx_size;
 
-        let resolver = RootObjectResolver::new(root, &self.db);
+        let resolver = RootObjectResolver::new(root, state_resolver);
         let mut session = self.vm.new_session(&resolver, ctx, gas_meter);
 
         //We do not execute pre_execute and post_execute functions for system call
@@ -310,7 +377,14 @@ impl MoveOS {
             }
         }
 
-        match self.execute_action(&mut session, action.clone(), tx_size) {
+        if let Some(tracer) = tracer {
+            tracer.start(&action);
+        }
+        let result = self.execute_action(&mut session, action.clone(), tx_size);
+        if let Some(tracer) = tracer {
+            tracer.stop(result.is_err());
+        }
+        match result {
             Ok(_) => {
                 let status = VMStatus::Executed;
                 if tracing::enabled!(tracing::Level::DEBUG) {
@@ -440,9 +514,9 @@ impl MoveOS {
     // Execute action with pre_execute and post_execute.
     // Return the action execution status if success,
     // else return VMError and a bool which indicate if we should respawn the session.
-    fn execute_action(
+    fn execute_action<S: MoveOSResolver, G: SwitchableGasMeter + ClassifiedGasMeter>(
         &self,
-        session: &mut MoveOSSession<'_, '_, RootObjectResolver<MoveOSStore>, MoveOSGasMeter>,
+        session: &mut MoveOSSession<'_, '_, S, G>,
         action: VerifiedMoveAction,
         tx_size: u64,
     ) -> Result<(), VMError> {
@@ -455,10 +529,10 @@ impl MoveOS {
         session.execute_move_action(action)
     }
 
-    fn execution_cleanup(
+    fn execution_cleanup<S: MoveOSResolver, G: SwitchableGasMeter + ClassifiedGasMeter>(
         &self,
         is_system_call: bool,
-        mut session: MoveOSSession<'_, '_, RootObjectResolver<MoveOSStore>, MoveOSGasMeter>,
+        mut session: MoveOSSession<'_, '_, S, G>,
         status: VMStatus,
         vm_error_info: Option<VMErrorInfo>,
     ) -> Result<(RawTransactionOutput, Option<VMErrorInfo>)> {
@@ -522,6 +596,11 @@ impl MoveOS {
         Ok((output, vm_error_info))
     }
 
+    /// Drop the cached cost table, it will be reloaded from the state on the next execution.
+    pub fn flush_cost_table(&self) {
+        self.cost_table.write().take();
+    }
+
     pub fn flush_module_cache(&self, is_upgrade: bool) -> Result<()> {
         if is_upgrade {
             self.vm.mark_loader_cache_as_invalid();
@@ -530,9 +609,9 @@ impl MoveOS {
     }
 }
 
-fn extract_execution_state(
+fn extract_execution_state<S: MoveOSResolver>(
     vm_err: VMError,
-    data_cache: &MoveosDataCache<RootObjectResolver<MoveOSStore>>,
+    data_cache: &MoveosDataCache<S>,
 ) -> Result<Vec<String>> {
     let mut execution_stack_trace = Vec::new();
     if let Some(exec_state) = vm_err.exec_state() {
@@ -557,10 +636,10 @@ fn extract_execution_state(
     Ok(execution_stack_trace)
 }
 
-fn func_name_from_db(
+fn func_name_from_db<S: MoveOSResolver>(
     module_id: &ModuleId,
     func_idx: &FunctionDefinitionIndex,
-    data_cache: &MoveosDataCache<RootObjectResolver<MoveOSStore>>,
+    data_cache: &MoveosDataCache<S>,
 ) -> Result<String> {
     let module_bytes = data_cache.load_module(module_id)?;
     let compiled_module = CompiledModule::deserialize(module_bytes.as_slice())?;
@@ -0,0 +1,380 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+//! Optimistic parallel execution of a batch of sequenced transactions, in the style of Block-STM.
+//!
+//! All the transactions of a batch are executed speculatively in parallel against the same base root,
+//! and every execution records the objects it read. The outputs are then committed in the sequence order:
+//! an output is valid if no object it read or wrote has been written by a transaction committed before it,
+//! otherwise the transaction is re-executed against the latest root. So the committed `StateChangeSet`s,
+//! and the state roots, are the same as the serial execution.
+//!
+//! Some objects are written by almost every transaction, e.g. the gas fee stores, and their changes commute.
+//! The `CommutativeObjects` tell the executor how to rebase such a written value to the latest committed value,
+//! so the transactions do not conflict on them.
+
+use crate::moveos::MoveOS;
+use anyhow::Result;
+use move_core_types::effects::Op;
+use moveos_types::h256::H256;
+use moveos_types::moveos_std::module_store::ModuleStore;
+use moveos_types::moveos_std::object::{ObjectID, ObjectMeta};
+use moveos_types::state::{FieldKey, ObjectChange, ObjectState, StateChangeSet};
+use moveos_types::state_resolver::{StateKV, StatelessResolver};
+use moveos_types::transaction::{RawTransactionOutput, VMErrorInfo, VerifiedMoveOSTransaction};
+use parking_lot::Mutex;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Default)]
+struct ReadSet {
+    objects: BTreeSet<ObjectID>,
+    /// The transaction iterated the fields of the root object
+    root_fields: bool,
+}
+
+/// A `StatelessResolver` wrapper which records the objects read by a transaction.
+///
+/// The object runtime loads a field via the state root of its parent object, so reading a field
+/// always reads the parent first. A missing field is recorded as a read of the child id only under the root,
+/// the missing fields of other objects are covered by the read of the parent object,
+/// because adding a field to an object also changes the object metadata.
+pub struct ReadSetRecorder<'a, R> {
+    resolver: &'a R,
+    root_state_root: H256,
+    read_set: Mutex<ReadSet>,
+}
+
+impl<'a, R> ReadSetRecorder<'a, R>
+where
+    R: StatelessResolver,
+{
+    pub fn new(resolver: &'a R, root: &ObjectMeta) -> Self {
+        Self {
+            resolver,
+            root_state_root: root.state_root(),
+            read_set: Mutex::new(ReadSet::default()),
+        }
+    }
+
+    fn into_read_set(self) -> ReadSet {
+        self.read_set.into_inner()
+    }
+
+    /// The ids of the objects read via the recorder.
+    pub fn into_read_objects(self) -> BTreeSet<ObjectID> {
+        self.into_read_set().objects
+    }
+}
+
+impl<R> StatelessResolver for ReadSetRecorder<'_, R>
+where
+    R: StatelessResolver,
+{
+    fn get_field_at(
+        &self,
+        state_root: H256,
+        key: &FieldKey,
+    ) -> Result<Option<ObjectState>, anyhow::Error> {
+        let result = self.resolver.get_field_at(state_root, key)?;
+        let mut read_set = self.read_set.lock();
+        match &result {
+            Some(state) => {
+                read_set.objects.insert(state.metadata.id.clone());
+            }
+            None if state_root == self.root_state_root => {
+                read_set.objects.insert(ObjectID::root().child_id(*key));
+            }
+            None => {}
+        }
+        Ok(result)
+    }
+
+    fn list_fields_at(
+        &self,
+        state_root: H256,
+        cursor: Option<FieldKey>,
+        limit: usize,
+    ) -> Result<Vec<StateKV>> {
+        let result = self.resolver.list_fields_at(state_root, cursor, limit)?;
+        let mut read_set = self.read_set.lock();
+        if state_root == self.root_state_root {
+            read_set.root_fields = true;
+        }
+        read_set
+            .objects
+            .extend(result.iter().map(|(_, state)| state.metadata.id.clone()));
+        Ok(result)
+    }
+}
+
+/// The objects whose changes commute, a transaction which wrote such an object is still valid after
+/// another transaction committed a change of it, the written value is rebased instead of re-executing it.
+/// Only the objects under the root object are supported.
+pub trait CommutativeObjects {
+    fn contains(&self, id: &ObjectID) -> bool;
+
+    /// Rebase the `written` value, which the transaction changed from the `base` value, to the `current` value.
+    /// Returns `None` if the change can not be rebased.
+    fn rebase(
+        &self,
+        id: &ObjectID,
+        base: &[u8],
+        current: &[u8],
+        written: &[u8],
+    ) -> Result<Option<Vec<u8>>>;
+}
+
+struct SpeculativeOutput {
+    output: RawTransactionOutput,
+    vm_error_info: Option<VMErrorInfo>,
+    read_set: ReadSet,
+    write_set: BTreeSet<ObjectID>,
+}
+
+impl SpeculativeOutput {
+    /// The output is valid if the transaction did not read or write any object written after its execution root,
+    /// except the commutative objects, whose written values are rebased to the values at the `current_root`.
+    fn validate<R: StatelessResolver>(
+        &mut self,
+        committed_writes: &BTreeSet<ObjectID>,
+        base_root: &ObjectMeta,
+        current_root: &ObjectMeta,
+        resolver: &R,
+        commutative_objects: Option<&dyn CommutativeObjects>,
+    ) -> Result<bool> {
+        if self.read_set.root_fields && !committed_writes.is_empty() {
+            return Ok(false);
+        }
+        let conflicts = self
+            .read_set
+            .objects
+            .iter()
+            .chain(self.write_set.iter())
+            .filter(|id| committed_writes.contains(*id))
+            .cloned()
+            .collect::<BTreeSet<_>>();
+        if conflicts.is_empty() {
+            return Ok(true);
+        }
+        let Some(commutative_objects) = commutative_objects else {
+            return Ok(false);
+        };
+
+        let mut rebased_values = vec![];
+        for id in conflicts {
+            if !commutative_objects.contains(&id)
+                || !id.parent().is_some_and(|parent| parent.is_root())
+            {
+                return Ok(false);
+            }
+            let field_key = id.field_key();
+            let (Some(base), Some(current)) = (
+                resolver.get_field_at(base_root.state_root(), &field_key)?,
+                resolver.get_field_at(current_root.state_root(), &field_key)?,
+            ) else {
+                return Ok(false);
+            };
+            // The `updated_at` is set by the transaction which wrote the value
+            let metadata_changed = ObjectMeta {
+                updated_at: 0,
+                ..base.metadata
+            } != ObjectMeta {
+                updated_at: 0,
+                ..current.metadata
+            };
+            if metadata_changed {
+                return Ok(false);
+            }
+            let written = match self.output.changeset.changes.get(&field_key) {
+                Some(change) if !change.fields.is_empty() => return Ok(false),
+                Some(change) => match &change.value {
+                    Some(Op::Modify(written)) => Some(written),
+                    Some(_) => return Ok(false),
+                    None => None,
+                },
+                None => None,
+            };
+            match written {
+                Some(written) => {
+                    match commutative_objects.rebase(&id, &base.value, &current.value, written)? {
+                        Some(value) => rebased_values.push((field_key, value)),
+                        None => return Ok(false),
+                    }
+                }
+                None if base.value != current.value => return Ok(false),
+                None => {}
+            }
+        }
+        for (field_key, value) in rebased_values {
+            if let Some(change) = self.output.changeset.changes.get_mut(&field_key) {
+                change.value = Some(Op::Modify(value));
+            }
+        }
+        Ok(true)
+    }
+
+    /// The change set records the root before the changes, rebase it to the latest root.
+    fn rebase(&mut self, base_root: &ObjectMeta, current_root: &ObjectMeta) {
+        let change_set = &mut self.output.changeset;
+        change_set.state_root = current_root.state_root();
+        change_set.global_size = current_root.size + change_set.global_size - base_root.size;
+    }
+
+    fn touch_modules(&self) -> bool {
+        let module_store_id = ModuleStore::object_id();
+        self.write_set
+            .iter()
+            .any(|id| id == &module_store_id || id.parent().as_ref() == Some(&module_store_id))
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ParallelExecutionStats {
+    pub tx_count: usize,
+    /// The transactions re-executed because of conflicts
+    pub reexecuted_count: usize,
+    /// The batch publishes or upgrades modules, and is executed serially
+    pub serial_fallback: bool,
+}
+
+pub struct ParallelExecutor<'a> {
+    moveos: &'a MoveOS,
+    commutative_objects: Option<&'a dyn CommutativeObjects>,
+}
+
+impl<'a> ParallelExecutor<'a> {
+    pub fn new(moveos: &'a MoveOS) -> Self {
+        Self {
+            moveos,
+            commutative_objects: None,
+        }
+    }
+
+    pub fn with_commutative_objects(
+        mut self,
+        commutative_objects: &'a dyn CommutativeObjects,
+    ) -> Self {
+        self.commutative_objects = Some(commutative_objects);
+        self
+    }
+
+    /// Execute the transactions in the batch order starting from the `root`, the root of every transaction is ignored.
+    /// The `commit` callback is called with the transaction index and output in order,
+    /// it should apply the change set to the store and return the new root.
+    pub fn execute_batch<F>(
+        &self,
+        root: ObjectMeta,
+        txs: Vec<VerifiedMoveOSTransaction>,
+        mut commit: F,
+    ) -> Result<ParallelExecutionStats>
+    where
+        F: FnMut(usize, RawTransactionOutput, Option<VMErrorInfo>) -> Result<ObjectMeta>,
+    {
+        let mut stats = ParallelExecutionStats {
+            tx_count: txs.len(),
+            ..Default::default()
+        };
+        let base_root = root;
+        let speculative_outputs = txs
+            .par_iter()
+            .map(|tx| self.execute_tx(tx.clone(), &base_root).ok())
+            .collect::<Vec<_>>();
+
+        // The VM loader caches modules across sessions, a transaction may observe the modules
+        // published by a concurrent transaction, so discard all the speculative outputs.
+        let serial_fallback = speculative_outputs
+            .iter()
+            .flatten()
+            .any(|output| output.output.is_upgrade || output.touch_modules());
+        if serial_fallback {
+            self.moveos.flush_module_cache(true)?;
+            stats.serial_fallback = true;
+        }
+
+        let mut current_root = base_root.clone();
+        let mut committed_writes = BTreeSet::new();
+        // After the gas schedule updated, the speculative outputs are charged with the stale cost table.
+        let mut gas_upgraded = false;
+        for (index, (tx, speculative_output)) in
+            txs.into_iter().zip(speculative_outputs).enumerate()
+        {
+            let valid_output = match speculative_output {
+                Some(mut output) if !serial_fallback && !gas_upgraded => output
+                    .validate(
+                        &committed_writes,
+                        &base_root,
+                        &current_root,
+                        self.moveos.moveos_store(),
+                        self.commutative_objects,
+                    )?
+                    .then_some(output),
+                _ => None,
+            };
+            let output = match valid_output {
+                Some(mut output) => {
+                    output.rebase(&base_root, &current_root);
+                    output
+                }
+                None => {
+                    stats.reexecuted_count += 1;
+                    self.execute_tx(tx, &current_root)?
+                }
+            };
+            let SpeculativeOutput {
+                output,
+                vm_error_info,
+                write_set,
+                ..
+            } = output;
+            committed_writes.extend(write_set);
+            if output.is_gas_upgrade {
+                gas_upgraded = true;
+                self.moveos.flush_cost_table();
+            }
+            if serial_fallback && output.is_upgrade {
+                self.moveos.flush_module_cache(true)?;
+            }
+            current_root = commit(index, output, vm_error_info)?;
+        }
+
+        if tracing::enabled!(tracing::Level::DEBUG) {
+            tracing::debug!(
+                "execute_batch txs: {}, reexecuted: {}, serial_fallback: {}",
+                stats.tx_count,
+                stats.reexecuted_count,
+                stats.serial_fallback
+            );
+        }
+        Ok(stats)
+    }
+
+    fn execute_tx(
+        &self,
+        mut tx: VerifiedMoveOSTransaction,
+        root: &ObjectMeta,
+    ) -> Result<SpeculativeOutput> {
+        tx.root = root.clone();
+        let recorder = ReadSetRecorder::new(self.moveos.moveos_store(), root);
+        let (output, vm_error_info) = self.moveos.execute_with_resolver(tx, &recorder)?;
+        let mut write_set = BTreeSet::new();
+        collect_write_set(&output.changeset, &mut write_set);
+        Ok(SpeculativeOutput {
+            output,
+            vm_error_info,
+            read_set: recorder.into_read_set(),
+            write_set,
+        })
+    }
+}
+
+/// Collect the ids of all the changed objects, include the parent objects whose metadata are changed.
+fn collect_write_set(change_set: &StateChangeSet, write_set: &mut BTreeSet<ObjectID>) {
+    fn collect(changes: &BTreeMap<FieldKey, ObjectChange>, write_set: &mut BTreeSet<ObjectID>) {
+        for change in changes.values() {
+            write_set.insert(change.metadata.id.clone());
+            collect(&change.fields, write_set);
+        }
+    }
+    collect(&change_set.changes, write_set);
+}
@@ -0,0 +1,239 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+//! The `MoveAction::Batch` executes multiple function calls in one session atomically.
+//! A batch call can be an entry function, or a public function which returns `Object<T>` values.
+//! The returned objects are held by the transaction, and must be consumed by value by the later calls,
+//! the later call refers to the returned object via `CallResultRef` argument.
+
+use super::moveos_vm::MoveOSSession;
+use super::tx_argument_resolver::{as_struct_no_panic, is_object};
+use move_binary_format::{
+    CompiledModule,
+    access::ModuleAccess,
+    errors::{Location, PartialVMError, VMResult},
+    file_format::Visibility,
+};
+use move_core_types::vm_status::StatusCode;
+use move_vm_runtime::session::LoadedFunctionInstantiation;
+use move_vm_types::loaded_data::runtime_types::Type;
+use moveos_common::types::{ClassifiedGasMeter, SwitchableGasMeter};
+use moveos_types::{
+    moveos_std::object::ObjectID,
+    state::MoveState,
+    state_resolver::MoveOSResolver,
+    transaction::{CallResultRef, FunctionCall},
+};
+use std::collections::BTreeSet;
+
+/// The max number of function calls in a batch
+pub const MAX_BATCH_CALLS: usize = 64;
+
+impl<'r, 'l, S, G> MoveOSSession<'r, 'l, S, G>
+where
+    S: MoveOSResolver,
+    G: SwitchableGasMeter + ClassifiedGasMeter,
+{
+    /// Verify the calls of a batch action.
+    /// The arguments of a call which refers to the previous results can only be resolved when executing,
+    /// so we only check the `CallResultRef` is valid, and every returned object is consumed exactly once.
+    pub(crate) fn verify_batch_calls(&self, calls: &[FunctionCall]) -> VMResult<()> {
+        if calls.is_empty() || calls.len() > MAX_BATCH_CALLS {
+            return Err(
+                PartialVMError::new(StatusCode::NUMBER_OF_ARGUMENTS_MISMATCH)
+                    .with_message(format!(
+                        "The number of batch calls should be in [1, {}], got: {}",
+                        MAX_BATCH_CALLS,
+                        calls.len()
+                    ))
+                    .finish(Location::Undefined),
+            );
+        }
+
+        let mut return_counts: Vec<usize> = Vec::with_capacity(calls.len());
+        let mut consumed_results = BTreeSet::new();
+        for (call_index, call) in calls.iter().enumerate() {
+            let loaded_function = self.session.load_function(
+                &call.function_id.module_id,
+                &call.function_id.function_name,
+                call.ty_args.as_slice(),
+            )?;
+            let location = Location::Module(call.function_id.module_id.clone());
+            self.verify_batch_function(call, &loaded_function, location.clone())?;
+
+            let mut has_result_ref = false;
+            for arg in call.args.iter() {
+                if let Some(result_ref) = CallResultRef::from_arg(arg) {
+                    has_result_ref = true;
+                    let return_count = return_counts
+                        .get(result_ref.call_index as usize)
+                        .copied()
+                        .unwrap_or_default();
+                    if result_ref.result_index as usize >= return_count {
+                        return Err(PartialVMError::new(
+                            StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT,
+                        )
+                        .with_message(format!(
+                            "Invalid {} in the call {}, it should refer to a result of a previous call",
+                            result_ref, call_index
+                        ))
+                        .finish(location));
+                    }
+                    if !consumed_results.insert(result_ref) {
+                        return Err(PartialVMError::new(
+                            StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT,
+                        )
+                        .with_message(format!("The {} is consumed more than once", result_ref))
+                        .finish(location));
+                    }
+                }
+            }
+            if !has_result_ref {
+                let _resolved_args =
+                    self.resolve_argument(&loaded_function, call.args.clone(), location, false)?;
+            }
+            return_counts.push(loaded_function.return_.len());
+        }
+
+        let returned_count: usize = return_counts.iter().sum();
+        if consumed_results.len() != returned_count {
+            return Err(PartialVMError::new(StatusCode::TYPE_MISMATCH)
+                .with_message(format!(
+                    "All the returned objects should be consumed in the batch, returned: {}, consumed: {}",
+                    returned_count,
+                    consumed_results.len()
+                ))
+                .finish(Location::Undefined));
+        }
+        Ok(())
+    }
+
+    /// The batch call should be an entry function or a public function,
+    /// and the function can only return `Object<T>` values.
+    fn verify_batch_function(
+        &self,
+        call: &FunctionCall,
+        loaded_function: &LoadedFunctionInstantiation,
+        location: Location,
+    ) -> VMResult<()> {
+        let module_bytes = self.session.load_module(&call.function_id.module_id)?;
+        let module = CompiledModule::deserialize(module_bytes.as_slice())
+            .map_err(|e| e.finish(location.clone()))?;
+        let callable = module.function_defs().iter().any(|fdef| {
+            let handle = module.function_handle_at(fdef.function);
+            module.identifier_at(handle.name) == call.function_id.function_name.as_ident_str()
+                && (fdef.is_entry || fdef.visibility == Visibility::Public)
+        });
+        if !callable {
+            return Err(PartialVMError::new(
+                StatusCode::EXECUTE_ENTRY_FUNCTION_CALLED_ON_NON_ENTRY_FUNCTION,
+            )
+            .with_message(format!(
+                "The batch call {} should be an entry function or a public function",
+                call.function_id
+            ))
+            .finish(location));
+        }
+
+        for return_type in loaded_function.return_.iter() {
+            let is_object_value = matches!(return_type, Type::StructInstantiation(_, _))
+                && as_struct_no_panic(&self.session, return_type)
+                    .map(|struct_type| is_object(&struct_type))
+                    .unwrap_or(false);
+            if !is_object_value {
+                return Err(PartialVMError::new(StatusCode::TYPE_MISMATCH)
+                    .with_message(format!(
+                        "The batch call {} can only return `Object<T>` values",
+                        call.function_id
+                    ))
+                    .finish(location));
+            }
+        }
+        Ok(())
+    }
+
+    /// Execute the calls of a batch action in order.
+    /// The calls are executed bypassing the visibility, so they are verified again here,
+    /// the dry run converts the action to `VerifiedMoveAction::Batch` without the verification.
+    pub(crate) fn execute_batch_calls(&mut self, calls: Vec<FunctionCall>) -> VMResult<()> {
+        self.verify_batch_calls(&calls)?;
+        let mut returned_objects: Vec<Vec<ObjectID>> = Vec::with_capacity(calls.len());
+        for call in calls {
+            let loaded_function = self.session.load_function(
+                &call.function_id.module_id,
+                &call.function_id.function_name,
+                call.ty_args.as_slice(),
+            )?;
+            let location = Location::Module(call.function_id.module_id.clone());
+
+            let mut consumed_objects = vec![];
+            let mut args = Vec::with_capacity(call.args.len());
+            for arg in call.args {
+                match CallResultRef::from_arg(&arg) {
+                    Some(result_ref) => {
+                        let object_id = returned_objects
+                            .get(result_ref.call_index as usize)
+                            .and_then(|objects| objects.get(result_ref.result_index as usize))
+                            .filter(|object_id| self.held_objects.contains(*object_id))
+                            .ok_or_else(|| {
+                                PartialVMError::new(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT)
+                                    .with_message(format!(
+                                        "The {} does not exist or is consumed",
+                                        result_ref
+                                    ))
+                                    .finish(location.clone())
+                            })?;
+                        args.push(object_id.to_bytes());
+                        consumed_objects.push(object_id.clone());
+                    }
+                    None => args.push(arg),
+                }
+            }
+
+            let serialized_args =
+                self.resolve_argument(&loaded_function, args, location.clone(), true)?;
+            for object_id in consumed_objects {
+                self.held_objects.remove(&object_id);
+            }
+            let return_values = self.session.execute_function_bypass_visibility(
+                &call.function_id.module_id,
+                &call.function_id.function_name,
+                call.ty_args,
+                serialized_args,
+                &mut self.gas_meter,
+            )?;
+            // Release the arguments, so the objects can be borrowed by the next call.
+            self.object_runtime
+                .write()
+                .release_arguments()
+                .map_err(|e| e.finish(location.clone()))?;
+
+            let mut objects = Vec::with_capacity(return_values.return_values.len());
+            for (value, _layout) in return_values.return_values {
+                // The `Object<T>` is a struct with only one `ObjectID` field, it has the same BCS encoding as `ObjectID`.
+                let object_id = ObjectID::from_bytes(value).map_err(|e| {
+                    PartialVMError::new(StatusCode::VALUE_DESERIALIZATION_ERROR)
+                        .with_message(format!("Invalid returned object: {:?}", e))
+                        .finish(location.clone())
+                })?;
+                self.object_runtime
+                    .write()
+                    .return_object_pointer(self, &object_id)
+                    .map_err(|e| e.finish(location.clone()))?;
+                self.held_objects.insert(object_id.clone());
+                objects.push(object_id);
+            }
+            returned_objects.push(objects);
+        }
+
+        if !self.held_objects.is_empty() {
+            return Err(PartialVMError::new(StatusCode::TYPE_MISMATCH)
+                .with_message(format!(
+                    "The returned objects are not consumed: {:?}",
+                    self.held_objects
+                ))
+                .finish(Location::Undefined));
+        }
+        Ok(())
+    }
+}
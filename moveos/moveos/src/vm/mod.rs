@@ -1,9 +1,11 @@
 // Copyright (c) Kanari Network
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod batch_call;
 #[allow(dead_code)]
 pub mod data_cache;
 pub mod moveos_vm;
+pub mod tracer;
 pub mod tx_argument_resolver;
 pub mod vm_status_explainer;
 
@@ -41,6 +41,7 @@ use moveos_types::{
     move_std::string::MoveString,
     move_types::FunctionId,
     moveos_std::copyable_any::Any,
+    moveos_std::object::ObjectID,
     moveos_std::simple_map::SimpleMap,
     moveos_std::tx_context::TxContext,
     moveos_std::{event::TransactionEvent, module_upgrade_flag::ModuleUpgradeFlag},
@@ -48,6 +49,7 @@ use moveos_types::{
     transaction::{FunctionCall, MoveAction, VerifiedMoveAction},
 };
 use moveos_verifier::verifier::INIT_FN_NAME_IDENTIFIER;
+use moveos_wasm::context::NativeWASMContext;
 use parking_lot::RwLock;
 use std::collections::BTreeSet;
 use std::rc::Rc;
@@ -141,6 +143,8 @@ pub struct MoveOSSession<'r, 'l, S, G> {
     pub(crate) object_runtime: Rc<RwLock<ObjectRuntime<'r>>>,
     pub(crate) gas_meter: G,
     pub(crate) read_only: bool,
+    /// The objects returned by the calls of a batch action, and not consumed yet.
+    pub(crate) held_objects: BTreeSet<ObjectID>,
 }
 
 #[allow(clippy::arc_with_non_send_sync)]
@@ -163,6 +167,7 @@ where
             object_runtime,
             gas_meter,
             read_only,
+            held_objects: BTreeSet::new(),
         }
     }
 
@@ -176,6 +181,7 @@ where
         Self {
             session: Self::new_inner_session(self.vm, self.remote, object_runtime.clone()),
             object_runtime,
+            held_objects: BTreeSet::new(),
             ..self
         }
     }
@@ -194,6 +200,7 @@ where
         ));
         extensions.add(NativeModuleContext::new(remote));
         extensions.add(NativeEventContext::default());
+        extensions.add(NativeWASMContext::default());
 
         // The VM code loader has bugs around module upgrade. After a module upgrade, the internal
         // cache needs to be flushed to work around those bugs.
@@ -300,6 +307,10 @@ where
                     init_function_modules,
                 })
             }
+            MoveAction::Batch(calls) => {
+                self.verify_batch_calls(&calls)?;
+                Ok(VerifiedMoveAction::Batch { calls })
+            }
         }
     }
 
@@ -467,6 +478,7 @@ where
 
                 self.execute_init_modules(modules_with_init)
             }
+            VerifiedMoveAction::Batch { calls } => self.execute_batch_calls(calls),
         };
 
         if action_result.is_ok() {
@@ -581,6 +593,7 @@ where
             object_runtime,
             gas_meter: _,
             read_only,
+            held_objects: _,
         } = self;
         let (changeset, raw_events, mut extensions) = session.finish_with_extensions()?;
         //We do not use the event API from data_cache. Instead, we use the NativeEventContext
@@ -592,6 +605,15 @@ where
 
         let event_context = extensions.remove::<NativeEventContext>();
         let raw_events = event_context.into_events();
+        // Release the WASM instances the transaction did not release
+        let mut wasm_context = extensions.remove::<NativeWASMContext>();
+        let unreleased_instances = wasm_context.release_all();
+        if unreleased_instances > 0 {
+            tracing::debug!(
+                "Release {} wasm instances on session finish",
+                unreleased_instances
+            );
+        }
         drop(extensions);
 
         let (ctx, state_changeset) =
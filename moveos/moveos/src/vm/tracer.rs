@@ -0,0 +1,521 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use move_binary_format::errors::PartialVMResult;
+use move_binary_format::file_format::CodeOffset;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::gas_algebra::{InternalGas, NumArgs, NumBytes};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use move_core_types::u256::U256;
+use move_vm_types::gas::{GasMeter, SimpleInstruction};
+use move_vm_types::views::{TypeView, ValueView, ValueVisitor};
+use moveos_common::types::{ClassifiedGasMeter, GasStatement, SwitchableGasMeter};
+use moveos_types::move_types::FunctionId;
+use moveos_types::trace::{CallTrace, TraceValue};
+use moveos_types::transaction::VerifiedMoveAction;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+#[derive(Debug, Default)]
+struct TracerState {
+    active: bool,
+    /// The entry calls of the action which are not started yet.
+    /// The VM does not notify the gas meter when it enters an entry call, so the entry call is started
+    /// by the first event received when no call is open.
+    pending_roots: VecDeque<CallTrace>,
+    stack: Vec<CallTrace>,
+    roots: Vec<CallTrace>,
+}
+
+impl TracerState {
+    fn open_root_if_needed(&mut self) {
+        if self.stack.is_empty() {
+            let root = self
+                .pending_roots
+                .pop_front()
+                .unwrap_or_else(|| CallTrace::new(None, vec![], vec![], false));
+            self.stack.push(root);
+        }
+    }
+
+    fn close_call(&mut self, call: CallTrace) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.calls.push(call),
+            None => self.roots.push(call),
+        }
+    }
+}
+
+/// Records the call tree of a transaction action, it is shared by the `TracingGasMeter`s of a session.
+#[derive(Debug, Clone, Default)]
+pub struct TxTracer {
+    state: Arc<Mutex<TracerState>>,
+}
+
+impl TxTracer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracing the calls of the action.
+    pub fn start(&self, action: &VerifiedMoveAction) {
+        let raw_args = |args: &[Vec<u8>]| {
+            args.iter()
+                .cloned()
+                .map(TraceValue::Raw)
+                .collect::<Vec<_>>()
+        };
+        let mut state = self.state.lock();
+        state.pending_roots = match action {
+            VerifiedMoveAction::Script { call } => vec![CallTrace::new(
+                None,
+                call.ty_args.clone(),
+                raw_args(&call.args),
+                false,
+            )],
+            VerifiedMoveAction::Function { call, .. } => vec![CallTrace::new(
+                Some(call.function_id.clone()),
+                call.ty_args.clone(),
+                raw_args(&call.args),
+                false,
+            )],
+            VerifiedMoveAction::Batch { calls } => calls
+                .iter()
+                .map(|call| {
+                    CallTrace::new(
+                        Some(call.function_id.clone()),
+                        call.ty_args.clone(),
+                        raw_args(&call.args),
+                        false,
+                    )
+                })
+                .collect(),
+            // The module init functions are unnamed roots.
+            VerifiedMoveAction::ModuleBundle { .. } => vec![],
+        }
+        .into();
+        state.active = true;
+    }
+
+    /// Stop tracing, the unfinished calls are closed without return values.
+    /// If the action failed before any call was traced, the failed entry call is recorded.
+    pub fn stop(&self, failed: bool) {
+        let mut state = self.state.lock();
+        state.active = false;
+        if failed && state.stack.is_empty() {
+            let root = state.pending_roots.pop_front();
+            state.roots.extend(root);
+        }
+        while let Some(call) = state.stack.pop() {
+            state.close_call(call);
+        }
+        state.pending_roots.clear();
+    }
+
+    pub fn into_calls(self) -> Vec<CallTrace> {
+        std::mem::take(&mut self.state.lock().roots)
+    }
+
+    fn on_call(
+        &self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: Vec<TypeTag>,
+        args: impl Iterator<Item = impl ValueView>,
+    ) {
+        let mut state = self.state.lock();
+        if !state.active {
+            return;
+        }
+        state.open_root_if_needed();
+        let function = Identifier::new(func_name)
+            .ok()
+            .map(|name| FunctionId::new(module_id.clone(), name));
+        let call = CallTrace::new(function, ty_args, args.map(trace_value).collect(), false);
+        state.stack.push(call);
+    }
+
+    fn on_return(&self, ret_vals: impl Iterator<Item = impl ValueView>) {
+        let mut state = self.state.lock();
+        if !state.active {
+            return;
+        }
+        state.open_root_if_needed();
+        let mut call = state.stack.pop().expect("call must exist");
+        call.return_values = Some(ret_vals.map(trace_value).collect());
+        state.close_call(call);
+    }
+
+    fn on_native_return(&self, ret_vals: Option<impl Iterator<Item = impl ValueView>>) {
+        let mut state = self.state.lock();
+        if !state.active {
+            return;
+        }
+        // The native call is started by `on_call` like the other calls
+        if let Some(mut call) = state.stack.pop() {
+            call.is_native = true;
+            call.return_values = ret_vals.map(|vals| vals.map(trace_value).collect());
+            state.close_call(call);
+        }
+    }
+}
+
+/// A gas meter wrapper which reports the calls to the `TxTracer`, the gas charging is delegated to the base meter.
+#[derive(Debug, Clone)]
+pub struct TracingGasMeter<G> {
+    base: G,
+    tracer: TxTracer,
+}
+
+impl<G> TracingGasMeter<G> {
+    pub fn new(base: G, tracer: TxTracer) -> Self {
+        Self { base, tracer }
+    }
+}
+
+macro_rules! delegate_mut {
+    ($(
+        fn $fn: ident $(<$($lt: lifetime),*>)? (&mut self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
+    )*) => {
+        $(fn $fn $(<$($lt)*>)? (&mut self, $($arg: $ty),*) -> $ret_ty {
+            self.base.$fn($($arg),*)
+        })*
+    };
+}
+
+impl<G: GasMeter> GasMeter for TracingGasMeter<G> {
+    delegate_mut! {
+        fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()>;
+
+        fn charge_br_true(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()>;
+
+        fn charge_br_false(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()>;
+
+        fn charge_branch(&mut self, target_offset: CodeOffset) -> PartialVMResult<()>;
+
+        fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()>;
+
+        fn charge_ld_const_after_deserialization(&mut self, val: impl ValueView)
+            -> PartialVMResult<()>;
+
+        fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_pack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_unpack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_write_ref(
+            &mut self,
+            new_val: impl ValueView,
+            old_val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_borrow_global(
+            &mut self,
+            is_mut: bool,
+            is_generic: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_exists(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            exists: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_move_from(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        fn charge_move_to(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            val: impl ValueView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_pack<'a>(
+            &mut self,
+            ty: impl TypeView + 'a,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        fn charge_vec_borrow(
+            &mut self,
+            is_mut: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_push_back(
+            &mut self,
+            ty: impl TypeView,
+            val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_pop_back(
+            &mut self,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_unpack(
+            &mut self,
+            ty: impl TypeView,
+            expect_num_elements: NumArgs,
+            elems: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        fn charge_load_resource(
+            &mut self,
+            addr: AccountAddress,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+            bytes_loaded: NumBytes,
+        ) -> PartialVMResult<()>;
+
+        fn charge_native_function_before_execution(
+            &mut self,
+            ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_drop_frame(
+            &mut self,
+            locals: impl Iterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        self.base.balance_internal()
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.tracer
+            .on_call(module_id, func_name, vec![], args.clone());
+        self.base
+            .charge_call(module_id, func_name, args, num_locals)
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.tracer.on_call(
+            module_id,
+            func_name,
+            ty_args.clone().map(|ty| ty.to_type_tag()).collect(),
+            args.clone(),
+        );
+        self.base
+            .charge_call_generic(module_id, func_name, ty_args, args, num_locals)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView> + Clone>,
+    ) -> PartialVMResult<()> {
+        self.tracer.on_native_return(ret_vals.clone());
+        self.base.charge_native_function(amount, ret_vals)
+    }
+
+    fn charge_return(
+        &mut self,
+        ret_vals: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()> {
+        self.tracer.on_return(ret_vals.clone());
+        self.base.charge_return(ret_vals)
+    }
+}
+
+impl<G: ClassifiedGasMeter> ClassifiedGasMeter for TracingGasMeter<G> {
+    fn charge_execution(&mut self, gas_cost: u64) -> PartialVMResult<()> {
+        self.base.charge_execution(gas_cost)
+    }
+
+    fn charge_io_write(&mut self, data_size: u64) -> PartialVMResult<()> {
+        self.base.charge_io_write(data_size)
+    }
+
+    fn check_constrains(&self, max_gas_amount: u64) -> PartialVMResult<()> {
+        self.base.check_constrains(max_gas_amount)
+    }
+
+    fn gas_statement(&self) -> GasStatement {
+        self.base.gas_statement()
+    }
+}
+
+impl<G: SwitchableGasMeter> SwitchableGasMeter for TracingGasMeter<G> {
+    fn stop_metering(&mut self) {
+        self.base.stop_metering()
+    }
+
+    fn start_metering(&mut self) {
+        self.base.start_metering()
+    }
+
+    fn is_metering(&self) -> bool {
+        self.base.is_metering()
+    }
+}
+
+fn trace_value(value: impl ValueView) -> TraceValue {
+    let mut builder = TraceValueBuilder::default();
+    value.visit(&mut builder);
+    builder.result.expect("value must be visited")
+}
+
+enum ContainerKind {
+    Vector,
+    Struct,
+    Reference { is_global: bool },
+}
+
+struct PendingContainer {
+    kind: ContainerKind,
+    len: usize,
+    items: Vec<TraceValue>,
+}
+
+impl PendingContainer {
+    fn into_value(mut self) -> TraceValue {
+        match self.kind {
+            ContainerKind::Vector => TraceValue::Vector(self.items),
+            ContainerKind::Struct => TraceValue::Struct(self.items),
+            ContainerKind::Reference { is_global } => TraceValue::Reference {
+                is_global,
+                value: Box::new(self.items.pop().expect("referenced value must exist")),
+            },
+        }
+    }
+}
+
+/// Builds the `TraceValue` from the pre-order traversal of the `ValueVisitor`.
+#[derive(Default)]
+struct TraceValueBuilder {
+    stack: Vec<PendingContainer>,
+    result: Option<TraceValue>,
+}
+
+impl TraceValueBuilder {
+    fn push_container(&mut self, kind: ContainerKind, len: usize) {
+        let container = PendingContainer {
+            kind,
+            len,
+            items: Vec::with_capacity(len),
+        };
+        if len == 0 {
+            self.push_value(container.into_value());
+        } else {
+            self.stack.push(container);
+        }
+    }
+
+    fn push_value(&mut self, mut value: TraceValue) {
+        while let Some(top) = self.stack.last_mut() {
+            top.items.push(value);
+            if top.items.len() < top.len {
+                return;
+            }
+            value = self.stack.pop().expect("container must exist").into_value();
+        }
+        self.result = Some(value);
+    }
+}
+
+impl ValueVisitor for TraceValueBuilder {
+    fn visit_u8(&mut self, _depth: usize, val: u8) {
+        self.push_value(TraceValue::U8(val))
+    }
+
+    fn visit_u16(&mut self, _depth: usize, val: u16) {
+        self.push_value(TraceValue::U16(val))
+    }
+
+    fn visit_u32(&mut self, _depth: usize, val: u32) {
+        self.push_value(TraceValue::U32(val))
+    }
+
+    fn visit_u64(&mut self, _depth: usize, val: u64) {
+        self.push_value(TraceValue::U64(val))
+    }
+
+    fn visit_u128(&mut self, _depth: usize, val: u128) {
+        self.push_value(TraceValue::U128(val))
+    }
+
+    fn visit_u256(&mut self, _depth: usize, val: U256) {
+        self.push_value(TraceValue::U256(val))
+    }
+
+    fn visit_bool(&mut self, _depth: usize, val: bool) {
+        self.push_value(TraceValue::Bool(val))
+    }
+
+    fn visit_address(&mut self, _depth: usize, val: AccountAddress) {
+        self.push_value(TraceValue::Address(val))
+    }
+
+    fn visit_struct(&mut self, _depth: usize, len: usize) -> bool {
+        self.push_container(ContainerKind::Struct, len);
+        true
+    }
+
+    fn visit_vec(&mut self, _depth: usize, len: usize) -> bool {
+        self.push_container(ContainerKind::Vector, len);
+        true
+    }
+
+    fn visit_ref(&mut self, _depth: usize, is_global: bool) -> bool {
+        self.push_container(ContainerKind::Reference { is_global }, 1);
+        true
+    }
+
+    fn visit_vec_u8(&mut self, _depth: usize, vals: &[u8]) {
+        self.push_value(TraceValue::Bytes(vals.to_vec()))
+    }
+}
@@ -13,10 +13,9 @@ use move_vm_types::loaded_data::runtime_types::{StructType, Type};
 use moveos_common::types::{ClassifiedGasMeter, SwitchableGasMeter};
 use moveos_object_runtime::TypeLayoutLoader;
 use moveos_object_runtime::resolved_arg::ResolvedArg;
-use moveos_types::state::ObjectState;
 use moveos_types::{
     move_std::{ascii::MoveAsciiString, string::MoveString},
-    moveos_std::object::{ObjectID, is_object_struct},
+    moveos_std::object::{ObjectID, ObjectMeta, is_object_struct},
     state::MoveState,
 };
 use moveos_types::{
@@ -94,32 +93,53 @@ where
             .collect())
     }
 
+    /// Get the object metadata for argument resolution.
+    /// The objects loaded in the ObjectRuntime are preferred, because they maybe changed by the previous calls in the same session.
     fn load_object_and_check_type(
         &self,
         object_id: &ObjectID,
         object_type: TypeTag,
         location: Location,
-    ) -> VMResult<ObjectState> {
-        let object = self
-            .remote
-            .get_object(object_id)
-            .map_err(|e| {
-                PartialVMError::new(StatusCode::STORAGE_ERROR)
-                    .with_message(format!("Failed to resolve object state: {:?}", e))
-                    .finish(location.clone())
-            })?
-            .ok_or_else(|| {
-                PartialVMError::new(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT)
-                    .with_message(format!("Object not found: {:?}", object_id))
-                    .finish(location.clone())
-            })?;
+    ) -> VMResult<ObjectMeta> {
+        let loaded_object = {
+            let object_runtime = self.object_runtime.read();
+            match object_runtime
+                .get_loaded_object(object_id)
+                .map_err(|e| e.finish(location.clone()))?
+            {
+                Some(rt_obj) if rt_obj.is_none() => Some(None),
+                Some(rt_obj) => Some(Some(
+                    rt_obj
+                        .metadata()
+                        .map_err(|e| e.finish(location.clone()))?
+                        .clone(),
+                )),
+                None => None,
+            }
+        };
+        let object = match loaded_object {
+            Some(object) => object,
+            None => self
+                .remote
+                .get_object(object_id)
+                .map_err(|e| {
+                    PartialVMError::new(StatusCode::STORAGE_ERROR)
+                        .with_message(format!("Failed to resolve object state: {:?}", e))
+                        .finish(location.clone())
+                })?
+                .map(|object| object.metadata),
+        }
+        .ok_or_else(|| {
+            PartialVMError::new(StatusCode::FAILED_TO_DESERIALIZE_ARGUMENT)
+                .with_message(format!("Object not found: {:?}", object_id))
+                .finish(location.clone())
+        })?;
 
         if !object.match_type(&object_type) {
             return Err(PartialVMError::new(StatusCode::TYPE_MISMATCH)
                 .with_message(format!(
                     "Invalid object type, object type in argument:{:?}, object type in store:{:?}",
-                    object_type,
-                    object.object_type()
+                    object_type, object.object_type
                 ))
                 .finish(location.clone()));
         }
@@ -296,10 +316,21 @@ where
                     object_type.clone(),
                     location.clone(),
                 )?;
+                // The object returned by a previous call of the batch is held by the transaction,
+                // it can only be consumed by value.
+                let is_held = self.held_objects.contains(&object_id);
+                if is_held && !matches!(ty, StructInstantiation(_, _)) {
+                    return Err(PartialVMError::new(StatusCode::TYPE_MISMATCH)
+                        .with_message(format!(
+                            "The object returned by a previous call must be passed by value, object id:{:?}",
+                            object_id
+                        ))
+                        .finish(location.clone()));
+                }
 
                 match ty {
                     Reference(_) => {
-                        let mut v = object.id().to_bytes();
+                        let mut v = object.id.to_bytes();
                         arg.append(&mut v);
                     }
                     MutableReference(_) => {
@@ -312,16 +343,15 @@ where
                                 .finish(location.clone()));
                         }
                         let sender = self.tx_context().sender();
-                        if !object.is_shared() && object.owner() != sender {
+                        if !object.is_shared() && object.owner != sender {
                             return Err(PartialVMError::new(StatusCode::NO_ACCOUNT_ROLE)
                                 .with_message(format!(
                                     "Object owner mismatch, object owner:{:?}, sender:{:?}",
-                                    object.owner(),
-                                    sender
+                                    object.owner, sender
                                 ))
                                 .finish(location.clone()));
                         }
-                        let mut v = object.id().to_bytes();
+                        let mut v = object.id.to_bytes();
                         arg.append(&mut v);
                     }
                     StructInstantiation(_, instantiation_types) => {
@@ -352,16 +382,15 @@ where
                                 .finish(location.clone()));
                         }
                         let sender = self.tx_context().sender();
-                        if object.owner() != sender {
+                        if !is_held && object.owner != sender {
                             return Err(PartialVMError::new(StatusCode::NO_ACCOUNT_ROLE)
                                 .with_message(format!(
                                     "Object owner mismatch, object owner:{:?}, sender:{:?}",
-                                    object.owner(),
-                                    sender
+                                    object.owner, sender
                                 ))
                                 .finish(location.clone()));
                         }
-                        let mut v = object.id().to_bytes();
+                        let mut v = object.id.to_bytes();
                         arg.append(&mut v);
                     }
                     _ => {
@@ -374,7 +403,7 @@ where
 
                 if load_object {
                     let mut object_runtime = self.object_runtime.write();
-                    object_runtime.load_object_argument(object.id(), ty, self)?;
+                    object_runtime.load_object_argument(&object.id, ty, self)?;
                 }
                 Ok(())
             } else if self.read_only || is_allowed_argument_struct(&struct_arg_type) {
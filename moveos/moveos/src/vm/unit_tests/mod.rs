@@ -2,4 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod data_cache_tests;
+pub mod tracer_tests;
 pub mod vm_arguments_tests;
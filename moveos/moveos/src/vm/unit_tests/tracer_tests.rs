@@ -0,0 +1,122 @@
+// Copyright (c) Kanari Network
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::vm::tracer::{TracingGasMeter, TxTracer};
+use move_core_types::account_address::AccountAddress;
+use move_core_types::gas_algebra::{InternalGas, NumArgs};
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::ModuleId;
+use move_vm_types::gas::{GasMeter, UnmeteredGasMeter};
+use move_vm_types::values::{Struct, Value};
+use moveos_types::move_types::FunctionId;
+use moveos_types::trace::TraceValue;
+use moveos_types::transaction::{FunctionCall, VerifiedMoveAction};
+
+fn module_id() -> ModuleId {
+    ModuleId::new(AccountAddress::ONE, Identifier::new("m").unwrap())
+}
+
+fn function_action() -> VerifiedMoveAction {
+    VerifiedMoveAction::Function {
+        call: FunctionCall::new(
+            FunctionId::new(module_id(), Identifier::new("entry").unwrap()),
+            vec![],
+            vec![vec![1u8]],
+        ),
+        bypass_visibility: false,
+    }
+}
+
+#[test]
+fn test_trace_call_tree() {
+    let tracer = TxTracer::new();
+    let mut gas_meter = TracingGasMeter::new(UnmeteredGasMeter, tracer.clone());
+    tracer.start(&function_action());
+
+    let args = vec![
+        Value::u64(7),
+        Value::struct_(Struct::pack(vec![
+            Value::bool(true),
+            Value::vector_u8(vec![1, 2]),
+        ])),
+    ];
+    gas_meter
+        .charge_call(&module_id(), "callee", args.iter(), NumArgs::new(2))
+        .unwrap();
+    gas_meter
+        .charge_call(
+            &module_id(),
+            "native_fn",
+            std::iter::empty::<&Value>(),
+            NumArgs::new(0),
+        )
+        .unwrap();
+    gas_meter
+        .charge_native_function(InternalGas::zero(), Some(vec![Value::u8(3)].iter()))
+        .unwrap();
+    gas_meter
+        .charge_return(vec![Value::address(AccountAddress::ONE)].iter())
+        .unwrap();
+    gas_meter
+        .charge_return(std::iter::empty::<&Value>())
+        .unwrap();
+    tracer.stop(false);
+
+    let calls = tracer.into_calls();
+    assert_eq!(calls.len(), 1);
+    let root = &calls[0];
+    assert_eq!(
+        root.function.as_ref().unwrap().function_name.as_str(),
+        "entry"
+    );
+    assert_eq!(root.args, vec![TraceValue::Raw(vec![1u8])]);
+    assert_eq!(root.return_values, Some(vec![]));
+
+    let callee = &root.calls[0];
+    assert_eq!(
+        callee.args,
+        vec![
+            TraceValue::U64(7),
+            TraceValue::Struct(vec![TraceValue::Bool(true), TraceValue::Bytes(vec![1, 2])]),
+        ]
+    );
+    assert_eq!(
+        callee.return_values,
+        Some(vec![TraceValue::Address(AccountAddress::ONE)])
+    );
+
+    let native = &callee.calls[0];
+    assert!(native.is_native);
+    assert_eq!(native.return_values, Some(vec![TraceValue::U8(3)]));
+}
+
+#[test]
+fn test_trace_failed_call() {
+    let tracer = TxTracer::new();
+    let mut gas_meter = TracingGasMeter::new(UnmeteredGasMeter, tracer.clone());
+    // The calls before the tracer started are not traced
+    gas_meter
+        .charge_call(
+            &module_id(),
+            "pre_execute",
+            std::iter::empty::<&Value>(),
+            NumArgs::new(0),
+        )
+        .unwrap();
+    tracer.start(&function_action());
+    gas_meter
+        .charge_call(
+            &module_id(),
+            "callee",
+            std::iter::empty::<&Value>(),
+            NumArgs::new(0),
+        )
+        .unwrap();
+    tracer.stop(true);
+
+    let calls = tracer.into_calls();
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].return_values, None);
+    assert_eq!(calls[0].calls.len(), 1);
+    assert_eq!(calls[0].calls[0].return_values, None);
+}
@@ -11,7 +11,7 @@ use anyhow::{Error, Result, ensure, format_err};
 use rocksdb::{
     AsColumnFamilyRef, BlockBasedIndexType, BlockBasedOptions, CStrLike, Cache, ColumnFamily,
     ColumnFamilyDescriptor, DB, DBCompressionType, DBRawIterator, DBRecoveryMode, Options,
-    ReadOptions, WriteBatch as DBWriteBatch, WriteOptions, statistics,
+    ReadOptions, WriteBatch as DBWriteBatch, WriteOptions, checkpoint::Checkpoint, statistics,
 };
 use serde::Serialize;
 use serde::de::DeserializeOwned;
@@ -222,6 +222,14 @@ impl RocksDB {
         Ok(())
     }
 
+    /// Create a checkpoint of the db in the `path`, the `path` should not exist.
+    /// The sst files are hard linked if the `path` is on the same filesystem, otherwise they are copied.
+    pub fn create_checkpoint(&self, path: impl AsRef<Path>) -> Result<()> {
+        let checkpoint = Checkpoint::new(&self.db)?;
+        checkpoint.create_checkpoint(path)?;
+        Ok(())
+    }
+
     /// List cf
     pub fn list_cf(path: impl AsRef<Path>) -> Result<Vec<String>, Error> {
         Ok(DB::list_cf(&Options::default(), path)?)
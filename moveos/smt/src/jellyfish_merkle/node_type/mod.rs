@@ -539,6 +539,46 @@ enum NodeTag {
     Leaf = 2,
 }
 
+/// Decodes the hashes of the children from the serialized bytes of a node,
+/// returns empty if the node is not an internal node.
+pub(crate) fn decode_children(val: &[u8]) -> Result<Vec<SMTNodeHash>> {
+    match val.first().and_then(|tag| NodeTag::from_u8(*tag)) {
+        Some(NodeTag::Internal) => {
+            let internal_node = InternalNode::deserialize(&val[1..])?;
+            Ok(internal_node
+                .children
+                .values()
+                .map(|child| child.hash)
+                .collect())
+        }
+        _ => Ok(vec![]),
+    }
+}
+
+/// Computes the hash of a node from its serialized bytes, the value of a leaf node is not decoded,
+/// so the hash can be computed without knowing the key and value types.
+pub(crate) fn decode_node_hash(val: &[u8]) -> Result<SMTNodeHash> {
+    if val.is_empty() {
+        return Err(NodeDecodeError::EmptyInput.into());
+    }
+    let tag = val[0];
+    match NodeTag::from_u8(tag) {
+        Some(NodeTag::Null) => Ok(*SPARSE_MERKLE_PLACEHOLDER_HASH_VALUE),
+        Some(NodeTag::Internal) => Ok(InternalNode::deserialize(&val[1..])?.merkle_hash()),
+        Some(NodeTag::Leaf) => {
+            let raw_kv: RawKV = bcs::from_bytes(&val[1..])?;
+            Ok(
+                SparseMerkleLeafNode::new(
+                    raw_kv.key.into(),
+                    SMTNodeHash::tag_sha256(&raw_kv.value),
+                )
+                .merkle_hash(),
+            )
+        }
+        None => Err(NodeDecodeError::UnknownTag { unknown_tag: tag }.into()),
+    }
+}
+
 /// The concrete node type of [`JellyfishMerkleTree`](super::JellyfishMerkleTree).
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub(crate) enum Node<K, V> {
@@ -38,6 +38,21 @@ pub trait NodeReader {
     fn get(&self, hash: &H256) -> Result<Option<Vec<u8>>>;
 }
 
+/// Get the hashes of the children of the encoded tree node, empty if the node is not an internal node.
+/// It is used to prefetch the children of the nodes which are read from a remote node.
+pub fn decode_node_children(node: &[u8]) -> Result<Vec<H256>> {
+    Ok(jellyfish_merkle::node_type::decode_children(node)?
+        .into_iter()
+        .map(Into::into)
+        .collect())
+}
+
+/// Compute the hash of the encoded tree node, the nodes read from a remote node should be checked
+/// against their hashes before they are written to the local store.
+pub fn decode_node_hash(node: &[u8]) -> Result<H256> {
+    Ok(jellyfish_merkle::node_type::decode_node_hash(node)?.into())
+}
+
 pub trait NodeWriter {
     fn write_nodes(&self, nodes: BTreeMap<H256, Vec<u8>>) -> Result<()>;
 }
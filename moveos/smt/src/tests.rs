@@ -63,3 +63,57 @@ fn test_smt() {
     let iter = smt.iter(changeset3.state_root, None).unwrap();
     assert_eq!(iter.count(), 2);
 }
+
+#[test]
+fn test_decode_node_children() {
+    let registry = prometheus::Registry::new();
+    let smt = SMTree::new(InMemoryNodeStore::default(), &registry);
+    let changeset = smt
+        .puts(
+            *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            (0..32)
+                .map(|i| (H256::random(), Some(format!("value{}", i))))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+    let root_node = changeset.nodes.get(&changeset.state_root).unwrap();
+    let children = decode_node_children(root_node).unwrap();
+    assert!(!children.is_empty());
+    for child in children.iter() {
+        assert!(changeset.nodes.contains_key(child));
+    }
+    // Only the leaf nodes have no children
+    let leaf_count = changeset
+        .nodes
+        .values()
+        .filter(|node| decode_node_children(node).unwrap().is_empty())
+        .count();
+    assert_eq!(leaf_count, 32);
+}
+
+#[test]
+fn test_decode_node_hash() {
+    let registry = prometheus::Registry::new();
+    let smt = SMTree::new(InMemoryNodeStore::default(), &registry);
+    let changeset = smt
+        .puts(
+            *SPARSE_MERKLE_PLACEHOLDER_HASH,
+            (0..32)
+                .map(|i| (H256::random(), Some(format!("value{}", i))))
+                .collect::<Vec<_>>(),
+        )
+        .unwrap();
+    for (hash, node) in changeset.nodes.iter() {
+        assert_eq!(decode_node_hash(node).unwrap(), *hash);
+    }
+    // Tamper the value of a leaf node
+    let (hash, node) = changeset
+        .nodes
+        .iter()
+        .find(|(_, node)| decode_node_children(node).unwrap().is_empty())
+        .unwrap();
+    let mut node = node.clone();
+    let last = node.len() - 1;
+    node[last] ^= 1;
+    assert_ne!(decode_node_hash(&node).unwrap(), *hash);
+}
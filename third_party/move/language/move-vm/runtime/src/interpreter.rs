@@ -149,6 +149,14 @@ impl Interpreter {
                         .map(|(_idx, val)| val)
                         .collect::<Vec<_>>();
 
+                    gas_meter
+                        .charge_return(
+                            self.operand_stack
+                                .last_n(current_frame.function.return_type_count())
+                                .map_err(|e| self.set_location(e))?,
+                        )
+                        .map_err(|e| self.set_location(e))?;
+
                     // TODO: Check if the error location is set correctly.
                     gas_meter
                         .charge_drop_frame(non_ref_vals.iter())
@@ -297,6 +297,16 @@ pub trait GasMeter {
         &mut self,
         locals: impl Iterator<Item = impl ValueView> + Clone,
     ) -> PartialVMResult<()>;
+
+    /// Called when a non-native function returns, with the values it leaves on the operand stack.
+    /// This is not used to charge gas, but allows meters wrapping a base meter to observe the
+    /// return values, e.g. for tracing.
+    fn charge_return(
+        &mut self,
+        _ret_vals: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()> {
+        Ok(())
+    }
 }
 
 /// A dummy gas meter that does not meter anything.